@@ -0,0 +1,5 @@
+-- Track the owning user of each project for multi-user (K8s) deployments.
+-- NULL in desktop mode, where every project belongs to the single local user.
+ALTER TABLE projects ADD COLUMN user_id BLOB;
+
+CREATE INDEX IF NOT EXISTS idx_projects_user_id ON projects (user_id);
@@ -0,0 +1,5 @@
+-- Track the owning user of each session for multi-user (K8s) deployments.
+-- NULL in desktop mode, where every session belongs to the single local user.
+ALTER TABLE sessions ADD COLUMN user_id BLOB;
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user_workspace ON sessions (user_id, workspace_id);
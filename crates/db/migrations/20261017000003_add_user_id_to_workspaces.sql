@@ -0,0 +1,5 @@
+-- Track the owning user of each workspace for multi-user (K8s) deployments.
+-- NULL in desktop mode, where every workspace belongs to the single local user.
+ALTER TABLE workspaces ADD COLUMN user_id BLOB;
+
+CREATE INDEX IF NOT EXISTS idx_workspaces_user_id ON workspaces (user_id, created_at DESC);
@@ -0,0 +1,5 @@
+-- Soft-delete for tasks: deleting stamps deleted_at instead of removing the row,
+-- so a task deleted by mistake can be restored. NULL means the task is live.
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_id_deleted_at ON tasks (project_id, deleted_at);
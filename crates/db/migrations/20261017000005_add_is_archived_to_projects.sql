@@ -0,0 +1,3 @@
+-- Archived projects are hidden from the default project listing but kept intact,
+-- so finished boards stop cluttering the UI without losing their history.
+ALTER TABLE projects ADD COLUMN is_archived INTEGER NOT NULL DEFAULT 0;
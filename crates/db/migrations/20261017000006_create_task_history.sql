@@ -0,0 +1,13 @@
+-- Audit log of task field changes. user_id is NULL in desktop mode, where
+-- every change is made by the single local user.
+CREATE TABLE IF NOT EXISTS task_history (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id     BLOB,
+    changed_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    field       TEXT NOT NULL,
+    old_value   TEXT NOT NULL,
+    new_value   TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_history_task_id_changed_at ON task_history (task_id, changed_at);
@@ -0,0 +1,3 @@
+-- Set while a running execution is suspended via the pause endpoint and
+-- cleared again on resume or completion.
+ALTER TABLE execution_processes ADD COLUMN paused_at TEXT;
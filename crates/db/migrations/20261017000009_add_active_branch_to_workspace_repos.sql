@@ -0,0 +1,3 @@
+-- Branch each repo's worktree currently has checked out. Empty until the
+-- worktree is first created.
+ALTER TABLE workspace_repos ADD COLUMN active_branch TEXT NOT NULL DEFAULT '';
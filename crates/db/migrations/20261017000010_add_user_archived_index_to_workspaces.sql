@@ -0,0 +1,3 @@
+-- Speeds up listing a user's archived workspaces and the bulk archive sweep.
+-- Mirrors idx_workspaces_user_archived in the PostgreSQL schema.
+CREATE INDEX IF NOT EXISTS idx_workspaces_user_archived ON workspaces (user_id, archived);
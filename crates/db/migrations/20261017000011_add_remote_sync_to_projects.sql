@@ -0,0 +1,5 @@
+-- When a linked project last pulled its metadata from the remote project,
+-- and the remote name it saw then. Both are NULL until the first sync and are
+-- cleared whenever the remote link changes.
+ALTER TABLE projects ADD COLUMN last_synced_at TEXT;
+ALTER TABLE projects ADD COLUMN remote_project_name TEXT;
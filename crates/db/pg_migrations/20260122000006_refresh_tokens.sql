@@ -0,0 +1,20 @@
+-- Refresh Token Storage for Multi-User Kubernetes Deployment
+-- Tracks issued refresh tokens per user so they can be rotated and revoked
+--
+-- Rollback procedure:
+-- DROP INDEX IF EXISTS idx_user_configs_refresh_tokens;
+-- ALTER TABLE user_configs DROP COLUMN IF EXISTS refresh_tokens;
+
+-- ============================================================================
+-- USER_CONFIGS.REFRESH_TOKENS
+-- ============================================================================
+-- Map of JWT ID (jti) -> expiry timestamp (RFC 3339). The opaque refresh token
+-- itself is never stored; its jti is the SHA-256 hash of the token value.
+ALTER TABLE user_configs
+    ADD COLUMN IF NOT EXISTS refresh_tokens JSONB NOT NULL DEFAULT '{}';
+
+COMMENT ON COLUMN user_configs.refresh_tokens IS 'Active refresh token IDs (jti) mapped to their expiry time';
+
+-- GIN index so revocation by jti does not need to scan every user row
+CREATE INDEX IF NOT EXISTS idx_user_configs_refresh_tokens
+    ON user_configs USING GIN (refresh_tokens);
@@ -0,0 +1,21 @@
+-- Full-Text Search for Tasks in Multi-User Kubernetes Deployment
+-- Adds a generated tsvector over task title and description for task search
+--
+-- Rollback procedure:
+-- DROP INDEX IF EXISTS idx_tasks_search_vector;
+-- ALTER TABLE tasks DROP COLUMN IF EXISTS search_vector;
+
+-- ============================================================================
+-- TASKS.SEARCH_VECTOR
+-- ============================================================================
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+        GENERATED ALWAYS AS (
+            to_tsvector('english', coalesce(title, '') || ' ' || coalesce(description, ''))
+        ) STORED;
+
+COMMENT ON COLUMN tasks.search_vector IS 'English full-text search vector of title and description';
+
+-- GIN index so plainto_tsquery matches do not scan every task row
+CREATE INDEX IF NOT EXISTS idx_tasks_search_vector
+    ON tasks USING GIN (search_vector);
@@ -0,0 +1,19 @@
+-- Soft-Delete for Tasks in Multi-User Kubernetes Deployment
+-- Deleting a task stamps deleted_at instead of removing the row so it can be restored
+--
+-- Rollback procedure:
+-- DROP INDEX IF EXISTS idx_tasks_user_project_live;
+-- ALTER TABLE tasks DROP COLUMN IF EXISTS deleted_at;
+
+-- ============================================================================
+-- TASKS.DELETED_AT
+-- ============================================================================
+ALTER TABLE tasks
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN tasks.deleted_at IS 'When the task was soft-deleted; NULL for live tasks';
+
+-- Partial index for the common case of listing a user's live tasks in a project
+CREATE INDEX IF NOT EXISTS idx_tasks_user_project_live
+    ON tasks (user_id, project_id)
+    WHERE deleted_at IS NULL;
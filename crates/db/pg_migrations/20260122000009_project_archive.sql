@@ -0,0 +1,13 @@
+-- Project Archiving for Multi-User Kubernetes Deployment
+-- Archived projects are hidden from the default listing without being deleted
+--
+-- Rollback procedure:
+-- ALTER TABLE projects DROP COLUMN IF EXISTS is_archived;
+
+-- ============================================================================
+-- PROJECTS.IS_ARCHIVED
+-- ============================================================================
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS is_archived BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN projects.is_archived IS 'Hidden from the default project listing when TRUE';
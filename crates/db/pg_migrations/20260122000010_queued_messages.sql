@@ -0,0 +1,27 @@
+-- Queued Follow-up Messages for Multi-User Kubernetes Deployment
+-- Persists queued follow-ups so they survive pod restarts
+--
+-- Rollback procedure:
+-- DROP TABLE IF EXISTS queued_messages;
+
+-- ============================================================================
+-- QUEUED_MESSAGES
+-- ============================================================================
+CREATE TABLE IF NOT EXISTS queued_messages (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    session_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    message_json JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_queued_messages_user_id ON queued_messages(user_id);
+CREATE INDEX IF NOT EXISTS idx_queued_messages_pending
+    ON queued_messages(session_id, created_at)
+    WHERE delivered_at IS NULL;
+
+COMMENT ON TABLE queued_messages IS 'Follow-up messages queued while a session execution is running';
+COMMENT ON COLUMN queued_messages.user_id IS 'Owner user ID for multi-tenant isolation';
+COMMENT ON COLUMN queued_messages.message_json IS 'Serialized QueuedMessage payload';
+COMMENT ON COLUMN queued_messages.delivered_at IS 'Set once the message has been consumed; NULL while pending';
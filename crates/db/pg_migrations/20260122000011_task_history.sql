@@ -0,0 +1,27 @@
+-- Task History for Multi-User Kubernetes Deployment
+-- Audit log of task field changes (who changed what, and when)
+--
+-- Rollback procedure:
+-- DROP TABLE IF EXISTS task_history;
+
+-- ============================================================================
+-- TASK_HISTORY
+-- ============================================================================
+CREATE TABLE IF NOT EXISTS task_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id UUID,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    field TEXT NOT NULL,
+    old_value JSONB,
+    new_value JSONB
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_history_task_id_changed_at ON task_history(task_id, changed_at);
+CREATE INDEX IF NOT EXISTS idx_task_history_user_id ON task_history(user_id);
+
+COMMENT ON TABLE task_history IS 'Audit log of task field changes';
+COMMENT ON COLUMN task_history.user_id IS 'User who made the change (NULL for system changes)';
+COMMENT ON COLUMN task_history.field IS 'Name of the changed task field (e.g., status, title)';
+COMMENT ON COLUMN task_history.old_value IS 'Field value before the change';
+COMMENT ON COLUMN task_history.new_value IS 'Field value after the change';
@@ -0,0 +1,13 @@
+-- Execution Pause/Resume for Multi-User Kubernetes Deployment
+-- Records when a running execution process was suspended
+--
+-- Rollback procedure:
+-- ALTER TABLE execution_processes DROP COLUMN IF EXISTS paused_at;
+
+-- ============================================================================
+-- EXECUTION_PROCESSES.PAUSED_AT
+-- ============================================================================
+ALTER TABLE execution_processes
+    ADD COLUMN IF NOT EXISTS paused_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN execution_processes.paused_at IS 'When the process was paused; NULL while running normally';
@@ -0,0 +1,26 @@
+-- Execution Cancellation for Multi-User Kubernetes Deployment
+-- Adds the 'cancelled' status and records why a user cancelled a process
+--
+-- Rollback procedure:
+-- ALTER TABLE execution_processes DROP COLUMN IF EXISTS cancelled_reason;
+-- ALTER TABLE execution_processes DROP CONSTRAINT IF EXISTS execution_processes_status_check;
+-- ALTER TABLE execution_processes ADD CONSTRAINT execution_processes_status_check
+--     CHECK (status IN ('running', 'completed', 'failed', 'killed'));
+
+-- ============================================================================
+-- EXECUTION_PROCESSES.STATUS
+-- ============================================================================
+ALTER TABLE execution_processes
+    DROP CONSTRAINT IF EXISTS execution_processes_status_check;
+
+ALTER TABLE execution_processes
+    ADD CONSTRAINT execution_processes_status_check
+    CHECK (status IN ('running', 'completed', 'failed', 'killed', 'cancelled'));
+
+-- ============================================================================
+-- EXECUTION_PROCESSES.CANCELLED_REASON
+-- ============================================================================
+ALTER TABLE execution_processes
+    ADD COLUMN IF NOT EXISTS cancelled_reason TEXT;
+
+COMMENT ON COLUMN execution_processes.cancelled_reason IS 'Reason given when a user cancelled the process; NULL otherwise';
@@ -0,0 +1,13 @@
+-- Active Branch Tracking for Multi-User Kubernetes Deployment
+-- Records which branch each repo worktree currently has checked out
+--
+-- Rollback procedure:
+-- ALTER TABLE workspace_repos DROP COLUMN IF EXISTS active_branch;
+
+-- ============================================================================
+-- WORKSPACE_REPOS.ACTIVE_BRANCH
+-- ============================================================================
+ALTER TABLE workspace_repos
+    ADD COLUMN IF NOT EXISTS active_branch TEXT NOT NULL DEFAULT '';
+
+COMMENT ON COLUMN workspace_repos.active_branch IS 'Branch checked out in the repo worktree; empty until the worktree is created';
@@ -0,0 +1,18 @@
+-- Remote Project Sync Status for Multi-User Kubernetes Deployment
+-- Records when a linked project last pulled its metadata from the remote project
+--
+-- Rollback procedure:
+-- ALTER TABLE projects DROP COLUMN IF EXISTS remote_project_name;
+-- ALTER TABLE projects DROP COLUMN IF EXISTS last_synced_at;
+
+-- ============================================================================
+-- PROJECTS.LAST_SYNCED_AT / PROJECTS.REMOTE_PROJECT_NAME
+-- ============================================================================
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS last_synced_at TIMESTAMPTZ;
+
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS remote_project_name TEXT;
+
+COMMENT ON COLUMN projects.last_synced_at IS 'Last successful pull from the linked remote project; NULL if never synced';
+COMMENT ON COLUMN projects.remote_project_name IS 'Remote project name seen at the last sync';
@@ -0,0 +1,18 @@
+-- Session Reviews for Multi-User Kubernetes Deployment
+-- Adds the 'review' run reason for processes started by a session review
+--
+-- Rollback procedure:
+-- UPDATE execution_processes SET run_reason = 'codingagent' WHERE run_reason = 'review';
+-- ALTER TABLE execution_processes DROP CONSTRAINT IF EXISTS execution_processes_run_reason_check;
+-- ALTER TABLE execution_processes ADD CONSTRAINT execution_processes_run_reason_check
+--     CHECK (run_reason IN ('setupscript', 'codingagent', 'devserver', 'cleanupscript'));
+
+-- ============================================================================
+-- EXECUTION_PROCESSES.RUN_REASON
+-- ============================================================================
+ALTER TABLE execution_processes
+    DROP CONSTRAINT IF EXISTS execution_processes_run_reason_check;
+
+ALTER TABLE execution_processes
+    ADD CONSTRAINT execution_processes_run_reason_check
+    CHECK (run_reason IN ('setupscript', 'codingagent', 'review', 'devserver', 'cleanupscript'));
@@ -144,4 +144,20 @@ impl DBService {
         run_migrations(&pool).await?;
         Ok(pool)
     }
+
+    /// Check that the database responds to a trivial query.
+    pub async fn health_check(&self) -> Result<(), Error> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+    }
+
+    /// Get the current pool statistics as (active, idle, max) connections.
+    pub fn pool_stats(&self) -> (u32, u32, u32) {
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        let max = self.pool.options().get_max_connections();
+        (size.saturating_sub(idle), idle, max)
+    }
 }
@@ -6,6 +6,9 @@
 
 use std::env;
 
+use serde::Serialize;
+use ts_rs::TS;
+
 /// Environment variable name for deployment mode override.
 const DEPLOYMENT_MODE_ENV: &str = "DEPLOYMENT_MODE";
 
@@ -27,7 +30,7 @@ const DATABASE_URL_ENV: &str = "DATABASE_URL";
 ///
 /// The deployment mode is detected in the following order:
 ///
-/// 1. Check `DEPLOYMENT_MODE` environment variable (values: "desktop", "kubernetes")
+/// 1. Check `DEPLOYMENT_MODE` environment variable (see [`DeploymentMode::parse`])
 /// 2. Check if `DATABASE_URL` starts with "postgres" (indicates Kubernetes mode)
 /// 3. Default to `Desktop` mode
 ///
@@ -50,7 +53,8 @@ const DATABASE_URL_ENV: &str = "DATABASE_URL";
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
 pub enum DeploymentMode {
     /// Single-user desktop mode with SQLite database.
     ///
@@ -77,7 +81,7 @@ impl DeploymentMode {
     /// Detect the deployment mode from environment variables.
     ///
     /// Detection priority:
-    /// 1. `DEPLOYMENT_MODE` environment variable ("desktop" or "kubernetes")
+    /// 1. `DEPLOYMENT_MODE` environment variable (see [`DeploymentMode::parse`])
     /// 2. `DATABASE_URL` starting with "postgres" indicates Kubernetes mode
     /// 3. Default to Desktop mode
     ///
@@ -98,27 +102,18 @@ impl DeploymentMode {
     pub fn detect() -> Self {
         // First, check explicit DEPLOYMENT_MODE environment variable
         if let Ok(mode_str) = env::var(DEPLOYMENT_MODE_ENV) {
-            let mode = mode_str.to_lowercase();
-            match mode.as_str() {
-                "kubernetes" | "k8s" => {
-                    tracing::info!(
-                        mode = "kubernetes",
-                        source = "DEPLOYMENT_MODE env var",
-                        "Detected deployment mode"
-                    );
-                    return Self::Kubernetes;
-                }
-                "desktop" | "local" => {
+            match Self::parse(&mode_str) {
+                Some(mode) => {
                     tracing::info!(
-                        mode = "desktop",
+                        mode = mode.as_str(),
                         source = "DEPLOYMENT_MODE env var",
                         "Detected deployment mode"
                     );
-                    return Self::Desktop;
+                    return mode;
                 }
-                other => {
+                None => {
                     tracing::warn!(
-                        value = other,
+                        value = %mode_str,
                         "Unknown DEPLOYMENT_MODE value, checking DATABASE_URL"
                     );
                 }
@@ -147,6 +142,29 @@ impl DeploymentMode {
         Self::Desktop
     }
 
+    /// Parse a `DEPLOYMENT_MODE` value.
+    ///
+    /// Matching is case-insensitive and ignores surrounding whitespace.
+    /// `kubernetes` and `k8s` select Kubernetes mode; `desktop` and `local`
+    /// select Desktop mode. Any other value returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use db::DeploymentMode;
+    ///
+    /// assert_eq!(DeploymentMode::parse("K8S"), Some(DeploymentMode::Kubernetes));
+    /// assert_eq!(DeploymentMode::parse("local"), Some(DeploymentMode::Desktop));
+    /// assert_eq!(DeploymentMode::parse("cloud"), None);
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "kubernetes" | "k8s" => Some(Self::Kubernetes),
+            "desktop" | "local" => Some(Self::Desktop),
+            _ => None,
+        }
+    }
+
     /// Check if the application is running in multi-user mode.
     ///
     /// Multi-user mode is enabled when running in Kubernetes mode.
@@ -197,11 +215,11 @@ impl DeploymentMode {
     ///
     /// # Returns
     ///
-    /// A string describing the expected database type.
+    /// A lowercase string naming the expected database type.
     pub fn expected_database(&self) -> &'static str {
         match self {
-            Self::Desktop => "SQLite",
-            Self::Kubernetes => "PostgreSQL",
+            Self::Desktop => "sqlite",
+            Self::Kubernetes => "postgresql",
         }
     }
 
@@ -388,8 +406,53 @@ mod tests {
 
     #[test]
     fn test_expected_database() {
-        assert_eq!(DeploymentMode::Desktop.expected_database(), "SQLite");
-        assert_eq!(DeploymentMode::Kubernetes.expected_database(), "PostgreSQL");
+        assert_eq!(DeploymentMode::Desktop.expected_database(), "sqlite");
+        assert_eq!(DeploymentMode::Kubernetes.expected_database(), "postgresql");
+    }
+
+    #[test]
+    fn test_parse_kubernetes_aliases() {
+        for value in ["kubernetes", "KUBERNETES", "Kubernetes", "k8s", "K8S", " k8s\n"] {
+            assert_eq!(
+                DeploymentMode::parse(value),
+                Some(DeploymentMode::Kubernetes),
+                "{value:?} should parse as Kubernetes"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_desktop_aliases() {
+        for value in ["desktop", "DESKTOP", "local", "Local"] {
+            assert_eq!(
+                DeploymentMode::parse(value),
+                Some(DeploymentMode::Desktop),
+                "{value:?} should parse as Desktop"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_values() {
+        for value in ["", "k8", "kube", "cloud", "postgres"] {
+            assert_eq!(DeploymentMode::parse(value), None, "{value:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_deployment_mode_unknown_value_defaults_to_desktop() {
+        // SAFETY: Test environment
+        unsafe {
+            set_env(DEPLOYMENT_MODE_ENV, "cloud");
+            remove_env(DATABASE_URL_ENV);
+        }
+
+        assert_eq!(DeploymentMode::detect(), DeploymentMode::Desktop);
+
+        // Clean up
+        unsafe {
+            remove_env(DEPLOYMENT_MODE_ENV);
+        }
     }
 
     #[test]
@@ -407,4 +470,16 @@ mod tests {
             remove_env(DEPLOYMENT_MODE_ENV);
         }
     }
+
+    #[test]
+    fn test_deployment_mode_serializes_lowercase() {
+        assert_eq!(
+            serde_json::to_value(DeploymentMode::Desktop).unwrap(),
+            "desktop"
+        );
+        assert_eq!(
+            serde_json::to_value(DeploymentMode::Kubernetes).unwrap(),
+            "kubernetes"
+        );
+    }
 }
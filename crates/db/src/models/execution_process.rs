@@ -47,6 +47,7 @@ pub enum ExecutionProcessStatus {
     Completed,
     Failed,
     Killed,
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
@@ -56,6 +57,7 @@ pub enum ExecutionProcessRunReason {
     SetupScript,
     CleanupScript,
     CodingAgent,
+    Review,
     DevServer,
 }
 
@@ -74,6 +76,10 @@ pub struct ExecutionProcess {
     pub dropped: bool,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// Set while the process is suspended by a pause request
+    pub paused_at: Option<DateTime<Utc>>,
+    /// Reason given when the process was cancelled by a user
+    pub cancelled_reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -146,6 +152,8 @@ impl ExecutionProcess {
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.paused_at as "paused_at?: DateTime<Utc>",
+                    ep.cancelled_reason,
                     ep.created_at as "created_at!: DateTime<Utc>",
                     ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep WHERE ep.id = ?"#,
@@ -220,6 +228,8 @@ impl ExecutionProcess {
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.paused_at as "paused_at?: DateTime<Utc>",
+                    ep.cancelled_reason,
                     ep.created_at as "created_at!: DateTime<Utc>",
                     ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep WHERE ep.rowid = ?"#,
@@ -247,6 +257,8 @@ impl ExecutionProcess {
                       ep.dropped as "dropped!: bool",
                       ep.started_at      as "started_at!: DateTime<Utc>",
                       ep.completed_at    as "completed_at?: DateTime<Utc>",
+                      ep.paused_at       as "paused_at?: DateTime<Utc>",
+                      ep.cancelled_reason,
                       ep.created_at      as "created_at!: DateTime<Utc>",
                       ep.updated_at      as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
@@ -260,6 +272,48 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Fetch a page of a session's execution processes, newest first, using
+    /// keyset pagination on `(created_at, id)`. Returns up to `limit`
+    /// processes created before the process `after`. Dropped processes are
+    /// skipped.
+    pub async fn find_by_session_paginated(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT
+                      ep.id              as "id!: Uuid",
+                      ep.session_id      as "session_id!: Uuid",
+                      ep.run_reason      as "run_reason!: ExecutionProcessRunReason",
+                      ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.status          as "status!: ExecutionProcessStatus",
+                      ep.exit_code,
+                      ep.dropped as "dropped!: bool",
+                      ep.started_at      as "started_at!: DateTime<Utc>",
+                      ep.completed_at    as "completed_at?: DateTime<Utc>",
+                      ep.paused_at       as "paused_at?: DateTime<Utc>",
+                      ep.cancelled_reason,
+                      ep.created_at      as "created_at!: DateTime<Utc>",
+                      ep.updated_at      as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               WHERE ep.session_id = $1
+                 AND ep.dropped = FALSE
+                 AND ($2 IS NULL OR (ep.created_at, ep.id) < (
+                       SELECT created_at, id FROM execution_processes WHERE id = $2
+                     ))
+               ORDER BY ep.created_at DESC, ep.id DESC
+               LIMIT $3"#,
+            session_id,
+            after,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find running execution processes
     pub async fn find_running(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
@@ -274,6 +328,8 @@ impl ExecutionProcess {
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.paused_at as "paused_at?: DateTime<Utc>",
+                    ep.cancelled_reason,
                     ep.created_at as "created_at!: DateTime<Utc>",
                     ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep WHERE ep.status = 'running' ORDER BY ep.created_at ASC"#,
@@ -291,7 +347,7 @@ impl ExecutionProcess {
             ExecutionProcess,
             r#"SELECT ep.id as "id!: Uuid", ep.session_id as "session_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                       ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
-                      ep.dropped as "dropped!: bool", ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+                      ep.dropped as "dropped!: bool", ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.paused_at as "paused_at?: DateTime<Utc>", ep.cancelled_reason, ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
                JOIN sessions s ON ep.session_id = s.id
                JOIN workspaces w ON s.workspace_id = w.id
@@ -341,6 +397,8 @@ impl ExecutionProcess {
             ep.dropped as "dropped!: bool",
             ep.started_at as "started_at!: DateTime<Utc>",
             ep.completed_at as "completed_at?: DateTime<Utc>",
+            ep.paused_at as "paused_at?: DateTime<Utc>",
+            ep.cancelled_reason,
             ep.created_at as "created_at!: DateTime<Utc>",
             ep.updated_at as "updated_at!: DateTime<Utc>"
         FROM execution_processes ep
@@ -370,7 +428,7 @@ impl ExecutionProcess {
                FROM execution_processes ep
                JOIN coding_agent_turns cat ON ep.id = cat.execution_process_id
                WHERE ep.session_id = $1
-                 AND ep.run_reason = 'codingagent'
+                 AND ep.run_reason IN ('codingagent', 'review')
                  AND ep.dropped = FALSE
                  AND cat.agent_session_id IS NOT NULL
                ORDER BY ep.created_at DESC
@@ -403,6 +461,8 @@ impl ExecutionProcess {
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.paused_at as "paused_at?: DateTime<Utc>",
+                    ep.cancelled_reason,
                     ep.created_at as "created_at!: DateTime<Utc>",
                     ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
@@ -433,6 +493,8 @@ impl ExecutionProcess {
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.paused_at as "paused_at?: DateTime<Utc>",
+                    ep.cancelled_reason,
                     ep.created_at as "created_at!: DateTime<Utc>",
                     ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
@@ -491,8 +553,12 @@ impl ExecutionProcess {
     pub async fn was_stopped(pool: &SqlitePool, id: Uuid) -> bool {
         if let Ok(exp_process) = Self::find_by_id(pool, id).await
             && exp_process.is_some_and(|ep| {
-                ep.status == ExecutionProcessStatus::Killed
-                    || ep.status == ExecutionProcessStatus::Completed
+                matches!(
+                    ep.status,
+                    ExecutionProcessStatus::Killed
+                        | ExecutionProcessStatus::Cancelled
+                        | ExecutionProcessStatus::Completed
+                )
             })
         {
             return true;
@@ -515,7 +581,7 @@ impl ExecutionProcess {
 
         sqlx::query!(
             r#"UPDATE execution_processes
-               SET status = $1, exit_code = $2, completed_at = $3
+               SET status = $1, exit_code = $2, completed_at = $3, paused_at = NULL
                WHERE id = $4"#,
             status,
             exit_code,
@@ -528,6 +594,46 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Record that the process was paused (`Some`) or resumed (`None`).
+    pub async fn set_paused_at(
+        pool: &SqlitePool,
+        id: Uuid,
+        paused_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_processes SET paused_at = $1, updated_at = datetime('now', 'subsec') WHERE id = $2",
+            paused_at,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark the process cancelled by a user, recording an optional reason.
+    pub async fn cancel(
+        pool: &SqlitePool,
+        id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let status = ExecutionProcessStatus::Cancelled;
+        let completed_at = Utc::now();
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = $1, cancelled_reason = $2, paused_at = NULL,
+                   completed_at = COALESCE(completed_at, $3),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $4"#,
+            status,
+            reason,
+            completed_at,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub fn executor_action(&self) -> Result<&ExecutorAction, anyhow::Error> {
         match &self.executor_action.0 {
             ExecutorActionField::ExecutorAction(action) => Ok(action),
@@ -641,13 +747,13 @@ impl ExecutionProcess {
         })
     }
 
-    /// Fetch the latest CodingAgent executor profile for a session.
-    /// Returns None if no CodingAgent execution process exists for this session.
+    /// Fetch the latest CodingAgent or Review executor profile for a session.
+    /// Returns None if neither kind of execution process exists for this session.
     pub async fn latest_executor_profile_for_session(
         pool: &SqlitePool,
         session_id: Uuid,
     ) -> Result<Option<ExecutorProfileId>, ExecutionProcessError> {
-        // Find the latest CodingAgent execution process for this session
+        // Find the latest CodingAgent or Review execution process for this session
         let latest_execution_process = sqlx::query_as!(
             ExecutionProcess,
             r#"SELECT
@@ -660,13 +766,16 @@ impl ExecutionProcess {
                     ep.dropped as "dropped!: bool",
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.paused_at as "paused_at?: DateTime<Utc>",
+                    ep.cancelled_reason,
                     ep.created_at as "created_at!: DateTime<Utc>",
                     ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
-               WHERE ep.session_id = ? AND ep.run_reason = ? AND ep.dropped = FALSE
+               WHERE ep.session_id = ?
+                 AND ep.run_reason IN ('codingagent', 'review')
+                 AND ep.dropped = FALSE
                ORDER BY ep.created_at DESC LIMIT 1"#,
-            session_id,
-            ExecutionProcessRunReason::CodingAgent
+            session_id
         )
         .fetch_optional(pool)
         .await?;
@@ -715,14 +824,14 @@ impl ExecutionProcess {
             JOIN sessions s ON ep.session_id = s.id
             JOIN workspaces w ON s.workspace_id = w.id
             WHERE w.archived = $1
-              AND ep.run_reason IN ('codingagent', 'setupscript', 'cleanupscript')
+              AND ep.run_reason IN ('codingagent', 'review', 'setupscript', 'cleanupscript')
               AND ep.dropped = FALSE
               AND ep.created_at = (
                   SELECT MAX(ep2.created_at)
                   FROM execution_processes ep2
                   JOIN sessions s2 ON ep2.session_id = s2.id
                   WHERE s2.workspace_id = s.workspace_id
-                    AND ep2.run_reason IN ('codingagent', 'setupscript', 'cleanupscript')
+                    AND ep2.run_reason IN ('codingagent', 'review', 'setupscript', 'cleanupscript')
                     AND ep2.dropped = FALSE
               )
             "#,
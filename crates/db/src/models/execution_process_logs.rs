@@ -13,6 +13,17 @@ pub struct ExecutionProcessLogs {
     pub inserted_at: DateTime<Utc>,
 }
 
+/// A stored log row tagged with its insertion sequence number (the SQLite
+/// `rowid`), which callers use as a cursor for incremental reads.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionProcessLogRow {
+    pub seq: i64,
+    pub execution_id: Uuid,
+    pub logs: String, // JSONL format
+    pub byte_size: i64,
+    pub inserted_at: DateTime<Utc>,
+}
+
 impl ExecutionProcessLogs {
     /// Find logs by execution process ID
     pub async fn find_by_execution_id(
@@ -35,6 +46,62 @@ impl ExecutionProcessLogs {
         .await
     }
 
+    /// Fetch up to `limit` log rows inserted after `after_seq`, oldest first.
+    /// Pass `None` to start from the beginning of the log.
+    pub async fn find_paginated(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        after_seq: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<ExecutionProcessLogRow>, sqlx::Error> {
+        let after_seq = after_seq.unwrap_or(0);
+        sqlx::query_as!(
+            ExecutionProcessLogRow,
+            r#"SELECT
+                rowid as "seq!: i64",
+                execution_id as "execution_id!: Uuid",
+                logs,
+                byte_size,
+                inserted_at as "inserted_at!: DateTime<Utc>"
+               FROM execution_process_logs
+               WHERE execution_id = $1 AND rowid > $2
+               ORDER BY rowid ASC
+               LIMIT $3"#,
+            execution_id,
+            after_seq,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Fetch the last `limit` log rows, returned oldest first
+    pub async fn find_tail(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ExecutionProcessLogRow>, sqlx::Error> {
+        let mut rows = sqlx::query_as!(
+            ExecutionProcessLogRow,
+            r#"SELECT
+                rowid as "seq!: i64",
+                execution_id as "execution_id!: Uuid",
+                logs,
+                byte_size,
+                inserted_at as "inserted_at!: DateTime<Utc>"
+               FROM execution_process_logs
+               WHERE execution_id = $1
+               ORDER BY rowid DESC
+               LIMIT $2"#,
+            execution_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+        rows.reverse();
+        Ok(rows)
+    }
+
     /// Parse JSONL logs back into Vec<LogMsg>
     pub fn parse_logs(records: &[Self]) -> Result<Vec<LogMsg>, serde_json::Error> {
         let mut messages = Vec::new();
@@ -174,6 +174,39 @@ impl Image {
         .fetch_all(pool)
         .await
     }
+
+    /// Find images created before `cutoff` that are not attached to a task
+    /// with a running execution process.
+    pub async fn find_unused_created_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Image,
+            r#"SELECT i.id as "id!: Uuid",
+                      i.file_path as "file_path!",
+                      i.original_name as "original_name!",
+                      i.mime_type,
+                      i.size_bytes as "size_bytes!",
+                      i.hash as "hash!",
+                      i.created_at as "created_at!: DateTime<Utc>",
+                      i.updated_at as "updated_at!: DateTime<Utc>"
+               FROM images i
+               WHERE datetime(i.created_at) < datetime($1)
+                 AND NOT EXISTS (
+                     SELECT 1
+                     FROM task_images ti
+                     JOIN workspaces w ON w.task_id = ti.task_id
+                     JOIN sessions s ON s.workspace_id = w.id
+                     JOIN execution_processes ep ON ep.session_id = s.id
+                     WHERE ti.image_id = i.id
+                       AND ep.status = 'running'
+                 )"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
 }
 
 impl TaskImage {
@@ -228,4 +261,27 @@ impl TaskImage {
         .await?;
         Ok(result)
     }
+
+    /// Check if an image is attached to any task in a project owned by `user_id`.
+    pub async fn is_referenced_by_user(
+        pool: &SqlitePool,
+        image_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1
+                FROM task_images ti
+                JOIN tasks t ON t.id = ti.task_id
+                JOIN projects p ON p.id = t.project_id
+                WHERE ti.image_id = $1 AND p.user_id = $2
+               ) AS "exists!: bool"
+            "#,
+            image_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(result)
+    }
 }
@@ -23,12 +23,21 @@ pub struct Project {
     pub name: String,
     pub default_agent_working_dir: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    pub is_archived: bool,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
     pub updated_at: DateTime<Utc>,
 }
 
+/// Remote sync state of a project, kept outside [`Project`] so listings do not
+/// carry it.
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectRemoteSync {
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub remote_project_name: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, TS)]
 pub struct CreateProject {
     pub name: String,
@@ -71,15 +80,74 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
+               WHERE is_archived = FALSE
                ORDER BY created_at DESC"#
         )
         .fetch_all(pool)
         .await
     }
 
+    /// Find all projects owned by a user (multi-user mode)
+    pub async fn find_by_user(pool: &SqlitePool, user_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      default_agent_working_dir,
+                      remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE user_id = $1 AND is_archived = FALSE
+               ORDER BY created_at DESC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Fetch a page of projects using keyset pagination on `id`.
+    ///
+    /// Returns up to `limit` projects with `id > after`, ordered by `id`.
+    /// `user_id` restricts the page to that user's projects (multi-user mode);
+    /// `None` includes all projects. Archived projects are skipped unless
+    /// `include_archived`.
+    pub async fn find_paginated(
+        pool: &SqlitePool,
+        user_id: Option<Uuid>,
+        after: Option<Uuid>,
+        limit: i64,
+        include_archived: bool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      default_agent_working_dir,
+                      remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE ($1 IS NULL OR user_id = $1)
+                 AND ($2 IS NULL OR id > $2)
+                 AND ($4 OR is_archived = FALSE)
+               ORDER BY id
+               LIMIT $3"#,
+            user_id,
+            after,
+            limit,
+            include_archived
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find the most actively used projects based on recent task activity
     pub async fn find_most_active(pool: &SqlitePool, limit: i32) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
@@ -88,9 +156,11 @@ impl Project {
             SELECT p.id as "id!: Uuid", p.name,
                    p.default_agent_working_dir,
                    p.remote_project_id as "remote_project_id: Uuid",
+                   p.is_archived as "is_archived!: bool",
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
-            WHERE p.id IN (
+            WHERE p.is_archived = FALSE
+              AND p.id IN (
                 SELECT DISTINCT t.project_id
                 FROM tasks t
                 INNER JOIN workspaces w ON w.task_id = t.id
@@ -111,6 +181,7 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -121,6 +192,44 @@ impl Project {
         .await
     }
 
+    /// Find a project by ID that is visible to `user_id`. Passing `None`
+    /// disables the owner filter, matching [`Project::find_by_id`].
+    pub async fn find_by_id_and_user(
+        pool: &SqlitePool,
+        id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      default_agent_working_dir,
+                      remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE id = $1
+                 AND (user_id = $2 OR $2 IS NULL)"#,
+            id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Get the user that owns a project. Returns `None` for projects created
+    /// in desktop mode.
+    pub async fn find_owner(pool: &SqlitePool, id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let owner = sqlx::query_scalar!(
+            r#"SELECT user_id AS "user_id: Uuid" FROM projects WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(owner.flatten())
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
@@ -128,6 +237,7 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -148,6 +258,7 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      is_archived as "is_archived!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -176,6 +287,7 @@ impl Project {
                           name,
                           default_agent_working_dir,
                           remote_project_id as "remote_project_id: Uuid",
+                          is_archived as "is_archived!: bool",
                           created_at as "created_at!: DateTime<Utc>",
                           updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
@@ -205,6 +317,7 @@ impl Project {
                          name,
                          default_agent_working_dir,
                          remote_project_id as "remote_project_id: Uuid",
+                         is_archived as "is_archived!: bool",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -214,6 +327,60 @@ impl Project {
         .await
     }
 
+    /// Hide a project from default listings. Returns `None` if it does not exist.
+    pub async fn archive(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        Self::set_archived(pool, id, true).await
+    }
+
+    /// Return an archived project to default listings. Returns `None` if it
+    /// does not exist.
+    pub async fn unarchive(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        Self::set_archived(pool, id, false).await
+    }
+
+    async fn set_archived(
+        pool: &SqlitePool,
+        id: Uuid,
+        is_archived: bool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"UPDATE projects
+               SET is_archived = $2
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name,
+                         default_agent_working_dir,
+                         remote_project_id as "remote_project_id: Uuid",
+                         is_archived as "is_archived!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            is_archived
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Record the owning user of a project (multi-user mode)
+    pub async fn set_user_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE projects
+               SET user_id = $2
+               WHERE id = $1"#,
+            id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn set_remote_project_id(
         pool: &SqlitePool,
         id: Uuid,
@@ -221,7 +388,9 @@ impl Project {
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"UPDATE projects
-               SET remote_project_id = $2
+               SET remote_project_id = $2,
+                   last_synced_at = NULL,
+                   remote_project_name = NULL
                WHERE id = $1"#,
             id,
             remote_project_id
@@ -243,7 +412,9 @@ impl Project {
     {
         sqlx::query!(
             r#"UPDATE projects
-               SET remote_project_id = $2
+               SET remote_project_id = $2,
+                   last_synced_at = NULL,
+                   remote_project_name = NULL
                WHERE id = $1"#,
             id,
             remote_project_id
@@ -254,6 +425,52 @@ impl Project {
         Ok(())
     }
 
+    /// Apply the metadata of the linked remote project and record the sync.
+    /// Returns `None` if the project does not exist.
+    pub async fn apply_remote_sync(
+        pool: &SqlitePool,
+        id: Uuid,
+        remote_name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"UPDATE projects
+               SET name = $2,
+                   remote_project_name = $2,
+                   last_synced_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name,
+                         default_agent_working_dir,
+                         remote_project_id as "remote_project_id: Uuid",
+                         is_archived as "is_archived!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            remote_name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// When the project last synced from its remote project and the remote
+    /// name seen then. Returns `None` if the project does not exist.
+    pub async fn find_remote_sync(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Option<ProjectRemoteSync>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRemoteSync,
+            r#"SELECT last_synced_at as "last_synced_at: DateTime<Utc>",
+                      remote_project_name
+               FROM projects
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(pool)
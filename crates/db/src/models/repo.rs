@@ -148,6 +148,33 @@ impl Repo {
         .await
     }
 
+    /// Find the repo registered for `path`, regardless of which user added it.
+    ///
+    /// `repos.path` is unique in SQLite, so at most one row matches. PostgreSQL
+    /// only indexes `(user_id, path)`, so there path deduplication is advisory.
+    pub async fn find_by_path(pool: &SqlitePool, path: &Path) -> Result<Option<Self>, sqlx::Error> {
+        let path_str = path.to_string_lossy().to_string();
+        sqlx::query_as!(
+            Repo,
+            r#"SELECT id as "id!: Uuid",
+                      path,
+                      name,
+                      display_name,
+                      setup_script,
+                      cleanup_script,
+                      copy_files,
+                      parallel_setup_script as "parallel_setup_script!: bool",
+                      dev_server_script,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM repos
+               WHERE path = $1"#,
+            path_str
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_by_ids(pool: &SqlitePool, ids: &[Uuid]) -> Result<Vec<Self>, sqlx::Error> {
         if ids.is_empty() {
             return Ok(Vec::new());
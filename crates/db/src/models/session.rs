@@ -27,6 +27,10 @@ pub struct Session {
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateSession {
     pub executor: Option<String>,
+    /// Owning user in multi-user mode; `None` in desktop mode.
+    #[serde(default)]
+    #[ts(skip)]
+    pub user_id: Option<Uuid>,
 }
 
 impl Session {
@@ -75,6 +79,80 @@ impl Session {
         .await
     }
 
+    /// Find sessions for a workspace that are visible to `user_id`, ordered like
+    /// [`Session::find_by_workspace_id`]. Sessions recorded without an owner
+    /// belong to the workspace owner. Passing `None` disables the owner filter.
+    pub async fn find_by_workspace_id_and_user(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Session,
+            r#"SELECT s.id AS "id!: Uuid",
+                      s.workspace_id AS "workspace_id!: Uuid",
+                      s.executor,
+                      s.created_at AS "created_at!: DateTime<Utc>",
+                      s.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM sessions s
+               LEFT JOIN (
+                   SELECT ep.session_id, MAX(ep.created_at) as last_used
+                   FROM execution_processes ep
+                   WHERE ep.run_reason != 'devserver' AND ep.dropped = FALSE
+                   GROUP BY ep.session_id
+               ) latest_ep ON s.id = latest_ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE s.workspace_id = $1
+                 AND (COALESCE(s.user_id, w.user_id) = $2 OR $2 IS NULL)
+               ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC"#,
+            workspace_id,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Find the sessions of a workspace that are still active: their latest
+    /// execution process is running, or they have not run anything yet.
+    /// Filtering and ordering match [`Session::find_by_workspace_id_and_user`].
+    pub async fn find_active_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Session,
+            r#"SELECT s.id AS "id!: Uuid",
+                      s.workspace_id AS "workspace_id!: Uuid",
+                      s.executor,
+                      s.created_at AS "created_at!: DateTime<Utc>",
+                      s.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM sessions s
+               LEFT JOIN (
+                   SELECT ep.session_id, MAX(ep.created_at) as last_used
+                   FROM execution_processes ep
+                   WHERE ep.run_reason != 'devserver' AND ep.dropped = FALSE
+                   GROUP BY ep.session_id
+               ) latest_ep ON s.id = latest_ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE s.workspace_id = $1
+                 AND (COALESCE(s.user_id, w.user_id) = $2 OR $2 IS NULL)
+                 AND COALESCE(
+                     (SELECT ep.status
+                      FROM execution_processes ep
+                      WHERE ep.session_id = s.id AND ep.dropped = FALSE
+                      ORDER BY ep.created_at DESC
+                      LIMIT 1),
+                     'running'
+                 ) = 'running'
+               ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC"#,
+            workspace_id,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find the most recently used session for a workspace.
     /// "Most recently used" is defined as the most recent non-dev server execution process.
     /// Sessions with no executions fall back to created_at for ordering.
@@ -105,6 +183,9 @@ impl Session {
         .await
     }
 
+    /// Create a session. When `data.user_id` is `None` the session inherits
+    /// the owner of its workspace, so sessions started by the system stay
+    /// visible to the workspace owner in multi-user mode.
     pub async fn create(
         pool: &SqlitePool,
         data: &CreateSession,
@@ -113,8 +194,8 @@ impl Session {
     ) -> Result<Self, SessionError> {
         Ok(sqlx::query_as!(
             Session,
-            r#"INSERT INTO sessions (id, workspace_id, executor)
-               VALUES ($1, $2, $3)
+            r#"INSERT INTO sessions (id, workspace_id, executor, user_id)
+               VALUES ($1, $2, $3, COALESCE($4, (SELECT user_id FROM workspaces WHERE id = $2)))
                RETURNING id AS "id!: Uuid",
                          workspace_id AS "workspace_id!: Uuid",
                          executor,
@@ -122,9 +203,53 @@ impl Session {
                          updated_at AS "updated_at!: DateTime<Utc>""#,
             id,
             workspace_id,
-            data.executor
+            data.executor,
+            data.user_id
         )
         .fetch_one(pool)
         .await?)
     }
+
+    /// Get the user that owns a session, falling back to the owner of its
+    /// workspace for sessions recorded without one. Returns `None` for
+    /// sessions created in desktop mode.
+    pub async fn find_owner(pool: &SqlitePool, id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let owner = sqlx::query_scalar!(
+            r#"SELECT COALESCE(s.user_id, w.user_id) AS "user_id: Uuid"
+               FROM sessions s
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE s.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(owner.flatten())
+    }
+
+    /// Count sessions in the workspaces of a project's non-deleted tasks
+    pub async fn count_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM sessions s
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1 AND t.deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count as u64)
+    }
+
+    /// Delete a session. Its execution processes are removed by the
+    /// `ON DELETE CASCADE` foreign key.
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM sessions WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
 }
@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
+use futures_core::stream::BoxStream;
 use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
@@ -8,7 +12,18 @@ use uuid::Uuid;
 use super::{project::Project, workspace::Workspace};
 
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug,
+    Clone,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    TS,
+    EnumString,
+    Display,
+    Default,
 )]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -32,6 +47,7 @@ pub struct Task {
     pub parent_workspace_id: Option<Uuid>, // Foreign key to parent Workspace
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>, // Set when the task is soft-deleted
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -57,6 +73,20 @@ impl std::ops::DerefMut for TaskWithAttemptStatus {
     }
 }
 
+/// A single task field change, recorded by [`Task::update`]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskHistoryEntry {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Option<Uuid>, // NULL in desktop mode
+    pub changed_at: DateTime<Utc>,
+    pub field: String,
+    #[ts(type = "JsonValue")]
+    pub old_value: sqlx::types::Json<Value>,
+    #[ts(type = "JsonValue")]
+    pub new_value: sqlx::types::Json<Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct TaskRelationships {
     pub parent_task: Option<Task>, // The task that owns the parent workspace
@@ -127,6 +157,7 @@ impl Task {
   t.parent_workspace_id           AS "parent_workspace_id: Uuid",
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
+  t.deleted_at                    AS "deleted_at: DateTime<Utc>",
 
   CASE WHEN EXISTS (
     SELECT 1
@@ -135,7 +166,7 @@ impl Task {
       JOIN execution_processes ep ON ep.session_id = s.id
      WHERE w.task_id       = t.id
        AND ep.status        = 'running'
-       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
      LIMIT 1
   ) THEN 1 ELSE 0 END            AS "has_in_progress_attempt!: i64",
 
@@ -145,7 +176,7 @@ impl Task {
       JOIN sessions s ON s.workspace_id = w.id
       JOIN execution_processes ep ON ep.session_id = s.id
      WHERE w.task_id       = t.id
-     AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+     AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
      ORDER BY ep.created_at DESC
      LIMIT 1
   ) IN ('failed','killed') THEN 1 ELSE 0 END
@@ -161,6 +192,7 @@ impl Task {
 
 FROM tasks t
 WHERE t.project_id = $1
+  AND t.deleted_at IS NULL
 ORDER BY t.created_at DESC"#,
             project_id
         )
@@ -179,6 +211,97 @@ ORDER BY t.created_at DESC"#,
                     parent_workspace_id: rec.parent_workspace_id,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
+                    deleted_at: rec.deleted_at,
+                },
+                has_in_progress_attempt: rec.has_in_progress_attempt != 0,
+                last_attempt_failed: rec.last_attempt_failed != 0,
+                executor: rec.executor,
+            })
+            .collect();
+
+        Ok(tasks)
+    }
+
+    /// Fetch a page of a project's tasks with attempt status, using keyset
+    /// pagination on `id`. Returns up to `limit` tasks with `id > after`,
+    /// ordered by `id`. Soft-deleted tasks are skipped unless `include_deleted`.
+    pub async fn find_by_project_id_with_attempt_status_paginated(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        after: Option<Uuid>,
+        limit: i64,
+        include_deleted: bool,
+    ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT
+  t.id                            AS "id!: Uuid",
+  t.project_id                    AS "project_id!: Uuid",
+  t.title,
+  t.description,
+  t.status                        AS "status!: TaskStatus",
+  t.parent_workspace_id           AS "parent_workspace_id: Uuid",
+  t.created_at                    AS "created_at!: DateTime<Utc>",
+  t.updated_at                    AS "updated_at!: DateTime<Utc>",
+  t.deleted_at                    AS "deleted_at: DateTime<Utc>",
+
+  CASE WHEN EXISTS (
+    SELECT 1
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+      JOIN execution_processes ep ON ep.session_id = s.id
+     WHERE w.task_id       = t.id
+       AND ep.status        = 'running'
+       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
+     LIMIT 1
+  ) THEN 1 ELSE 0 END            AS "has_in_progress_attempt!: i64",
+
+  CASE WHEN (
+    SELECT ep.status
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+      JOIN execution_processes ep ON ep.session_id = s.id
+     WHERE w.task_id       = t.id
+     AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
+     ORDER BY ep.created_at DESC
+     LIMIT 1
+  ) IN ('failed','killed') THEN 1 ELSE 0 END
+                                 AS "last_attempt_failed!: i64",
+
+  ( SELECT s.executor
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+      WHERE w.task_id = t.id
+     ORDER BY s.created_at DESC
+      LIMIT 1
+    )                               AS "executor!: String"
+
+FROM tasks t
+WHERE t.project_id = $1
+  AND ($2 IS NULL OR t.id > $2)
+  AND ($4 OR t.deleted_at IS NULL)
+ORDER BY t.id
+LIMIT $3"#,
+            project_id,
+            after,
+            limit,
+            include_deleted
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let tasks = records
+            .into_iter()
+            .map(|rec| TaskWithAttemptStatus {
+                task: Task {
+                    id: rec.id,
+                    project_id: rec.project_id,
+                    title: rec.title,
+                    description: rec.description,
+                    status: rec.status,
+                    parent_workspace_id: rec.parent_workspace_id,
+                    created_at: rec.created_at,
+                    updated_at: rec.updated_at,
+                    deleted_at: rec.deleted_at,
                 },
                 has_in_progress_attempt: rec.has_in_progress_attempt != 0,
                 last_attempt_failed: rec.last_attempt_failed != 0,
@@ -192,19 +315,101 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>"
                FROM tasks
-               WHERE id = $1"#,
+               WHERE id = $1 AND deleted_at IS NULL"#,
             id
         )
         .fetch_optional(pool)
         .await
     }
 
+    /// Find a task by ID, limited to tasks in `user_id`'s projects; `None`
+    /// disables the check.
+    pub async fn find_by_id_and_user(
+        pool: &SqlitePool,
+        id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>"
+               FROM tasks
+               WHERE id = $1
+                 AND deleted_at IS NULL
+                 AND ($2 IS NULL OR project_id IN (SELECT id FROM projects WHERE user_id = $2))"#,
+            id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Search tasks whose title or description contains `query` (case-insensitive
+    /// for ASCII), most recently created first. `user_id` limits results to the
+    /// user's projects and `project_id` to a single project; `None` disables
+    /// either filter.
+    pub async fn search(
+        pool: &SqlitePool,
+        user_id: Option<Uuid>,
+        query: &str,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        // Match `query` literally: escape LIKE wildcards
+        let pattern = format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_workspace_id as "parent_workspace_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>", t.deleted_at as "deleted_at: DateTime<Utc>"
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               WHERE (t.title LIKE $1 ESCAPE '\' OR t.description LIKE $1 ESCAPE '\')
+                 AND t.deleted_at IS NULL
+                 AND ($2 IS NULL OR p.user_id = $2)
+                 AND ($3 IS NULL OR t.project_id = $3)
+               ORDER BY t.created_at DESC"#,
+            pattern,
+            user_id,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Stream a project's non-deleted tasks, oldest first, without loading them
+    /// all into memory. `user_id` limits results to the user's projects; `None`
+    /// disables the filter.
+    pub fn stream_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> BoxStream<'_, Result<Self, sqlx::Error>> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_workspace_id as "parent_workspace_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>", t.deleted_at as "deleted_at: DateTime<Utc>"
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.project_id = $1
+                 AND t.deleted_at IS NULL
+                 AND ($2 IS NULL OR p.user_id = $2)
+               ORDER BY t.created_at ASC"#,
+            project_id,
+            user_id
+        )
+        .fetch(pool)
+    }
+
+    /// Look up a task by SQLite rowid, including soft-deleted tasks, so change
+    /// hooks can tell a soft delete apart from an ordinary update
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -223,7 +428,7 @@ ORDER BY t.created_at DESC"#,
             Task,
             r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id)
                VALUES ($1, $2, $3, $4, $5, $6)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
@@ -235,6 +440,34 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    /// Insert several tasks in one transaction, so either all of them are
+    /// created or none are. Returns the number of tasks created.
+    pub async fn bulk_create(pool: &SqlitePool, tasks: &[CreateTask]) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for data in tasks {
+            let id = Uuid::new_v4();
+            let status = data.status.clone().unwrap_or_default();
+            sqlx::query!(
+                "INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                id,
+                data.project_id,
+                data.title,
+                data.description,
+                status,
+                data.parent_workspace_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(tasks.len() as u64)
+    }
+
+    /// Update a task, recording each changed field in the task history with
+    /// `user_id` as the author (`None` in desktop mode).
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &SqlitePool,
         id: Uuid,
@@ -243,13 +476,16 @@ ORDER BY t.created_at DESC"#,
         description: Option<String>,
         status: TaskStatus,
         parent_workspace_id: Option<Uuid>,
+        user_id: Option<Uuid>,
     ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
+        let before = Self::find_by_id(pool, id).await?;
+
+        let task = sqlx::query_as!(
             Task,
             r#"UPDATE tasks
                SET title = $3, description = $4, status = $5, parent_workspace_id = $6
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>""#,
             id,
             project_id,
             title,
@@ -258,9 +494,181 @@ ORDER BY t.created_at DESC"#,
             parent_workspace_id
         )
         .fetch_one(pool)
+        .await?;
+
+        if let Some(before) = before {
+            for (field, old, new) in before.changed_fields(&task) {
+                Self::record_change(pool, task.id, user_id, field, old, new).await?;
+            }
+        }
+
+        Ok(task)
+    }
+
+    /// User-editable fields that differ between `self` and `updated`, as
+    /// `(field, old, new)` JSON values
+    fn changed_fields(&self, updated: &Task) -> Vec<(&'static str, Value, Value)> {
+        let mut changes = Vec::new();
+        if self.title != updated.title {
+            changes.push(("title", json!(self.title), json!(updated.title)));
+        }
+        if self.description != updated.description {
+            changes.push((
+                "description",
+                json!(self.description),
+                json!(updated.description),
+            ));
+        }
+        if self.status != updated.status {
+            changes.push(("status", json!(self.status), json!(updated.status)));
+        }
+        if self.parent_workspace_id != updated.parent_workspace_id {
+            changes.push((
+                "parent_workspace_id",
+                json!(self.parent_workspace_id),
+                json!(updated.parent_workspace_id),
+            ));
+        }
+        changes
+    }
+
+    /// Append a field change to the task history
+    pub async fn record_change<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+        field: &str,
+        old: Value,
+        new: Value,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        let old_value = sqlx::types::Json(&old);
+        let new_value = sqlx::types::Json(&new);
+        sqlx::query!(
+            r#"INSERT INTO task_history (id, task_id, user_id, field, old_value, new_value)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+            id,
+            task_id,
+            user_id,
+            field,
+            old_value,
+            new_value
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch the change history of a task, oldest first. `user_id` limits
+    /// results to tasks in the user's projects; `None` disables the filter.
+    pub async fn find_history(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<TaskHistoryEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskHistoryEntry,
+            r#"SELECT h.id as "id!: Uuid", h.task_id as "task_id!: Uuid", h.user_id as "user_id: Uuid", h.changed_at as "changed_at!: DateTime<Utc>", h.field, h.old_value as "old_value!: sqlx::types::Json<Value>", h.new_value as "new_value!: sqlx::types::Json<Value>"
+               FROM task_history h
+               JOIN tasks t ON t.id = h.task_id
+               WHERE h.task_id = $1
+                 AND ($2 IS NULL OR t.project_id IN (SELECT id FROM projects WHERE user_id = $2))
+               ORDER BY h.changed_at ASC, h.rowid ASC"#,
+            task_id,
+            user_id
+        )
+        .fetch_all(pool)
         .await
     }
 
+    /// Set the status of several tasks at once, recording each change in the
+    /// task history. `user_id` limits the update to tasks in that user's
+    /// projects; `None` disables the check. Missing, deleted and foreign tasks
+    /// are skipped. Returns the number of tasks updated.
+    pub async fn bulk_update_status(
+        pool: &SqlitePool,
+        ids: &[Uuid],
+        status: TaskStatus,
+        user_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        let mut ids = ids.to_vec();
+        ids.sort();
+        ids.dedup();
+
+        let mut tx = pool.begin().await?;
+        let mut updated = 0;
+        for id in ids {
+            let previous = sqlx::query_scalar!(
+                r#"SELECT status as "status!: TaskStatus"
+                   FROM tasks
+                   WHERE id = $1
+                     AND deleted_at IS NULL
+                     AND ($2 IS NULL OR project_id IN (SELECT id FROM projects WHERE user_id = $2))"#,
+                id,
+                user_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            let Some(previous) = previous else {
+                continue;
+            };
+
+            sqlx::query!(
+                "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                id,
+                status
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            if previous != status {
+                Self::record_change(
+                    &mut *tx,
+                    id,
+                    user_id,
+                    "status",
+                    json!(previous),
+                    json!(status),
+                )
+                .await?;
+            }
+            updated += 1;
+        }
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    /// Count non-deleted tasks per status. `project_id` limits the count to
+    /// one project and `user_id` to that user's projects; `None` disables
+    /// either filter. Statuses without tasks are absent from the map.
+    pub async fn count_by_status(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        user_id: Option<Uuid>,
+    ) -> Result<HashMap<TaskStatus, u64>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT status as "status!: TaskStatus", COUNT(*) as "count!: i64"
+               FROM tasks
+               WHERE deleted_at IS NULL
+                 AND ($1 IS NULL OR project_id = $1)
+                 AND ($2 IS NULL OR project_id IN (SELECT id FROM projects WHERE user_id = $2))
+               GROUP BY status"#,
+            project_id,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.status, row.count as u64))
+            .collect())
+    }
+
     pub async fn update_status(
         pool: &SqlitePool,
         id: Uuid,
@@ -310,13 +718,149 @@ ORDER BY t.created_at DESC"#,
         Ok(result.rows_affected())
     }
 
+    /// Soft-delete a task by stamping `deleted_at`. The row is kept so the
+    /// task can be brought back with [`Task::restore`].
     pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<u64, sqlx::Error>
     where
         E: Executor<'e, Database = Sqlite>,
     {
-        let result = sqlx::query!("DELETE FROM tasks WHERE id = $1", id)
-            .execute(executor)
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET deleted_at = datetime('now', 'subsec'), updated_at = CURRENT_TIMESTAMP
+               WHERE id = $1 AND deleted_at IS NULL"#,
+            id
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Move a task to another project, recording the change in the task
+    /// history. `user_id` requires both the task's current project and the
+    /// target project to belong to that user; `None` disables the check.
+    /// Workspaces follow the task, as they reference it rather than the
+    /// project. Returns `None` if the task or target project is not found.
+    pub async fn move_to_project(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        target_project_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let target_exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM projects WHERE id = $1 AND ($2 IS NULL OR user_id = $2)
+               ) as "exists!: bool""#,
+            target_project_id,
+            user_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if !target_exists {
+            return Ok(None);
+        }
+
+        let previous = sqlx::query_scalar!(
+            r#"SELECT project_id as "project_id!: Uuid"
+               FROM tasks
+               WHERE id = $1
+                 AND deleted_at IS NULL
+                 AND ($2 IS NULL OR project_id IN (SELECT id FROM projects WHERE user_id = $2))"#,
+            task_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(previous) = previous else {
+            return Ok(None);
+        };
+
+        let task = sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET project_id = $2, updated_at = CURRENT_TIMESTAMP
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>""#,
+            task_id,
+            target_project_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if previous != target_project_id {
+            Self::record_change(
+                &mut *tx,
+                task_id,
+                user_id,
+                "project_id",
+                json!(previous),
+                json!(target_project_id),
+            )
             .await?;
+        }
+        tx.commit().await?;
+
+        Ok(Some(task))
+    }
+
+    /// Clear `deleted_at` on a soft-deleted task. `user_id` limits the restore
+    /// to tasks in that user's projects; `None` disables the check. Returns
+    /// `None` if no matching deleted task exists.
+    pub async fn restore(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET deleted_at = NULL, updated_at = CURRENT_TIMESTAMP
+               WHERE id = $1
+                 AND deleted_at IS NOT NULL
+                 AND ($2 IS NULL OR project_id IN (SELECT id FROM projects WHERE user_id = $2))
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>""#,
+            task_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find a soft-deleted task. `user_id` limits the lookup to tasks in that
+    /// user's projects; `None` disables the check.
+    pub async fn find_deleted_by_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>"
+               FROM tasks
+               WHERE id = $1
+                 AND deleted_at IS NOT NULL
+                 AND ($2 IS NULL OR project_id IN (SELECT id FROM projects WHERE user_id = $2))"#,
+            task_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Permanently delete a soft-deleted task (FK CASCADE removes its
+    /// workspaces). Child tasks must be detached with
+    /// [`Task::nullify_children_by_workspace_id`] first.
+    pub async fn purge<'e, E>(executor: E, id: Uuid) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            "DELETE FROM tasks WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(executor)
+        .await?;
         Ok(result.rows_affected())
     }
 
@@ -327,9 +871,9 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this workspace as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>"
                FROM tasks
-               WHERE parent_workspace_id = $1
+               WHERE parent_workspace_id = $1 AND deleted_at IS NULL
                ORDER BY created_at DESC"#,
             workspace_id,
         )
@@ -108,7 +108,9 @@ impl Workspace {
         Task::find_by_id(pool, self.task_id).await
     }
 
-    /// Fetch all workspaces, optionally filtered by task_id. Newest first.
+    /// Fetch all workspaces, optionally filtered by task_id. A task's workspaces
+    /// are newest first; the unfiltered listing puts pinned workspaces first,
+    /// then the most recently updated.
     pub async fn fetch_all(
         pool: &SqlitePool,
         task_id: Option<Uuid>,
@@ -149,7 +151,7 @@ impl Workspace {
                               pinned AS "pinned!: bool",
                               name
                        FROM workspaces
-                       ORDER BY created_at DESC"#
+                       ORDER BY pinned DESC, updated_at DESC"#
             )
             .fetch_all(pool)
             .await
@@ -276,6 +278,123 @@ impl Workspace {
         .await
     }
 
+    /// Find a workspace by ID that is visible to `user_id`. Passing `None`
+    /// disables the owner filter, matching [`Workspace::find_by_id`].
+    pub async fn find_by_id_and_user(
+        pool: &SqlitePool,
+        id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       agent_working_dir,
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>",
+                       archived          AS "archived!: bool",
+                       pinned            AS "pinned!: bool",
+                       name
+               FROM    workspaces
+               WHERE   id = $1
+                 AND   (user_id = $2 OR $2 IS NULL)"#,
+            id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Get the user that owns a workspace. Returns `None` for workspaces
+    /// created in desktop mode.
+    pub async fn find_owner(pool: &SqlitePool, id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let owner = sqlx::query_scalar!(
+            r#"SELECT user_id AS "user_id: Uuid" FROM workspaces WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(owner.flatten())
+    }
+
+    /// Fetch all workspaces owned by a user (multi-user mode). Pinned first,
+    /// then most recently updated.
+    pub async fn find_by_user(pool: &SqlitePool, user_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       agent_working_dir,
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>",
+                       archived          AS "archived!: bool",
+                       pinned            AS "pinned!: bool",
+                       name
+               FROM    workspaces
+               WHERE   user_id = $1
+               ORDER BY pinned DESC, updated_at DESC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Fetch the workspaces of a task that are visible to `user_id`, newest
+    /// first. Passing `None` disables the owner filter.
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       agent_working_dir,
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>",
+                       archived          AS "archived!: bool",
+                       pinned            AS "pinned!: bool",
+                       name
+               FROM    workspaces
+               WHERE   task_id = $1
+                 AND   (user_id = $2 OR $2 IS NULL)
+               ORDER BY created_at DESC"#,
+            task_id,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record the owning user of a workspace (multi-user mode)
+    pub async fn set_user_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE workspaces
+               SET user_id = $2
+               WHERE id = $1"#,
+            id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Workspace,
@@ -470,6 +589,112 @@ impl Workspace {
         Ok(())
     }
 
+    /// Archive a workspace.
+    pub async fn archive(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        Self::set_archived(pool, workspace_id, true).await
+    }
+
+    /// Fetch the archived workspaces visible to `user_id`, most recently
+    /// updated first. Passing `None` disables the owner filter.
+    pub async fn find_archived(
+        pool: &SqlitePool,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       agent_working_dir,
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>",
+                       archived          AS "archived!: bool",
+                       pinned            AS "pinned!: bool",
+                       name
+               FROM    workspaces
+               WHERE   archived = 1
+                 AND   (user_id = $1 OR $1 IS NULL)
+               ORDER BY updated_at DESC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Archive every unpinned workspace visible to `user_id` that has not been
+    /// updated for `age`. Passing `None` disables the owner filter. Returns the
+    /// number of workspaces archived.
+    pub async fn archive_older_than(
+        pool: &SqlitePool,
+        age: chrono::Duration,
+        user_id: Option<Uuid>,
+    ) -> Result<u32, sqlx::Error> {
+        let cutoff = Utc::now() - age;
+        let result = sqlx::query!(
+            r#"UPDATE workspaces
+               SET    archived = 1, updated_at = datetime('now', 'subsec')
+               WHERE  archived = 0
+                 AND  pinned = 0
+                 AND  datetime(updated_at) < datetime($1)
+                 AND  (user_id = $2 OR $2 IS NULL)"#,
+            cutoff,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() as u32)
+    }
+
+    /// Pin a workspace so it is listed first. Returns `None` if it does not exist.
+    pub async fn pin(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        Self::set_pinned(pool, id, true).await
+    }
+
+    /// Unpin a workspace. Returns `None` if it does not exist.
+    pub async fn unpin(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        Self::set_pinned(pool, id, false).await
+    }
+
+    async fn set_pinned(
+        pool: &SqlitePool,
+        id: Uuid,
+        pinned: bool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"UPDATE workspaces
+               SET pinned = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, agent_working_dir, setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived as "archived!: bool", pinned as "pinned!: bool", name"#,
+            id,
+            pinned
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Set the directory agents run in, relative to the workspace root; `None`
+    /// runs them in the root. Returns `None` if the workspace does not exist.
+    pub async fn set_agent_working_dir(
+        pool: &SqlitePool,
+        id: Uuid,
+        agent_working_dir: Option<&str>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"UPDATE workspaces
+               SET agent_working_dir = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, agent_working_dir, setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", archived as "archived!: bool", pinned as "pinned!: bool", name"#,
+            id,
+            agent_working_dir
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Update workspace fields. Only non-None values will be updated.
     /// For `name`, pass `Some("")` to clear the name, `Some("foo")` to set it, or `None` to leave unchanged.
     pub async fn update(
@@ -562,7 +787,7 @@ impl Workspace {
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
                       AND ep.status = 'running'
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                     LIMIT 1
                 ) THEN 1 ELSE 0 END AS "is_running!: i64",
 
@@ -571,13 +796,13 @@ impl Workspace {
                     FROM sessions s
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                     ORDER BY ep.created_at DESC
                     LIMIT 1
                 ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
 
             FROM workspaces w
-            ORDER BY w.updated_at DESC"#
+            ORDER BY w.pinned DESC, w.updated_at DESC"#
         )
         .fetch_all(pool)
         .await?;
@@ -605,7 +830,7 @@ impl Workspace {
             .filter(|ws| archived.is_none_or(|a| ws.workspace.archived == a))
             .collect();
 
-        // Apply limit if provided (already sorted by updated_at DESC from query)
+        // Apply limit if provided (already sorted pinned first, then by updated_at DESC)
         if let Some(lim) = limit {
             workspaces.truncate(lim as usize);
         }
@@ -639,6 +864,23 @@ impl Workspace {
             .map_err(WorkspaceError::Database)
     }
 
+    /// Count workspaces of the non-deleted tasks in a project
+    pub async fn count_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1 AND t.deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count as u64)
+    }
+
     pub async fn find_by_id_with_status(
         pool: &SqlitePool,
         id: Uuid,
@@ -663,7 +905,7 @@ impl Workspace {
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
                       AND ep.status = 'running'
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                     LIMIT 1
                 ) THEN 1 ELSE 0 END AS "is_running!: i64",
 
@@ -672,7 +914,7 @@ impl Workspace {
                     FROM sessions s
                     JOIN execution_processes ep ON ep.session_id = s.id
                     WHERE s.workspace_id = w.id
-                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                     ORDER BY ep.created_at DESC
                     LIMIT 1
                 ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
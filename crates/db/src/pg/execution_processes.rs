@@ -4,12 +4,14 @@
 //! that include user_id filtering for multi-tenant isolation in Kubernetes deployments.
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::execution_process::{
-    ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
-    ExecutorActionField, LatestProcessInfo,
+use crate::{
+    models::execution_process::{
+        ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus, ExecutorActionField,
+        LatestProcessInfo,
+    },
+    pg::InstrumentedPool,
 };
 
 /// Find execution process by ID, ensuring it belongs to the specified user.
@@ -24,12 +26,13 @@ use crate::models::execution_process::{
 ///
 /// The execution process if found and owned by the user, None otherwise.
 pub async fn find_by_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<ExecutionProcess>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             session_id,
             run_reason,
@@ -39,15 +42,16 @@ pub async fn find_by_id_for_user(
             dropped,
             started_at,
             completed_at,
+            paused_at,
+            cancelled_reason,
             created_at,
             updated_at
         FROM execution_processes
         WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| ExecutionProcess {
         id: r.id,
@@ -56,6 +60,7 @@ pub async fn find_by_id_for_user(
             "setupscript" => ExecutionProcessRunReason::SetupScript,
             "cleanupscript" => ExecutionProcessRunReason::CleanupScript,
             "codingagent" => ExecutionProcessRunReason::CodingAgent,
+            "review" => ExecutionProcessRunReason::Review,
             "devserver" => ExecutionProcessRunReason::DevServer,
             _ => ExecutionProcessRunReason::CodingAgent,
         },
@@ -66,12 +71,15 @@ pub async fn find_by_id_for_user(
             "completed" => ExecutionProcessStatus::Completed,
             "failed" => ExecutionProcessStatus::Failed,
             "killed" => ExecutionProcessStatus::Killed,
+            "cancelled" => ExecutionProcessStatus::Cancelled,
             _ => ExecutionProcessStatus::Running,
         },
         exit_code: r.exit_code,
         dropped: r.dropped,
         started_at: r.started_at,
         completed_at: r.completed_at,
+        paused_at: r.paused_at,
+        cancelled_reason: r.cancelled_reason,
         created_at: r.created_at,
         updated_at: r.updated_at,
     }))
@@ -90,13 +98,14 @@ pub async fn find_by_id_for_user(
 ///
 /// A vector of execution processes.
 pub async fn find_by_session_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     session_id: Uuid,
     show_soft_deleted: bool,
 ) -> Result<Vec<ExecutionProcess>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             id,
             session_id,
             run_reason,
@@ -106,18 +115,19 @@ pub async fn find_by_session_id_for_user(
             dropped,
             started_at,
             completed_at,
+            paused_at,
+            cancelled_reason,
             created_at,
             updated_at
         FROM execution_processes
         WHERE session_id = $1 AND user_id = $2
           AND ($3 OR dropped = FALSE)
         ORDER BY created_at ASC"#,
-        session_id,
-        user_id,
-        show_soft_deleted
-    )
-    .fetch_all(pool)
-    .await?;
+            session_id,
+            user_id,
+            show_soft_deleted
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -128,6 +138,7 @@ pub async fn find_by_session_id_for_user(
                 "setupscript" => ExecutionProcessRunReason::SetupScript,
                 "cleanupscript" => ExecutionProcessRunReason::CleanupScript,
                 "codingagent" => ExecutionProcessRunReason::CodingAgent,
+                "review" => ExecutionProcessRunReason::Review,
                 "devserver" => ExecutionProcessRunReason::DevServer,
                 _ => ExecutionProcessRunReason::CodingAgent,
             },
@@ -138,12 +149,15 @@ pub async fn find_by_session_id_for_user(
                 "completed" => ExecutionProcessStatus::Completed,
                 "failed" => ExecutionProcessStatus::Failed,
                 "killed" => ExecutionProcessStatus::Killed,
+                "cancelled" => ExecutionProcessStatus::Cancelled,
                 _ => ExecutionProcessStatus::Running,
             },
             exit_code: r.exit_code,
             dropped: r.dropped,
             started_at: r.started_at,
             completed_at: r.completed_at,
+            paused_at: r.paused_at,
+            cancelled_reason: r.cancelled_reason,
             created_at: r.created_at,
             updated_at: r.updated_at,
         })
@@ -161,11 +175,12 @@ pub async fn find_by_session_id_for_user(
 ///
 /// A vector of running execution processes.
 pub async fn find_running_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
 ) -> Result<Vec<ExecutionProcess>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             id,
             session_id,
             run_reason,
@@ -175,15 +190,16 @@ pub async fn find_running_for_user(
             dropped,
             started_at,
             completed_at,
+            paused_at,
+            cancelled_reason,
             created_at,
             updated_at
         FROM execution_processes
         WHERE user_id = $1 AND status = 'running'
         ORDER BY created_at ASC"#,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -194,6 +210,7 @@ pub async fn find_running_for_user(
                 "setupscript" => ExecutionProcessRunReason::SetupScript,
                 "cleanupscript" => ExecutionProcessRunReason::CleanupScript,
                 "codingagent" => ExecutionProcessRunReason::CodingAgent,
+                "review" => ExecutionProcessRunReason::Review,
                 "devserver" => ExecutionProcessRunReason::DevServer,
                 _ => ExecutionProcessRunReason::CodingAgent,
             },
@@ -204,6 +221,8 @@ pub async fn find_running_for_user(
             dropped: r.dropped,
             started_at: r.started_at,
             completed_at: r.completed_at,
+            paused_at: r.paused_at,
+            cancelled_reason: r.cancelled_reason,
             created_at: r.created_at,
             updated_at: r.updated_at,
         })
@@ -222,14 +241,15 @@ pub async fn find_running_for_user(
 ///
 /// A vector of running dev server execution processes.
 pub async fn find_running_dev_servers_by_project_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     project_id: Uuid,
 ) -> Result<Vec<ExecutionProcess>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT ep.id, ep.session_id, ep.run_reason, ep.executor_action,
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT ep.id, ep.session_id, ep.run_reason, ep.executor_action,
                   ep.status, ep.exit_code, ep.dropped, ep.started_at,
-                  ep.completed_at, ep.created_at, ep.updated_at
+                  ep.completed_at, ep.paused_at, ep.cancelled_reason, ep.created_at, ep.updated_at
         FROM execution_processes ep
         JOIN sessions s ON ep.session_id = s.id
         JOIN workspaces w ON s.workspace_id = w.id
@@ -239,11 +259,10 @@ pub async fn find_running_dev_servers_by_project_for_user(
           AND ep.run_reason = 'devserver'
           AND t.project_id = $2
         ORDER BY ep.created_at ASC"#,
-        user_id,
-        project_id
-    )
-    .fetch_all(pool)
-    .await?;
+            user_id,
+            project_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -258,6 +277,8 @@ pub async fn find_running_dev_servers_by_project_for_user(
             dropped: r.dropped,
             started_at: r.started_at,
             completed_at: r.completed_at,
+            paused_at: r.paused_at,
+            cancelled_reason: r.cancelled_reason,
             created_at: r.created_at,
             updated_at: r.updated_at,
         })
@@ -276,23 +297,23 @@ pub async fn find_running_dev_servers_by_project_for_user(
 ///
 /// True if there are running non-dev-server processes.
 pub async fn has_running_non_dev_server_processes_for_workspace_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<bool, sqlx::Error> {
-    let count: i64 = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!"
+    let count: i64 = pool
+        .fetch_one(sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!"
         FROM execution_processes ep
         JOIN sessions s ON ep.session_id = s.id
         WHERE s.workspace_id = $1
           AND ep.user_id = $2
           AND ep.status = 'running'
           AND ep.run_reason != 'devserver'"#,
-        workspace_id,
-        user_id
-    )
-    .fetch_one(pool)
-    .await?;
+            workspace_id,
+            user_id
+        ))
+        .await?;
     Ok(count > 0)
 }
 
@@ -308,14 +329,15 @@ pub async fn has_running_non_dev_server_processes_for_workspace_for_user(
 ///
 /// A vector of running dev server execution processes.
 pub async fn find_running_dev_servers_by_workspace_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<Vec<ExecutionProcess>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT ep.id, ep.session_id, ep.run_reason, ep.executor_action,
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT ep.id, ep.session_id, ep.run_reason, ep.executor_action,
                   ep.status, ep.exit_code, ep.dropped, ep.started_at,
-                  ep.completed_at, ep.created_at, ep.updated_at
+                  ep.completed_at, ep.paused_at, ep.cancelled_reason, ep.created_at, ep.updated_at
         FROM execution_processes ep
         JOIN sessions s ON ep.session_id = s.id
         WHERE s.workspace_id = $1
@@ -323,11 +345,10 @@ pub async fn find_running_dev_servers_by_workspace_for_user(
           AND ep.status = 'running'
           AND ep.run_reason = 'devserver'
         ORDER BY ep.created_at DESC"#,
-        workspace_id,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            workspace_id,
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -342,6 +363,8 @@ pub async fn find_running_dev_servers_by_workspace_for_user(
             dropped: r.dropped,
             started_at: r.started_at,
             completed_at: r.completed_at,
+            paused_at: r.paused_at,
+            cancelled_reason: r.cancelled_reason,
             created_at: r.created_at,
             updated_at: r.updated_at,
         })
@@ -362,7 +385,7 @@ pub async fn find_running_dev_servers_by_workspace_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn update_completion_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     status: ExecutionProcessStatus,
@@ -379,17 +402,17 @@ pub async fn update_completion_for_user(
         ExecutionProcessStatus::Completed => "completed",
         ExecutionProcessStatus::Failed => "failed",
         ExecutionProcessStatus::Killed => "killed",
+        ExecutionProcessStatus::Cancelled => "cancelled",
     };
 
-    let result = sqlx::query!(
-        "UPDATE execution_processes SET status = $3, exit_code = $4, completed_at = $5, updated_at = NOW() WHERE id = $1 AND user_id = $2",
+    let result = pool.execute(sqlx::query!(
+        "UPDATE execution_processes SET status = $3, exit_code = $4, completed_at = $5, paused_at = NULL, updated_at = NOW() WHERE id = $1 AND user_id = $2",
         id,
         user_id,
         status_str,
         exit_code,
         completed_at
-    )
-    .execute(pool)
+    ))
     .await?;
 
     if result.rows_affected() == 0 {
@@ -412,24 +435,24 @@ pub async fn update_completion_for_user(
 ///
 /// The number of rows affected.
 pub async fn drop_at_and_after_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     session_id: Uuid,
     boundary_process_id: Uuid,
 ) -> Result<i64, sqlx::Error> {
-    let result = sqlx::query!(
-        r#"UPDATE execution_processes
+    let result = pool
+        .execute(sqlx::query!(
+            r#"UPDATE execution_processes
         SET dropped = TRUE, updated_at = NOW()
         WHERE session_id = $1
           AND user_id = $2
           AND created_at >= (SELECT created_at FROM execution_processes WHERE id = $3)
           AND dropped = FALSE"#,
-        session_id,
-        user_id,
-        boundary_process_id
-    )
-    .execute(pool)
-    .await?;
+            session_id,
+            user_id,
+            boundary_process_id
+        ))
+        .await?;
     Ok(result.rows_affected() as i64)
 }
 
@@ -445,12 +468,13 @@ pub async fn drop_at_and_after_for_user(
 ///
 /// A vector of latest process info for workspaces.
 pub async fn find_latest_for_workspaces_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     archived: bool,
 ) -> Result<Vec<LatestProcessInfo>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             s.workspace_id,
             ep.id as execution_process_id,
             ep.session_id,
@@ -461,7 +485,7 @@ pub async fn find_latest_for_workspaces_for_user(
         JOIN workspaces w ON s.workspace_id = w.id
         WHERE w.archived = $1
           AND ep.user_id = $2
-          AND ep.run_reason IN ('codingagent', 'setupscript', 'cleanupscript')
+          AND ep.run_reason IN ('codingagent', 'review', 'setupscript', 'cleanupscript')
           AND ep.dropped = FALSE
           AND ep.created_at = (
               SELECT MAX(ep2.created_at)
@@ -469,14 +493,13 @@ pub async fn find_latest_for_workspaces_for_user(
               JOIN sessions s2 ON ep2.session_id = s2.id
               WHERE s2.workspace_id = s.workspace_id
                 AND ep2.user_id = $2
-                AND ep2.run_reason IN ('codingagent', 'setupscript', 'cleanupscript')
+                AND ep2.run_reason IN ('codingagent', 'review', 'setupscript', 'cleanupscript')
                 AND ep2.dropped = FALSE
           )"#,
-        archived,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            archived,
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -489,6 +512,7 @@ pub async fn find_latest_for_workspaces_for_user(
                 "completed" => ExecutionProcessStatus::Completed,
                 "failed" => ExecutionProcessStatus::Failed,
                 "killed" => ExecutionProcessStatus::Killed,
+                "cancelled" => ExecutionProcessStatus::Cancelled,
                 _ => ExecutionProcessStatus::Running,
             },
             completed_at: r.completed_at,
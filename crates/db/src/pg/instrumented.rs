@@ -0,0 +1,185 @@
+//! Tracing wrapper around [`PgPool`].
+//!
+//! Every query run through [`InstrumentedPool`] executes inside a `db.query`
+//! span that records the SQL statement and the number of rows returned or
+//! affected, so slow queries can be found in distributed traces. The spans are
+//! exported together with the rest of the request trace when
+//! `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+
+use sqlx::{
+    Error, Execute, FromRow, PgPool, Postgres,
+    postgres::{PgArguments, PgQueryResult, PgRow},
+    query::{Map, Query, QueryAs, QueryScalar},
+};
+use tracing::Span;
+
+/// A [`PgPool`] whose queries run inside `db.query` tracing spans.
+///
+/// `execute` takes the [`Query`] built by `sqlx::query!` or `sqlx::query` for
+/// statements without output columns; the `fetch_*` methods take the [`Map`]
+/// built by `sqlx::query!`, `sqlx::query_as!` and `sqlx::query_scalar!`. The
+/// `fetch_*_as` and `fetch_all_scalar` methods take the runtime-checked
+/// [`QueryAs`] and [`QueryScalar`] built by `sqlx::query_as` and
+/// `sqlx::query_scalar`.
+#[derive(Debug, Clone)]
+pub struct InstrumentedPool {
+    pool: PgPool,
+}
+
+impl InstrumentedPool {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The wrapped pool, for transactions and other uninstrumented access.
+    pub fn inner(&self) -> &PgPool {
+        &self.pool
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn execute<'q>(
+        &self,
+        query: Query<'q, Postgres, PgArguments>,
+    ) -> Result<PgQueryResult, Error> {
+        let result = query.execute(&self.pool).await?;
+        record_rows_affected(result.rows_affected());
+        Ok(result)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_one<'q, F, O>(
+        &self,
+        query: Map<'q, Postgres, F, PgArguments>,
+    ) -> Result<O, Error>
+    where
+        F: FnMut(PgRow) -> Result<O, Error> + Send,
+        O: Send + Unpin,
+    {
+        let row = query.fetch_one(&self.pool).await?;
+        record_rows_affected(1);
+        Ok(row)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_all<'q, F, O>(
+        &self,
+        query: Map<'q, Postgres, F, PgArguments>,
+    ) -> Result<Vec<O>, Error>
+    where
+        F: FnMut(PgRow) -> Result<O, Error> + Send,
+        O: Send + Unpin,
+    {
+        let rows = query.fetch_all(&self.pool).await?;
+        record_rows_affected(rows.len() as u64);
+        Ok(rows)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_optional<'q, F, O>(
+        &self,
+        query: Map<'q, Postgres, F, PgArguments>,
+    ) -> Result<Option<O>, Error>
+    where
+        F: FnMut(PgRow) -> Result<O, Error> + Send,
+        O: Send + Unpin,
+    {
+        let row = query.fetch_optional(&self.pool).await?;
+        record_rows_affected(u64::from(row.is_some()));
+        Ok(row)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_one_as<'q, O>(
+        &self,
+        query: QueryAs<'q, Postgres, O, PgArguments>,
+    ) -> Result<O, Error>
+    where
+        O: Send + Unpin + for<'r> FromRow<'r, PgRow>,
+    {
+        let row = query.fetch_one(&self.pool).await?;
+        record_rows_affected(1);
+        Ok(row)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_all_as<'q, O>(
+        &self,
+        query: QueryAs<'q, Postgres, O, PgArguments>,
+    ) -> Result<Vec<O>, Error>
+    where
+        O: Send + Unpin + for<'r> FromRow<'r, PgRow>,
+    {
+        let rows = query.fetch_all(&self.pool).await?;
+        record_rows_affected(rows.len() as u64);
+        Ok(rows)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_optional_as<'q, O>(
+        &self,
+        query: QueryAs<'q, Postgres, O, PgArguments>,
+    ) -> Result<Option<O>, Error>
+    where
+        O: Send + Unpin + for<'r> FromRow<'r, PgRow>,
+    {
+        let row = query.fetch_optional(&self.pool).await?;
+        record_rows_affected(u64::from(row.is_some()));
+        Ok(row)
+    }
+
+    #[tracing::instrument(
+        name = "db.query",
+        skip_all,
+        fields(db.system = "postgresql", db.statement = query.sql(), db.rows_affected)
+    )]
+    pub async fn fetch_all_scalar<'q, O>(
+        &self,
+        query: QueryScalar<'q, Postgres, O, PgArguments>,
+    ) -> Result<Vec<O>, Error>
+    where
+        O: Send + Unpin,
+        (O,): Send + Unpin + for<'r> FromRow<'r, PgRow>,
+    {
+        let rows = query.fetch_all(&self.pool).await?;
+        record_rows_affected(rows.len() as u64);
+        Ok(rows)
+    }
+}
+
+impl From<PgPool> for InstrumentedPool {
+    fn from(pool: PgPool) -> Self {
+        Self::new(pool)
+    }
+}
+
+fn record_rows_affected(rows: u64) {
+    Span::current().record("db.rows_affected", rows);
+}
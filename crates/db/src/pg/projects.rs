@@ -4,10 +4,12 @@
 //! that include user_id filtering for multi-tenant isolation in Kubernetes deployments.
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::project::{CreateProject, Project, UpdateProject};
+use crate::{
+    models::project::{CreateProject, Project, UpdateProject},
+    pg::InstrumentedPool,
+};
 
 /// Count projects for a specific user.
 ///
@@ -19,13 +21,13 @@ use crate::models::project::{CreateProject, Project, UpdateProject};
 /// # Returns
 ///
 /// The count of projects owned by the user.
-pub async fn count_for_user(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
-    let result = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!" FROM projects WHERE user_id = $1"#,
-        user_id
-    )
-    .fetch_one(pool)
-    .await?;
+pub async fn count_for_user(pool: &InstrumentedPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+    let result = pool
+        .fetch_one(sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM projects WHERE user_id = $1"#,
+            user_id
+        ))
+        .await?;
 
     Ok(result)
 }
@@ -40,23 +42,27 @@ pub async fn count_for_user(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::E
 /// # Returns
 ///
 /// A vector of projects owned by the user.
-pub async fn find_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Project>, sqlx::Error> {
+pub async fn find_all_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+) -> Result<Vec<Project>, sqlx::Error> {
     // Note: PostgreSQL schema doesn't have default_agent_working_dir column
     // We return None for that field to maintain compatibility with the Project struct
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             id,
             name,
             remote_project_id,
+            is_archived,
             created_at,
             updated_at
         FROM projects
-        WHERE user_id = $1
+        WHERE user_id = $1 AND is_archived = FALSE
         ORDER BY created_at DESC"#,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -65,6 +71,7 @@ pub async fn find_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Proje
             name: r.name,
             default_agent_working_dir: None,
             remote_project_id: r.remote_project_id,
+            is_archived: r.is_archived,
             created_at: r.created_at,
             updated_at: r.updated_at,
         })
@@ -83,30 +90,32 @@ pub async fn find_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Proje
 ///
 /// The project if found and owned by the user, None otherwise.
 pub async fn find_by_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<Project>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             name,
             remote_project_id,
+            is_archived,
             created_at,
             updated_at
         FROM projects
         WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| Project {
         id: r.id,
         name: r.name,
         default_agent_working_dir: None,
         remote_project_id: r.remote_project_id,
+        is_archived: r.is_archived,
         created_at: r.created_at,
         updated_at: r.updated_at,
     }))
@@ -124,31 +133,33 @@ pub async fn find_by_id_for_user(
 ///
 /// The project if found and owned by the user, None otherwise.
 pub async fn find_by_remote_project_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     remote_project_id: Uuid,
 ) -> Result<Option<Project>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             name,
             remote_project_id,
+            is_archived,
             created_at,
             updated_at
         FROM projects
         WHERE remote_project_id = $1 AND user_id = $2
         LIMIT 1"#,
-        remote_project_id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            remote_project_id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| Project {
         id: r.id,
         name: r.name,
         default_agent_working_dir: None,
         remote_project_id: r.remote_project_id,
+        is_archived: r.is_archived,
         created_at: r.created_at,
         updated_at: r.updated_at,
     }))
@@ -167,32 +178,34 @@ pub async fn find_by_remote_project_id_for_user(
 ///
 /// The created project.
 pub async fn create_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     data: &CreateProject,
     project_id: Uuid,
 ) -> Result<Project, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"INSERT INTO projects (id, user_id, name)
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"INSERT INTO projects (id, user_id, name)
         VALUES ($1, $2, $3)
         RETURNING
             id,
             name,
             remote_project_id,
+            is_archived,
             created_at,
             updated_at"#,
-        project_id,
-        user_id,
-        data.name,
-    )
-    .fetch_one(pool)
-    .await?;
+            project_id,
+            user_id,
+            data.name,
+        ))
+        .await?;
 
     Ok(Project {
         id: record.id,
         name: record.name,
         default_agent_working_dir: None,
         remote_project_id: record.remote_project_id,
+        is_archived: record.is_archived,
         created_at: record.created_at,
         updated_at: record.updated_at,
     })
@@ -211,7 +224,7 @@ pub async fn create_for_user(
 ///
 /// The updated project.
 pub async fn update_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     payload: &UpdateProject,
@@ -222,28 +235,30 @@ pub async fn update_for_user(
 
     let name = payload.name.clone().unwrap_or(existing.name);
 
-    let record = sqlx::query!(
-        r#"UPDATE projects
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"UPDATE projects
         SET name = $3, updated_at = NOW()
         WHERE id = $1 AND user_id = $2
         RETURNING
             id,
             name,
             remote_project_id,
+            is_archived,
             created_at,
             updated_at"#,
-        id,
-        user_id,
-        name,
-    )
-    .fetch_one(pool)
-    .await?;
+            id,
+            user_id,
+            name,
+        ))
+        .await?;
 
     Ok(Project {
         id: record.id,
         name: record.name,
         default_agent_working_dir: None,
         remote_project_id: record.remote_project_id,
+        is_archived: record.is_archived,
         created_at: record.created_at,
         updated_at: record.updated_at,
     })
@@ -262,21 +277,21 @@ pub async fn update_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn set_remote_project_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     remote_project_id: Option<Uuid>,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
-        r#"UPDATE projects
+    let result = pool
+        .execute(sqlx::query!(
+            r#"UPDATE projects
         SET remote_project_id = $3, updated_at = NOW()
         WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id,
-        remote_project_id
-    )
-    .execute(pool)
-    .await?;
+            id,
+            user_id,
+            remote_project_id
+        ))
+        .await?;
 
     if result.rows_affected() == 0 {
         return Err(sqlx::Error::RowNotFound);
@@ -285,6 +300,53 @@ pub async fn set_remote_project_id_for_user(
     Ok(())
 }
 
+/// Archive or unarchive a project, ensuring it belongs to the specified user.
+///
+/// # Arguments
+///
+/// * `pool` - PostgreSQL connection pool
+/// * `user_id` - User ID for filtering
+/// * `id` - Project ID to update
+/// * `is_archived` - Whether the project should be hidden from default listings
+///
+/// # Returns
+///
+/// The updated project, or None if not found or not owned by the user.
+pub async fn set_archived_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+    is_archived: bool,
+) -> Result<Option<Project>, sqlx::Error> {
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"UPDATE projects
+        SET is_archived = $3, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING
+            id,
+            name,
+            remote_project_id,
+            is_archived,
+            created_at,
+            updated_at"#,
+            id,
+            user_id,
+            is_archived
+        ))
+        .await?;
+
+    Ok(record.map(|r| Project {
+        id: r.id,
+        name: r.name,
+        default_agent_working_dir: None,
+        remote_project_id: r.remote_project_id,
+        is_archived: r.is_archived,
+        created_at: r.created_at,
+        updated_at: r.updated_at,
+    }))
+}
+
 /// Delete a project, ensuring it belongs to the specified user.
 ///
 /// # Arguments
@@ -296,14 +358,18 @@ pub async fn set_remote_project_id_for_user(
 /// # Returns
 ///
 /// The number of rows deleted (0 or 1).
-pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM projects WHERE id = $1 AND user_id = $2",
-        id,
-        user_id
-    )
-    .execute(pool)
-    .await?;
+pub async fn delete_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = pool
+        .execute(sqlx::query!(
+            "DELETE FROM projects WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(result.rows_affected())
 }
@@ -320,14 +386,16 @@ pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u
 ///
 /// A vector of the most active projects for the user.
 pub async fn find_most_active_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     limit: i32,
 ) -> Result<Vec<Project>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT p.id, p.name, p.remote_project_id, p.created_at, p.updated_at
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT p.id, p.name, p.remote_project_id, p.is_archived, p.created_at, p.updated_at
         FROM projects p
         WHERE p.user_id = $1
+          AND p.is_archived = FALSE
           AND p.id IN (
               SELECT DISTINCT t.project_id
               FROM tasks t
@@ -336,11 +404,10 @@ pub async fn find_most_active_for_user(
               ORDER BY w.updated_at DESC
           )
         LIMIT $2"#,
-        user_id,
-        limit as i64
-    )
-    .fetch_all(pool)
-    .await?;
+            user_id,
+            limit as i64
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -349,6 +416,7 @@ pub async fn find_most_active_for_user(
             name: r.name,
             default_agent_working_dir: None,
             remote_project_id: r.remote_project_id,
+            is_archived: r.is_archived,
             created_at: r.created_at,
             updated_at: r.updated_at,
         })
@@ -6,10 +6,12 @@
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::repo::{Repo, UpdateRepo};
+use crate::{
+    models::repo::{Repo, UpdateRepo},
+    pg::InstrumentedPool,
+};
 
 /// Find a repo by ID, ensuring it belongs to the specified user.
 ///
@@ -23,12 +25,13 @@ use crate::models::repo::{Repo, UpdateRepo};
 ///
 /// The repo if found and owned by the user, None otherwise.
 pub async fn find_by_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<Repo>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             path,
             name,
@@ -42,11 +45,10 @@ pub async fn find_by_id_for_user(
             updated_at
         FROM repos
         WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| Repo {
         id: r.id,
@@ -75,7 +77,7 @@ pub async fn find_by_id_for_user(
 ///
 /// A vector of repos owned by the user.
 pub async fn find_by_ids_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     ids: &[Uuid],
 ) -> Result<Vec<Repo>, sqlx::Error> {
@@ -103,9 +105,13 @@ pub async fn find_by_ids_for_user(
 /// # Returns
 ///
 /// A vector of repos owned by the user.
-pub async fn list_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Repo>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+pub async fn list_all_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+) -> Result<Vec<Repo>, sqlx::Error> {
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             id,
             path,
             name,
@@ -120,10 +126,9 @@ pub async fn list_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Repo>
         FROM repos
         WHERE user_id = $1
         ORDER BY display_name ASC"#,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -159,7 +164,7 @@ pub async fn list_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Repo>
 ///
 /// The found or newly created repo.
 pub async fn find_or_create_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     path: &Path,
     display_name: &str,
@@ -171,8 +176,9 @@ pub async fn find_or_create_for_user(
         .map(|name| name.to_string_lossy().to_string())
         .unwrap_or_else(|| id.to_string());
 
-    let record = sqlx::query!(
-        r#"INSERT INTO repos (id, user_id, path, name, display_name)
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"INSERT INTO repos (id, user_id, path, name, display_name)
         VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (user_id, path) DO UPDATE SET updated_at = NOW()
         RETURNING
@@ -187,14 +193,13 @@ pub async fn find_or_create_for_user(
             dev_server_script,
             created_at,
             updated_at"#,
-        id,
-        user_id,
-        path_str,
-        repo_name,
-        display_name,
-    )
-    .fetch_one(pool)
-    .await?;
+            id,
+            user_id,
+            path_str,
+            repo_name,
+            display_name,
+        ))
+        .await?;
 
     Ok(Repo {
         id: record.id,
@@ -224,7 +229,7 @@ pub async fn find_or_create_for_user(
 ///
 /// The updated repo.
 pub async fn update_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     payload: &UpdateRepo,
@@ -261,8 +266,9 @@ pub async fn update_for_user(
         Some(v) => v.clone(),
     };
 
-    let record = sqlx::query!(
-        r#"UPDATE repos
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"UPDATE repos
         SET display_name = $4,
             setup_script = $5,
             cleanup_script = $6,
@@ -283,18 +289,17 @@ pub async fn update_for_user(
             dev_server_script,
             created_at,
             updated_at"#,
-        id,
-        user_id,
-        existing.path.to_string_lossy().to_string(),
-        display_name,
-        setup_script,
-        cleanup_script,
-        copy_files,
-        parallel_setup_script,
-        dev_server_script,
-    )
-    .fetch_one(pool)
-    .await?;
+            id,
+            user_id,
+            existing.path.to_string_lossy().to_string(),
+            display_name,
+            setup_script,
+            cleanup_script,
+            copy_files,
+            parallel_setup_script,
+            dev_server_script,
+        ))
+        .await?;
 
     Ok(Repo {
         id: record.id,
@@ -325,20 +330,19 @@ pub async fn update_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn update_name_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     name: &str,
     display_name: &str,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
+    let result = pool.execute(sqlx::query!(
         "UPDATE repos SET name = $3, display_name = $4, updated_at = NOW() WHERE id = $1 AND user_id = $2",
         id,
         user_id,
         name,
         display_name
-    )
-    .execute(pool)
+    ))
     .await?;
 
     if result.rows_affected() == 0 {
@@ -359,14 +363,18 @@ pub async fn update_name_for_user(
 /// # Returns
 ///
 /// The number of rows deleted (0 or 1).
-pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM repos WHERE id = $1 AND user_id = $2",
-        id,
-        user_id
-    )
-    .execute(pool)
-    .await?;
+pub async fn delete_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = pool
+        .execute(sqlx::query!(
+            "DELETE FROM repos WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(result.rows_affected())
 }
@@ -382,16 +390,19 @@ pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u
 /// # Returns
 ///
 /// The number of rows deleted.
-pub async fn delete_orphaned_for_user(pool: &PgPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        r#"DELETE FROM repos
+pub async fn delete_orphaned_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = pool
+        .execute(sqlx::query!(
+            r#"DELETE FROM repos
         WHERE user_id = $1
           AND id NOT IN (SELECT repo_id FROM project_repos)
           AND id NOT IN (SELECT repo_id FROM workspace_repos)"#,
-        user_id
-    )
-    .execute(pool)
-    .await?;
+            user_id
+        ))
+        .await?;
 
     Ok(result.rows_affected())
 }
@@ -408,23 +419,23 @@ pub async fn delete_orphaned_for_user(pool: &PgPool, user_id: Uuid) -> Result<u6
 ///
 /// A vector of repos linked to the project and owned by the user.
 pub async fn find_repos_for_project_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     project_id: Uuid,
 ) -> Result<Vec<Repo>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT r.id, r.path, r.name, r.display_name, r.setup_script,
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT r.id, r.path, r.name, r.display_name, r.setup_script,
                   r.cleanup_script, r.copy_files, r.parallel_setup_script,
                   r.dev_server_script, r.created_at, r.updated_at
         FROM repos r
         JOIN project_repos pr ON r.id = pr.repo_id
         WHERE pr.project_id = $1 AND r.user_id = $2
         ORDER BY r.display_name ASC"#,
-        project_id,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            project_id,
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -4,10 +4,12 @@
 //! that include user_id filtering for multi-tenant isolation in Kubernetes deployments.
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::session::{CreateSession, Session};
+use crate::{
+    models::session::{CreateSession, Session},
+    pg::InstrumentedPool,
+};
 
 /// Find a session by ID, ensuring it belongs to the specified user.
 ///
@@ -21,12 +23,13 @@ use crate::models::session::{CreateSession, Session};
 ///
 /// The session if found and owned by the user, None otherwise.
 pub async fn find_by_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<Session>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             workspace_id,
             executor,
@@ -34,11 +37,10 @@ pub async fn find_by_id_for_user(
             updated_at
         FROM sessions
         WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| Session {
         id: r.id,
@@ -62,12 +64,13 @@ pub async fn find_by_id_for_user(
 ///
 /// A vector of sessions.
 pub async fn find_by_workspace_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<Vec<Session>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT s.id, s.workspace_id, s.executor, s.created_at, s.updated_at
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT s.id, s.workspace_id, s.executor, s.created_at, s.updated_at
         FROM sessions s
         LEFT JOIN (
             SELECT ep.session_id, MAX(ep.created_at) as last_used
@@ -77,11 +80,65 @@ pub async fn find_by_workspace_id_for_user(
         ) latest_ep ON s.id = latest_ep.session_id
         WHERE s.workspace_id = $1 AND s.user_id = $2
         ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC"#,
-        workspace_id,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            workspace_id,
+            user_id
+        ))
+        .await?;
+
+    Ok(records
+        .into_iter()
+        .map(|r| Session {
+            id: r.id,
+            workspace_id: r.workspace_id,
+            executor: r.executor,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        })
+        .collect())
+}
+
+/// Find the active sessions for a workspace, ensuring they belong to the specified user.
+/// A session is active when its latest execution process is running or it has none.
+/// Ordered like [`find_by_workspace_id_for_user`].
+///
+/// # Arguments
+///
+/// * `pool` - PostgreSQL connection pool
+/// * `user_id` - User ID for filtering
+/// * `workspace_id` - Workspace ID to find sessions for
+///
+/// # Returns
+///
+/// A vector of active sessions.
+pub async fn find_active_for_workspace_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    workspace_id: Uuid,
+) -> Result<Vec<Session>, sqlx::Error> {
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT s.id, s.workspace_id, s.executor, s.created_at, s.updated_at
+        FROM sessions s
+        LEFT JOIN (
+            SELECT ep.session_id, MAX(ep.created_at) as last_used
+            FROM execution_processes ep
+            WHERE ep.run_reason != 'devserver' AND ep.dropped = FALSE
+            GROUP BY ep.session_id
+        ) latest_ep ON s.id = latest_ep.session_id
+        WHERE s.workspace_id = $1 AND s.user_id = $2
+          AND COALESCE(
+              (SELECT ep.status
+               FROM execution_processes ep
+               WHERE ep.session_id = s.id AND ep.dropped = FALSE
+               ORDER BY ep.created_at DESC
+               LIMIT 1),
+              'running'
+          ) = 'running'
+        ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC"#,
+            workspace_id,
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -108,12 +165,13 @@ pub async fn find_by_workspace_id_for_user(
 ///
 /// The most recently used session, or None if no sessions exist.
 pub async fn find_latest_by_workspace_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<Option<Session>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT s.id, s.workspace_id, s.executor, s.created_at, s.updated_at
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT s.id, s.workspace_id, s.executor, s.created_at, s.updated_at
         FROM sessions s
         LEFT JOIN (
             SELECT ep.session_id, MAX(ep.created_at) as last_used
@@ -124,11 +182,10 @@ pub async fn find_latest_by_workspace_id_for_user(
         WHERE s.workspace_id = $1 AND s.user_id = $2
         ORDER BY COALESCE(latest_ep.last_used, s.created_at) DESC
         LIMIT 1"#,
-        workspace_id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            workspace_id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| Session {
         id: r.id,
@@ -153,14 +210,15 @@ pub async fn find_latest_by_workspace_id_for_user(
 ///
 /// The created session.
 pub async fn create_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     data: &CreateSession,
     id: Uuid,
     workspace_id: Uuid,
 ) -> Result<Session, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"INSERT INTO sessions (id, user_id, workspace_id, executor)
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"INSERT INTO sessions (id, user_id, workspace_id, executor)
         VALUES ($1, $2, $3, $4)
         RETURNING
             id,
@@ -168,13 +226,12 @@ pub async fn create_for_user(
             executor,
             created_at,
             updated_at"#,
-        id,
-        user_id,
-        workspace_id,
-        data.executor
-    )
-    .fetch_one(pool)
-    .await?;
+            id,
+            user_id,
+            workspace_id,
+            data.executor
+        ))
+        .await?;
 
     Ok(Session {
         id: record.id,
@@ -196,14 +253,18 @@ pub async fn create_for_user(
 /// # Returns
 ///
 /// The number of rows deleted (0 or 1).
-pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM sessions WHERE id = $1 AND user_id = $2",
-        id,
-        user_id
-    )
-    .execute(pool)
-    .await?;
+pub async fn delete_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = pool
+        .execute(sqlx::query!(
+            "DELETE FROM sessions WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(result.rows_affected())
 }
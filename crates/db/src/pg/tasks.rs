@@ -4,10 +4,12 @@
 //! that include user_id filtering for multi-tenant isolation in Kubernetes deployments.
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus};
+use crate::{
+    models::task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus},
+    pg::InstrumentedPool,
+};
 
 /// Find all tasks for a project, ensuring they belong to the specified user.
 /// Returns tasks with attempt status information.
@@ -22,12 +24,13 @@ use crate::models::task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus};
 ///
 /// A vector of tasks with attempt status information.
 pub async fn find_by_project_id_with_attempt_status_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     project_id: Uuid,
 ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             t.id,
             t.project_id,
             t.title,
@@ -36,6 +39,7 @@ pub async fn find_by_project_id_with_attempt_status_for_user(
             t.parent_workspace_id,
             t.created_at,
             t.updated_at,
+            t.deleted_at,
 
             CASE WHEN EXISTS (
                 SELECT 1
@@ -44,7 +48,7 @@ pub async fn find_by_project_id_with_attempt_status_for_user(
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE w.task_id = t.id
                   AND ep.status = 'running'
-                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                 LIMIT 1
             ) THEN TRUE ELSE FALSE END AS "has_in_progress_attempt!",
 
@@ -54,7 +58,7 @@ pub async fn find_by_project_id_with_attempt_status_for_user(
                 JOIN sessions s ON s.workspace_id = w.id
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE w.task_id = t.id
-                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                 ORDER BY ep.created_at DESC
                 LIMIT 1
             ) IN ('failed','killed') THEN TRUE ELSE FALSE END AS "last_attempt_failed!",
@@ -70,13 +74,12 @@ pub async fn find_by_project_id_with_attempt_status_for_user(
             ) AS "executor!"
 
         FROM tasks t
-        WHERE t.project_id = $1 AND t.user_id = $2
+        WHERE t.project_id = $1 AND t.user_id = $2 AND t.deleted_at IS NULL
         ORDER BY t.created_at DESC"#,
-        project_id,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            project_id,
+            user_id
+        ))
+        .await?;
 
     let tasks = records
         .into_iter()
@@ -100,6 +103,7 @@ pub async fn find_by_project_id_with_attempt_status_for_user(
                     parent_workspace_id: rec.parent_workspace_id,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
+                    deleted_at: rec.deleted_at,
                 },
                 has_in_progress_attempt: rec.has_in_progress_attempt,
                 last_attempt_failed: rec.last_attempt_failed,
@@ -123,12 +127,13 @@ pub async fn find_by_project_id_with_attempt_status_for_user(
 ///
 /// The task if found and owned by the user, None otherwise.
 pub async fn find_by_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<Task>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             project_id,
             title,
@@ -136,14 +141,14 @@ pub async fn find_by_id_for_user(
             status,
             parent_workspace_id,
             created_at,
-            updated_at
+            updated_at,
+            deleted_at
         FROM tasks
-        WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"#,
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|rec| {
         let status = match rec.status.as_str() {
@@ -164,10 +169,83 @@ pub async fn find_by_id_for_user(
             parent_workspace_id: rec.parent_workspace_id,
             created_at: rec.created_at,
             updated_at: rec.updated_at,
+            deleted_at: rec.deleted_at,
         }
     }))
 }
 
+/// Full-text search a user's tasks by title and description.
+///
+/// Uses the generated `search_vector` column, ranking the best matches first.
+///
+/// # Arguments
+///
+/// * `pool` - PostgreSQL connection pool
+/// * `user_id` - User ID for filtering
+/// * `query` - Free-text search query, parsed with `plainto_tsquery`
+/// * `project_id` - Optional project ID to restrict the search to
+///
+/// # Returns
+///
+/// A vector of matching tasks owned by the user.
+pub async fn search_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    query: &str,
+    project_id: Option<Uuid>,
+) -> Result<Vec<Task>, sqlx::Error> {
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
+            id,
+            project_id,
+            title,
+            description,
+            status,
+            parent_workspace_id,
+            created_at,
+            updated_at,
+            deleted_at
+        FROM tasks
+        WHERE search_vector @@ plainto_tsquery('english', $1)
+          AND user_id = $2
+          AND deleted_at IS NULL
+          AND ($3::uuid IS NULL OR project_id = $3)
+        ORDER BY ts_rank(search_vector, plainto_tsquery('english', $1)) DESC,
+                 created_at DESC"#,
+            query,
+            user_id,
+            project_id
+        ))
+        .await?;
+
+    Ok(records
+        .into_iter()
+        .map(|rec| {
+            let status = match rec.status.as_str() {
+                "todo" => TaskStatus::Todo,
+                "inprogress" => TaskStatus::InProgress,
+                "inreview" => TaskStatus::InReview,
+                "done" => TaskStatus::Done,
+                "cancelled" => TaskStatus::Cancelled,
+                _ => TaskStatus::Todo,
+            };
+
+            Task {
+                id: rec.id,
+                project_id: rec.project_id,
+                title: rec.title,
+                description: rec.description,
+                status,
+                parent_workspace_id: rec.parent_workspace_id,
+                created_at: rec.created_at,
+                updated_at: rec.updated_at,
+                deleted_at: rec.deleted_at,
+            }
+        })
+        .collect())
+}
+
 /// Create a new task for a user.
 ///
 /// # Arguments
@@ -181,7 +259,7 @@ pub async fn find_by_id_for_user(
 ///
 /// The created task.
 pub async fn create_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     data: &CreateTask,
     task_id: Uuid,
@@ -189,7 +267,7 @@ pub async fn create_for_user(
     let status = data.status.clone().unwrap_or_default();
     let status_str = status.to_string().to_lowercase();
 
-    let record = sqlx::query!(
+    let record = pool.fetch_one(sqlx::query!(
         r#"INSERT INTO tasks (id, user_id, project_id, title, description, status, parent_workspace_id)
         VALUES ($1, $2, $3, $4, $5, $6, $7)
         RETURNING
@@ -200,7 +278,8 @@ pub async fn create_for_user(
             status,
             parent_workspace_id,
             created_at,
-            updated_at"#,
+            updated_at,
+            deleted_at"#,
         task_id,
         user_id,
         data.project_id,
@@ -208,8 +287,7 @@ pub async fn create_for_user(
         data.description,
         status_str,
         data.parent_workspace_id
-    )
-    .fetch_one(pool)
+    ))
     .await?;
 
     let result_status = match record.status.as_str() {
@@ -230,6 +308,7 @@ pub async fn create_for_user(
         parent_workspace_id: record.parent_workspace_id,
         created_at: record.created_at,
         updated_at: record.updated_at,
+        deleted_at: record.deleted_at,
     })
 }
 
@@ -250,7 +329,7 @@ pub async fn create_for_user(
 ///
 /// The updated task.
 pub async fn update_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     project_id: Uuid,
@@ -261,8 +340,9 @@ pub async fn update_for_user(
 ) -> Result<Task, sqlx::Error> {
     let status_str = status.to_string().to_lowercase();
 
-    let record = sqlx::query!(
-        r#"UPDATE tasks
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"UPDATE tasks
         SET title = $4, description = $5, status = $6, parent_workspace_id = $7, updated_at = NOW()
         WHERE id = $1 AND user_id = $2 AND project_id = $3
         RETURNING
@@ -273,17 +353,17 @@ pub async fn update_for_user(
             status,
             parent_workspace_id,
             created_at,
-            updated_at"#,
-        id,
-        user_id,
-        project_id,
-        title,
-        description,
-        status_str,
-        parent_workspace_id
-    )
-    .fetch_one(pool)
-    .await?;
+            updated_at,
+            deleted_at"#,
+            id,
+            user_id,
+            project_id,
+            title,
+            description,
+            status_str,
+            parent_workspace_id
+        ))
+        .await?;
 
     let result_status = match record.status.as_str() {
         "todo" => TaskStatus::Todo,
@@ -303,6 +383,7 @@ pub async fn update_for_user(
         parent_workspace_id: record.parent_workspace_id,
         created_at: record.created_at,
         updated_at: record.updated_at,
+        deleted_at: record.deleted_at,
     })
 }
 
@@ -319,21 +400,21 @@ pub async fn update_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn update_status_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
     status: TaskStatus,
 ) -> Result<(), sqlx::Error> {
     let status_str = status.to_string().to_lowercase();
 
-    let result = sqlx::query!(
-        "UPDATE tasks SET status = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2",
-        id,
-        user_id,
-        status_str
-    )
-    .execute(pool)
-    .await?;
+    let result = pool
+        .execute(sqlx::query!(
+            "UPDATE tasks SET status = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2",
+            id,
+            user_id,
+            status_str
+        ))
+        .await?;
 
     if result.rows_affected() == 0 {
         return Err(sqlx::Error::RowNotFound);
@@ -342,7 +423,7 @@ pub async fn update_status_for_user(
     Ok(())
 }
 
-/// Delete a task, ensuring it belongs to the specified user.
+/// Soft-delete a task, ensuring it belongs to the specified user.
 ///
 /// # Arguments
 ///
@@ -352,19 +433,82 @@ pub async fn update_status_for_user(
 ///
 /// # Returns
 ///
-/// The number of rows deleted (0 or 1).
-pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM tasks WHERE id = $1 AND user_id = $2",
+/// The number of rows marked deleted (0 or 1).
+pub async fn delete_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = pool.execute(sqlx::query!(
+        "UPDATE tasks SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL",
         id,
         user_id
-    )
-    .execute(pool)
+    ))
     .await?;
 
     Ok(result.rows_affected())
 }
 
+/// Restore a soft-deleted task, ensuring it belongs to the specified user.
+///
+/// # Arguments
+///
+/// * `pool` - PostgreSQL connection pool
+/// * `user_id` - User ID for filtering
+/// * `id` - Task ID to restore
+///
+/// # Returns
+///
+/// The restored task, or None if no deleted task matched.
+pub async fn restore_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<Option<Task>, sqlx::Error> {
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"UPDATE tasks
+        SET deleted_at = NULL, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+        RETURNING
+            id,
+            project_id,
+            title,
+            description,
+            status,
+            parent_workspace_id,
+            created_at,
+            updated_at,
+            deleted_at"#,
+            id,
+            user_id
+        ))
+        .await?;
+
+    Ok(record.map(|rec| {
+        let status = match rec.status.as_str() {
+            "todo" => TaskStatus::Todo,
+            "inprogress" => TaskStatus::InProgress,
+            "inreview" => TaskStatus::InReview,
+            "done" => TaskStatus::Done,
+            "cancelled" => TaskStatus::Cancelled,
+            _ => TaskStatus::Todo,
+        };
+
+        Task {
+            id: rec.id,
+            project_id: rec.project_id,
+            title: rec.title,
+            description: rec.description,
+            status,
+            parent_workspace_id: rec.parent_workspace_id,
+            created_at: rec.created_at,
+            updated_at: rec.updated_at,
+            deleted_at: rec.deleted_at,
+        }
+    }))
+}
+
 /// Find children tasks by workspace ID, ensuring they belong to the specified user.
 ///
 /// # Arguments
@@ -377,12 +521,13 @@ pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u
 ///
 /// A vector of child tasks.
 pub async fn find_children_by_workspace_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<Vec<Task>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             id,
             project_id,
             title,
@@ -390,15 +535,15 @@ pub async fn find_children_by_workspace_id_for_user(
             status,
             parent_workspace_id,
             created_at,
-            updated_at
+            updated_at,
+            deleted_at
         FROM tasks
-        WHERE parent_workspace_id = $1 AND user_id = $2
+        WHERE parent_workspace_id = $1 AND user_id = $2 AND deleted_at IS NULL
         ORDER BY created_at DESC"#,
-        workspace_id,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+            workspace_id,
+            user_id
+        ))
+        .await?;
 
     Ok(records
         .into_iter()
@@ -421,6 +566,7 @@ pub async fn find_children_by_workspace_id_for_user(
                 parent_workspace_id: rec.parent_workspace_id,
                 created_at: rec.created_at,
                 updated_at: rec.updated_at,
+                deleted_at: rec.deleted_at,
             }
         })
         .collect())
@@ -439,16 +585,15 @@ pub async fn find_children_by_workspace_id_for_user(
 ///
 /// The number of rows affected.
 pub async fn nullify_children_by_workspace_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
+    let result = pool.execute(sqlx::query!(
         "UPDATE tasks SET parent_workspace_id = NULL, updated_at = NOW() WHERE parent_workspace_id = $1 AND user_id = $2",
         workspace_id,
         user_id
-    )
-    .execute(pool)
+    ))
     .await?;
 
     Ok(result.rows_affected())
@@ -4,10 +4,12 @@
 //! that include user_id filtering for multi-tenant isolation in Kubernetes deployments.
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::workspace::{CreateWorkspace, Workspace, WorkspaceWithStatus};
+use crate::{
+    models::workspace::{CreateWorkspace, Workspace, WorkspaceWithStatus},
+    pg::InstrumentedPool,
+};
 
 /// Find a workspace by ID, ensuring it belongs to the specified user.
 ///
@@ -21,14 +23,15 @@ use crate::models::workspace::{CreateWorkspace, Workspace, WorkspaceWithStatus};
 ///
 /// The workspace if found and owned by the user, None otherwise.
 pub async fn find_by_id_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<Workspace>, sqlx::Error> {
     // Note: PostgreSQL schema doesn't have setup_completed_at column
     // We return None for that field to maintain compatibility with the Workspace struct
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             id,
             task_id,
             container_ref,
@@ -41,11 +44,10 @@ pub async fn find_by_id_for_user(
             name
         FROM workspaces
         WHERE id = $1 AND user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|r| Workspace {
         id: r.id,
@@ -62,7 +64,8 @@ pub async fn find_by_id_for_user(
     }))
 }
 
-/// Fetch all workspaces for a user, optionally filtered by task_id. Newest first.
+/// Fetch all workspaces for a user, optionally filtered by task_id. A task's
+/// workspaces are newest first; otherwise pinned first, then most recently updated.
 ///
 /// # Arguments
 ///
@@ -74,14 +77,15 @@ pub async fn find_by_id_for_user(
 ///
 /// A vector of workspaces.
 pub async fn fetch_all_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     task_id: Option<Uuid>,
 ) -> Result<Vec<Workspace>, sqlx::Error> {
     let workspaces = match task_id {
         Some(tid) => {
-            let records = sqlx::query!(
-                r#"SELECT
+            let records = pool
+                .fetch_all(sqlx::query!(
+                    r#"SELECT
                     id,
                     task_id,
                     container_ref,
@@ -95,11 +99,10 @@ pub async fn fetch_all_for_user(
                 FROM workspaces
                 WHERE task_id = $1 AND user_id = $2
                 ORDER BY created_at DESC"#,
-                tid,
-                user_id
-            )
-            .fetch_all(pool)
-            .await?;
+                    tid,
+                    user_id
+                ))
+                .await?;
 
             records
                 .into_iter()
@@ -119,8 +122,9 @@ pub async fn fetch_all_for_user(
                 .collect()
         }
         None => {
-            let records = sqlx::query!(
-                r#"SELECT
+            let records = pool
+                .fetch_all(sqlx::query!(
+                    r#"SELECT
                     id,
                     task_id,
                     container_ref,
@@ -133,11 +137,10 @@ pub async fn fetch_all_for_user(
                     name
                 FROM workspaces
                 WHERE user_id = $1
-                ORDER BY created_at DESC"#,
-                user_id
-            )
-            .fetch_all(pool)
-            .await?;
+                ORDER BY pinned DESC, updated_at DESC"#,
+                    user_id
+                ))
+                .await?;
 
             records
                 .into_iter()
@@ -175,14 +178,15 @@ pub async fn fetch_all_for_user(
 ///
 /// The created workspace.
 pub async fn create_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     data: &CreateWorkspace,
     id: Uuid,
     task_id: Uuid,
 ) -> Result<Workspace, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"INSERT INTO workspaces (id, user_id, task_id, branch, agent_working_dir)
+    let record = pool
+        .fetch_one(sqlx::query!(
+            r#"INSERT INTO workspaces (id, user_id, task_id, branch, agent_working_dir)
         VALUES ($1, $2, $3, $4, $5)
         RETURNING
             id,
@@ -195,14 +199,13 @@ pub async fn create_for_user(
             archived,
             pinned,
             name"#,
-        id,
-        user_id,
-        task_id,
-        data.branch,
-        data.agent_working_dir,
-    )
-    .fetch_one(pool)
-    .await?;
+            id,
+            user_id,
+            task_id,
+            data.branch,
+            data.agent_working_dir,
+        ))
+        .await?;
 
     Ok(Workspace {
         id: record.id,
@@ -232,18 +235,17 @@ pub async fn create_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn update_container_ref_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
     container_ref: &str,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
+    let result = pool.execute(sqlx::query!(
         "UPDATE workspaces SET container_ref = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2",
         workspace_id,
         user_id,
         container_ref
-    )
-    .execute(pool)
+    ))
     .await?;
 
     if result.rows_affected() == 0 {
@@ -265,16 +267,15 @@ pub async fn update_container_ref_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn clear_container_ref_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
+    let result = pool.execute(sqlx::query!(
         "UPDATE workspaces SET container_ref = NULL, updated_at = NOW() WHERE id = $1 AND user_id = $2",
         workspace_id,
         user_id
-    )
-    .execute(pool)
+    ))
     .await?;
 
     if result.rows_affected() == 0 {
@@ -296,17 +297,17 @@ pub async fn clear_container_ref_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn touch_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
-        "UPDATE workspaces SET updated_at = NOW() WHERE id = $1 AND user_id = $2",
-        workspace_id,
-        user_id
-    )
-    .execute(pool)
-    .await?;
+    let result = pool
+        .execute(sqlx::query!(
+            "UPDATE workspaces SET updated_at = NOW() WHERE id = $1 AND user_id = $2",
+            workspace_id,
+            user_id
+        ))
+        .await?;
 
     if result.rows_affected() == 0 {
         return Err(sqlx::Error::RowNotFound);
@@ -331,7 +332,7 @@ pub async fn touch_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn update_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
     archived: Option<bool>,
@@ -342,22 +343,22 @@ pub async fn update_for_user(
     let name_value = name.filter(|s| !s.is_empty());
     let name_provided = name.is_some();
 
-    let result = sqlx::query!(
-        r#"UPDATE workspaces SET
+    let result = pool
+        .execute(sqlx::query!(
+            r#"UPDATE workspaces SET
             archived = COALESCE($3, archived),
             pinned = COALESCE($4, pinned),
             name = CASE WHEN $5 THEN $6 ELSE name END,
             updated_at = NOW()
         WHERE id = $1 AND user_id = $2"#,
-        workspace_id,
-        user_id,
-        archived,
-        pinned,
-        name_provided,
-        name_value,
-    )
-    .execute(pool)
-    .await?;
+            workspace_id,
+            user_id,
+            archived,
+            pinned,
+            name_provided,
+            name_value,
+        ))
+        .await?;
 
     if result.rows_affected() == 0 {
         return Err(sqlx::Error::RowNotFound);
@@ -379,18 +380,17 @@ pub async fn update_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn set_archived_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
     archived: bool,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
+    let result = pool.execute(sqlx::query!(
         "UPDATE workspaces SET archived = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2",
         workspace_id,
         user_id,
         archived
-    )
-    .execute(pool)
+    ))
     .await?;
 
     if result.rows_affected() == 0 {
@@ -413,19 +413,19 @@ pub async fn set_archived_for_user(
 ///
 /// Ok(()) if successful.
 pub async fn update_branch_name_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     workspace_id: Uuid,
     new_branch_name: &str,
 ) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!(
-        "UPDATE workspaces SET branch = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2",
-        workspace_id,
-        user_id,
-        new_branch_name
-    )
-    .execute(pool)
-    .await?;
+    let result = pool
+        .execute(sqlx::query!(
+            "UPDATE workspaces SET branch = $3, updated_at = NOW() WHERE id = $1 AND user_id = $2",
+            workspace_id,
+            user_id,
+            new_branch_name
+        ))
+        .await?;
 
     if result.rows_affected() == 0 {
         return Err(sqlx::Error::RowNotFound);
@@ -445,14 +445,18 @@ pub async fn update_branch_name_for_user(
 /// # Returns
 ///
 /// The number of rows deleted (0 or 1).
-pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM workspaces WHERE id = $1 AND user_id = $2",
-        id,
-        user_id
-    )
-    .execute(pool)
-    .await?;
+pub async fn delete_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = pool
+        .execute(sqlx::query!(
+            "DELETE FROM workspaces WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(result.rows_affected())
 }
@@ -467,13 +471,16 @@ pub async fn delete_for_user(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<u
 /// # Returns
 ///
 /// The count of workspaces owned by the user.
-pub async fn count_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
-    let result = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!" FROM workspaces WHERE user_id = $1"#,
-        user_id
-    )
-    .fetch_one(pool)
-    .await?;
+pub async fn count_all_for_user(
+    pool: &InstrumentedPool,
+    user_id: Uuid,
+) -> Result<i64, sqlx::Error> {
+    let result = pool
+        .fetch_one(sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM workspaces WHERE user_id = $1"#,
+            user_id
+        ))
+        .await?;
 
     Ok(result)
 }
@@ -491,13 +498,14 @@ pub async fn count_all_for_user(pool: &PgPool, user_id: Uuid) -> Result<i64, sql
 ///
 /// A vector of workspaces with status information.
 pub async fn find_all_with_status_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     archived: Option<bool>,
     limit: Option<i64>,
 ) -> Result<Vec<WorkspaceWithStatus>, sqlx::Error> {
-    let records = sqlx::query!(
-        r#"SELECT
+    let records = pool
+        .fetch_all(sqlx::query!(
+            r#"SELECT
             w.id,
             w.task_id,
             w.container_ref,
@@ -515,7 +523,7 @@ pub async fn find_all_with_status_for_user(
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE s.workspace_id = w.id
                   AND ep.status = 'running'
-                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                 LIMIT 1
             ) THEN TRUE ELSE FALSE END AS "is_running!",
 
@@ -524,18 +532,17 @@ pub async fn find_all_with_status_for_user(
                 FROM sessions s
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE s.workspace_id = w.id
-                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                 ORDER BY ep.created_at DESC
                 LIMIT 1
             ) IN ('failed','killed') THEN TRUE ELSE FALSE END AS "is_errored!"
 
         FROM workspaces w
         WHERE w.user_id = $1
-        ORDER BY w.updated_at DESC"#,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+        ORDER BY w.pinned DESC, w.updated_at DESC"#,
+            user_id
+        ))
+        .await?;
 
     let mut workspaces: Vec<WorkspaceWithStatus> = records
         .into_iter()
@@ -580,12 +587,13 @@ pub async fn find_all_with_status_for_user(
 ///
 /// The workspace with status if found and owned by the user, None otherwise.
 pub async fn find_by_id_with_status_for_user(
-    pool: &PgPool,
+    pool: &InstrumentedPool,
     user_id: Uuid,
     id: Uuid,
 ) -> Result<Option<WorkspaceWithStatus>, sqlx::Error> {
-    let record = sqlx::query!(
-        r#"SELECT
+    let record = pool
+        .fetch_optional(sqlx::query!(
+            r#"SELECT
             w.id,
             w.task_id,
             w.container_ref,
@@ -603,7 +611,7 @@ pub async fn find_by_id_with_status_for_user(
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE s.workspace_id = w.id
                   AND ep.status = 'running'
-                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                 LIMIT 1
             ) THEN TRUE ELSE FALSE END AS "is_running!",
 
@@ -612,18 +620,17 @@ pub async fn find_by_id_with_status_for_user(
                 FROM sessions s
                 JOIN execution_processes ep ON ep.session_id = s.id
                 WHERE s.workspace_id = w.id
-                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                  AND ep.run_reason IN ('setupscript','cleanupscript','codingagent','review')
                 ORDER BY ep.created_at DESC
                 LIMIT 1
             ) IN ('failed','killed') THEN TRUE ELSE FALSE END AS "is_errored!"
 
         FROM workspaces w
         WHERE w.id = $1 AND w.user_id = $2"#,
-        id,
-        user_id
-    )
-    .fetch_optional(pool)
-    .await?;
+            id,
+            user_id
+        ))
+        .await?;
 
     Ok(record.map(|rec| WorkspaceWithStatus {
         workspace: Workspace {
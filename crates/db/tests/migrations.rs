@@ -42,6 +42,17 @@ fn mig_unit_01_migration_naming_convention() {
         "20260122000003_pty_sessions.sql",
         "20260122000004_user_indexes.sql",
         "20260122000005_user_id_not_null.sql",
+        "20260122000006_refresh_tokens.sql",
+        "20260122000007_task_search_vector.sql",
+        "20260122000008_task_soft_delete.sql",
+        "20260122000009_project_archive.sql",
+        "20260122000010_queued_messages.sql",
+        "20260122000011_task_history.sql",
+        "20260122000012_execution_pause.sql",
+        "20260122000013_execution_cancel.sql",
+        "20260122000014_workspace_repo_active_branch.sql",
+        "20260122000015_project_remote_sync.sql",
+        "20260122000016_execution_review.sql",
     ];
 
     for file in &migration_files {
@@ -76,8 +87,8 @@ fn mig_unit_01_migration_naming_convention() {
 /// MIG-UNIT-02: Verify expected number of migrations
 #[test]
 fn mig_unit_02_expected_migration_count() {
-    // We expect 5 migrations for the multi-user deployment
-    let expected_count = 5;
+    // We expect 16 migrations for the multi-user deployment
+    let expected_count = 16;
 
     // Migration versions in order
     let versions = vec![
@@ -86,6 +97,17 @@ fn mig_unit_02_expected_migration_count() {
         "20260122000003", // pty_sessions
         "20260122000004", // user_indexes
         "20260122000005", // user_id_not_null
+        "20260122000006", // refresh_tokens
+        "20260122000007", // task_search_vector
+        "20260122000008", // task_soft_delete
+        "20260122000009", // project_archive
+        "20260122000010", // queued_messages
+        "20260122000011", // task_history
+        "20260122000012", // execution_pause
+        "20260122000013", // execution_cancel
+        "20260122000014", // workspace_repo_active_branch
+        "20260122000015", // project_remote_sync
+        "20260122000016", // execution_review
     ];
 
     assert_eq!(
@@ -115,6 +137,17 @@ fn mig_unit_03_migration_descriptions() {
         ("pty_sessions", "Creates PTY sessions tracking table"),
         ("user_indexes", "Creates indexes for user_id filtering"),
         ("user_id_not_null", "Ensures NOT NULL on user_id columns"),
+        ("refresh_tokens", "Adds refresh token tracking to user configs"),
+        ("task_search_vector", "Adds full-text search vector to tasks"),
+        ("task_soft_delete", "Adds soft-delete timestamp to tasks"),
+        ("project_archive", "Adds archived flag to projects"),
+        ("queued_messages", "Creates persistent follow-up queue table"),
+        ("task_history", "Creates task field change audit log"),
+        ("execution_pause", "Adds paused timestamp to execution processes"),
+        ("execution_cancel", "Execution Cancellation"),
+        ("workspace_repo_active_branch", "Adds active branch to workspace repos"),
+        ("project_remote_sync", "Adds remote sync status to projects"),
+        ("execution_review", "Adds the review run reason to execution processes"),
     ];
 
     for (name, purpose) in descriptions {
@@ -260,6 +293,7 @@ fn mig_unit_07_user_configs_schema() {
         ("oauth_credentials", "BYTEA", true), // Nullable, encrypted
         ("created_at", "TIMESTAMPTZ", false), // NOT NULL, DEFAULT NOW()
         ("updated_at", "TIMESTAMPTZ", false), // NOT NULL, DEFAULT NOW()
+        ("refresh_tokens", "JSONB", false), // NOT NULL, DEFAULT '{}'
     ];
 
     for (name, data_type, nullable) in columns {
@@ -376,7 +410,7 @@ async fn mig_03_projects_table_schema() {
         ORDER BY ordinal_position
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query columns");
 
@@ -415,7 +449,7 @@ async fn mig_11_user_id_indexes_exist() {
         AND indexname LIKE 'idx_%_user_id'
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query indexes");
 
@@ -453,7 +487,7 @@ async fn mig_12_composite_indexes_exist() {
         WHERE schemaname = 'public'
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query indexes");
 
@@ -495,7 +529,7 @@ async fn mig_13_user_id_not_null_constraints() {
         AND table_schema = 'public'
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query columns");
 
@@ -536,7 +570,7 @@ async fn mig_15_uuid_extension_enabled() {
         WHERE extname = 'uuid-ossp'
         "#,
     )
-    .fetch_optional(&service.pool)
+    .fetch_optional(&service.pool())
     .await
     .expect("Failed to query extensions");
 
@@ -547,7 +581,7 @@ async fn mig_15_uuid_extension_enabled() {
 
     // Test uuid_generate_v4() works
     let uuid: (uuid::Uuid,) = sqlx::query_as("SELECT uuid_generate_v4()")
-        .fetch_one(&service.pool)
+        .fetch_one(&service.pool())
         .await
         .expect("uuid_generate_v4() should work");
 
@@ -572,7 +606,7 @@ async fn mig_16_user_configs_trigger() {
         AND trigger_name = 'update_user_configs_updated_at'
         "#,
     )
-    .fetch_optional(&service.pool)
+    .fetch_optional(&service.pool())
     .await
     .expect("Failed to query triggers");
 
@@ -590,7 +624,7 @@ async fn mig_16_user_configs_trigger() {
         AND routine_schema = 'public'
         "#,
     )
-    .fetch_optional(&service.pool)
+    .fetch_optional(&service.pool())
     .await
     .expect("Failed to query functions");
 
@@ -636,7 +670,7 @@ async fn mig_17_initial_schema_tables() {
         AND table_type = 'BASE TABLE'
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query tables");
 
@@ -669,7 +703,7 @@ async fn mig_18_migration_order() {
         ORDER BY version ASC
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query migrations");
 
@@ -696,6 +730,17 @@ async fn mig_18_migration_order() {
         20260122000003,
         20260122000004,
         20260122000005,
+        20260122000006,
+        20260122000007,
+        20260122000008,
+        20260122000009,
+        20260122000010,
+        20260122000011,
+        20260122000012,
+        20260122000013,
+        20260122000014,
+        20260122000015,
+        20260122000016,
     ];
 
     for expected in expected_versions {
@@ -725,7 +770,7 @@ async fn mig_09_user_configs_table_schema() {
         ORDER BY ordinal_position
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query columns");
 
@@ -743,6 +788,10 @@ async fn mig_09_user_configs_table_schema() {
         column_names.contains(&"oauth_credentials".to_string()),
         "Should have oauth_credentials column"
     );
+    assert!(
+        column_names.contains(&"refresh_tokens".to_string()),
+        "Should have refresh_tokens column"
+    );
 
     // Verify user_id is primary key (not null in this check)
     let user_id_col = columns.iter().find(|(n, _, _)| n == "user_id");
@@ -776,7 +825,7 @@ async fn mig_10_pty_sessions_table_schema() {
         ORDER BY ordinal_position
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query columns");
 
@@ -834,7 +883,7 @@ async fn mig_14_foreign_key_constraints() {
         AND tc.table_schema = 'public'
         "#,
     )
-    .fetch_all(&service.pool)
+    .fetch_all(&service.pool())
     .await
     .expect("Failed to query foreign keys");
 
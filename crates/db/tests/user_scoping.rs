@@ -0,0 +1,1073 @@
+//! SQLite tests for per-user scoping of models in multi-user deployments.
+//!
+//! Each test runs the SQLite migrations against a fresh in-memory database.
+
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    execution_process_logs::ExecutionProcessLogs,
+    image::{CreateImage, Image, TaskImage},
+    project::{CreateProject, Project},
+    project_repo::ProjectRepo,
+    repo::Repo,
+    session::{CreateSession, Session},
+    task::{CreateTask, Task, TaskStatus},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+async fn setup_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory database");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+    pool
+}
+
+async fn create_project(pool: &SqlitePool, name: &str, user_id: Option<Uuid>) -> Project {
+    let data = CreateProject {
+        name: name.to_string(),
+        repositories: vec![],
+    };
+    let project = Project::create(pool, &data, Uuid::new_v4())
+        .await
+        .expect("Failed to create project");
+    Project::set_user_id(pool, project.id, user_id)
+        .await
+        .expect("Failed to set project owner");
+    project
+}
+
+async fn create_workspace(pool: &SqlitePool) -> Workspace {
+    let project = create_project(pool, "project", None).await;
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(project.id, "task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .expect("Failed to create task");
+    Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch: "vk/test".to_string(),
+            agent_working_dir: None,
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await
+    .expect("Failed to create workspace")
+}
+
+async fn create_session(pool: &SqlitePool, workspace_id: Uuid, user_id: Option<Uuid>) -> Session {
+    Session::create(
+        pool,
+        &CreateSession {
+            executor: None,
+            user_id,
+        },
+        Uuid::new_v4(),
+        workspace_id,
+    )
+    .await
+    .expect("Failed to create session")
+}
+
+#[tokio::test]
+async fn project_find_by_user_isolates_users() {
+    let pool = setup_pool().await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+
+    let alice_project = create_project(&pool, "alice-project", Some(alice)).await;
+    let bob_project = create_project(&pool, "bob-project", Some(bob)).await;
+
+    let alice_projects = Project::find_by_user(&pool, alice).await.unwrap();
+    assert_eq!(alice_projects.len(), 1);
+    assert_eq!(alice_projects[0].id, alice_project.id);
+
+    let bob_projects = Project::find_by_user(&pool, bob).await.unwrap();
+    assert_eq!(bob_projects.len(), 1);
+    assert_eq!(bob_projects[0].id, bob_project.id);
+
+    // Desktop mode still sees everything
+    assert_eq!(Project::find_all(&pool).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn project_find_by_user_excludes_unowned_projects() {
+    let pool = setup_pool().await;
+    let user = Uuid::new_v4();
+
+    create_project(&pool, "desktop-project", None).await;
+
+    assert!(Project::find_by_user(&pool, user).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn session_find_by_workspace_id_and_user_isolates_users() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+
+    let alice_session = create_session(&pool, workspace.id, Some(alice)).await;
+    let bob_session = create_session(&pool, workspace.id, Some(bob)).await;
+
+    let alice_sessions = Session::find_by_workspace_id_and_user(&pool, workspace.id, Some(alice))
+        .await
+        .unwrap();
+    assert_eq!(alice_sessions.len(), 1);
+    assert_eq!(alice_sessions[0].id, alice_session.id);
+
+    let bob_sessions = Session::find_by_workspace_id_and_user(&pool, workspace.id, Some(bob))
+        .await
+        .unwrap();
+    assert_eq!(bob_sessions.len(), 1);
+    assert_eq!(bob_sessions[0].id, bob_session.id);
+
+    let stranger_sessions =
+        Session::find_by_workspace_id_and_user(&pool, workspace.id, Some(Uuid::new_v4()))
+            .await
+            .unwrap();
+    assert!(stranger_sessions.is_empty());
+}
+
+#[tokio::test]
+async fn session_find_by_workspace_id_and_user_without_user_returns_all() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+
+    create_session(&pool, workspace.id, Some(Uuid::new_v4())).await;
+    create_session(&pool, workspace.id, None).await;
+
+    let sessions = Session::find_by_workspace_id_and_user(&pool, workspace.id, None)
+        .await
+        .unwrap();
+    assert_eq!(sessions.len(), 2);
+}
+
+#[tokio::test]
+async fn session_find_owner_returns_creator() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let user = Uuid::new_v4();
+
+    let owned = create_session(&pool, workspace.id, Some(user)).await;
+    let unowned = create_session(&pool, workspace.id, None).await;
+
+    assert_eq!(
+        Session::find_owner(&pool, owned.id).await.unwrap(),
+        Some(user)
+    );
+    assert_eq!(Session::find_owner(&pool, unowned.id).await.unwrap(), None);
+    assert_eq!(
+        Session::find_owner(&pool, Uuid::new_v4()).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn session_without_owner_belongs_to_workspace_owner() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let user = Uuid::new_v4();
+
+    // Recorded before the workspace had an owner, so the row keeps a NULL user_id
+    let session = create_session(&pool, workspace.id, None).await;
+    Workspace::set_user_id(&pool, workspace.id, Some(user))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Session::find_owner(&pool, session.id).await.unwrap(),
+        Some(user)
+    );
+    let visible = Session::find_by_workspace_id_and_user(&pool, workspace.id, Some(user))
+        .await
+        .unwrap();
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].id, session.id);
+    assert!(
+        Session::find_by_workspace_id_and_user(&pool, workspace.id, Some(Uuid::new_v4()))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn project_and_workspace_find_owner_return_owner() {
+    let pool = setup_pool().await;
+    let user = Uuid::new_v4();
+
+    let owned = create_project(&pool, "owned", Some(user)).await;
+    let unowned = create_project(&pool, "unowned", None).await;
+    assert_eq!(
+        Project::find_owner(&pool, owned.id).await.unwrap(),
+        Some(user)
+    );
+    assert_eq!(Project::find_owner(&pool, unowned.id).await.unwrap(), None);
+
+    let workspace = create_workspace(&pool).await;
+    assert_eq!(
+        Workspace::find_owner(&pool, workspace.id).await.unwrap(),
+        None
+    );
+    Workspace::set_user_id(&pool, workspace.id, Some(user))
+        .await
+        .unwrap();
+    assert_eq!(
+        Workspace::find_owner(&pool, workspace.id).await.unwrap(),
+        Some(user)
+    );
+    assert_eq!(
+        Workspace::find_owner(&pool, Uuid::new_v4()).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn session_delete_removes_only_that_session() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+
+    let doomed = create_session(&pool, workspace.id, None).await;
+    let kept = create_session(&pool, workspace.id, None).await;
+
+    assert_eq!(Session::delete(&pool, doomed.id).await.unwrap(), 1);
+    assert!(
+        Session::find_by_id(&pool, doomed.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(Session::find_by_id(&pool, kept.id).await.unwrap().is_some());
+
+    // Deleting again is a no-op
+    assert_eq!(Session::delete(&pool, doomed.id).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn workspace_find_by_user_isolates_users() {
+    let pool = setup_pool().await;
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+
+    let alice_workspace = create_workspace(&pool).await;
+    Workspace::set_user_id(&pool, alice_workspace.id, Some(alice))
+        .await
+        .unwrap();
+    let bob_workspace = create_workspace(&pool).await;
+    Workspace::set_user_id(&pool, bob_workspace.id, Some(bob))
+        .await
+        .unwrap();
+    create_workspace(&pool).await;
+
+    let alice_workspaces = Workspace::find_by_user(&pool, alice).await.unwrap();
+    assert_eq!(alice_workspaces.len(), 1);
+    assert_eq!(alice_workspaces[0].id, alice_workspace.id);
+
+    let bob_workspaces = Workspace::find_by_user(&pool, bob).await.unwrap();
+    assert_eq!(bob_workspaces.len(), 1);
+    assert_eq!(bob_workspaces[0].id, bob_workspace.id);
+
+    // Desktop mode still sees everything
+    assert_eq!(Workspace::fetch_all(&pool, None).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn workspace_pin_lists_pinned_first() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let older = create_workspace(&pool).await;
+    let newer = create_workspace(&pool).await;
+    for workspace in [&older, &newer] {
+        Workspace::set_user_id(&pool, workspace.id, Some(owner))
+            .await
+            .unwrap();
+    }
+    let make_stale = |id: Uuid| {
+        sqlx::query("UPDATE workspaces SET updated_at = '2026-01-01 00:00:00' WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+    };
+    make_stale(older.id).await.unwrap();
+
+    let ids = |workspaces: Vec<Workspace>| workspaces.into_iter().map(|w| w.id).collect::<Vec<_>>();
+    assert_eq!(
+        ids(Workspace::find_by_user(&pool, owner).await.unwrap()),
+        vec![newer.id, older.id]
+    );
+
+    let pinned = Workspace::pin(&pool, older.id).await.unwrap().unwrap();
+    assert!(pinned.pinned);
+    // Pinned sorts ahead of a more recently updated workspace
+    make_stale(older.id).await.unwrap();
+    assert_eq!(
+        ids(Workspace::find_by_user(&pool, owner).await.unwrap()),
+        vec![older.id, newer.id]
+    );
+    assert_eq!(
+        ids(Workspace::fetch_all(&pool, None).await.unwrap()),
+        vec![older.id, newer.id]
+    );
+
+    let unpinned = Workspace::unpin(&pool, older.id).await.unwrap().unwrap();
+    assert!(!unpinned.pinned);
+    assert!(
+        Workspace::pin(&pool, Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn workspace_find_by_id_and_user_hides_other_users_workspaces() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let workspace = create_workspace(&pool).await;
+    Workspace::set_user_id(&pool, workspace.id, Some(owner))
+        .await
+        .unwrap();
+
+    assert!(
+        Workspace::find_by_id_and_user(&pool, workspace.id, Some(owner))
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        Workspace::find_by_id_and_user(&pool, workspace.id, Some(Uuid::new_v4()))
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        Workspace::find_by_id_and_user(&pool, workspace.id, None)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn task_find_by_id_and_user_follows_project_owner() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let project = create_project(&pool, "mine", Some(owner)).await;
+    let task = Task::create(
+        &pool,
+        &CreateTask::from_title_description(project.id, "task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        Task::find_by_id_and_user(&pool, task.id, Some(owner))
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        Task::find_by_id_and_user(&pool, task.id, Some(Uuid::new_v4()))
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        Task::find_by_id_and_user(&pool, task.id, None)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    Task::delete(&pool, task.id).await.unwrap();
+    assert!(
+        Task::find_by_id_and_user(&pool, task.id, Some(owner))
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn workspace_find_by_task_id_filters_by_owner() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let first = create_workspace(&pool).await;
+    let second = Workspace::create(
+        &pool,
+        &CreateWorkspace {
+            branch: "vk/second".to_string(),
+            agent_working_dir: None,
+        },
+        Uuid::new_v4(),
+        first.task_id,
+    )
+    .await
+    .unwrap();
+    Workspace::set_user_id(&pool, first.id, Some(owner))
+        .await
+        .unwrap();
+    Workspace::set_user_id(&pool, second.id, Some(Uuid::new_v4()))
+        .await
+        .unwrap();
+
+    let owned = Workspace::find_by_task_id(&pool, first.task_id, Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0].id, first.id);
+
+    let all = Workspace::find_by_task_id(&pool, first.task_id, None)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+}
+
+#[tokio::test]
+async fn session_create_inherits_workspace_owner() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let workspace = create_workspace(&pool).await;
+    Workspace::set_user_id(&pool, workspace.id, Some(owner))
+        .await
+        .unwrap();
+
+    let session = create_session(&pool, workspace.id, None).await;
+    assert_eq!(
+        Session::find_owner(&pool, session.id).await.unwrap(),
+        Some(owner)
+    );
+
+    let sessions = Session::find_by_workspace_id_and_user(&pool, workspace.id, Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(sessions.len(), 1);
+}
+
+#[tokio::test]
+async fn project_find_paginated_walks_pages_in_id_order() {
+    let pool = setup_pool().await;
+    let user = Uuid::new_v4();
+
+    for i in 0..5 {
+        create_project(&pool, &format!("project-{i}"), Some(user)).await;
+    }
+    create_project(&pool, "other-project", Some(Uuid::new_v4())).await;
+
+    let first = Project::find_paginated(&pool, Some(user), None, 2, false)
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 2);
+    assert!(first[0].id < first[1].id);
+
+    let second = Project::find_paginated(&pool, Some(user), Some(first[1].id), 10, false)
+        .await
+        .unwrap();
+    assert_eq!(second.len(), 3);
+    assert!(second.iter().all(|p| p.id > first[1].id));
+
+    // Desktop mode pages over every project
+    assert_eq!(
+        Project::find_paginated(&pool, None, None, 10, false)
+            .await
+            .unwrap()
+            .len(),
+        6
+    );
+}
+
+#[tokio::test]
+async fn archived_projects_are_hidden_from_default_listing() {
+    let pool = setup_pool().await;
+    let user = Uuid::new_v4();
+
+    let active = create_project(&pool, "active", Some(user)).await;
+    let archived = create_project(&pool, "archived", Some(user)).await;
+
+    let updated = Project::archive(&pool, archived.id)
+        .await
+        .unwrap()
+        .expect("project should exist");
+    assert!(updated.is_archived);
+
+    let listed = Project::find_by_user(&pool, user).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, active.id);
+
+    let page = Project::find_paginated(&pool, Some(user), None, 10, false)
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 1);
+
+    let page = Project::find_paginated(&pool, Some(user), None, 10, true)
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 2);
+
+    // Archived projects stay reachable by id
+    assert!(
+        Project::find_by_id(&pool, archived.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    let restored = Project::unarchive(&pool, archived.id)
+        .await
+        .unwrap()
+        .expect("project should exist");
+    assert!(!restored.is_archived);
+    assert_eq!(Project::find_by_user(&pool, user).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn task_search_matches_title_and_description_within_user_projects() {
+    let pool = setup_pool().await;
+    let user = Uuid::new_v4();
+    let project = create_project(&pool, "mine", Some(user)).await;
+    let other_project = create_project(&pool, "theirs", Some(Uuid::new_v4())).await;
+
+    let create = |project_id, title: &str, description: Option<&str>| {
+        let data = CreateTask::from_title_description(
+            project_id,
+            title.to_string(),
+            description.map(str::to_string),
+        );
+        let pool = pool.clone();
+        async move {
+            Task::create(&pool, &data, Uuid::new_v4())
+                .await
+                .expect("Failed to create task")
+        }
+    };
+
+    let by_title = create(project.id, "Fix login redirect", None).await;
+    let by_description = create(project.id, "Auth", Some("the LOGIN page loops")).await;
+    create(project.id, "Unrelated", None).await;
+    create(other_project.id, "Login for someone else", None).await;
+
+    let mut ids: Vec<Uuid> = Task::search(&pool, Some(user), "login", None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    ids.sort();
+    let mut expected = vec![by_title.id, by_description.id];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    // LIKE wildcards in the query are matched literally
+    assert!(
+        Task::search(&pool, Some(user), "%", None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Desktop mode searches every project, optionally narrowed to one
+    assert_eq!(
+        Task::search(&pool, None, "login", None)
+            .await
+            .unwrap()
+            .len(),
+        3
+    );
+    assert_eq!(
+        Task::search(&pool, None, "login", Some(other_project.id))
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn execution_process_logs_paginate_by_sequence() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let session = create_session(&pool, workspace.id, None).await;
+    let exec_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO execution_processes (id, session_id) VALUES ($1, $2)")
+        .bind(exec_id)
+        .bind(session.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    for i in 0..5 {
+        let line = format!(r#"{{"Stdout":"line {i}"}}"#);
+        ExecutionProcessLogs::append_log_line(&pool, exec_id, &line)
+            .await
+            .unwrap();
+    }
+
+    let tail = ExecutionProcessLogs::find_tail(&pool, exec_id, 2)
+        .await
+        .unwrap();
+    assert_eq!(tail.len(), 2);
+    assert!(tail[0].seq < tail[1].seq);
+    assert!(tail[1].logs.contains("line 4"));
+
+    let first = ExecutionProcessLogs::find_paginated(&pool, exec_id, None, 3)
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 3);
+    assert!(first[0].logs.contains("line 0"));
+
+    let rest = ExecutionProcessLogs::find_paginated(&pool, exec_id, Some(first[2].seq), 10)
+        .await
+        .unwrap();
+    assert_eq!(rest.len(), 2);
+    assert!(rest[0].logs.contains("line 3"));
+
+    let other = ExecutionProcessLogs::find_paginated(&pool, Uuid::new_v4(), None, 10)
+        .await
+        .unwrap();
+    assert!(other.is_empty());
+}
+
+#[tokio::test]
+async fn execution_process_cancel_records_reason() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let session = create_session(&pool, workspace.id, None).await;
+    let exec_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO execution_processes (id, session_id) VALUES ($1, $2)")
+        .bind(exec_id)
+        .bind(session.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    ExecutionProcess::cancel(&pool, exec_id, Some("wrong branch"))
+        .await
+        .unwrap();
+
+    let process = ExecutionProcess::find_by_id(&pool, exec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(process.status, ExecutionProcessStatus::Cancelled);
+    assert_eq!(process.cancelled_reason.as_deref(), Some("wrong branch"));
+    assert!(process.completed_at.is_some());
+    assert!(ExecutionProcess::was_stopped(&pool, exec_id).await);
+}
+
+#[tokio::test]
+async fn session_find_active_skips_finished_sessions() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let idle = create_session(&pool, workspace.id, None).await;
+    let running = create_session(&pool, workspace.id, None).await;
+    let finished = create_session(&pool, workspace.id, None).await;
+
+    // The latest process decides: `finished` ran again after an earlier run
+    for (session_id, status, created_at) in [
+        (running.id, "completed", "2026-01-01 00:00:00"),
+        (running.id, "running", "2026-01-02 00:00:00"),
+        (finished.id, "running", "2026-01-01 00:00:00"),
+        (finished.id, "failed", "2026-01-02 00:00:00"),
+    ] {
+        sqlx::query(
+            "INSERT INTO execution_processes (id, session_id, status, created_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(session_id)
+        .bind(status)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let mut active: Vec<Uuid> = Session::find_active_for_workspace(&pool, workspace.id, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    active.sort();
+    let mut expected = vec![idle.id, running.id];
+    expected.sort();
+    assert_eq!(active, expected);
+
+    let other_user = Some(Uuid::new_v4());
+    assert!(
+        Session::find_active_for_workspace(&pool, workspace.id, other_user)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn task_delete_is_soft_and_restore_is_scoped_to_owner() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let project = create_project(&pool, "mine", Some(owner)).await;
+    let task = Task::create(
+        &pool,
+        &CreateTask::from_title_description(project.id, "oops".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(Task::delete(&pool, task.id).await.unwrap(), 1);
+    // Deleting again is a no-op
+    assert_eq!(Task::delete(&pool, task.id).await.unwrap(), 0);
+
+    // Hidden from default lookups, but still listed on request
+    assert!(Task::find_by_id(&pool, task.id).await.unwrap().is_none());
+    assert!(
+        Task::find_by_project_id_with_attempt_status(&pool, project.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let listed =
+        Task::find_by_project_id_with_attempt_status_paginated(&pool, project.id, None, 10, true)
+            .await
+            .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0].deleted_at.is_some());
+
+    // Another user cannot restore it
+    assert!(
+        Task::restore(&pool, task.id, Some(Uuid::new_v4()))
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let restored = Task::restore(&pool, task.id, Some(owner))
+        .await
+        .unwrap()
+        .expect("owner should be able to restore");
+    assert!(restored.deleted_at.is_none());
+    assert!(Task::find_by_id(&pool, task.id).await.unwrap().is_some());
+
+    // Restoring a live task finds nothing to restore
+    assert!(Task::restore(&pool, task.id, None).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn task_move_to_project_requires_owned_target() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let source = create_project(&pool, "source", Some(owner)).await;
+    let target = create_project(&pool, "target", Some(owner)).await;
+    let foreign = create_project(&pool, "foreign", Some(Uuid::new_v4())).await;
+    let task = Task::create(
+        &pool,
+        &CreateTask::from_title_description(source.id, "task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        Task::move_to_project(&pool, task.id, foreign.id, Some(owner))
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let moved = Task::move_to_project(&pool, task.id, target.id, Some(owner))
+        .await
+        .unwrap()
+        .expect("owner should be able to move the task");
+    assert_eq!(moved.project_id, target.id);
+
+    let history = Task::find_history(&pool, task.id, Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].field, "project_id");
+}
+
+#[tokio::test]
+async fn task_update_records_changed_fields_in_history() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let project = create_project(&pool, "mine", Some(owner)).await;
+    let task = Task::create(
+        &pool,
+        &CreateTask::from_title_description(project.id, "draft".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+
+    Task::update(
+        &pool,
+        task.id,
+        project.id,
+        "final".to_string(),
+        None,
+        TaskStatus::InProgress,
+        None,
+        Some(owner),
+    )
+    .await
+    .unwrap();
+
+    let history = Task::find_history(&pool, task.id, Some(owner))
+        .await
+        .unwrap();
+    let fields: Vec<&str> = history.iter().map(|h| h.field.as_str()).collect();
+    assert_eq!(fields, vec!["title", "status"]);
+    assert_eq!(history[0].user_id, Some(owner));
+    assert_eq!(*history[0].old_value, serde_json::json!("draft"));
+    assert_eq!(*history[0].new_value, serde_json::json!("final"));
+    assert_eq!(*history[1].new_value, serde_json::json!("inprogress"));
+
+    // Other users cannot read the history; desktop mode sees everything
+    assert!(
+        Task::find_history(&pool, task.id, Some(Uuid::new_v4()))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        Task::find_history(&pool, task.id, None)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn image_find_unused_created_before_respects_cutoff() {
+    let pool = setup_pool().await;
+    let image = Image::create(
+        &pool,
+        &CreateImage {
+            file_path: "old.png".to_string(),
+            original_name: "old.png".to_string(),
+            mime_type: Some("image/png".to_string()),
+            size_bytes: 1,
+            hash: "old-hash".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let later = chrono::Utc::now() + chrono::Duration::minutes(1);
+    let stale = Image::find_unused_created_before(&pool, later)
+        .await
+        .unwrap();
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].id, image.id);
+
+    let earlier = chrono::Utc::now() - chrono::Duration::days(1);
+    assert!(
+        Image::find_unused_created_before(&pool, earlier)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn task_image_is_referenced_by_user_follows_project_owner() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let project = create_project(&pool, "mine", Some(owner)).await;
+    let task = Task::create(
+        &pool,
+        &CreateTask::from_title_description(project.id, "task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let image = Image::create(
+        &pool,
+        &CreateImage {
+            file_path: "shot.png".to_string(),
+            original_name: "shot.png".to_string(),
+            mime_type: Some("image/png".to_string()),
+            size_bytes: 1,
+            hash: "shot-hash".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        !TaskImage::is_referenced_by_user(&pool, image.id, owner)
+            .await
+            .unwrap()
+    );
+
+    TaskImage::associate_many_dedup(&pool, task.id, &[image.id])
+        .await
+        .unwrap();
+    assert!(
+        TaskImage::is_referenced_by_user(&pool, image.id, owner)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !TaskImage::is_referenced_by_user(&pool, image.id, Uuid::new_v4())
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn task_bulk_update_status_skips_foreign_and_missing_tasks() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let mine = create_project(&pool, "mine", Some(owner)).await;
+    let theirs = create_project(&pool, "theirs", Some(Uuid::new_v4())).await;
+
+    let mut ids = Vec::new();
+    for (project_id, title) in [(mine.id, "a"), (mine.id, "b"), (theirs.id, "c")] {
+        let task = Task::create(
+            &pool,
+            &CreateTask::from_title_description(project_id, title.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        ids.push(task.id);
+    }
+    ids.push(Uuid::new_v4());
+
+    let updated = Task::bulk_update_status(&pool, &ids, TaskStatus::Done, Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(updated, 2);
+
+    for (id, expected) in [
+        (ids[0], TaskStatus::Done),
+        (ids[1], TaskStatus::Done),
+        (ids[2], TaskStatus::Todo),
+    ] {
+        let task = Task::find_by_id(&pool, id).await.unwrap().unwrap();
+        assert_eq!(task.status, expected);
+    }
+
+    let history = Task::find_history(&pool, ids[0], Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].field, "status");
+    assert_eq!(*history[0].new_value, serde_json::json!("done"));
+}
+
+#[tokio::test]
+async fn task_count_by_status_is_scoped_to_project_and_user() {
+    let pool = setup_pool().await;
+    let owner = Uuid::new_v4();
+    let mine = create_project(&pool, "mine", Some(owner)).await;
+    let theirs = create_project(&pool, "theirs", Some(Uuid::new_v4())).await;
+
+    let mut ids = Vec::new();
+    for (project_id, title) in [(mine.id, "a"), (mine.id, "b"), (theirs.id, "c")] {
+        let task = Task::create(
+            &pool,
+            &CreateTask::from_title_description(project_id, title.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        ids.push(task.id);
+    }
+    Task::update_status(&pool, ids[1], TaskStatus::Done)
+        .await
+        .unwrap();
+
+    let counts = Task::count_by_status(&pool, Some(mine.id), Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(counts.get(&TaskStatus::Todo), Some(&1));
+    assert_eq!(counts.get(&TaskStatus::Done), Some(&1));
+    assert_eq!(counts.get(&TaskStatus::InProgress), None);
+
+    let all = Task::count_by_status(&pool, None, None).await.unwrap();
+    assert_eq!(all.values().sum::<u64>(), 3);
+
+    let foreign = Task::count_by_status(&pool, Some(theirs.id), Some(owner))
+        .await
+        .unwrap();
+    assert!(foreign.is_empty());
+}
+
+#[tokio::test]
+async fn repo_find_by_path_is_shared_across_users() {
+    let pool = setup_pool().await;
+    let alice = create_project(&pool, "alice", Some(Uuid::new_v4())).await;
+    let bob = create_project(&pool, "bob", Some(Uuid::new_v4())).await;
+    let path = std::path::Path::new("/workspaces/shared/repo");
+
+    assert!(Repo::find_by_path(&pool, path).await.unwrap().is_none());
+
+    let repo = Repo::find_or_create(&pool, path, "repo").await.unwrap();
+    ProjectRepo::create(&pool, alice.id, repo.id).await.unwrap();
+
+    let existing = Repo::find_by_path(&pool, path).await.unwrap().unwrap();
+    assert_eq!(existing.id, repo.id);
+    ProjectRepo::create(&pool, bob.id, existing.id)
+        .await
+        .unwrap();
+
+    for project in [&alice, &bob] {
+        let links = ProjectRepo::find_by_project_id(&pool, project.id)
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].repo_id, repo.id);
+    }
+}
+
+#[tokio::test]
+async fn workspace_repo_active_branch_starts_empty_and_updates() {
+    let pool = setup_pool().await;
+    let workspace = create_workspace(&pool).await;
+    let repo = Repo::find_or_create(&pool, std::path::Path::new("/repos/app"), "app")
+        .await
+        .unwrap();
+    WorkspaceRepo::create_many(
+        &pool,
+        workspace.id,
+        &[CreateWorkspaceRepo {
+            repo_id: repo.id,
+            target_branch: "main".to_string(),
+        }],
+    )
+    .await
+    .unwrap();
+
+    let created = WorkspaceRepo::find_by_workspace_and_repo_id(&pool, workspace.id, repo.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(created.active_branch, "");
+
+    WorkspaceRepo::update_active_branch(&pool, workspace.id, repo.id, "vk/feature")
+        .await
+        .unwrap();
+
+    let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(&pool, workspace.id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0].target_branch, "main");
+    assert_eq!(repos[0].active_branch, "vk/feature");
+}
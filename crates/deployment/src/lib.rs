@@ -31,6 +31,7 @@ use services::services::{
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
+    workspace_repo_cache::WorkspaceRepoCache,
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -106,6 +107,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn file_search_cache(&self) -> &Arc<FileSearchCache>;
 
+    fn workspace_repo_cache(&self) -> &Arc<WorkspaceRepoCache>;
+
     fn approvals(&self) -> &Approvals;
 
     fn queued_message_service(&self) -> &QueuedMessageService;
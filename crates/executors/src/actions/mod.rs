@@ -14,6 +14,7 @@ use crate::{
     approvals::ExecutorApprovalService,
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild},
+    profile::ExecutorProfileId,
 };
 pub mod coding_agent_follow_up;
 pub mod coding_agent_initial;
@@ -59,6 +60,20 @@ impl ExecutorAction {
         self.next_action.as_deref()
     }
 
+    /// The coding agent profile this action runs, `None` for scripts.
+    pub fn executor_profile_id(&self) -> Option<&ExecutorProfileId> {
+        match self.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                Some(&request.executor_profile_id)
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                Some(&request.executor_profile_id)
+            }
+            ExecutorActionType::ReviewRequest(request) => Some(&request.executor_profile_id),
+            ExecutorActionType::ScriptRequest(_) => None,
+        }
+    }
+
     pub fn base_executor(&self) -> Option<BaseCodingAgent> {
         match self.typ() {
             ExecutorActionType::CodingAgentInitialRequest(request) => Some(request.base_executor()),
@@ -31,6 +31,11 @@ impl CommandParts {
         Self { program, args }
     }
 
+    /// The program to run, before it is resolved against `PATH`.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
     pub async fn into_resolved(self) -> Result<(PathBuf, Vec<String>), ExecutorError> {
         let CommandParts { program, args } = self;
         let executable = resolve_executable_path(&program)
@@ -9,7 +9,7 @@ use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
 use crate::{
-    command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
+    command::{CmdOverrides, CommandBuildError, CommandBuilder, CommandParts, apply_overrides},
     env::ExecutionEnv,
     executors::{
         AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
@@ -153,6 +153,10 @@ impl StandardCodingAgentExecutor for Amp {
         Ok(child.into())
     }
 
+    async fn initial_command(&self) -> Result<Option<CommandParts>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?.build_initial()?))
+    }
+
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
         let entry_index_provider = EntryIndexProvider::start_from(&msg_store);
 
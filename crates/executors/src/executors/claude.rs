@@ -206,6 +206,10 @@ impl StandardCodingAgentExecutor for ClaudeCode {
             .await
     }
 
+    async fn initial_command(&self) -> Result<Option<CommandParts>, ExecutorError> {
+        Ok(Some(self.build_command_builder().await?.build_initial()?))
+    }
+
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
         let entry_index_provider = EntryIndexProvider::start_from(&msg_store);
 
@@ -199,6 +199,10 @@ impl StandardCodingAgentExecutor for Codex {
             .await
     }
 
+    async fn initial_command(&self) -> Result<Option<CommandParts>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?.build_initial()?))
+    }
+
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
         normalize_logs(msg_store, worktree_path);
     }
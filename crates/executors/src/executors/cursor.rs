@@ -16,7 +16,7 @@ use workspace_utils::{
 };
 
 use crate::{
-    command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
+    command::{CmdOverrides, CommandBuildError, CommandBuilder, CommandParts, apply_overrides},
     env::ExecutionEnv,
     executors::{
         AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
@@ -147,6 +147,10 @@ impl StandardCodingAgentExecutor for CursorAgent {
         Ok(child.into())
     }
 
+    async fn initial_command(&self) -> Result<Option<CommandParts>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?.build_initial()?))
+    }
+
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
         let entry_index_provider = EntryIndexProvider::start_from(&msg_store);
 
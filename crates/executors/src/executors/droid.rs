@@ -171,6 +171,10 @@ impl StandardCodingAgentExecutor for Droid {
         spawn_droid(continue_cmd, &combined_prompt, current_dir, env, &self.cmd).await
     }
 
+    async fn initial_command(&self) -> Result<Option<CommandParts>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?.build_initial()?))
+    }
+
     fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
         normalize_logs(
             msg_store.clone(),
@@ -17,7 +17,7 @@ use crate::executors::qa_mock::QaMockExecutor;
 use crate::{
     actions::{ExecutorAction, review::RepoReviewContext},
     approvals::ExecutorApprovalService,
-    command::CommandBuildError,
+    command::{CommandBuildError, CommandParts},
     env::ExecutionEnv,
     executors::{
         amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
@@ -109,6 +109,23 @@ pub enum CodingAgent {
 }
 
 impl CodingAgent {
+    /// The model configured for this agent, if it has a model setting and one
+    /// is set.
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            Self::ClaudeCode(agent) => agent.model.as_deref(),
+            Self::Gemini(agent) => agent.model.as_deref(),
+            Self::Codex(agent) => agent.model.as_deref(),
+            Self::Opencode(agent) => agent.model.as_deref(),
+            Self::CursorAgent(agent) => agent.model.as_deref(),
+            Self::Copilot(agent) => agent.model.as_deref(),
+            Self::Droid(agent) => agent.model.as_deref(),
+            Self::Amp(_) | Self::QwenCode(_) => None,
+            #[cfg(feature = "qa-mode")]
+            Self::QaMock(_) => None,
+        }
+    }
+
     pub fn get_mcp_config(&self) -> McpConfig {
         match self {
             Self::Codex(_) => McpConfig::new(
@@ -238,6 +255,12 @@ pub trait StandardCodingAgentExecutor {
         Err(ExecutorError::SetupHelperNotSupported)
     }
 
+    /// The command a new session would be started with, used to check that the
+    /// executor is installed before anything is spawned.
+    async fn initial_command(&self) -> Result<Option<CommandParts>, ExecutorError> {
+        Ok(None)
+    }
+
     fn get_availability_info(&self) -> AvailabilityInfo {
         let config_files_found = self
             .default_mcp_config_path()
@@ -1,14 +1,19 @@
 use std::{
     collections::HashMap,
     fs,
+    path::Path,
+    process::Stdio,
     str::FromStr,
     sync::{LazyLock, RwLock},
+    time::Duration,
 };
 
 use convert_case::{Case, Casing};
 use serde::{Deserialize, Deserializer, Serialize, de::Error as DeError};
 use thiserror::Error;
+use tokio::process::Command;
 use ts_rs::TS;
+use workspace_utils::shell::resolve_executable_path;
 
 use crate::executors::{
     AvailabilityInfo, BaseCodingAgent, CodingAgent, StandardCodingAgentExecutor,
@@ -116,6 +121,46 @@ impl std::fmt::Display for ExecutorProfileId {
     }
 }
 
+impl FromStr for ExecutorProfileId {
+    type Err = ProfileError;
+
+    /// Parse the `EXECUTOR[:VARIANT]` form produced by `Display`. Executor names
+    /// are also accepted in kebab-case, like in the serialized form.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (executor, variant) = match s.split_once(':') {
+            Some((executor, variant)) => (executor, Some(variant)),
+            None => (s, None),
+        };
+        let norm = executor.trim().replace('-', "_").to_ascii_uppercase();
+        let executor = BaseCodingAgent::from_str(&norm)
+            .map_err(|_| ProfileError::Validation(format!("unknown executor '{executor}'")))?;
+        Ok(match variant.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(variant) => Self::with_variant(executor, canonical_variant_key(variant)),
+            None => Self::new(executor),
+        })
+    }
+}
+
+/// Whether an executor profile can be started on this machine
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProfileValidation {
+    pub available: bool,
+    /// Why the executor cannot be started, when it is unavailable
+    pub reason: Option<String>,
+    /// First line of `<program> --version`, when the program reports one
+    pub version: Option<String>,
+}
+
+impl ProfileValidation {
+    fn unavailable(reason: String) -> Self {
+        Self {
+            available: false,
+            reason: Some(reason),
+            version: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ExecutorConfig {
     #[serde(flatten)]
@@ -401,6 +446,52 @@ impl ExecutorConfigs {
             .cloned()
     }
 
+    /// Check that the executable behind `executor_profile_id` is on `PATH`
+    pub async fn validate_profile(
+        &self,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> ProfileValidation {
+        let Some(agent) = self.get_coding_agent(executor_profile_id) else {
+            return ProfileValidation::unavailable(format!(
+                "Unknown executor profile '{executor_profile_id}'"
+            ));
+        };
+
+        let command = match agent.initial_command().await {
+            Ok(Some(command)) => command,
+            // Executors without an external command are always available
+            Ok(None) => {
+                return ProfileValidation {
+                    available: true,
+                    reason: None,
+                    version: None,
+                };
+            }
+            Err(e) => return ProfileValidation::unavailable(e.to_string()),
+        };
+
+        let program = command.program();
+        let Some(executable) = resolve_executable_path(program).await else {
+            return ProfileValidation::unavailable(format!("'{program}' was not found on PATH"));
+        };
+
+        // `npx --version` would report npm's version rather than the agent's
+        let is_package_runner = executable
+            .file_stem()
+            .is_some_and(|stem| stem.eq_ignore_ascii_case("npx"));
+        let version = if is_package_runner {
+            None
+        } else {
+            program_version(&executable).await
+        };
+
+        ProfileValidation {
+            available: true,
+            reason: None,
+            version,
+        }
+    }
+
     pub fn get_coding_agent_or_default(
         &self,
         executor_profile_id: &ExecutorProfileId,
@@ -474,6 +565,12 @@ impl ExecutorConfigs {
         tracing::info!("Recommended executor: {}", selected);
         Ok(ExecutorProfileId::new(selected))
     }
+
+    /// The profile used when no executor can be recommended, e.g. on a fresh
+    /// machine without any agent installed.
+    pub fn get_default_executor_profile() -> ExecutorProfileId {
+        ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+    }
 }
 
 pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
@@ -482,3 +579,45 @@ pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
         variant: None,
     }
 }
+
+/// First line printed by `<executable> --version`, or `None` if the program
+/// fails, prints nothing or does not answer within a few seconds.
+async fn program_version(executable: &Path) -> Option<String> {
+    let output = tokio::time::timeout(
+        Duration::from_secs(5),
+        Command::new(executable)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_executor_profile_ids() {
+        assert_eq!(
+            "CLAUDE_CODE".parse::<ExecutorProfileId>().unwrap(),
+            ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+        );
+        assert_eq!(
+            "claude-code:plan".parse::<ExecutorProfileId>().unwrap(),
+            ExecutorProfileId::with_variant(BaseCodingAgent::ClaudeCode, "PLAN".to_string())
+        );
+        assert!("not-an-agent".parse::<ExecutorProfileId>().is_err());
+    }
+}
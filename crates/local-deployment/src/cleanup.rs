@@ -6,10 +6,18 @@
 //! - PTY session cleanup (idle sessions)
 //! - Orphaned process cleanup (processes without active sessions)
 //! - Workspace cleanup (expired workspaces)
+//! - Approval expiry (pending approvals nobody answered)
+//! - Image pruning (cached images older than a configurable age)
+//! - Stale worktree pruning (workspace directories without a database record)
 //!
 //! All cleanup actions are logged with structured fields for audit purposes.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use services::services::{
+    approvals::Approvals, container::ContainerService, image::ImageService,
+    workspace_manager::WorkspaceManager,
+};
 
 use crate::container::LocalContainerService;
 use crate::pty::PtyService;
@@ -17,6 +25,30 @@ use crate::pty::PtyService;
 /// Default cleanup interval for the combined cleanup job (5 minutes).
 const DEFAULT_CLEANUP_INTERVAL_SECS: u64 = 300;
 
+/// Default maximum age of cached images before they are pruned (30 days).
+const DEFAULT_IMAGE_MAX_AGE_DAYS: u64 = 30;
+
+/// Default cap on queued follow-up messages per session.
+const DEFAULT_MAX_QUEUED_MESSAGES_PER_SESSION: usize = 1;
+
+/// Default interval between stale worktree prunes (24 hours).
+const DEFAULT_WORKTREE_PRUNE_INTERVAL_HOURS: u64 = 24;
+
+const SECS_PER_HOUR: u64 = 60 * 60;
+const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
+
+/// Lower bound for the cleanup interval; shorter intervals thrash the database.
+const MIN_CLEANUP_INTERVAL_SECS: u64 = 30;
+
+/// Lower bound for the PTY idle timeout so active terminals are not reaped.
+const MIN_PTY_SESSION_TIMEOUT_SECS: u64 = 60;
+
+/// Lower bound for the approval timeout so users have time to respond.
+const MIN_APPROVAL_TIMEOUT_SECS: u64 = 10;
+
+/// Upper bound for queued follow-up messages per session.
+const MAX_QUEUED_MESSAGES_PER_SESSION: usize = 100;
+
 /// Cleanup job configuration.
 #[derive(Debug, Clone)]
 pub struct CleanupConfig {
@@ -24,6 +56,14 @@ pub struct CleanupConfig {
     pub cleanup_interval: Duration,
     /// PTY session idle timeout.
     pub pty_session_timeout: Duration,
+    /// Maximum age of cached images before they are pruned.
+    pub image_max_age: Duration,
+    /// How long an approval request may stay pending before it is denied.
+    pub approval_timeout: Duration,
+    /// Maximum number of queued follow-up messages per session.
+    pub max_queued_messages_per_session: usize,
+    /// How often workspace directories without a database record are removed.
+    pub worktree_prune_interval: Duration,
 }
 
 impl Default for CleanupConfig {
@@ -33,6 +73,14 @@ impl Default for CleanupConfig {
             pty_session_timeout: Duration::from_secs(
                 crate::pty::cleanup::DEFAULT_SESSION_TIMEOUT_SECS,
             ),
+            image_max_age: Duration::from_secs(DEFAULT_IMAGE_MAX_AGE_DAYS * SECS_PER_DAY),
+            approval_timeout: Duration::from_secs(
+                utils::approvals::APPROVAL_TIMEOUT_SECONDS as u64,
+            ),
+            max_queued_messages_per_session: DEFAULT_MAX_QUEUED_MESSAGES_PER_SESSION,
+            worktree_prune_interval: Duration::from_secs(
+                DEFAULT_WORKTREE_PRUNE_INTERVAL_HOURS * SECS_PER_HOUR,
+            ),
         }
     }
 }
@@ -40,29 +88,116 @@ impl Default for CleanupConfig {
 impl CleanupConfig {
     /// Load cleanup configuration from environment variables.
     ///
+    /// Unset variables use their default; unparsable values are logged and
+    /// replaced by the default.
+    ///
     /// Environment variables:
     /// - `CLEANUP_INTERVAL_SECS`: Combined cleanup interval (default: 300)
-    /// - `PTY_SESSION_TIMEOUT_SECS`: PTY session timeout (default: 1800)
+    /// - `PTY_IDLE_TIMEOUT_MINS`: PTY session idle timeout in minutes (default: 30).
+    ///   Falls back to `PTY_SESSION_TIMEOUT_SECS` when unset.
+    /// - `IMAGE_MAX_AGE_DAYS`: Age after which cached images are pruned (default: 30)
+    /// - `APPROVAL_TIMEOUT_SECS`: Approval request timeout (default: 300)
+    /// - `MAX_QUEUED_MESSAGES_PER_SESSION`: Queued follow-ups per session (default: 1)
+    /// - `PRUNE_STALE_WORKTREES_INTERVAL_HOURS`: Stale worktree prune interval (default: 24)
     pub fn from_env() -> Self {
-        let cleanup_interval_secs: u64 = std::env::var("CLEANUP_INTERVAL_SECS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(DEFAULT_CLEANUP_INTERVAL_SECS);
+        let defaults = Self::default();
+
+        let cleanup_interval_secs =
+            env_or("CLEANUP_INTERVAL_SECS", defaults.cleanup_interval.as_secs());
+
+        let pty_session_timeout = match env_parse::<u64>("PTY_IDLE_TIMEOUT_MINS") {
+            Some(mins) => Duration::from_secs(mins.saturating_mul(60)),
+            None => crate::pty::cleanup::get_cleanup_config_from_env().1,
+        };
+
+        let image_max_age_days = env_or("IMAGE_MAX_AGE_DAYS", DEFAULT_IMAGE_MAX_AGE_DAYS);
 
-        let (_, pty_timeout) = crate::pty::cleanup::get_cleanup_config_from_env();
+        let approval_timeout_secs =
+            env_or("APPROVAL_TIMEOUT_SECS", defaults.approval_timeout.as_secs());
+
+        let max_queued_messages_per_session = env_or(
+            "MAX_QUEUED_MESSAGES_PER_SESSION",
+            defaults.max_queued_messages_per_session,
+        );
+
+        let worktree_prune_interval_hours = env_or(
+            "PRUNE_STALE_WORKTREES_INTERVAL_HOURS",
+            DEFAULT_WORKTREE_PRUNE_INTERVAL_HOURS,
+        );
 
         Self {
             cleanup_interval: Duration::from_secs(cleanup_interval_secs),
-            pty_session_timeout: pty_timeout,
+            pty_session_timeout,
+            image_max_age: Duration::from_secs(image_max_age_days.saturating_mul(SECS_PER_DAY)),
+            approval_timeout: Duration::from_secs(approval_timeout_secs),
+            max_queued_messages_per_session,
+            worktree_prune_interval: Duration::from_secs(
+                worktree_prune_interval_hours.saturating_mul(SECS_PER_HOUR),
+            ),
+        }
+    }
+
+    /// Check that every threshold is within a safe range.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cleanup_interval.as_secs() < MIN_CLEANUP_INTERVAL_SECS {
+            return Err(format!(
+                "CLEANUP_INTERVAL_SECS must be at least {MIN_CLEANUP_INTERVAL_SECS} (got {})",
+                self.cleanup_interval.as_secs()
+            ));
+        }
+        if self.pty_session_timeout.as_secs() < MIN_PTY_SESSION_TIMEOUT_SECS {
+            return Err(format!(
+                "PTY idle timeout must be at least {MIN_PTY_SESSION_TIMEOUT_SECS} seconds (got {})",
+                self.pty_session_timeout.as_secs()
+            ));
+        }
+        if self.image_max_age.as_secs() < SECS_PER_DAY {
+            return Err("IMAGE_MAX_AGE_DAYS must be at least 1".to_string());
+        }
+        if self.approval_timeout.as_secs() < MIN_APPROVAL_TIMEOUT_SECS {
+            return Err(format!(
+                "APPROVAL_TIMEOUT_SECS must be at least {MIN_APPROVAL_TIMEOUT_SECS} (got {})",
+                self.approval_timeout.as_secs()
+            ));
+        }
+        if !(1..=MAX_QUEUED_MESSAGES_PER_SESSION).contains(&self.max_queued_messages_per_session) {
+            return Err(format!(
+                "MAX_QUEUED_MESSAGES_PER_SESSION must be between 1 and {MAX_QUEUED_MESSAGES_PER_SESSION} (got {})",
+                self.max_queued_messages_per_session
+            ));
+        }
+        if self.worktree_prune_interval.as_secs() < SECS_PER_HOUR {
+            return Err("PRUNE_STALE_WORKTREES_INTERVAL_HOURS must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parse an environment variable, logging values that fail to parse.
+fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    match value.trim().parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            tracing::warn!(variable = name, value = %value, "Ignoring invalid cleanup setting");
+            None
         }
     }
 }
 
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    env_parse(name).unwrap_or(default)
+}
+
 /// Spawns the combined resource cleanup job.
 ///
 /// This job runs periodically and cleans up:
 /// - Idle PTY sessions
 /// - Orphaned execution processes
+/// - Expired approval requests
+/// - Cached images older than the configured maximum age
+/// - Workspace directories without a database record, once per
+///   `worktree_prune_interval`
 ///
 /// All cleanup actions are logged with structured fields (user_id, session_id,
 /// execution_id, action type, timestamp) for security auditing.
@@ -71,6 +206,8 @@ impl CleanupConfig {
 ///
 /// * `pty_service` - The PTY service to clean up idle sessions.
 /// * `container_service` - The container service to clean up orphaned processes.
+/// * `approvals` - The approval store whose expired requests are auto-denied.
+/// * `image_service` - The image service used to prune old images.
 /// * `config` - Cleanup job configuration.
 ///
 /// # Returns
@@ -79,17 +216,25 @@ impl CleanupConfig {
 pub fn spawn_cleanup_job(
     pty_service: PtyService,
     container_service: LocalContainerService,
+    approvals: Approvals,
+    image_service: ImageService,
     config: CleanupConfig,
 ) -> tokio::task::JoinHandle<()> {
     tracing::info!(
         cleanup_interval_secs = config.cleanup_interval.as_secs(),
         pty_session_timeout_secs = config.pty_session_timeout.as_secs(),
+        image_max_age_secs = config.image_max_age.as_secs(),
+        approval_timeout_secs = config.approval_timeout.as_secs(),
+        max_queued_messages_per_session = config.max_queued_messages_per_session,
+        worktree_prune_interval_secs = config.worktree_prune_interval.as_secs(),
         action = "cleanup_job_started",
         "Starting combined resource cleanup job"
     );
 
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(config.cleanup_interval);
+        // Orphaned workspaces are already pruned once at startup
+        let mut last_worktree_prune = Instant::now();
 
         loop {
             interval.tick().await;
@@ -125,9 +270,63 @@ pub fn spawn_cleanup_job(
                 );
             }
 
+            // 3. Deny approvals that have been pending past their expiry
+            let approvals_expired = approvals.expire_old().await.len();
+            if approvals_expired > 0 {
+                tracing::info!(
+                    cleaned_count = approvals_expired,
+                    action = "approval_expiry",
+                    resource_type = "approval",
+                    timestamp = %timestamp,
+                    "Denied expired approval requests"
+                );
+            }
+
+            // 4. Prune cached images past their maximum age
+            let images_pruned = match image_service.prune_by_age(config.image_max_age).await {
+                Ok(count) => count,
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        action = "image_prune_failed",
+                        "Failed to prune old images"
+                    );
+                    0
+                }
+            };
+            if images_pruned > 0 {
+                tracing::info!(
+                    cleaned_count = images_pruned,
+                    action = "image_prune",
+                    resource_type = "image",
+                    timestamp = %timestamp,
+                    "Pruned old images"
+                );
+            }
+
+            // 5. Remove workspace directories whose workspace no longer exists
+            let mut worktrees_pruned = 0;
+            if last_worktree_prune.elapsed() >= config.worktree_prune_interval {
+                last_worktree_prune = Instant::now();
+                worktrees_pruned =
+                    WorkspaceManager::cleanup_orphan_workspaces(&container_service.db().pool).await;
+                if worktrees_pruned > 0 {
+                    tracing::info!(
+                        cleaned_count = worktrees_pruned,
+                        action = "stale_worktree_prune",
+                        resource_type = "workspace_directory",
+                        timestamp = %timestamp,
+                        "Pruned stale worktrees"
+                    );
+                }
+            }
+
             tracing::debug!(
                 pty_sessions_cleaned = pty_cleaned,
                 processes_cleaned = orphaned_cleaned,
+                approvals_expired,
+                images_pruned,
+                worktrees_pruned,
                 action = "cleanup_cycle_completed",
                 timestamp = %timestamp,
                 "Resource cleanup cycle completed"
@@ -197,5 +396,43 @@ mod tests {
             config.pty_session_timeout.as_secs(),
             crate::pty::cleanup::DEFAULT_SESSION_TIMEOUT_SECS
         );
+        assert_eq!(
+            config.image_max_age.as_secs(),
+            DEFAULT_IMAGE_MAX_AGE_DAYS * SECS_PER_DAY
+        );
+    }
+
+    #[test]
+    fn test_cleanup_config_default_is_valid() {
+        assert!(CleanupConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_cleanup_config_rejects_short_interval() {
+        let config = CleanupConfig {
+            cleanup_interval: Duration::from_secs(MIN_CLEANUP_INTERVAL_SECS - 1),
+            ..CleanupConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("CLEANUP_INTERVAL_SECS"));
+    }
+
+    #[test]
+    fn test_cleanup_config_rejects_sub_hour_worktree_prune_interval() {
+        let config = CleanupConfig {
+            worktree_prune_interval: Duration::from_secs(SECS_PER_HOUR - 1),
+            ..CleanupConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("PRUNE_STALE_WORKTREES_INTERVAL_HOURS"));
+    }
+
+    #[test]
+    fn test_cleanup_config_rejects_zero_queued_messages() {
+        let config = CleanupConfig {
+            max_queued_messages_per_session: 0,
+            ..CleanupConfig::default()
+        };
+        assert!(config.validate().is_err());
     }
 }
@@ -8,6 +8,59 @@ use services::services::container::ContainerError;
 #[cfg(unix)]
 use tokio::time::Duration;
 
+/// Signal used to pause executions, from `EXECUTION_PAUSE_SIGNAL` (default `SIGSTOP`)
+#[cfg(unix)]
+fn pause_signal() -> Signal {
+    match std::env::var("EXECUTION_PAUSE_SIGNAL") {
+        Ok(name) => name.parse().unwrap_or_else(|_| {
+            tracing::warn!("Invalid EXECUTION_PAUSE_SIGNAL '{}', using SIGSTOP", name);
+            Signal::SIGSTOP
+        }),
+        Err(_) => Signal::SIGSTOP,
+    }
+}
+
+#[cfg(unix)]
+fn signal_process_group(child: &mut AsyncGroupChild, signal: Signal) -> Result<(), ContainerError> {
+    let pid = child
+        .inner()
+        .id()
+        .ok_or_else(|| ContainerError::KillFailed(std::io::Error::other("process has exited")))?;
+    let pgid = getpgid(Some(Pid::from_raw(pid as i32)))
+        .map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))?;
+    killpg(pgid, signal).map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))
+}
+
+/// Suspend the whole process group with the configured pause signal.
+pub fn pause_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, pause_signal())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        Err(ContainerError::Other(anyhow::anyhow!(
+            "Pausing executions is only supported on Unix"
+        )))
+    }
+}
+
+/// Continue a process group suspended by [`pause_process_group`].
+pub fn resume_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, Signal::SIGCONT)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        Err(ContainerError::Other(anyhow::anyhow!(
+            "Resuming executions is only supported on Unix"
+        )))
+    }
+}
+
 pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
     // hit the whole process group, not just the leader
     #[cfg(unix)]
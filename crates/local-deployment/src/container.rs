@@ -36,16 +36,18 @@ use executors::{
     env::{ExecutionEnv, RepoContext},
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
-    profile::ExecutorProfileId,
+    profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
 use serde_json::json;
 use services::services::{
+    agent_tokens::{self, AgentTokenSenders},
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
     config::Config,
-    container::{ContainerError, ContainerRef, ContainerService},
+    container::{ContainerError, ContainerRef, ContainerService, ResourceUsage, RunningProcess},
     diff_stream::{self, DiffStreamHandle},
+    file_search::FileSearchCache,
     git::{GitCli, GitService},
     image::ImageService,
     notification::NotificationService,
@@ -61,7 +63,7 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{command, copy, resources::ResourceMonitor};
 
 /// Tracks ownership information for an execution process
 #[derive(Clone, Debug)]
@@ -78,6 +80,8 @@ pub struct LocalContainerService {
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    /// Reply token channels of running coding agent processes
+    agent_token_senders: AgentTokenSenders,
     /// Tracks user ownership of execution processes for multi-user isolation
     execution_owners: Arc<RwLock<HashMap<Uuid, ExecutionOwnership>>>,
     config: Arc<RwLock<Config>>,
@@ -87,6 +91,8 @@ pub struct LocalContainerService {
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
     notification_service: NotificationService,
+    file_search_cache: Arc<FileSearchCache>,
+    resource_monitor: ResourceMonitor,
 }
 
 impl LocalContainerService {
@@ -100,10 +106,12 @@ impl LocalContainerService {
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
+        file_search_cache: Arc<FileSearchCache>,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
         let execution_owners = Arc::new(RwLock::new(HashMap::new()));
+        let agent_token_senders = Arc::new(RwLock::new(HashMap::new()));
         let notification_service = NotificationService::new(config.clone());
 
         let container = LocalContainerService {
@@ -111,6 +119,7 @@ impl LocalContainerService {
             child_store,
             interrupt_senders,
             msg_stores,
+            agent_token_senders,
             execution_owners,
             config,
             git,
@@ -119,6 +128,8 @@ impl LocalContainerService {
             approvals,
             queued_message_service,
             notification_service,
+            file_search_cache,
+            resource_monitor: ResourceMonitor::new(),
         };
 
         container.spawn_workspace_cleanup();
@@ -314,6 +325,38 @@ impl LocalContainerService {
         self.stop_execution(execution_process, status).await
     }
 
+    /// Load `process_id` and check that it is a running process of `workspace`.
+    async fn running_process_in_workspace(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let pool = &self.db.pool;
+        let process = ExecutionProcess::find_by_id(pool, process_id)
+            .await?
+            .ok_or(ContainerError::ProcessNotRunning(process_id))?;
+        let in_workspace = process
+            .parent_workspace_and_session(pool)
+            .await?
+            .is_some_and(|(parent, _)| parent.id == workspace.id);
+        if !in_workspace || process.status != ExecutionProcessStatus::Running {
+            return Err(ContainerError::ProcessNotRunning(process_id));
+        }
+        Ok(process)
+    }
+
+    /// Mark a process paused or resumed and return the updated row.
+    async fn record_pause_state(
+        &self,
+        process_id: Uuid,
+        paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        ExecutionProcess::set_paused_at(&self.db.pool, process_id, paused_at).await?;
+        ExecutionProcess::find_by_id(&self.db.pool, process_id)
+            .await?
+            .ok_or(ContainerError::ProcessNotRunning(process_id))
+    }
+
     /// List all execution processes owned by a specific user.
     ///
     /// Returns a list of (execution_id, ownership_info) tuples for all processes
@@ -446,7 +489,11 @@ impl LocalContainerService {
         Ok(execution_process)
     }
 
-    pub async fn cleanup_workspace(db: &DBService, workspace: &Workspace) {
+    pub async fn cleanup_workspace(
+        db: &DBService,
+        file_search_cache: &FileSearchCache,
+        workspace: &Workspace,
+    ) {
         let Some(container_ref) = &workspace.container_ref else {
             return;
         };
@@ -467,7 +514,7 @@ impl LocalContainerService {
                 tracing::warn!("Failed to remove workspace directory: {}", e);
             }
         } else {
-            WorkspaceManager::cleanup_workspace(&workspace_dir, &repositories)
+            WorkspaceManager::cleanup_workspace(&workspace_dir, &repositories, file_search_cache)
                 .await
                 .unwrap_or_else(|e| {
                     tracing::warn!(
@@ -482,7 +529,10 @@ impl LocalContainerService {
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
     }
 
-    pub async fn cleanup_expired_workspaces(db: &DBService) -> Result<(), DeploymentError> {
+    pub async fn cleanup_expired_workspaces(
+        db: &DBService,
+        file_search_cache: &FileSearchCache,
+    ) -> Result<(), DeploymentError> {
         let expired_workspaces = Workspace::find_expired_for_cleanup(&db.pool).await?;
         if expired_workspaces.is_empty() {
             tracing::debug!("No expired workspaces found");
@@ -493,13 +543,14 @@ impl LocalContainerService {
             expired_workspaces.len()
         );
         for workspace in &expired_workspaces {
-            Self::cleanup_workspace(db, workspace).await;
+            Self::cleanup_workspace(db, file_search_cache, workspace).await;
         }
         Ok(())
     }
 
     pub fn spawn_workspace_cleanup(&self) {
         let db = self.db.clone();
+        let file_search_cache = self.file_search_cache.clone();
         let cleanup_expired = Self::cleanup_expired_workspaces;
         tokio::spawn(async move {
             WorkspaceManager::cleanup_orphan_workspaces(&db.pool).await;
@@ -509,9 +560,11 @@ impl LocalContainerService {
             loop {
                 cleanup_interval.tick().await;
                 tracing::info!("Starting periodic workspace cleanup...");
-                cleanup_expired(&db).await.unwrap_or_else(|e| {
-                    tracing::error!("Failed to clean up expired workspaces: {}", e)
-                });
+                cleanup_expired(&db, &file_search_cache)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Failed to clean up expired workspaces: {}", e)
+                    });
             }
         });
     }
@@ -540,7 +593,7 @@ impl LocalContainerService {
     /// Get the commit message based on the execution run reason.
     async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
         match ctx.execution_process.run_reason {
-            ExecutionProcessRunReason::CodingAgent => {
+            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::Review => {
                 // Try to retrieve the task summary from the coding agent turn
                 // otherwise fallback to default message
                 match CodingAgentTurn::find_by_execution_process_id(
@@ -775,7 +828,7 @@ impl LocalContainerService {
 
                     let should_start_next = if matches!(
                         ctx.execution_process.run_reason,
-                        ExecutionProcessRunReason::CodingAgent
+                        ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::Review
                     ) {
                         // Check if agent made commits OR if we just committed uncommitted changes
                         changes_committed
@@ -808,7 +861,9 @@ impl LocalContainerService {
                     // If it failed or was killed, just clear the queue and finalize
                     let should_execute_queued = !matches!(
                         ctx.execution_process.status,
-                        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
+                        ExecutionProcessStatus::Failed
+                            | ExecutionProcessStatus::Killed
+                            | ExecutionProcessStatus::Cancelled
                     );
 
                     if let Some(queued_msg) =
@@ -1224,6 +1279,10 @@ impl ContainerService for LocalContainerService {
         &self.msg_stores
     }
 
+    fn agent_token_senders(&self) -> &AgentTokenSenders {
+        &self.agent_token_senders
+    }
+
     fn db(&self) -> &DBService {
         &self.db
     }
@@ -1278,13 +1337,30 @@ impl ContainerService for LocalContainerService {
             })
             .collect();
 
+        // Enforce the owner's disk quota before allocating new worktrees
+        if let Some(user_id) = Workspace::find_owner(&self.db.pool, workspace.id).await? {
+            WorkspaceManager::enforce_user_quota(&user_id).await?;
+        }
+
         let created_workspace = WorkspaceManager::create_workspace(
             &workspace_dir,
             &workspace_inputs,
             &workspace.branch,
+            &self.file_search_cache,
         )
         .await?;
 
+        // Every worktree starts out on the workspace branch
+        for repo in &repositories {
+            WorkspaceRepo::update_active_branch(
+                &self.db.pool,
+                workspace.id,
+                repo.id,
+                &workspace.branch,
+            )
+            .await?;
+        }
+
         // Copy project files and images to workspace
         self.copy_files_and_images(&created_workspace.workspace_dir, workspace)
             .await?;
@@ -1307,7 +1383,7 @@ impl ContainerService for LocalContainerService {
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError> {
         self.try_stop(workspace, true).await;
-        Self::cleanup_workspace(&self.db, workspace).await;
+        Self::cleanup_workspace(&self.db, &self.file_search_cache, workspace).await;
         Ok(())
     }
 
@@ -1337,8 +1413,13 @@ impl ContainerService for LocalContainerService {
             WorkspaceManager::get_workspace_base_dir().join(&workspace_dir_name)
         };
 
-        WorkspaceManager::ensure_workspace_exists(&workspace_dir, &repositories, &workspace.branch)
-            .await?;
+        WorkspaceManager::ensure_workspace_exists(
+            &workspace_dir,
+            &repositories,
+            &workspace.branch,
+            &self.file_search_cache,
+        )
+        .await?;
 
         if workspace.container_ref.is_none() {
             Workspace::update_container_ref(
@@ -1453,6 +1534,21 @@ impl ContainerService for LocalContainerService {
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
             .await;
 
+        if let Some(profile_id) = executor_action.executor_profile_id()
+            && let Some(msg_store) = self.get_msg_store_by_id(&execution_process.id).await
+        {
+            let model = ExecutorConfigs::get_cached()
+                .get_coding_agent(profile_id)
+                .and_then(|agent| agent.model().map(str::to_string));
+            agent_tokens::spawn_token_forwarder(
+                self.agent_token_senders.clone(),
+                execution_process.id,
+                msg_store,
+                model,
+            )
+            .await;
+        }
+
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
 
@@ -1468,6 +1564,136 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn stop_process(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let process = self
+            .running_process_in_workspace(workspace, process_id)
+            .await?;
+        // A paused process group would ignore the interrupt until resumed
+        if process.paused_at.is_some()
+            && let Some(child) = self.get_child_from_store(&process_id).await
+        {
+            command::resume_process_group(&mut *child.write().await)?;
+        }
+
+        self.stop_execution(&process, ExecutionProcessStatus::Cancelled)
+            .await?;
+        tracing::info!("Cancelled execution process {}", process_id);
+
+        ExecutionProcess::find_by_id(&self.db.pool, process_id)
+            .await?
+            .ok_or(ContainerError::ProcessNotRunning(process_id))
+    }
+
+    async fn pause_execution(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let process = self
+            .running_process_in_workspace(workspace, process_id)
+            .await?;
+        if process.paused_at.is_some() {
+            return Ok(process);
+        }
+
+        let child = self
+            .get_child_from_store(&process_id)
+            .await
+            .ok_or(ContainerError::ProcessNotRunning(process_id))?;
+        command::pause_process_group(&mut *child.write().await)?;
+        tracing::info!("Paused execution process {}", process_id);
+
+        self.record_pause_state(process_id, Some(chrono::Utc::now()))
+            .await
+    }
+
+    async fn resume_execution(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let process = self
+            .running_process_in_workspace(workspace, process_id)
+            .await?;
+        if process.paused_at.is_none() {
+            return Ok(process);
+        }
+
+        let child = self
+            .get_child_from_store(&process_id)
+            .await
+            .ok_or(ContainerError::ProcessNotRunning(process_id))?;
+        command::resume_process_group(&mut *child.write().await)?;
+        tracing::info!("Resumed execution process {}", process_id);
+
+        self.record_pause_state(process_id, None).await
+    }
+
+    async fn list_running(&self) -> Vec<RunningProcess> {
+        let process_ids: Vec<Uuid> = self.child_store.read().await.keys().copied().collect();
+
+        let mut running = Vec::with_capacity(process_ids.len());
+        for process_id in process_ids {
+            let process = match ExecutionProcess::find_by_id(&self.db.pool, process_id).await {
+                Ok(Some(process)) => process,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("Failed to load running execution process {process_id}: {e}");
+                    continue;
+                }
+            };
+            let session = match process.parent_session(&self.db.pool).await {
+                Ok(Some(session)) => session,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to load session for execution process {process_id}: {e}"
+                    );
+                    continue;
+                }
+            };
+            running.push(RunningProcess {
+                workspace_id: session.workspace_id,
+                session_id: session.id,
+                process_id,
+                started_at: process.started_at,
+            });
+        }
+
+        running.sort_by_key(|p| p.started_at);
+        running
+    }
+
+    async fn get_resource_usage(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<ResourceUsage, ContainerError> {
+        if let Some(usage) = self.resource_monitor.cached(workspace_id) {
+            return Ok(usage);
+        }
+
+        let mut root_pids = Vec::new();
+        for process in self.list_running().await {
+            if process.workspace_id != workspace_id {
+                continue;
+            }
+            if let Some(child) = self.get_child_from_store(&process.process_id).await
+                && let Some(pid) = child.write().await.inner().id()
+            {
+                root_pids.push(pid);
+            }
+        }
+
+        let monitor = self.resource_monitor.clone();
+        tokio::task::spawn_blocking(move || monitor.sample(workspace_id, &root_pids))
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!(e)))
+    }
+
     async fn stop_execution(
         &self,
         execution_process: &ExecutionProcess,
@@ -1638,7 +1864,9 @@ impl ContainerService for LocalContainerService {
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
         if !matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript,
+            ExecutionProcessRunReason::CodingAgent
+                | ExecutionProcessRunReason::Review
+                | ExecutionProcessRunReason::CleanupScript,
         ) {
             return Ok(false);
         }
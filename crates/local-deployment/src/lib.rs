@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use db::{DBService, DBServicePg, DeploymentMode};
@@ -21,8 +25,10 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
+    workspace_repo_cache::WorkspaceRepoCache,
     worktree_manager::WorktreeManager,
 };
+use sqlx::{FromRow, postgres::PgRow, sqlite::SqliteRow};
 use tokio::sync::RwLock;
 use utils::{
     api::oauth::LoginStatus,
@@ -37,6 +43,37 @@ pub mod container;
 mod copy;
 pub mod pty;
 mod cleanup;
+mod resources;
+
+/// A positional argument for [`DbBackend::execute_raw`] and [`DbBackend::fetch_one_raw`].
+///
+/// `Null` is bound as a nullable text value; on PostgreSQL cast the placeholder
+/// (e.g. `$1::uuid`) when the target column has a different type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlArg {
+    Text(String),
+    Uuid(Uuid),
+    Int(i64),
+    Bool(bool),
+    Null,
+}
+
+/// Bind a list of [`SqlArg`]s onto a sqlx query or query_as builder, in order.
+macro_rules! bind_args {
+    ($query:expr, $args:expr) => {{
+        let mut query = $query;
+        for arg in $args {
+            query = match arg {
+                SqlArg::Text(v) => query.bind(v),
+                SqlArg::Uuid(v) => query.bind(v),
+                SqlArg::Int(v) => query.bind(v),
+                SqlArg::Bool(v) => query.bind(v),
+                SqlArg::Null => query.bind(None::<String>),
+            };
+        }
+        query
+    }};
+}
 
 /// Database backend abstraction for supporting both SQLite (desktop) and PostgreSQL (K8s) modes.
 ///
@@ -84,6 +121,83 @@ impl DbBackend {
     pub fn is_postgres(&self) -> bool {
         matches!(self, DbBackend::Postgres(_))
     }
+
+    /// Short lowercase name of the backend, as reported by the health endpoint.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DbBackend::Sqlite(_) => "sqlite",
+            DbBackend::Postgres(_) => "postgres",
+        }
+    }
+
+    /// Check that the backing database is reachable.
+    pub async fn health_check(&self) -> Result<(), sqlx::Error> {
+        match self {
+            DbBackend::Sqlite(db) => db.health_check().await,
+            DbBackend::Postgres(db) => db.health_check().await,
+        }
+    }
+
+    /// Result of the most recent background health check.
+    ///
+    /// `None` for SQLite, which has no health monitor.
+    pub fn last_health_check(&self) -> Option<bool> {
+        match self {
+            DbBackend::Sqlite(_) => None,
+            DbBackend::Postgres(db) => Some(db.is_healthy.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Get the current pool statistics as (active, idle, max) connections.
+    pub fn pool_stats(&self) -> (u32, u32, u32) {
+        match self {
+            DbBackend::Sqlite(db) => db.pool_stats(),
+            DbBackend::Postgres(db) => db.pool_stats(),
+        }
+    }
+
+    /// Run a statement against whichever backend is active.
+    ///
+    /// The SQL must use `$N` placeholders, which both SQLite and PostgreSQL accept.
+    ///
+    /// # Returns
+    ///
+    /// The number of rows affected.
+    pub async fn execute_raw(&self, query: &str, args: Vec<SqlArg>) -> Result<u64, sqlx::Error> {
+        let result = match self {
+            DbBackend::Sqlite(db) => bind_args!(sqlx::query(query), args)
+                .execute(&db.pool)
+                .await?
+                .rows_affected(),
+            DbBackend::Postgres(db) => bind_args!(sqlx::query(query), args)
+                .execute(&db.pool())
+                .await?
+                .rows_affected(),
+        };
+        Ok(result)
+    }
+
+    /// Fetch exactly one row from whichever backend is active and map it into `T`.
+    ///
+    /// The SQL must use `$N` placeholders. Returns `sqlx::Error::RowNotFound` when the
+    /// query yields no rows.
+    pub async fn fetch_one_raw<T>(&self, query: &str, args: Vec<SqlArg>) -> Result<T, sqlx::Error>
+    where
+        T: for<'r> FromRow<'r, SqliteRow> + for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        match self {
+            DbBackend::Sqlite(db) => {
+                bind_args!(sqlx::query_as::<_, T>(query), args)
+                    .fetch_one(&db.pool)
+                    .await
+            }
+            DbBackend::Postgres(db) => {
+                bind_args!(sqlx::query_as::<_, T>(query), args)
+                    .fetch_one(&db.pool())
+                    .await
+            }
+        }
+    }
 }
 
 /// Configuration backend abstraction for supporting both file-based (desktop) and
@@ -137,6 +251,7 @@ pub struct LocalDeployment {
     filesystem: FilesystemService,
     events: EventService,
     file_search_cache: Arc<FileSearchCache>,
+    workspace_repo_cache: Arc<WorkspaceRepoCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
@@ -151,6 +266,26 @@ struct PendingHandoff {
     app_verifier: String,
 }
 
+/// Environment variable naming the user that should receive the desktop config
+/// file when a deployment first starts in K8s mode.
+const CONFIG_MIGRATION_USER_ID_ENV: &str = "VK_CONFIG_MIGRATION_USER_ID";
+
+/// Read the target user for the one-time desktop config import, if configured.
+fn config_migration_user_id() -> Option<Uuid> {
+    let raw = std::env::var(CONFIG_MIGRATION_USER_ID_ENV).ok()?;
+    match Uuid::parse_str(raw.trim()) {
+        Ok(user_id) => Some(user_id),
+        Err(e) => {
+            tracing::warn!(
+                "{} is not a valid UUID, skipping config migration: {}",
+                CONFIG_MIGRATION_USER_ID_ENV,
+                e
+            );
+            None
+        }
+    }
+}
+
 #[async_trait]
 impl Deployment for LocalDeployment {
     async fn new() -> Result<Self, DeploymentError> {
@@ -162,15 +297,45 @@ impl Deployment for LocalDeployment {
             "Initializing LocalDeployment"
         );
 
-        // Load configuration based on deployment mode
-        let (raw_config, config_backend) = if mode.is_kubernetes() {
-            // In K8s mode, we need PostgreSQL for the config service
-            // Initialize PostgreSQL first to load config from database
+        // In K8s mode, initialize PostgreSQL first to load config from database.
+        // This one service is shared by every consumer, so a pool recreated by
+        // its health monitor is seen everywhere.
+        let pg_db = if mode.is_kubernetes() {
             let pg_db = DBServicePg::new().await.map_err(|e| {
                 tracing::error!(?e, "Failed to initialize PostgreSQL database");
                 DeploymentError::DbInit(e.to_string())
             })?;
-            let config_service = ConfigServicePg::new(pg_db.pool.clone());
+            pg_db.spawn_health_monitor(Duration::from_secs(
+                db::pg::DEFAULT_HEALTH_CHECK_INTERVAL_SECS,
+            ));
+            Some(pg_db)
+        } else {
+            None
+        };
+
+        // Load configuration based on deployment mode
+        let (raw_config, config_backend) = if let Some(pg_db) = &pg_db {
+            let config_service = ConfigServicePg::new(pg_db.clone());
+
+            // Seed the database with an existing desktop config the first time the
+            // deployment starts in K8s mode, so switching modes keeps user settings
+            if let Some(user_id) = config_migration_user_id() {
+                match config_service
+                    .migrate_from_file(user_id, &config_path())
+                    .await
+                {
+                    Ok(true) => tracing::info!(
+                        user_id = %user_id,
+                        "Imported desktop config file into database"
+                    ),
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!(
+                        user_id = %user_id,
+                        "Failed to import desktop config file: {}",
+                        e
+                    ),
+                }
+            }
 
             // Note: In K8s mode, user_id comes from JWT token, not generated locally.
             // For initialization, we use a default config. The actual user config
@@ -184,10 +349,20 @@ impl Deployment for LocalDeployment {
             let mut raw_config = load_config_from_file(&config_path()).await;
 
             let profiles = ExecutorConfigs::get_cached();
-            if !raw_config.onboarding_acknowledged
-                && let Ok(recommended_executor) = profiles.get_recommended_executor_profile().await
-            {
-                raw_config.executor_profile = recommended_executor;
+            if !raw_config.onboarding_acknowledged {
+                raw_config.executor_profile =
+                    match profiles.get_recommended_executor_profile().await {
+                        Ok(recommended_executor) => recommended_executor,
+                        Err(e) => {
+                            let fallback = ExecutorConfigs::get_default_executor_profile();
+                            tracing::warn!(
+                                error = %e,
+                                executor_profile = %fallback,
+                                "No executor profile could be recommended, using the default"
+                            );
+                            fallback
+                        }
+                    };
             }
 
             // Check if app version has changed and set release notes flag
@@ -211,7 +386,9 @@ impl Deployment for LocalDeployment {
 
         if let Some(workspace_dir) = &raw_config.workspace_dir {
             let path = utils::path::expand_tilde(workspace_dir);
-            WorktreeManager::set_workspace_dir_override(path);
+            if let Err(e) = WorktreeManager::set_workspace_dir_override(path) {
+                tracing::warn!("Ignoring workspace_dir from config: {}", e);
+            }
         }
 
         let config = Arc::new(RwLock::new(raw_config));
@@ -226,15 +403,12 @@ impl Deployment for LocalDeployment {
         // Create shared components for EventService
         let events_msg_store = Arc::new(MsgStore::new());
         let events_entry_count = Arc::new(RwLock::new(0));
+        let file_search_cache = Arc::new(FileSearchCache::new());
+        let workspace_repo_cache = Arc::new(WorkspaceRepoCache::new());
 
         // Initialize database backends based on deployment mode
-        let (db, db_backend) = if mode.is_kubernetes() {
+        let (db, db_backend) = if let Some(pg_db) = pg_db {
             // In K8s mode, use PostgreSQL for user data
-            let pg_db = DBServicePg::new().await.map_err(|e| {
-                tracing::error!(?e, "Failed to initialize PostgreSQL database");
-                DeploymentError::DbInit(e.to_string())
-            })?;
-
             // We still need SQLite for local operations (EventService hooks, ImageService)
             // Create a local SQLite database for caching and local operations
             let sqlite_db = {
@@ -242,6 +416,8 @@ impl Deployment for LocalDeployment {
                     events_msg_store.clone(),
                     events_entry_count.clone(),
                     DBService::new().await?, // Temporary DB service for the hook
+                    file_search_cache.clone(),
+                    workspace_repo_cache.clone(),
                 );
                 DBService::new_with_after_connect(hook).await?
             };
@@ -255,6 +431,8 @@ impl Deployment for LocalDeployment {
                     events_msg_store.clone(),
                     events_entry_count.clone(),
                     DBService::new().await?, // Temporary DB service for the hook
+                    file_search_cache.clone(),
+                    workspace_repo_cache.clone(),
                 );
                 DBService::new_with_after_connect(hook).await?
             };
@@ -275,7 +453,15 @@ impl Deployment for LocalDeployment {
         }
 
         let approvals = Approvals::new(msg_stores.clone());
-        let queued_message_service = QueuedMessageService::new();
+        let queued_message_service = match db_backend.as_postgres() {
+            Some(pg_db) => QueuedMessageService::new_with_db(pg_db.clone()),
+            None => QueuedMessageService::new(),
+        };
+        match queued_message_service.restore_pending().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Restored {} queued follow-up messages", count),
+            Err(e) => tracing::warn!("Failed to restore queued messages: {}", e),
+        }
 
         let oauth_credentials = Arc::new(OAuthCredentials::new(credentials_path()));
         if let Err(e) = oauth_credentials.load().await {
@@ -323,21 +509,35 @@ impl Deployment for LocalDeployment {
             analytics_ctx,
             approvals.clone(),
             queued_message_service.clone(),
+            file_search_cache.clone(),
         )
         .await;
 
         let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
 
-        let file_search_cache = Arc::new(FileSearchCache::new());
-
-        let pty = PtyService::new();
+        // In K8s mode, mirror PTY session metadata into PostgreSQL
+        let pty = match db_backend.as_postgres() {
+            Some(pg_db) => PtyService::new_with_db(pg_db.clone()),
+            None => PtyService::new(),
+        };
 
-        // Spawn the resource cleanup job for PTY sessions and orphaned processes
+        // Spawn the resource cleanup job for PTY sessions, orphaned processes and
+        // expired approvals
         {
             let pty_service = pty.clone();
             let container_service = container.clone();
-            let cleanup_config = cleanup::CleanupConfig::from_env();
-            cleanup::spawn_cleanup_job(pty_service, container_service, cleanup_config);
+            let mut cleanup_config = cleanup::CleanupConfig::from_env();
+            if let Err(e) = cleanup_config.validate() {
+                tracing::warn!("Invalid cleanup configuration, using defaults: {}", e);
+                cleanup_config = cleanup::CleanupConfig::default();
+            }
+            cleanup::spawn_cleanup_job(
+                pty_service,
+                container_service,
+                approvals.clone(),
+                image.clone(),
+                cleanup_config,
+            );
         }
 
         let deployment = Self {
@@ -356,6 +556,7 @@ impl Deployment for LocalDeployment {
             filesystem,
             events,
             file_search_cache,
+            workspace_repo_cache,
             approvals,
             queued_message_service,
             remote_client,
@@ -415,6 +616,10 @@ impl Deployment for LocalDeployment {
         &self.file_search_cache
     }
 
+    fn workspace_repo_cache(&self) -> &Arc<WorkspaceRepoCache> {
+        &self.workspace_repo_cache
+    }
+
     fn approvals(&self) -> &Approvals {
         &self.approvals
     }
@@ -580,6 +785,67 @@ impl LocalDeployment {
     ///
     /// `true` if JWT authentication should be enforced, `false` otherwise.
     pub fn requires_auth(&self) -> bool {
-        self.mode.is_kubernetes()
+        self.mode.is_multi_user()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    async fn sqlite_backend() -> DbBackend {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE items (id BLOB PRIMARY KEY, name TEXT, qty INTEGER, done BOOLEAN, note TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        DbBackend::Sqlite(DBService { pool })
+    }
+
+    #[tokio::test]
+    async fn execute_and_fetch_raw_on_sqlite() {
+        let backend = sqlite_backend().await;
+        let id = Uuid::new_v4();
+
+        let inserted = backend
+            .execute_raw(
+                "INSERT INTO items (id, name, qty, done, note) VALUES ($1, $2, $3, $4, $5)",
+                vec![
+                    SqlArg::Uuid(id),
+                    SqlArg::Text("widget".to_string()),
+                    SqlArg::Int(3),
+                    SqlArg::Bool(true),
+                    SqlArg::Null,
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(inserted, 1);
+
+        let (name, qty, done, note): (String, i64, bool, Option<String>) = backend
+            .fetch_one_raw(
+                "SELECT name, qty, done, note FROM items WHERE id = $1",
+                vec![SqlArg::Uuid(id)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(name, "widget");
+        assert_eq!(qty, 3);
+        assert!(done);
+        assert_eq!(note, None);
+
+        let missing = backend
+            .fetch_one_raw::<(String,)>(
+                "SELECT name FROM items WHERE id = $1",
+                vec![SqlArg::Uuid(Uuid::new_v4())],
+            )
+            .await;
+        assert!(matches!(missing, Err(sqlx::Error::RowNotFound)));
     }
 }
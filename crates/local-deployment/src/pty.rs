@@ -8,11 +8,13 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
-use db::DeploymentMode;
+use db::{DBServicePg, DeploymentMode, pg::InstrumentedPool};
 use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use serde::Serialize;
 use services::services::workspace_manager::WorkspaceManager;
 use thiserror::Error;
 use tokio::sync::mpsc;
+use ts_rs::TS;
 use utils::shell::get_interactive_shell;
 use uuid::Uuid;
 
@@ -36,6 +38,8 @@ pub enum PtyError {
 struct PtySession {
     /// The user who owns this session
     user_id: Uuid,
+    /// The workspace the terminal was opened for, if any
+    workspace_id: Option<Uuid>,
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
     _output_handle: thread::JoinHandle<()>,
@@ -44,20 +48,54 @@ struct PtySession {
     created_at: DateTime<Utc>,
     /// Timestamp of last activity (write, resize, etc.)
     last_activity_at: DateTime<Utc>,
+    /// Timestamp of the last activity update written to `pty_sessions`
+    persisted_activity_at: DateTime<Utc>,
 }
 
+/// Public view of a live PTY session.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PtySessionInfo {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_activity_at: DateTime<Utc>,
+}
+
+/// Minimum interval between `last_activity_at` updates written to PostgreSQL,
+/// so keystrokes do not each turn into a database write.
+const ACTIVITY_PERSIST_INTERVAL_SECS: i64 = 60;
+
 #[derive(Clone)]
 pub struct PtyService {
     sessions: Arc<Mutex<HashMap<Uuid, PtySession>>>,
+    /// PostgreSQL database for persisting session metadata (K8s mode only).
+    db: Option<DBServicePg>,
 }
 
 impl PtyService {
     pub fn new() -> Self {
         Self {
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            db: None,
         }
     }
 
+    /// Create a PTY service that mirrors session metadata into the
+    /// `pty_sessions` table, so session ownership survives pod restarts.
+    pub fn new_with_db(db: DBServicePg) -> Self {
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            db: Some(db),
+        }
+    }
+
+    /// The current PostgreSQL pool, fetched on each use so a pool recreated by
+    /// the health monitor is picked up. Queries run through it are traced.
+    fn pool(&self) -> Option<InstrumentedPool> {
+        self.db.as_ref().map(DBServicePg::instrumented_pool)
+    }
+
     /// Create a new PTY session for a user.
     ///
     /// In Kubernetes (multi-user) mode, validates that the working directory is within
@@ -66,6 +104,7 @@ impl PtyService {
     /// # Arguments
     ///
     /// * `user_id` - The UUID of the user creating the session
+    /// * `workspace_id` - The workspace the session belongs to, if any
     /// * `working_dir` - The directory where the PTY session should start
     /// * `cols` - Number of columns for the terminal
     /// * `rows` - Number of rows for the terminal
@@ -76,6 +115,7 @@ impl PtyService {
     pub async fn create_session(
         &self,
         user_id: Uuid,
+        workspace_id: Option<Uuid>,
         working_dir: PathBuf,
         cols: u16,
         rows: u16,
@@ -126,7 +166,7 @@ impl PtyService {
             cmd.env("COLORTERM", "truecolor");
 
             // In K8s mode, set HOME to user's workspace directory
-            if mode.is_kubernetes() {
+            if mode.is_multi_user() {
                 cmd.env("HOME", user_home.to_string_lossy().to_string());
             }
 
@@ -171,12 +211,14 @@ impl PtyService {
         let now = Utc::now();
         let session = PtySession {
             user_id,
+            workspace_id,
             writer,
             master,
             _output_handle: output_handle,
             closed: false,
             created_at: now,
             last_activity_at: now,
+            persisted_activity_at: now,
         };
 
         self.sessions
@@ -184,9 +226,14 @@ impl PtyService {
             .map_err(|e| PtyError::CreateFailed(e.to_string()))?
             .insert(session_id, session);
 
+        if let Some(pool) = self.pool() {
+            persist_session(&pool, session_id, user_id, workspace_id, now).await;
+        }
+
         tracing::info!(
             session_id = %session_id,
             user_id = %user_id,
+            workspace_id = ?workspace_id,
             "Created PTY session"
         );
 
@@ -260,7 +307,12 @@ impl PtyService {
             .map_err(|e| PtyError::WriteFailed(e.to_string()))?;
 
         // Update activity timestamp
-        session.last_activity_at = Utc::now();
+        let persist = session.record_activity();
+        drop(sessions);
+
+        if persist && let Some(pool) = self.pool() {
+            touch_session(&pool, session_id).await;
+        }
 
         Ok(())
     }
@@ -268,6 +320,8 @@ impl PtyService {
     /// Resize a PTY session.
     ///
     /// Validates that the session belongs to the specified user before resizing.
+    /// `portable_pty` applies the size with `TIOCSWINSZ` on Unix, which sends
+    /// `SIGWINCH` to the shell, and `ResizePseudoConsole` on Windows.
     pub async fn resize(
         &self,
         user_id: Uuid,
@@ -278,6 +332,13 @@ impl PtyService {
         // First validate ownership
         self.validate_session_ownership(&session_id, &user_id)?;
 
+        // A zero-sized terminal makes full-screen programs divide by zero
+        if cols == 0 || rows == 0 {
+            return Err(PtyError::ResizeFailed(format!(
+                "invalid terminal size {cols}x{rows}"
+            )));
+        }
+
         let mut sessions = self
             .sessions
             .lock()
@@ -301,7 +362,12 @@ impl PtyService {
             .map_err(|e| PtyError::ResizeFailed(e.to_string()))?;
 
         // Update activity timestamp
-        session.last_activity_at = Utc::now();
+        let persist = session.record_activity();
+        drop(sessions);
+
+        if persist && let Some(pool) = self.pool() {
+            touch_session(&pool, session_id).await;
+        }
 
         Ok(())
     }
@@ -313,12 +379,12 @@ impl PtyService {
         // Validate ownership first
         self.validate_session_ownership(&session_id, &user_id)?;
 
-        if let Some(mut session) = self
+        let removed = self
             .sessions
             .lock()
             .map_err(|_| PtyError::SessionClosed)?
-            .remove(&session_id)
-        {
+            .remove(&session_id);
+        if let Some(mut session) = removed {
             session.closed = true;
             tracing::info!(
                 session_id = %session_id,
@@ -326,6 +392,10 @@ impl PtyService {
                 "Closed PTY session"
             );
         }
+
+        if let Some(pool) = self.pool() {
+            delete_sessions(&pool, vec![session_id]).await;
+        }
         Ok(())
     }
 
@@ -352,22 +422,45 @@ impl PtyService {
             .unwrap_or(false)
     }
 
-    /// List all session IDs belonging to a specific user.
-    pub fn list_user_sessions(&self, user_id: &Uuid) -> Vec<Uuid> {
-        self.sessions
+    /// List the live sessions belonging to a specific user, oldest first.
+    pub fn list_user_sessions(&self, user_id: &Uuid) -> Vec<PtySessionInfo> {
+        let mut sessions: Vec<PtySessionInfo> = self
+            .sessions
             .lock()
             .map(|sessions| {
                 sessions
                     .iter()
                     .filter(|(_, session)| session.user_id == *user_id)
-                    .map(|(id, _)| *id)
+                    .map(|(id, session)| session.info(*id))
+                    .collect()
+            })
+            .unwrap_or_default();
+        sessions.sort_by_key(|session| session.created_at);
+        sessions
+    }
+
+    /// List the live sessions opened for a workspace, oldest first.
+    pub fn list_sessions_for_workspace(&self, workspace_id: Uuid) -> Vec<PtySessionInfo> {
+        let mut sessions: Vec<PtySessionInfo> = self
+            .sessions
+            .lock()
+            .map(|sessions| {
+                sessions
+                    .iter()
+                    .filter(|(_, session)| session.workspace_id == Some(workspace_id))
+                    .map(|(id, session)| session.info(*id))
                     .collect()
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        sessions.sort_by_key(|session| session.created_at);
+        sessions
     }
 
     /// Clean up idle sessions that have been inactive for longer than the specified timeout.
     ///
+    /// In K8s mode, idle rows are also removed from `pty_sessions`, including
+    /// rows left behind by pods that have since restarted.
+    ///
     /// Returns the number of sessions cleaned up.
     pub fn cleanup_idle_sessions(&self, timeout: Duration) -> usize {
         let now = Utc::now();
@@ -399,6 +492,13 @@ impl PtyService {
                 );
             }
         }
+        drop(sessions);
+
+        if let Some(pool) = self.pool() {
+            spawn_persistence(async move {
+                delete_idle_sessions(&pool, timeout).await;
+            });
+        }
 
         count
     }
@@ -429,11 +529,133 @@ impl PtyService {
                 );
             }
         }
+        drop(sessions);
+
+        if let Some(pool) = self.pool() {
+            spawn_persistence(async move {
+                delete_sessions(&pool, user_sessions).await;
+            });
+        }
 
         count
     }
 }
 
+impl PtySession {
+    fn info(&self, id: Uuid) -> PtySessionInfo {
+        PtySessionInfo {
+            id,
+            user_id: self.user_id,
+            workspace_id: self.workspace_id,
+            created_at: self.created_at,
+            last_activity_at: self.last_activity_at,
+        }
+    }
+
+    /// Record activity on the session.
+    ///
+    /// Returns `true` when enough time has passed since the last persisted
+    /// activity that `pty_sessions` should be updated.
+    fn record_activity(&mut self) -> bool {
+        let now = Utc::now();
+        self.last_activity_at = now;
+        if now.signed_duration_since(self.persisted_activity_at)
+            >= chrono::Duration::seconds(ACTIVITY_PERSIST_INTERVAL_SECS)
+        {
+            self.persisted_activity_at = now;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// =========================================================================
+// PostgreSQL persistence (K8s mode)
+//
+// Persistence is best-effort: the in-memory map remains the source of truth
+// for live sessions, so database failures are logged rather than surfaced.
+// =========================================================================
+
+/// Run a persistence task in the background from synchronous code.
+fn spawn_persistence<F>(task: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(task);
+        }
+        Err(_) => tracing::warn!("No async runtime available to persist PTY session changes"),
+    }
+}
+
+async fn persist_session(
+    pool: &InstrumentedPool,
+    session_id: Uuid,
+    user_id: Uuid,
+    workspace_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+) {
+    // Only link workspaces that exist in PostgreSQL to satisfy the foreign key
+    let query = sqlx::query(
+        r#"
+        INSERT INTO pty_sessions (id, user_id, workspace_id, created_at, last_activity_at)
+        VALUES ($1, $2, (SELECT id FROM workspaces WHERE id = $3), $4, $4)
+        ON CONFLICT (id) DO NOTHING
+        "#,
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .bind(workspace_id)
+    .bind(created_at);
+    let result = pool.execute(query).await;
+
+    if let Err(e) = result {
+        tracing::warn!(session_id = %session_id, "Failed to persist PTY session: {}", e);
+    }
+}
+
+async fn touch_session(pool: &InstrumentedPool, session_id: Uuid) {
+    let query = sqlx::query("UPDATE pty_sessions SET last_activity_at = NOW() WHERE id = $1")
+        .bind(session_id);
+    let result = pool.execute(query).await;
+
+    if let Err(e) = result {
+        tracing::warn!(session_id = %session_id, "Failed to update PTY session activity: {}", e);
+    }
+}
+
+async fn delete_sessions(pool: &InstrumentedPool, session_ids: Vec<Uuid>) {
+    if session_ids.is_empty() {
+        return;
+    }
+
+    let query = sqlx::query("DELETE FROM pty_sessions WHERE id = ANY($1)").bind(&session_ids);
+    let result = pool.execute(query).await;
+
+    if let Err(e) = result {
+        tracing::warn!(count = session_ids.len(), "Failed to delete PTY sessions: {}", e);
+    }
+}
+
+async fn delete_idle_sessions(pool: &InstrumentedPool, timeout: Duration) {
+    let query = sqlx::query(
+        "DELETE FROM pty_sessions WHERE last_activity_at < NOW() - make_interval(secs => $1)",
+    )
+    .bind(timeout.as_secs_f64());
+    let result = pool.execute(query).await;
+
+    match result {
+        Ok(done) if done.rows_affected() > 0 => tracing::info!(
+            deleted = done.rows_affected(),
+            "Deleted idle PTY session rows"
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to delete idle PTY session rows: {}", e),
+    }
+}
+
 impl Default for PtyService {
     fn default() -> Self {
         Self::new()
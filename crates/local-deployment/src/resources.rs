@@ -0,0 +1,163 @@
+//! CPU and memory sampling for the execution processes of a workspace.
+//!
+//! Local workspaces are not isolated containers, so a workspace's usage is the
+//! sum over the process trees of its running execution processes. On Linux the
+//! memory limit comes from the cgroup the server runs in, which is the pod limit
+//! in Kubernetes.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use services::services::container::ResourceUsage;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use uuid::Uuid;
+
+/// How long a sample is served before the process table is read again.
+const CACHE_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub(crate) struct ResourceMonitor {
+    state: Arc<Mutex<MonitorState>>,
+}
+
+struct MonitorState {
+    system: System,
+    primed: bool,
+    cache: HashMap<Uuid, (Instant, ResourceUsage)>,
+}
+
+impl ResourceMonitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MonitorState {
+                system: System::new(),
+                primed: false,
+                cache: HashMap::new(),
+            })),
+        }
+    }
+
+    /// The last sample for `workspace_id`, if it is younger than [`CACHE_TTL`].
+    pub(crate) fn cached(&self, workspace_id: Uuid) -> Option<ResourceUsage> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .cache
+            .get(&workspace_id)
+            .filter(|(sampled_at, _)| sampled_at.elapsed() < CACHE_TTL)
+            .map(|(_, usage)| usage.clone())
+    }
+
+    /// Sample the process trees rooted at `root_pids` and cache the result.
+    /// Blocks while the process table is read; call from a blocking task.
+    pub(crate) fn sample(&self, workspace_id: Uuid, root_pids: &[u32]) -> ResourceUsage {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        refresh_processes(&mut state.system);
+        if !state.primed {
+            // CPU usage is measured between two refreshes
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+            refresh_processes(&mut state.system);
+            state.primed = true;
+        }
+
+        let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+        for (pid, process) in state.system.processes() {
+            if let Some(parent) = process.parent() {
+                children.entry(parent).or_default().push(*pid);
+            }
+        }
+
+        let mut cpu_percent = 0.0;
+        let mut memory_bytes = 0;
+        let mut pending: Vec<Pid> = root_pids.iter().copied().map(Pid::from_u32).collect();
+        while let Some(pid) = pending.pop() {
+            if let Some(process) = state.system.process(pid) {
+                cpu_percent += process.cpu_usage();
+                memory_bytes += process.memory();
+            }
+            if let Some(descendants) = children.get(&pid) {
+                pending.extend(descendants);
+            }
+        }
+
+        let usage = ResourceUsage {
+            cpu_percent,
+            memory_bytes,
+            memory_limit_bytes: memory_limit_bytes(),
+        };
+        state
+            .cache
+            .retain(|_, (sampled_at, _)| sampled_at.elapsed() < CACHE_TTL);
+        state
+            .cache
+            .insert(workspace_id, (Instant::now(), usage.clone()));
+        usage
+    }
+}
+
+fn refresh_processes(system: &mut System) {
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_cpu().with_memory(),
+    );
+}
+
+/// Memory limit of the server's cgroup, or `None` when it is unlimited.
+#[cfg(target_os = "linux")]
+fn memory_limit_bytes() -> Option<u64> {
+    // Values this large mean "no limit" under cgroup v1
+    const UNLIMITED: u64 = 1 << 60;
+
+    let cgroup_path = std::fs::read_to_string("/proc/self/cgroup")
+        .ok()
+        .and_then(|contents| {
+            contents
+                .lines()
+                .find_map(|line| line.strip_prefix("0::").map(str::to_string))
+        })
+        .unwrap_or_else(|| "/".to_string());
+    let v2_path = format!(
+        "/sys/fs/cgroup{}/memory.max",
+        cgroup_path.trim_end_matches('/')
+    );
+
+    [
+        v2_path.as_str(),
+        "/sys/fs/cgroup/memory.max",
+        "/sys/fs/cgroup/memory/memory.limit_in_bytes",
+    ]
+    .iter()
+    .find_map(|path| std::fs::read_to_string(path).ok())
+    .and_then(|value| value.trim().parse::<u64>().ok())
+    .filter(|limit| *limit < UNLIMITED)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn memory_limit_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_measures_process_tree_and_caches_result() {
+        let monitor = ResourceMonitor::new();
+        let workspace_id = Uuid::new_v4();
+        assert!(monitor.cached(workspace_id).is_none());
+
+        let usage = monitor.sample(workspace_id, &[std::process::id()]);
+        assert!(usage.memory_bytes > 0);
+
+        let cached = monitor.cached(workspace_id).expect("sample is cached");
+        assert_eq!(cached.memory_bytes, usage.memory_bytes);
+
+        let idle = monitor.sample(Uuid::new_v4(), &[]);
+        assert_eq!(idle.memory_bytes, 0);
+    }
+}
@@ -29,6 +29,7 @@ fn generate_types_content() -> String {
         db::models::task::TaskStatus::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
+        db::models::task::TaskHistoryEntry::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
@@ -50,7 +51,11 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        services::services::container::RunningProcess::decl(),
+        services::services::container::ResourceUsage::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_logs::ExecutionProcessLogRow::decl(),
+        server::routes::execution_processes::CancelExecutionProcessRequest::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -59,9 +64,13 @@ fn generate_types_content() -> String {
         utils::approvals::ApprovalStatus::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
+        services::services::approvals::PendingApproval::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
         utils::response::ApiResponse::<()>::decl(),
+        utils::response::ErrorCode::decl(),
+        utils::response::PaginatedResponse::<()>::decl(),
+        server::routes::pagination::PaginationQuery::decl(),
         utils::api::oauth::LoginStatus::decl(),
         utils::api::oauth::ProfileResponse::decl(),
         utils::api::oauth::ProviderProfile::decl(),
@@ -92,8 +101,14 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::ProjectStatsResponse::decl(),
+        server::routes::projects::ProjectSyncStatus::decl(),
+        server::routes::projects::DuplicateProjectRequest::decl(),
+        server::routes::projects::ImportTasksResponse::decl(),
+        services::services::task_import::TaskImportRowError::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
+        server::routes::repo::CloneRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
@@ -105,6 +120,11 @@ fn generate_types_content() -> String {
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
+        server::routes::auth::TokenExchangeRequest::decl(),
+        server::routes::auth::RefreshTokenRequest::decl(),
+        server::routes::auth::RefreshTokenResponse::decl(),
+        server::routes::health::HealthResponse::decl(),
+        db::DeploymentMode::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
@@ -112,11 +132,15 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::PushTaskAttemptRequest::decl(),
         server::routes::task_attempts::RenameBranchRequest::decl(),
         server::routes::task_attempts::RenameBranchResponse::decl(),
+        server::routes::sessions::review::ReviewType::decl(),
         server::routes::sessions::review::StartReviewRequest::decl(),
         server::routes::sessions::review::ReviewError::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::MoveTaskRequest::decl(),
+        server::routes::tasks::BulkUpdateTaskStatusRequest::decl(),
+        server::routes::tasks::BulkUpdateTaskStatusResponse::decl(),
         server::routes::task_attempts::pr::CreatePrApiRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -145,10 +169,27 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::workspace_summary::WorkspaceSummary::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryResponse::decl(),
         server::routes::task_attempts::workspace_summary::DiffStats::decl(),
+        services::services::workspace_manager::WorktreeStatus::decl(),
+        services::services::workspace_manager::WorkspaceQuota::decl(),
+        services::services::workspace_manager::ChangedFile::decl(),
+        services::services::workspace_manager::RepoDiff::decl(),
+        services::services::workspace_manager::CommitResult::decl(),
+        server::routes::workspaces::CommitWorkspaceRequest::decl(),
+        services::services::workspace_manager::PushResult::decl(),
+        server::routes::workspaces::PushWorkspaceRequest::decl(),
+        server::routes::workspaces::ResetWorkspaceRequest::decl(),
+        server::routes::workspaces::CreateWorkspacePullRequest::decl(),
+        server::routes::workspaces::UpdateAgentWorkingDirRequest::decl(),
+        local_deployment::pty::PtySessionInfo::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
+        services::services::filesystem::FileMetadata::decl(),
+        server::routes::filesystem::ReadFileRequest::decl(),
+        server::routes::filesystem::ReadFileResponse::decl(),
+        server::routes::filesystem::WriteFileRequest::decl(),
         services::services::file_search::SearchMode::decl(),
         services::services::config::Config::decl(),
+        services::services::config::UpdateConfig::decl(),
         services::services::config::NotificationConfig::decl(),
         services::services::config::ThemeMode::decl(),
         services::services::config::EditorConfig::decl(),
@@ -159,8 +200,11 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::git::GitBranch::decl(),
+        services::services::repo::BranchInfo::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
+        services::services::agent_tokens::AgentTokenRole::decl(),
+        services::services::agent_tokens::AgentToken::decl(),
         services::services::git::ConflictOp::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
@@ -173,6 +217,7 @@ fn generate_types_content() -> String {
         executors::executors::AvailabilityInfo::decl(),
         executors::command::CommandBuilder::decl(),
         executors::profile::ExecutorProfileId::decl(),
+        executors::profile::ProfileValidation::decl(),
         executors::profile::ExecutorConfig::decl(),
         executors::profile::ExecutorConfigs::decl(),
         executors::executors::BaseAgentCapability::decl(),
@@ -14,7 +14,9 @@ use executors::{command::CommandBuildError, executors::ExecutorError};
 use git2::Error as Git2Error;
 use local_deployment::pty::PtyError;
 use services::services::{
+    admin::AdminError,
     config::{ConfigError, EditorOpenError},
+    config_db::ConfigDbError,
     container::ContainerError,
     git::GitServiceError,
     git_host::GitHostError,
@@ -22,10 +24,11 @@ use services::services::{
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
-use utils::response::ApiResponse;
+use utils::response::{ApiResponse, ErrorCode};
 
 #[derive(Debug, Error, ts_rs::TS)]
 #[ts(type = "string")]
@@ -59,6 +62,8 @@ pub enum ApiError {
     #[error(transparent)]
     Config(#[from] ConfigError),
     #[error(transparent)]
+    ConfigDb(#[from] ConfigDbError),
+    #[error(transparent)]
     Image(#[from] ImageError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
@@ -76,6 +81,10 @@ pub enum ApiError {
     Conflict(String),
     #[error("Forbidden: {0}")]
     Forbidden(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Unprocessable entity: {0}")]
+    UnprocessableEntity(String),
     #[error(transparent)]
     CommandBuilder(#[from] CommandBuildError),
     #[error(transparent)]
@@ -94,18 +103,99 @@ impl From<Git2Error> for ApiError {
     }
 }
 
+impl From<AdminError> for ApiError {
+    fn from(err: AdminError) -> Self {
+        match err {
+            AdminError::Disabled => ApiError::Forbidden(err.to_string()),
+            AdminError::InvalidToken => ApiError::Unauthorized,
+            AdminError::ConfigDb(err) => ApiError::ConfigDb(err),
+        }
+    }
+}
+
 impl From<RemoteClientNotConfigured> for ApiError {
     fn from(_: RemoteClientNotConfigured) -> Self {
         ApiError::BadRequest("Remote client not configured".to_string())
     }
 }
 
+impl ApiError {
+    /// Machine-readable code sent alongside the message so clients don't
+    /// have to parse it.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            ApiError::Project(ProjectError::ProjectNotFound) => ErrorCode::ProjectNotFound,
+            ApiError::Workspace(WorkspaceError::ProjectNotFound) => ErrorCode::ProjectNotFound,
+            ApiError::Workspace(WorkspaceError::TaskNotFound) => ErrorCode::NotFound,
+            ApiError::Session(SessionError::NotFound) => ErrorCode::NotFound,
+            ApiError::Workspace(WorkspaceError::ValidationError(_))
+            | ApiError::Workspace(WorkspaceError::BranchNotFound(_)) => ErrorCode::BadRequest,
+            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound) => {
+                ErrorCode::NotFound
+            }
+            ApiError::GitService(GitServiceError::MergeConflicts(_)) => ErrorCode::MergeConflicts,
+            ApiError::GitService(GitServiceError::RebaseInProgress) => ErrorCode::RebaseInProgress,
+            ApiError::Container(ContainerError::WorkspaceManager(
+                WorkspaceManagerError::QuotaExceeded { .. },
+            )) => ErrorCode::QuotaExceeded,
+            ApiError::Container(ContainerError::WorkspaceManager(
+                WorkspaceManagerError::Unauthorized(_),
+            )) => ErrorCode::Forbidden,
+            ApiError::Container(ContainerError::ProcessNotRunning(_)) => ErrorCode::Conflict,
+            ApiError::Image(ImageError::InvalidFormat) => ErrorCode::InvalidImageFormat,
+            ApiError::Image(ImageError::TooLarge(_, _)) => ErrorCode::PayloadTooLarge,
+            ApiError::Image(ImageError::NotFound) => ErrorCode::NotFound,
+            ApiError::ConfigDb(ConfigDbError::ConfigNotFound(_)) => ErrorCode::NotFound,
+            ApiError::EditorOpen(EditorOpenError::LaunchFailed { .. }) => ErrorCode::InternalError,
+            ApiError::EditorOpen(_) | ApiError::Multipart(_) => ErrorCode::BadRequest,
+            ApiError::RemoteClient(err) => match err {
+                RemoteClientError::Auth | RemoteClientError::Token(_) => ErrorCode::Unauthorized,
+                RemoteClientError::Timeout => ErrorCode::RemoteTimeout,
+                RemoteClientError::Transport(_) | RemoteClientError::Http { .. } => {
+                    ErrorCode::RemoteUnavailable
+                }
+                RemoteClientError::Api(code) => match code {
+                    services::services::remote_client::HandoffErrorCode::NotFound => {
+                        ErrorCode::NotFound
+                    }
+                    services::services::remote_client::HandoffErrorCode::Expired => {
+                        ErrorCode::Unauthorized
+                    }
+                    services::services::remote_client::HandoffErrorCode::AccessDenied => {
+                        ErrorCode::Forbidden
+                    }
+                    services::services::remote_client::HandoffErrorCode::ProviderError
+                    | services::services::remote_client::HandoffErrorCode::InternalError => {
+                        ErrorCode::RemoteUnavailable
+                    }
+                    _ => ErrorCode::BadRequest,
+                },
+                RemoteClientError::Storage(_) => ErrorCode::InternalError,
+                RemoteClientError::Serde(_) | RemoteClientError::Url(_) => ErrorCode::BadRequest,
+            },
+            ApiError::Unauthorized => ErrorCode::Unauthorized,
+            ApiError::BadRequest(_) => ErrorCode::BadRequest,
+            ApiError::Conflict(_) => ErrorCode::Conflict,
+            ApiError::Forbidden(_) => ErrorCode::Forbidden,
+            ApiError::NotFound(_) => ErrorCode::NotFound,
+            ApiError::UnprocessableEntity(_) => ErrorCode::UnprocessableEntity,
+            ApiError::Pty(PtyError::SessionNotFound(_)) => ErrorCode::SessionNotFound,
+            ApiError::Pty(PtyError::SessionClosed) => ErrorCode::SessionClosed,
+            _ => ErrorCode::InternalError,
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status_code, error_type) = match &self {
+            ApiError::Project(ProjectError::ProjectNotFound) => {
+                (StatusCode::NOT_FOUND, "ProjectError")
+            }
             ApiError::Project(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ProjectError"),
             ApiError::Repo(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ProjectRepoError"),
             ApiError::Workspace(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorkspaceError"),
+            ApiError::Session(SessionError::NotFound) => (StatusCode::NOT_FOUND, "SessionNotFound"),
             ApiError::Session(_) => (StatusCode::INTERNAL_SERVER_ERROR, "SessionError"),
             ApiError::ScratchError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ScratchError"),
             ApiError::ExecutionProcess(err) => match err {
@@ -126,12 +216,22 @@ impl IntoResponse for ApiError {
             },
             ApiError::GitHost(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHostError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::WorkspaceManager(
+                WorkspaceManagerError::QuotaExceeded { .. },
+            )) => (StatusCode::INSUFFICIENT_STORAGE, "QuotaExceeded"),
+            ApiError::Container(ContainerError::ProcessNotRunning(_)) => {
+                (StatusCode::CONFLICT, "ProcessNotRunning")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::CommandBuilder(_) => (StatusCode::INTERNAL_SERVER_ERROR, "CommandBuildError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
             ApiError::Worktree(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
             ApiError::Config(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
+            ApiError::ConfigDb(ConfigDbError::ConfigNotFound(_)) => {
+                (StatusCode::NOT_FOUND, "ConfigDbError")
+            }
+            ApiError::ConfigDb(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigDbError"),
             ApiError::Image(img_err) => match img_err {
                 ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
                 ImageError::TooLarge(_, _) => (StatusCode::PAYLOAD_TOO_LARGE, "ImageTooLarge"),
@@ -181,7 +281,11 @@ impl IntoResponse for ApiError {
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
-            ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
+            ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "Forbidden"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NotFound"),
+            ApiError::UnprocessableEntity(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "UnprocessableEntity")
+            }
             ApiError::Pty(err) => match err {
                 PtyError::SessionNotFound(_) => (StatusCode::NOT_FOUND, "PtyError"),
                 PtyError::SessionClosed => (StatusCode::GONE, "PtyError"),
@@ -263,9 +367,21 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
+            ApiError::NotFound(msg) => msg.clone(),
+            ApiError::UnprocessableEntity(msg) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
         };
-        let response = ApiResponse::<()>::error(&error_message);
+        let code = self.error_code();
+        // Ownership violations carry a stable error code clients can match on
+        if let ApiError::Forbidden(_) = &self {
+            let body = serde_json::json!({
+                "error": error_type,
+                "message": error_message,
+                "code": code.to_code_string(),
+            });
+            return (status_code, Json(body)).into_response();
+        }
+        let response = ApiResponse::<()>::error_with_code(&error_message, code);
         (status_code, Json(response)).into_response()
     }
 }
@@ -328,6 +444,12 @@ impl From<RepoServiceError> for ApiError {
             RepoServiceError::InvalidFolderName(name) => {
                 ApiError::BadRequest(format!("Invalid folder name: {}", name))
             }
+            RepoServiceError::InvalidGitUrl(url) => {
+                ApiError::BadRequest(format!("Not a remote git URL: {}", url))
+            }
+            RepoServiceError::BranchNotFound(branch) => {
+                ApiError::BadRequest(format!("Branch not found: {}", branch))
+            }
         }
     }
 }
@@ -345,3 +467,63 @@ impl From<ProjectRepoError> for ApiError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+
+    async fn response_json(err: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = err.into_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn forbidden_returns_403_with_error_code() {
+        let (status, body) =
+            response_json(ApiError::Forbidden("Not your project".to_string())).await;
+
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "error": "Forbidden",
+                "message": "Not your project",
+                "code": "FORBIDDEN",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn project_not_found_returns_404() {
+        let (status, body) = response_json(ApiError::Project(ProjectError::ProjectNotFound)).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["code"], "PROJECT_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn not_found_returns_404_with_message() {
+        let (status, body) =
+            response_json(ApiError::NotFound("Workspace not found".to_string())).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["message"], "Workspace not found");
+        assert_eq!(body["code"], "NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn quota_exceeded_carries_error_code() {
+        let err = ApiError::Container(ContainerError::WorkspaceManager(
+            WorkspaceManagerError::QuotaExceeded { used: 2, limit: 1 },
+        ));
+        let (status, body) = response_json(err).await;
+
+        assert_eq!(status, StatusCode::INSUFFICIENT_STORAGE);
+        assert_eq!(body["code"], "QUOTA_EXCEEDED");
+    }
+}
@@ -1,6 +1,8 @@
+use std::time::Duration;
+
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, routes};
+use server::{DeploymentImpl, middleware, routes};
 use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
@@ -9,6 +11,8 @@ use tracing_subscriber::{EnvFilter, prelude::*};
 use utils::{
     assets::asset_dir,
     browser::open_browser,
+    log_format::{LogContext, LogFormat, fmt_layer},
+    otel,
     port_file::write_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
 };
@@ -39,10 +43,13 @@ async fn main() -> Result<(), VibeKanbanError> {
         "warn,server={level},services={level},db={level},executors={level},deployment={level},local_deployment={level},utils={level}",
         level = log_level
     );
-    let env_filter = EnvFilter::try_new(filter_string).expect("Failed to create tracing filter");
+    let env_filter = EnvFilter::try_new(&filter_string).expect("Failed to create tracing filter");
+    let otel_filter = EnvFilter::try_new(&filter_string).expect("Failed to create tracing filter");
+    let log_context = LogContext::new("vibe-kanban", db::DeploymentMode::detect().as_str());
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
+        .with(fmt_layer(LogFormat::from_env(), log_context).with_filter(env_filter))
         .with(sentry_layer())
+        .with(otel::otel_layer().with_filter(otel_filter))
         .init();
 
     // Create asset directory if it doesn't exist
@@ -83,6 +90,10 @@ async fn main() -> Result<(), VibeKanbanError> {
         }
     });
 
+    if deployment.requires_auth() {
+        middleware::init_jwks_from_env().await;
+    }
+
     let app_router = routes::router(deployment.clone());
 
     let port = std::env::var("BACKEND_PORT")
@@ -128,6 +139,7 @@ async fn main() -> Result<(), VibeKanbanError> {
         .await?;
 
     perform_cleanup_actions(&deployment).await;
+    otel::shutdown();
 
     Ok(())
 }
@@ -174,4 +186,13 @@ pub async fn perform_cleanup_actions(deployment: &DeploymentImpl) {
         .kill_all_running_processes()
         .await
         .expect("Failed to cleanly kill running execution processes");
+
+    // Send any queued analytics events, without holding up exit for long
+    if let Some(analytics) = deployment.analytics()
+        && tokio::time::timeout(Duration::from_secs(5), analytics.flush())
+            .await
+            .is_err()
+    {
+        tracing::warn!("Timed out flushing analytics events on shutdown");
+    }
 }
@@ -19,10 +19,12 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use utils::response::PaginatedResponse;
 use uuid::Uuid;
 
 use crate::routes::{
     containers::ContainerQuery,
+    pagination::MAX_PAGE_LIMIT,
     task_attempts::{CreateTaskAttemptBody, WorkspaceRepoInput},
 };
 
@@ -474,6 +476,27 @@ impl TaskServer {
             .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
     }
 
+    /// GET every page of a cursor-paginated endpoint and concatenate the items.
+    async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<Vec<T>, CallToolResult> {
+        let mut items = Vec::new();
+        let mut after: Option<Uuid> = None;
+        loop {
+            let mut rb = self.client.get(url).query(&[("limit", MAX_PAGE_LIMIT)]);
+            if let Some(after) = after {
+                rb = rb.query(&[("after", after)]);
+            }
+            let page: PaginatedResponse<T> = self.send_json(rb).await?;
+            items.extend(page.items);
+            match page.next_cursor {
+                Some(next) if page.has_more => after = Some(next),
+                _ => return Ok(items),
+            }
+        }
+    }
+
     async fn send_empty_json(&self, rb: reqwest::RequestBuilder) -> Result<(), CallToolResult> {
         let resp = rb
             .send()
@@ -621,7 +644,7 @@ impl TaskServer {
     #[tool(description = "List all the available projects")]
     async fn list_projects(&self) -> Result<CallToolResult, ErrorData> {
         let url = self.url("/api/projects");
-        let projects: Vec<Project> = match self.send_json(self.client.get(&url)).await {
+        let projects: Vec<Project> = match self.get_all_pages(&url).await {
             Ok(ps) => ps,
             Err(e) => return Ok(e),
         };
@@ -802,11 +825,10 @@ impl TaskServer {
         };
 
         let url = self.url(&format!("/api/tasks?project_id={}", project_id));
-        let all_tasks: Vec<TaskWithAttemptStatus> =
-            match self.send_json(self.client.get(&url)).await {
-                Ok(t) => t,
-                Err(e) => return Ok(e),
-            };
+        let all_tasks: Vec<TaskWithAttemptStatus> = match self.get_all_pages(&url).await {
+            Ok(t) => t,
+            Err(e) => return Ok(e),
+        };
 
         let task_limit = limit.unwrap_or(50).max(0) as usize;
         let filtered = all_tasks.into_iter().filter(|t| {
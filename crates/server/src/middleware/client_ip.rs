@@ -0,0 +1,171 @@
+//! Client IP resolution for security audit logs.
+//!
+//! Behind an ingress every connection comes from the proxy and the client's
+//! address is in `X-Forwarded-For`. The header is only believed when the
+//! connection comes from an address in `TRUSTED_PROXIES`, a comma-separated
+//! list of IPs and CIDR ranges; the client is then the right-most forwarded
+//! address that is not itself a trusted proxy. Otherwise the connection IP is
+//! used.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::OnceLock,
+};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{HeaderMap, Request},
+};
+
+pub const TRUSTED_PROXIES_ENV: &str = "TRUSTED_PROXIES";
+
+static TRUSTED_PROXIES: OnceLock<Vec<IpRange>> = OnceLock::new();
+
+/// An IP address with a prefix length, e.g. `10.0.0.0/8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IpRange {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    fn parse(value: &str) -> Option<Self> {
+        let (addr, prefix) = match value.split_once('/') {
+            Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse().ok()?)),
+            None => (value.parse::<IpAddr>().ok()?, None),
+        };
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { addr, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(range), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(range) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(range), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(range) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+fn parse_trusted_proxies(value: &str) -> Vec<IpRange> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let range = IpRange::parse(entry);
+            if range.is_none() {
+                tracing::warn!(entry, "Ignoring invalid {TRUSTED_PROXIES_ENV} entry");
+            }
+            range
+        })
+        .collect()
+}
+
+fn trusted_proxies() -> &'static [IpRange] {
+    TRUSTED_PROXIES.get_or_init(|| {
+        std::env::var(TRUSTED_PROXIES_ENV)
+            .map(|value| parse_trusted_proxies(&value))
+            .unwrap_or_default()
+    })
+}
+
+/// The IP address of the client that sent `request`, if known.
+pub fn client_ip(request: &Request<Body>) -> Option<IpAddr> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    resolve_client_ip(request.headers(), peer, trusted_proxies())
+}
+
+fn resolve_client_ip(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    trusted: &[IpRange],
+) -> Option<IpAddr> {
+    let is_trusted = |ip: IpAddr| trusted.iter().any(|range| range.contains(ip));
+    let peer = peer?;
+    if !is_trusted(peer) {
+        return Some(peer.to_canonical());
+    }
+
+    let forwarded: Vec<IpAddr> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|entry| entry.trim().parse().ok())
+        .collect();
+    Some(
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|ip| !is_trusted(*ip))
+            .unwrap_or(peer)
+            .to_canonical(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn headers(forwarded_for: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_str(forwarded_for).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let ranges = parse_trusted_proxies("10.0.0.0/8, 192.168.1.5, fd00::/8, bogus, 1.2.3.4/33");
+        assert_eq!(ranges.len(), 3);
+        assert!(ranges[0].contains(ip("10.42.0.7")));
+        assert!(!ranges[0].contains(ip("11.0.0.1")));
+        assert!(ranges[1].contains(ip("::ffff:192.168.1.5")));
+        assert!(ranges[2].contains(ip("fd12::1")));
+        assert!(!ranges[2].contains(ip("10.42.0.7")));
+    }
+
+    #[test]
+    fn uses_forwarded_for_only_from_trusted_proxies() {
+        let trusted = parse_trusted_proxies("10.0.0.0/8");
+        let forwarded = headers("203.0.113.9, 198.51.100.2, 10.0.0.3");
+
+        assert_eq!(
+            resolve_client_ip(&forwarded, Some(ip("10.0.0.1")), &trusted),
+            Some(ip("198.51.100.2"))
+        );
+        assert_eq!(
+            resolve_client_ip(&forwarded, Some(ip("198.51.100.7")), &trusted),
+            Some(ip("198.51.100.7"))
+        );
+        assert_eq!(
+            resolve_client_ip(&HeaderMap::new(), Some(ip("10.0.0.1")), &trusted),
+            Some(ip("10.0.0.1"))
+        );
+        assert_eq!(resolve_client_ip(&forwarded, None, &trusted), None);
+    }
+}
@@ -0,0 +1,88 @@
+//! Gzip compression for API and frontend responses.
+//!
+//! Responses smaller than `COMPRESSION_MIN_SIZE_BYTES` (default 1024) are sent
+//! as-is, as are content types that are already compressed or must stream
+//! unbuffered: images, gzip archives, server-sent events and gRPC.
+
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{NotForContentType, Predicate, SizeAbove},
+};
+
+/// Default minimum response size, in bytes, before compression kicks in.
+const DEFAULT_MIN_SIZE_BYTES: u16 = 1024;
+
+/// Build the compression layer from `COMPRESSION_MIN_SIZE_BYTES`.
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    let min_size = std::env::var("COMPRESSION_MIN_SIZE_BYTES")
+        .ok()
+        .and_then(|v| v.parse::<u16>().ok())
+        .unwrap_or(DEFAULT_MIN_SIZE_BYTES);
+    compression_layer_with_min_size(min_size)
+}
+
+fn compression_layer_with_min_size(min_size: u16) -> CompressionLayer<impl Predicate> {
+    let predicate = SizeAbove::new(min_size)
+        .and(NotForContentType::const_new("image/"))
+        .and(NotForContentType::const_new("application/gzip"))
+        .and(NotForContentType::SSE)
+        .and(NotForContentType::GRPC);
+    CompressionLayer::new().gzip(true).compress_when(predicate)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::Body,
+        http::{self, HeaderValue, Request, header},
+        routing::get,
+    };
+    use tower::Service;
+
+    use super::*;
+
+    const MIN_SIZE: u16 = 1024;
+
+    fn app() -> Router {
+        let large = "x".repeat(MIN_SIZE as usize * 4);
+        let image = large.clone();
+        Router::new()
+            .route("/small", get(|| async { "ok" }))
+            .route("/large", get(move || async move { large }))
+            .route(
+                "/image",
+                get(move || async move { ([(header::CONTENT_TYPE, "image/png")], image) }),
+            )
+            .layer(compression_layer_with_min_size(MIN_SIZE))
+    }
+
+    async fn content_encoding(uri: &str) -> Option<HeaderValue> {
+        let request = Request::builder()
+            .uri(uri)
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().call(request).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        response.headers().get(header::CONTENT_ENCODING).cloned()
+    }
+
+    #[tokio::test]
+    async fn compresses_large_responses() {
+        assert_eq!(
+            content_encoding("/large").await,
+            Some(HeaderValue::from_static("gzip"))
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_small_responses() {
+        assert_eq!(content_encoding("/small").await, None);
+    }
+
+    #[tokio::test]
+    async fn skips_images() {
+        assert_eq!(content_encoding("/image").await, None);
+    }
+}
@@ -0,0 +1,142 @@
+//! CORS configuration for Kubernetes deployments.
+//!
+//! In K8s mode the frontend is usually served from a different origin than the
+//! API, so cross-origin requests are allowed for the origins listed in
+//! `CORS_ALLOWED_ORIGINS` (comma-separated, or `*` for any origin). Desktop mode
+//! keeps using [`super::validate_origin`] instead.
+
+use std::time::Duration;
+
+use axum::http::HeaderValue;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+/// Origins that may make cross-origin requests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// Any origin (`*`).
+    Any,
+    /// Only the listed origins. Empty means same-origin requests only.
+    List(Vec<HeaderValue>),
+}
+
+/// CORS settings read from `CORS_ALLOWED_ORIGINS`, `CORS_ALLOW_CREDENTIALS` and
+/// `CORS_MAX_AGE_SECS`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorsConfig {
+    pub allowed_origins: CorsOrigins,
+    pub allow_credentials: bool,
+    pub max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Build the configuration from environment variables.
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var("CORS_ALLOWED_ORIGINS").ok().as_deref(),
+            std::env::var("CORS_ALLOW_CREDENTIALS").ok().as_deref(),
+            std::env::var("CORS_MAX_AGE_SECS").ok().as_deref(),
+        )
+    }
+
+    fn from_values(
+        origins: Option<&str>,
+        allow_credentials: Option<&str>,
+        max_age_secs: Option<&str>,
+    ) -> Self {
+        let allowed_origins = match origins.map(str::trim) {
+            Some("*") => CorsOrigins::Any,
+            Some(list) => CorsOrigins::List(
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|origin| !origin.is_empty())
+                    .filter_map(|origin| match HeaderValue::from_str(origin) {
+                        Ok(value) => Some(value),
+                        Err(_) => {
+                            tracing::warn!(origin, "Ignoring invalid CORS origin");
+                            None
+                        }
+                    })
+                    .collect(),
+            ),
+            None => CorsOrigins::List(Vec::new()),
+        };
+
+        let mut allow_credentials = allow_credentials
+            .is_some_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"));
+
+        // Browsers reject credentialed responses with a wildcard origin
+        if allow_credentials && allowed_origins == CorsOrigins::Any {
+            tracing::warn!("CORS_ALLOW_CREDENTIALS is ignored when CORS_ALLOWED_ORIGINS is '*'");
+            allow_credentials = false;
+        }
+
+        let max_age = max_age_secs
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        Self {
+            allowed_origins,
+            allow_credentials,
+            max_age,
+        }
+    }
+
+    /// Build the tower-http layer for this configuration.
+    pub fn layer(&self) -> CorsLayer {
+        let allow_origin = match &self.allowed_origins {
+            CorsOrigins::Any => AllowOrigin::any(),
+            CorsOrigins::List(origins) => AllowOrigin::list(origins.clone()),
+        };
+
+        let layer = CorsLayer::new()
+            .allow_origin(allow_origin)
+            // Mirroring is valid with credentials, unlike a `*` wildcard
+            .allow_methods(AllowMethods::mirror_request())
+            .allow_headers(AllowHeaders::mirror_request())
+            .allow_credentials(self.allow_credentials);
+
+        match self.max_age {
+            Some(max_age) => layer.max_age(max_age),
+            None => layer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_same_origin_only() {
+        let config = CorsConfig::from_values(None, None, None);
+        assert_eq!(config.allowed_origins, CorsOrigins::List(Vec::new()));
+        assert!(!config.allow_credentials);
+        assert_eq!(config.max_age, None);
+    }
+
+    #[test]
+    fn test_parses_origin_list() {
+        let config = CorsConfig::from_values(
+            Some("https://app.example.com, https://admin.example.com,"),
+            Some("true"),
+            Some("600"),
+        );
+        assert_eq!(
+            config.allowed_origins,
+            CorsOrigins::List(vec![
+                HeaderValue::from_static("https://app.example.com"),
+                HeaderValue::from_static("https://admin.example.com"),
+            ])
+        );
+        assert!(config.allow_credentials);
+        assert_eq!(config.max_age, Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_wildcard_disables_credentials() {
+        let config = CorsConfig::from_values(Some("*"), Some("true"), Some("soon"));
+        assert_eq!(config.allowed_origins, CorsOrigins::Any);
+        assert!(!config.allow_credentials);
+        assert_eq!(config.max_age, None);
+    }
+}
@@ -1,7 +1,17 @@
 pub mod auth;
+pub mod client_ip;
+pub mod compression;
+pub mod cors;
 pub mod model_loaders;
 pub mod origin;
+pub mod rate_limit;
+pub mod trace;
 
-pub use auth::{AuthError, JwtClaims, OptionalUserContext, UserContext, UserContextExt, extract_bearer_token, require_user, verify_jwt};
+pub use auth::{AuthError, JwtClaims, OptionalUserContext, UserContext, UserContextExt, extract_bearer_token, init_jwks_from_env, require_user, verify_configured_jwt, verify_jwt};
+pub use client_ip::client_ip;
+pub use compression::compression_layer;
+pub use cors::{CorsConfig, CorsOrigins};
 pub use model_loaders::*;
 pub use origin::*;
+pub use rate_limit::{RateLimitLayer, RateLimiter};
+pub use trace::{RequestId, trace_layer, trace_request};
@@ -11,6 +11,7 @@ use db::models::{
 use deployment::Deployment;
 use uuid::Uuid;
 
+use super::auth::UserContext;
 use crate::DeploymentImpl;
 
 pub async fn load_project_middleware(
@@ -19,10 +20,27 @@ pub async fn load_project_middleware(
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Load the project from the database
-    let project = match Project::find_by_id(&deployment.db().pool, project_id).await {
+    let pool = &deployment.db().pool;
+    let user_id = request
+        .extensions()
+        .get::<UserContext>()
+        .map(|ctx| ctx.user_id);
+
+    // Load the project from the database, scoped to the caller in multi-user mode
+    let project = match Project::find_by_id_and_user(pool, project_id, user_id).await {
         Ok(Some(project)) => project,
         Ok(None) => {
+            // Distinguish a project owned by someone else from a missing one
+            if let Some(user_id) = user_id
+                && let Ok(Some(_)) = Project::find_by_id(pool, project_id).await
+            {
+                tracing::warn!(
+                    user_id = %user_id,
+                    project_id = %project_id,
+                    "User attempted to access a project they do not own"
+                );
+                return Err(StatusCode::FORBIDDEN);
+            }
             tracing::warn!("Project {} not found", project_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -46,10 +64,27 @@ pub async fn load_task_middleware(
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Load the task and validate it belongs to the project
-    let task = match Task::find_by_id(&deployment.db().pool, task_id).await {
+    let pool = &deployment.db().pool;
+    let user_id = request
+        .extensions()
+        .get::<UserContext>()
+        .map(|ctx| ctx.user_id);
+
+    // Load the task, scoped to the owner of its project in multi-user mode
+    let task = match Task::find_by_id_and_user(pool, task_id, user_id).await {
         Ok(Some(task)) => task,
         Ok(None) => {
+            // Distinguish a task in someone else's project from a missing one
+            if let Some(user_id) = user_id
+                && let Ok(Some(_)) = Task::find_by_id(pool, task_id).await
+            {
+                tracing::warn!(
+                    user_id = %user_id,
+                    task_id = %task_id,
+                    "User attempted to access a task they do not own"
+                );
+                return Err(StatusCode::FORBIDDEN);
+            }
             tracing::warn!("Task {} not found", task_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -73,10 +108,24 @@ pub async fn load_workspace_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Load the Workspace from the database
-    let workspace = match Workspace::find_by_id(&deployment.db().pool, workspace_id).await {
+    let pool = &deployment.db().pool;
+    let user_id = request.extensions().get::<UserContext>().map(|ctx| ctx.user_id);
+
+    // Load the Workspace from the database, scoped to the caller in multi-user mode
+    let workspace = match Workspace::find_by_id_and_user(pool, workspace_id, user_id).await {
         Ok(Some(w)) => w,
         Ok(None) => {
+            // Distinguish a workspace owned by someone else from a missing one
+            if let Some(user_id) = user_id
+                && let Ok(Some(_)) = Workspace::find_by_id(pool, workspace_id).await
+            {
+                tracing::warn!(
+                    user_id = %user_id,
+                    workspace_id = %workspace_id,
+                    "User attempted to access a workspace they do not own"
+                );
+                return Err(StatusCode::FORBIDDEN);
+            }
             tracing::warn!("Workspace {} not found", workspace_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -0,0 +1,334 @@
+//! Fixed-window rate limiting for unauthenticated and token endpoints.
+//!
+//! Clients are keyed by authenticated user ID when one is available, and
+//! otherwise by [`client_ip`], which only believes `X-Forwarded-For` from
+//! `TRUSTED_PROXIES`. Each key may make `RATE_LIMIT_MAX_REQUESTS` requests per
+//! `RATE_LIMIT_WINDOW_SECS` window; further requests receive HTTP 429 with a
+//! `Retry-After` header until the window resets.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json,
+    body::Body,
+    extract::Request,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use futures_util::future::BoxFuture;
+use tower::{Layer, Service};
+use utils::response::ApiResponse;
+
+use super::{auth::UserContext, client_ip::client_ip};
+
+/// Default number of requests allowed per window.
+const DEFAULT_MAX_REQUESTS: u32 = 60;
+
+/// Default window length in seconds.
+const DEFAULT_WINDOW_SECS: u64 = 60;
+
+/// Once the map grows past this many keys, expired windows are swept on write.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+/// Shared request counters, keyed by client.
+#[derive(Debug)]
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    clients: DashMap<String, (u32, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests: max_requests.max(1),
+            window,
+            clients: DashMap::new(),
+        }
+    }
+
+    /// Build a limiter from `RATE_LIMIT_MAX_REQUESTS` and `RATE_LIMIT_WINDOW_SECS`.
+    pub fn from_env() -> Self {
+        let max_requests = std::env::var("RATE_LIMIT_MAX_REQUESTS")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_MAX_REQUESTS);
+        let window_secs = std::env::var("RATE_LIMIT_WINDOW_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_WINDOW_SECS);
+
+        Self::new(max_requests, Duration::from_secs(window_secs))
+    }
+
+    /// Record a request for `key`.
+    ///
+    /// Returns `Err(retry_after)` with the time until the current window
+    /// resets if the key has exhausted its allowance.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+
+        if self.clients.len() > SWEEP_THRESHOLD {
+            self.clients
+                .retain(|_, (_, started)| now.duration_since(*started) < self.window);
+        }
+
+        let mut entry = self.clients.entry(key.to_string()).or_insert((0, now));
+        let (count, started) = entry.value_mut();
+
+        let elapsed = now.duration_since(*started);
+        if elapsed >= self.window {
+            *count = 1;
+            *started = now;
+            return Ok(());
+        }
+
+        if *count >= self.max_requests {
+            return Err(self.window - elapsed);
+        }
+
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Tower layer that applies a [`RateLimiter`] to every wrapped route.
+///
+/// Each layer instance owns its own counters, so applying separate layers
+/// to separate routers gives them independent budgets.
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self {
+            limiter: Arc::new(limiter),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(RateLimiter::from_env())
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // Without a connection address (never the case behind the real
+        // listener) there is nothing to key on, so don't limit
+        let Some(key) = client_key(&request) else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        match self.limiter.check(&key) {
+            Ok(()) => Box::pin(self.inner.call(request)),
+            Err(retry_after) => {
+                tracing::warn!(
+                    action = "rate_limited",
+                    client = %key,
+                    path = %request.uri().path(),
+                    retry_after_secs = retry_after.as_secs(),
+                    security_event = true,
+                    "Rate limit exceeded"
+                );
+                let response = too_many_requests(retry_after);
+                Box::pin(async move { Ok(response) })
+            }
+        }
+    }
+}
+
+/// Identify the caller: authenticated user first, then client IP.
+fn client_key(request: &Request<Body>) -> Option<String> {
+    if let Some(user) = request.extensions().get::<UserContext>() {
+        return Some(format!("user:{}", user.user_id));
+    }
+
+    client_ip(request).map(|ip| format!("ip:{ip}"))
+}
+
+fn too_many_requests(retry_after: Duration) -> Response {
+    // Round up so clients never retry before the window has actually reset
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let secs = secs.max(1);
+
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ApiResponse::<()>::error(
+            "Too many requests. Please try again later.",
+        )),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::{Router, extract::ConnectInfo, routing::get};
+
+    use super::*;
+
+    fn app(max_requests: u32) -> Router {
+        Router::new()
+            .route("/limited", get(|| async { "ok" }))
+            .layer(RateLimitLayer::new(RateLimiter::new(
+                max_requests,
+                Duration::from_secs(60),
+            )))
+    }
+
+    fn request_from(ip: &str) -> Request<Body> {
+        let mut request = Request::builder()
+            .uri("/limited")
+            .body(Body::empty())
+            .unwrap();
+        let addr = SocketAddr::new(ip.parse().unwrap(), 40000);
+        request.extensions_mut().insert(ConnectInfo(addr));
+        request
+    }
+
+    #[tokio::test]
+    async fn test_requests_over_limit_get_429() {
+        let mut app = app(5);
+
+        for i in 0..5 {
+            let response = app.call(request_from("10.0.0.1")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "request {} should pass", i);
+        }
+
+        for _ in 0..3 {
+            let response = app.call(request_from("10.0.0.1")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+            let retry_after: u64 = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .expect("Retry-After header should be set")
+                .to_str()
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!((1..=60).contains(&retry_after));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clients_have_independent_budgets() {
+        let mut app = app(2);
+
+        for _ in 0..2 {
+            let response = app.call(request_from("10.0.0.1")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let response = app.call(request_from("10.0.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = app.call(request_from("10.0.0.2")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_separate_layers_have_separate_counters() {
+        let mut first = app(1);
+        let mut second = app(1);
+
+        let response = first.call(request_from("10.0.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = first.call(request_from("10.0.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = second.call(request_from("10.0.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_window_resets_after_expiry() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(20));
+
+        assert!(limiter.check("ip:10.0.0.1").is_ok());
+        assert!(limiter.check("ip:10.0.0.1").is_err());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(limiter.check("ip:10.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn test_client_key_prefers_user_context() {
+        let user_id = uuid::Uuid::new_v4();
+        let mut request = request_from("10.0.0.1");
+        request
+            .extensions_mut()
+            .insert(UserContext::new(user_id, None));
+
+        assert_eq!(client_key(&request), Some(format!("user:{}", user_id)));
+    }
+
+    #[test]
+    fn test_client_key_ignores_untrusted_forwarded_headers() {
+        let mut request = request_from("198.51.100.7");
+        request.headers_mut().insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 10.0.0.1"),
+        );
+        request
+            .headers_mut()
+            .insert("x-real-ip", HeaderValue::from_static("203.0.113.8"));
+
+        assert_eq!(client_key(&request).as_deref(), Some("ip:198.51.100.7"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_client_address_are_not_limited() {
+        let mut app = app(1);
+
+        for _ in 0..3 {
+            let request = Request::builder()
+                .uri("/limited")
+                .body(Body::empty())
+                .unwrap();
+            let response = app.call(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
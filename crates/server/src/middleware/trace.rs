@@ -0,0 +1,141 @@
+//! Request tracing with a per-request correlation ID.
+//!
+//! [`trace_request`] takes the caller's `X-Request-ID` header, or generates a
+//! UUID v4 when it is missing or unusable, and echoes it on the response.
+//! [`trace_layer`] wraps every request in a `request` span carrying that ID so
+//! log lines from different pods can be correlated. With OpenTelemetry export
+//! enabled the span also continues any trace named by a `traceparent` header.
+
+use axum::{
+    extract::Request,
+    http::{self, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tower_http::{
+    classify::{ServerErrorsAsFailures, SharedClassifier},
+    trace::{MakeSpan, TraceLayer},
+};
+use tracing::Span;
+use uuid::Uuid;
+
+pub const X_REQUEST_ID: &str = "x-request-id";
+
+/// Longest client-supplied request ID we accept before generating our own.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Correlation ID of the current request, stored in request extensions.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Attach a request ID to the request and echo it as `X-Request-ID` on the response.
+pub async fn trace_request(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    request.extensions_mut().insert(RequestId(id.clone()));
+    let mut response = next.run(request).await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(X_REQUEST_ID), value);
+    }
+    response
+}
+
+/// Only short IDs of visible ASCII are trusted, so they are safe to log verbatim.
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Builds the `request` span from the [`RequestId`] set by [`trace_request`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestSpan;
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &http::Request<B>) -> Span {
+        let request_id = request
+            .extensions()
+            .get::<RequestId>()
+            .map(|id| id.0.as_str())
+            .unwrap_or_default();
+        let span = tracing::info_span!(
+            "request",
+            request_id = %request_id,
+            method = %request.method(),
+            uri = %request.uri(),
+        );
+        // Join the caller's distributed trace when one is propagated
+        utils::otel::set_parent_from_headers(&span, request.headers());
+        span
+    }
+}
+
+/// HTTP trace layer whose spans carry the request ID. Must sit inside
+/// [`trace_request`] so the ID is set before the span is created.
+pub fn trace_layer() -> TraceLayer<SharedClassifier<ServerErrorsAsFailures>, RequestSpan> {
+    TraceLayer::new_for_http().make_span_with(RequestSpan)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, middleware::from_fn, routing::get};
+    use tower::Service;
+
+    use super::*;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(trace_layer())
+            .layer(from_fn(trace_request))
+    }
+
+    async fn response_id(request: http::Request<Body>) -> String {
+        let response = app().call(request).await.unwrap();
+        response
+            .headers()
+            .get(X_REQUEST_ID)
+            .expect("X-Request-ID header should be set")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn echoes_incoming_request_id() {
+        let request = http::Request::builder()
+            .uri("/")
+            .header(X_REQUEST_ID, "abc-123")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(response_id(request).await, "abc-123");
+    }
+
+    #[tokio::test]
+    async fn generates_request_id_when_missing() {
+        let request = http::Request::builder()
+            .uri("/")
+            .body(Body::empty())
+            .unwrap();
+        let id = response_id(request).await;
+        assert!(Uuid::parse_str(&id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn replaces_oversized_request_id() {
+        let request = http::Request::builder()
+            .uri("/")
+            .header(X_REQUEST_ID, "a".repeat(MAX_REQUEST_ID_LEN + 1))
+            .body(Body::empty())
+            .unwrap();
+        let id = response_id(request).await;
+        assert!(Uuid::parse_str(&id).is_ok());
+    }
+}
@@ -0,0 +1,375 @@
+//! Operator-only maintenance routes for K8s multi-user deployments.
+//!
+//! These routes sit outside the `require_user` middleware and are instead
+//! authorized by the `X-Admin-Secret` header, which must match the
+//! `ADMIN_SECRET` environment variable. When `ADMIN_SECRET` is unset every
+//! admin request is rejected.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use axum::{
+    Router,
+    extract::{Json, Path, Query, State},
+    http::HeaderMap,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    admin::{AdminService, verify_admin_token},
+    config_db::{ConfigKeySummary, parse_encryption_key},
+    container::{ContainerError, ContainerService},
+    workspace_manager::WorkspaceManager,
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Header carrying the shared admin secret.
+const ADMIN_SECRET_HEADER: &str = "x-admin-secret";
+
+/// Request body for POST /api/admin/rotate-credentials-key
+#[derive(Debug, Deserialize)]
+pub struct RotateCredentialsKeyRequest {
+    /// The key credentials are currently encrypted with (64 hex characters).
+    pub old_key: String,
+    /// The key to re-encrypt credentials with (64 hex characters).
+    pub new_key: String,
+}
+
+/// Response from POST /api/admin/rotate-credentials-key
+#[derive(Debug, Serialize)]
+pub struct RotateCredentialsKeyResponse {
+    /// Number of users whose credentials were re-encrypted.
+    pub rotated: u32,
+}
+
+/// Response from POST /api/admin/migrate-workspaces
+#[derive(Debug, Serialize)]
+pub struct MigrateWorkspacesResponse {
+    /// Workspaces moved into the current workspace base directory.
+    pub migrated: u32,
+    /// Workspaces already in place, without a directory on disk, or running.
+    pub skipped: u32,
+    /// Workspaces that could not be moved and were left where they were.
+    pub failed: u32,
+}
+
+/// Response from POST /api/admin/prune-worktrees
+#[derive(Debug, Serialize)]
+pub struct PruneWorktreesResponse {
+    /// Workspace directories removed because no workspace record points at them.
+    pub pruned: u32,
+}
+
+/// Query parameters for POST /api/admin/archive-old-workspaces
+#[derive(Debug, Deserialize)]
+pub struct ArchiveOldWorkspacesQuery {
+    /// Archive workspaces not updated for this many days. Defaults to 90.
+    pub older_than_days: Option<u32>,
+    /// Only archive workspaces owned by this user.
+    pub user_id: Option<Uuid>,
+}
+
+/// Response from POST /api/admin/archive-old-workspaces
+#[derive(Debug, Serialize)]
+pub struct ArchiveOldWorkspacesResponse {
+    /// Number of workspaces archived.
+    pub archived: u32,
+}
+
+/// Request body for the bulk user maintenance routes
+#[derive(Debug, Deserialize)]
+pub struct AdminUsersRequest {
+    pub user_ids: Vec<Uuid>,
+}
+
+/// Response from the bulk user maintenance routes
+#[derive(Debug, Serialize)]
+pub struct AdminUsersResponse {
+    /// Number of users that were changed.
+    pub updated: u64,
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/admin/rotate-credentials-key",
+            post(rotate_credentials_key),
+        )
+        .route("/admin/migrate-workspaces", post(migrate_workspaces))
+        .route("/admin/prune-worktrees", post(prune_worktrees))
+        .route(
+            "/admin/archive-old-workspaces",
+            post(archive_old_workspaces),
+        )
+        .route("/admin/users", get(list_users))
+        .route(
+            "/admin/users/{user_id}/config-summary",
+            get(get_user_config_summary),
+        )
+        .route("/admin/users/reset-configs", post(reset_user_configs))
+        .route(
+            "/admin/users/delete-credentials",
+            post(delete_user_credentials),
+        )
+}
+
+async fn rotate_credentials_key(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Json(payload): Json<RotateCredentialsKeyRequest>,
+) -> Result<ResponseJson<ApiResponse<RotateCredentialsKeyResponse>>, ApiError> {
+    require_admin_secret(&headers)?;
+
+    let config_service = deployment.config_service().ok_or_else(|| {
+        ApiError::BadRequest("Key rotation is only available in multi-user mode".to_string())
+    })?;
+    let old_key = parse_encryption_key(&payload.old_key)
+        .ok_or_else(|| ApiError::BadRequest("old_key must be 64 hex characters".to_string()))?;
+    let new_key = parse_encryption_key(&payload.new_key)
+        .ok_or_else(|| ApiError::BadRequest("new_key must be 64 hex characters".to_string()))?;
+    if old_key == new_key {
+        return Err(ApiError::BadRequest(
+            "new_key must differ from old_key".to_string(),
+        ));
+    }
+
+    let rotated = config_service
+        .rotate_encryption_key(old_key, new_key)
+        .await?;
+
+    tracing::warn!(
+        rotated,
+        security_event = true,
+        "OAuth credentials encryption key rotated via admin API"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(
+        RotateCredentialsKeyResponse { rotated },
+    )))
+}
+
+fn admin_service(deployment: &DeploymentImpl) -> Result<AdminService, ApiError> {
+    deployment
+        .config_service()
+        .cloned()
+        .map(AdminService::new)
+        .ok_or_else(|| {
+            ApiError::BadRequest(
+                "User maintenance is only available in multi-user mode".to_string(),
+            )
+        })
+}
+
+/// List every user with a stored configuration, oldest first.
+async fn list_users(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<ResponseJson<ApiResponse<Vec<Uuid>>>, ApiError> {
+    let user_ids = admin_service(&deployment)?
+        .list_all_user_ids(admin_token(&headers))
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(user_ids)))
+}
+
+/// Which configuration fields a user has set, for support staff who must not
+/// see the values themselves.
+async fn get_user_config_summary(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ConfigKeySummary>>, ApiError> {
+    let summary = admin_service(&deployment)?
+        .config_summary(admin_token(&headers), user_id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// Reset the configuration of the given users to the defaults.
+async fn reset_user_configs(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Json(payload): Json<AdminUsersRequest>,
+) -> Result<ResponseJson<ApiResponse<AdminUsersResponse>>, ApiError> {
+    let updated = admin_service(&deployment)?
+        .bulk_reset_configs(admin_token(&headers), &payload.user_ids)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(AdminUsersResponse {
+        updated,
+    })))
+}
+
+/// Delete the stored OAuth credentials of the given users.
+async fn delete_user_credentials(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Json(payload): Json<AdminUsersRequest>,
+) -> Result<ResponseJson<ApiResponse<AdminUsersResponse>>, ApiError> {
+    let updated = admin_service(&deployment)?
+        .bulk_delete_credentials(admin_token(&headers), &payload.user_ids)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(AdminUsersResponse {
+        updated,
+    })))
+}
+
+/// Move every non-archived workspace into the current workspace base directory,
+/// e.g. after `WORKSPACE_BASE_DIR` was changed. Workspaces with running
+/// processes are skipped so a later call can pick them up.
+async fn migrate_workspaces(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<ResponseJson<ApiResponse<MigrateWorkspacesResponse>>, ApiError> {
+    require_admin_secret(&headers)?;
+
+    let pool = &deployment.db().pool;
+    let base_dir = WorkspaceManager::get_workspace_base_dir();
+    let busy: HashSet<_> = deployment
+        .container()
+        .list_running()
+        .await
+        .into_iter()
+        .map(|process| process.workspace_id)
+        .collect();
+
+    let mut response = MigrateWorkspacesResponse {
+        migrated: 0,
+        skipped: 0,
+        failed: 0,
+    };
+    for workspace in Workspace::fetch_all(pool, None).await? {
+        if workspace.archived {
+            continue;
+        }
+        let Some(old_dir) = workspace.container_ref.as_deref().map(PathBuf::from) else {
+            response.skipped += 1;
+            continue;
+        };
+        let Some(dir_name) = old_dir.file_name() else {
+            response.skipped += 1;
+            continue;
+        };
+        let new_dir = base_dir.join(dir_name);
+        if new_dir == old_dir || !old_dir.exists() {
+            response.skipped += 1;
+            continue;
+        }
+        if busy.contains(&workspace.id) {
+            tracing::info!(
+                workspace_id = %workspace.id,
+                "Skipping workspace migration: processes are running"
+            );
+            response.skipped += 1;
+            continue;
+        }
+
+        let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+        match WorkspaceManager::move_workspace(pool, workspace.id, &old_dir, &new_dir, &repos).await
+        {
+            Ok(()) => {
+                tracing::info!(
+                    workspace_id = %workspace.id,
+                    from = %old_dir.display(),
+                    to = %new_dir.display(),
+                    "Migrated workspace"
+                );
+                response.migrated += 1;
+            }
+            Err(e) => {
+                tracing::error!(
+                    workspace_id = %workspace.id,
+                    from = %old_dir.display(),
+                    error = %e,
+                    "Failed to migrate workspace"
+                );
+                response.failed += 1;
+            }
+        }
+    }
+
+    tracing::warn!(
+        migrated = response.migrated,
+        skipped = response.skipped,
+        failed = response.failed,
+        "Workspace migration via admin API finished"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Remove directories in the workspace base directory whose workspace record
+/// has been deleted, together with the worktrees inside them.
+async fn prune_worktrees(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<ResponseJson<ApiResponse<PruneWorktreesResponse>>, ApiError> {
+    require_admin_secret(&headers)?;
+
+    let base_dir = WorkspaceManager::get_workspace_base_dir();
+    let pruned = WorkspaceManager::prune_stale_worktrees(&deployment.db().pool, &base_dir)
+        .await
+        .map_err(ContainerError::from)?;
+
+    tracing::warn!(
+        pruned,
+        base_dir = %base_dir.display(),
+        "Stale worktree prune via admin API finished"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(PruneWorktreesResponse {
+        pruned,
+    })))
+}
+
+/// Archive every unpinned workspace that has not been updated for
+/// `older_than_days` days, optionally only those of one user.
+async fn archive_old_workspaces(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Query(query): Query<ArchiveOldWorkspacesQuery>,
+) -> Result<ResponseJson<ApiResponse<ArchiveOldWorkspacesResponse>>, ApiError> {
+    require_admin_secret(&headers)?;
+
+    let older_than_days = query.older_than_days.unwrap_or(90);
+    if older_than_days == 0 {
+        return Err(ApiError::BadRequest(
+            "older_than_days must be at least 1".to_string(),
+        ));
+    }
+
+    let archived = Workspace::archive_older_than(
+        &deployment.db().pool,
+        chrono::Duration::days(i64::from(older_than_days)),
+        query.user_id,
+    )
+    .await?;
+
+    tracing::warn!(
+        archived,
+        older_than_days,
+        user_id = ?query.user_id,
+        "Old workspaces archived via admin API"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(
+        ArchiveOldWorkspacesResponse { archived },
+    )))
+}
+
+/// Check the `X-Admin-Secret` header against `ADMIN_SECRET`.
+fn require_admin_secret(headers: &HeaderMap) -> Result<(), ApiError> {
+    verify_admin_token(admin_token(headers))?;
+    Ok(())
+}
+
+/// The `X-Admin-Secret` header, or an empty token when it is missing.
+fn admin_token(headers: &HeaderMap) -> &str {
+    headers
+        .get(ADMIN_SECRET_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+}
@@ -3,15 +3,37 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json as ResponseJson,
-    routing::post,
+    routing::{get, post},
 };
 use deployment::Deployment;
+use services::services::approvals::PendingApproval;
 use utils::{
     approvals::{ApprovalResponse, ApprovalStatus},
     response::ApiResponse,
 };
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::OptionalUserContext};
+
+/// List approvals still awaiting a response across all of the caller's
+/// sessions, so any open tab can answer them. Desktop mode lists them all.
+pub async fn get_pending_approvals(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<PendingApproval>>>, StatusCode> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+
+    match deployment
+        .approvals()
+        .list_pending_for_user(&deployment.db().pool, user_id)
+        .await
+    {
+        Ok(pending) => Ok(ResponseJson(ApiResponse::success(pending))),
+        Err(e) => {
+            tracing::error!("Failed to list pending approvals: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
@@ -44,5 +66,7 @@ pub async fn respond_to_approval(
 }
 
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+    Router::new()
+        .route("/approvals/pending", get(get_pending_approvals))
+        .route("/approvals/{id}/respond", post(respond_to_approval))
 }
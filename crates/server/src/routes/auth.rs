@@ -0,0 +1,270 @@
+//! Token lifecycle routes for K8s multi-user deployments.
+//!
+//! These routes sit outside the `require_user` middleware: a client calls
+//! them to obtain its first token pair, and again whenever its access token
+//! has expired.
+
+use axum::{
+    Router,
+    extract::{Json, State},
+    response::Json as ResponseJson,
+    routing::post,
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::{Rng, distributions::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use services::services::config_db::ConfigServicePg;
+use sha2::{Digest, Sha256};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::auth::{JwtClaims, decode_claims, get_jwt_secret, sign_jwt, verify_configured_jwt},
+};
+
+/// Default lifetime of an issued access token (15 minutes).
+const DEFAULT_ACCESS_TOKEN_TTL_SECS: i64 = 15 * 60;
+
+/// Default lifetime of an issued refresh token (30 days).
+const DEFAULT_REFRESH_TOKEN_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Request body for POST /api/auth/token
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct TokenExchangeRequest {
+    /// A token issued by the identity provider, verified against `JWKS_URI`
+    /// or `JWT_SECRET`.
+    pub id_token: String,
+}
+
+/// Request body for POST /api/auth/refresh
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct RefreshTokenRequest {
+    /// The access token being replaced. It may already be expired.
+    pub access_token: String,
+    /// The opaque refresh token issued alongside the access token.
+    pub refresh_token: String,
+}
+
+/// Response from POST /api/auth/token and POST /api/auth/refresh - a fresh
+/// access/refresh token pair
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct RefreshTokenResponse {
+    pub access_token: String,
+    pub access_token_expires_at: DateTime<Utc>,
+    pub refresh_token: String,
+    pub refresh_token_expires_at: DateTime<Utc>,
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/auth/token", post(exchange))
+        .route("/auth/refresh", post(refresh))
+}
+
+async fn exchange(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TokenExchangeRequest>,
+) -> Result<ResponseJson<ApiResponse<RefreshTokenResponse>>, ApiError> {
+    let config_service = deployment.config_service().ok_or_else(|| {
+        ApiError::BadRequest("Token exchange is only available in multi-user mode".to_string())
+    })?;
+    let secret = get_jwt_secret().ok_or(ApiError::Unauthorized)?;
+
+    let response = exchange_token(config_service, secret, &payload.id_token).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn refresh(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RefreshTokenRequest>,
+) -> Result<ResponseJson<ApiResponse<RefreshTokenResponse>>, ApiError> {
+    let config_service = deployment.config_service().ok_or_else(|| {
+        ApiError::BadRequest("Token refresh is only available in multi-user mode".to_string())
+    })?;
+    let secret = get_jwt_secret().ok_or(ApiError::Unauthorized)?;
+
+    let response = refresh_token_pair(config_service, secret, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Exchange an identity provider token for this server's first token pair.
+///
+/// Access tokens minted by [`issue_token_pair`] are rejected here, so a
+/// session can only be extended through [`refresh_token_pair`].
+pub async fn exchange_token(
+    config_service: &ConfigServicePg,
+    secret: &[u8],
+    id_token: &str,
+) -> Result<RefreshTokenResponse, ApiError> {
+    let user = verify_configured_jwt(id_token).map_err(|e| {
+        tracing::warn!(error = %e, security_event = true, "Token exchange rejected: bad token");
+        ApiError::Unauthorized
+    })?;
+
+    if decode_claims(id_token, secret, true).is_ok_and(|claims| claims.jti.is_some()) {
+        tracing::warn!(
+            user_id = %user.user_id,
+            security_event = true,
+            "Token exchange rejected: token was issued by this server"
+        );
+        return Err(ApiError::Unauthorized);
+    }
+
+    let response = issue_token_pair(config_service, secret, user.user_id, user.email).await?;
+
+    tracing::debug!(user_id = %user.user_id, "Issued token pair from exchange");
+
+    Ok(response)
+}
+
+/// Trade an access token and its refresh token for a new pair.
+///
+/// The access token may be expired, but must carry this server's signature
+/// and a `jti` matching the refresh token, which is revoked on success.
+pub async fn refresh_token_pair(
+    config_service: &ConfigServicePg,
+    secret: &[u8],
+    payload: &RefreshTokenRequest,
+) -> Result<RefreshTokenResponse, ApiError> {
+    // The access token must carry our signature, but may be past its expiry
+    let claims = decode_claims(&payload.access_token, secret, false).map_err(|e| {
+        tracing::warn!(error = %e, security_event = true, "Refresh rejected: bad access token");
+        ApiError::Unauthorized
+    })?;
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| ApiError::Unauthorized)?;
+    let jti = claims.jti.ok_or(ApiError::Unauthorized)?;
+
+    // The refresh token must be the one this access token was issued with
+    if hash_sha256_hex(&payload.refresh_token) != jti {
+        tracing::warn!(
+            user_id = %user_id,
+            security_event = true,
+            "Refresh rejected: refresh token does not match access token"
+        );
+        return Err(ApiError::Unauthorized);
+    }
+
+    let expires_at = config_service
+        .get_refresh_token_expiry(user_id, &jti)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+
+    // Refresh tokens are single use: whoever revokes it first wins
+    let revoked = config_service.revoke_refresh_token(&jti).await?;
+    if !revoked || expires_at <= Utc::now() {
+        tracing::warn!(
+            user_id = %user_id,
+            security_event = true,
+            "Refresh rejected: refresh token expired or already used"
+        );
+        return Err(ApiError::Unauthorized);
+    }
+
+    let response = issue_token_pair(config_service, secret, user_id, claims.email).await?;
+
+    tracing::debug!(user_id = %user_id, "Issued refreshed token pair");
+
+    Ok(response)
+}
+
+/// Issue a new access token and a matching refresh token for a user.
+///
+/// The refresh token is an opaque random string; its SHA-256 hash is used as
+/// the `jti` of the access token and as the key under which it is stored.
+pub async fn issue_token_pair(
+    config_service: &ConfigServicePg,
+    secret: &[u8],
+    user_id: Uuid,
+    email: Option<String>,
+) -> Result<RefreshTokenResponse, ApiError> {
+    let now = Utc::now();
+    let access_token_expires_at = now + Duration::seconds(ttl_from_env(
+        "ACCESS_TOKEN_TTL_SECS",
+        DEFAULT_ACCESS_TOKEN_TTL_SECS,
+    ));
+    let refresh_token_expires_at = now + Duration::seconds(ttl_from_env(
+        "REFRESH_TOKEN_TTL_SECS",
+        DEFAULT_REFRESH_TOKEN_TTL_SECS,
+    ));
+
+    let refresh_token = generate_secret();
+    let jti = hash_sha256_hex(&refresh_token);
+
+    config_service
+        .store_refresh_token(user_id, &jti, refresh_token_expires_at)
+        .await?;
+
+    let claims = JwtClaims {
+        sub: user_id.to_string(),
+        email,
+        exp: access_token_expires_at.timestamp(),
+        iat: Some(now.timestamp()),
+        jti: Some(jti),
+    };
+    let access_token = sign_jwt(&claims, secret).map_err(|_| ApiError::Unauthorized)?;
+
+    Ok(RefreshTokenResponse {
+        access_token,
+        access_token_expires_at,
+        refresh_token,
+        refresh_token_expires_at,
+    })
+}
+
+fn ttl_from_env(key: &str, default: i64) -> i64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default)
+}
+
+fn generate_secret() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect()
+}
+
+fn hash_sha256_hex(input: &str) -> String {
+    let mut output = String::with_capacity(64);
+    let digest = Sha256::digest(input.as_bytes());
+    for byte in digest {
+        use std::fmt::Write;
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_sha256_hex_is_stable() {
+        let hash = hash_sha256_hex("refresh-token");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_sha256_hex("refresh-token"));
+        assert_ne!(hash, hash_sha256_hex("other-token"));
+    }
+
+    #[test]
+    fn test_generate_secret_is_random() {
+        let a = generate_secret();
+        let b = generate_secret();
+        assert_eq!(a.len(), 64);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_ttl_from_env_falls_back_to_default() {
+        assert_eq!(ttl_from_env("VK_TEST_UNSET_TTL_SECS", 42), 42);
+    }
+}
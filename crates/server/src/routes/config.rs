@@ -14,12 +14,12 @@ use executors::{
         AvailabilityInfo, BaseAgentCapability, BaseCodingAgent, StandardCodingAgentExecutor,
     },
     mcp_config::{McpConfig, read_agent_config, write_agent_config},
-    profile::{ExecutorConfigs, ExecutorProfileId},
+    profile::{ExecutorConfigs, ExecutorProfileId, ProfileValidation},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use services::services::config::{
-    Config, ConfigError, SoundFile,
+    Config, ConfigError, SoundFile, UpdateConfig,
     editor::{EditorConfig, EditorType},
     save_config_to_file,
 };
@@ -27,12 +27,16 @@ use tokio::fs;
 use ts_rs::TS;
 use utils::{api::oauth::LoginStatus, assets::config_path, response::ApiResponse};
 
-use crate::{DeploymentImpl, error::ApiError, middleware::OptionalUserContext};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext},
+};
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/info", get(get_user_system_info))
-        .route("/config", put(update_config))
+        .route("/config", get(get_config).put(update_config))
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
@@ -41,6 +45,10 @@ pub fn router() -> Router<DeploymentImpl> {
             get(check_editor_availability),
         )
         .route("/agents/check-availability", get(check_agent_availability))
+        .route(
+            "/executors/{profile_id}/validate",
+            get(validate_executor_profile),
+        )
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -91,12 +99,17 @@ async fn get_user_system_info(
     if let Some(ref ctx) = user_ctx {
         tracing::debug!(user_id = %ctx.user_id, "Fetching user system info");
     }
-    // TODO: In K8s mode, load config from database using ConfigServicePg
-    let config = deployment.config().read().await;
+    let config = match load_config(&deployment, user_ctx.as_ref()).await {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Failed to load config: {}", e);
+            return ResponseJson(ApiResponse::error("Failed to load config"));
+        }
+    };
     let login_status = deployment.get_login_status().await;
 
     let user_system_info = UserSystemInfo {
-        config: config.clone(),
+        config,
         analytics_user_id: deployment.user_id().to_string(),
         login_status,
         profiles: ExecutorConfigs::get_cached(),
@@ -116,42 +129,63 @@ async fn get_user_system_info(
     ResponseJson(ApiResponse::success(user_system_info))
 }
 
+/// Load the caller's config: their own row in K8s mode, the shared in-memory
+/// config on desktop.
+async fn load_config(
+    deployment: &DeploymentImpl,
+    user_ctx: Option<&UserContext>,
+) -> Result<Config, ApiError> {
+    if let Some(config_service) = deployment.config_service() {
+        let ctx = user_ctx.ok_or(ApiError::Unauthorized)?;
+        return Ok(config_service.load_config(ctx.user_id).await?);
+    }
+    Ok(deployment.config().read().await.clone())
+}
+
+async fn get_config(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Config>>, ApiError> {
+    let config = load_config(&deployment, user_ctx.as_ref()).await?;
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
     OptionalUserContext(user_ctx): OptionalUserContext,
-    Json(new_config): Json<Config>,
-) -> ResponseJson<ApiResponse<Config>> {
+    Json(update): Json<UpdateConfig>,
+) -> Result<ResponseJson<ApiResponse<Config>>, ApiError> {
     // Log user context for tracing in multi-user mode
     if let Some(ref ctx) = user_ctx {
         tracing::debug!(user_id = %ctx.user_id, "Updating config for user");
     }
-    // TODO: In K8s mode, save config to database using ConfigServicePg
 
-    let config_path = config_path();
+    let old_config = load_config(&deployment, user_ctx.as_ref()).await?;
+    let new_config = update.merge_into(&old_config);
 
     // Validate git branch prefix
     if !utils::git::is_valid_branch_prefix(&new_config.git_branch_prefix) {
-        return ResponseJson(ApiResponse::error(
-            "Invalid git branch prefix. Must be a valid git branch name component without slashes.",
+        return Err(ApiError::BadRequest(
+            "Invalid git branch prefix. Must be a valid git branch name component without slashes."
+                .to_string(),
         ));
     }
 
-    // Get old config state before updating
-    let old_config = deployment.config().read().await.clone();
+    // In K8s mode each user's config lives in their own database row
+    if let Some(config_service) = deployment.config_service() {
+        let ctx = user_ctx.ok_or(ApiError::Unauthorized)?;
+        config_service.save_config(ctx.user_id, &new_config).await?;
+        track_config_events(&deployment, &old_config, &new_config).await;
+        return Ok(ResponseJson(ApiResponse::success(new_config)));
+    }
 
-    match save_config_to_file(&new_config, &config_path).await {
-        Ok(_) => {
-            let mut config = deployment.config().write().await;
-            *config = new_config.clone();
-            drop(config);
+    save_config_to_file(&new_config, &config_path()).await?;
+    *deployment.config().write().await = new_config.clone();
 
-            // Track config events when fields transition from false → true and run side effects
-            handle_config_events(&deployment, &old_config, &new_config).await;
+    // Track config events when fields transition from false → true and run side effects
+    handle_config_events(&deployment, &old_config, &new_config).await;
 
-            ResponseJson(ApiResponse::success(new_config))
-        }
-        Err(e) => ResponseJson(ApiResponse::error(&format!("Failed to save config: {}", e))),
-    }
+    Ok(ResponseJson(ApiResponse::success(new_config)))
 }
 
 /// Track config events when fields transition from false → true
@@ -498,3 +532,17 @@ async fn check_agent_availability(
 
     ResponseJson(ApiResponse::success(info))
 }
+
+/// Check that the executable behind an `EXECUTOR[:VARIANT]` profile is
+/// installed, and report its version when it has one.
+async fn validate_executor_profile(
+    Path(profile_id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<ProfileValidation>>, ApiError> {
+    let profile_id = profile_id
+        .parse::<ExecutorProfileId>()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let validation = ExecutorConfigs::get_cached()
+        .validate_profile(&profile_id)
+        .await;
+    Ok(ResponseJson(ApiResponse::success(validation)))
+}
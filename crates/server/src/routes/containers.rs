@@ -1,16 +1,26 @@
+use std::collections::HashMap;
+
 use axum::{
     Router,
-    extract::{Query, State},
+    extract::{Path, Query, State},
     response::Json as ResponseJson,
     routing::get,
 };
-use db::models::workspace::{Workspace, WorkspaceContext};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    workspace::{Workspace, WorkspaceContext, WorkspaceError},
+};
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::container::{ContainerService, ResourceUsage, RunningProcess};
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext},
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ContainerQuery {
@@ -63,6 +73,119 @@ pub async fn get_context(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RunningQuery {
+    pub workspace_id: Option<Uuid>,
+}
+
+/// Running processes the caller may see, optionally limited to one workspace.
+/// In K8s mode only processes in workspaces owned by the user are returned.
+async fn visible_running_processes(
+    deployment: &DeploymentImpl,
+    user_ctx: Option<&UserContext>,
+    workspace_id: Option<Uuid>,
+) -> Result<Vec<RunningProcess>, ApiError> {
+    let pool = &deployment.db().pool;
+    if let (Some(ctx), Some(workspace_id)) = (user_ctx, workspace_id) {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, workspace_id).await?,
+            "workspace",
+        )?;
+    }
+
+    let mut owned: HashMap<Uuid, bool> = HashMap::new();
+    let mut visible = Vec::new();
+    for process in deployment.container().list_running().await {
+        if workspace_id.is_some_and(|id| id != process.workspace_id) {
+            continue;
+        }
+        if let Some(ctx) = user_ctx {
+            let is_owner = match owned.get(&process.workspace_id) {
+                Some(is_owner) => *is_owner,
+                None => {
+                    let is_owner = Workspace::find_owner(pool, process.workspace_id).await?
+                        == Some(ctx.user_id);
+                    owned.insert(process.workspace_id, is_owner);
+                    is_owner
+                }
+            };
+            if !is_owner {
+                continue;
+            }
+        }
+        visible.push(process);
+    }
+    Ok(visible)
+}
+
+pub async fn list_running(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<RunningQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<RunningProcess>>>, ApiError> {
+    let running =
+        visible_running_processes(&deployment, user_ctx.as_ref(), query.workspace_id).await?;
+    Ok(ResponseJson(ApiResponse::success(running)))
+}
+
+/// Kill every running process the caller may see and return the ones stopped.
+pub async fn stop_running(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<RunningQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<RunningProcess>>>, ApiError> {
+    let running =
+        visible_running_processes(&deployment, user_ctx.as_ref(), query.workspace_id).await?;
+
+    let mut stopped = Vec::with_capacity(running.len());
+    for process in running {
+        let Some(execution) =
+            ExecutionProcess::find_by_id(&deployment.db().pool, process.process_id).await?
+        else {
+            continue;
+        };
+        if let Err(e) = deployment
+            .container()
+            .stop_execution(&execution, ExecutionProcessStatus::Killed)
+            .await
+        {
+            tracing::error!(
+                "Failed to stop running execution process {}: {:?}",
+                process.process_id,
+                e
+            );
+            continue;
+        }
+        stopped.push(process);
+    }
+
+    Ok(ResponseJson(ApiResponse::success(stopped)))
+}
+
+/// CPU and memory used by a workspace's running processes, sampled at most
+/// every few seconds.
+pub async fn get_resource_usage(
+    State(deployment): State<DeploymentImpl>,
+    Path(workspace_id): Path<Uuid>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<ResourceUsage>>, ApiError> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    if Workspace::find_by_id_and_user(&deployment.db().pool, workspace_id, user_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )));
+    }
+
+    let usage = deployment
+        .container()
+        .get_resource_usage(workspace_id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(usage)))
+}
+
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     Router::new()
         // NOTE: /containers/info is required by the VSCode extension (vibe-kanban-vscode)
@@ -70,4 +193,12 @@ pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         // Do not remove this endpoint without updating the extension.
         .route("/containers/info", get(get_container_info))
         .route("/containers/attempt-context", get(get_context))
+        .route(
+            "/containers/running",
+            get(list_running).delete(stop_running),
+        )
+        .route(
+            "/containers/{workspace_id}/resources",
+            get(get_resource_usage),
+        )
 }
@@ -1,28 +1,131 @@
+use std::time::Duration;
+
 use axum::{
     BoxError, Router,
-    extract::State,
+    extract::{Query, State},
     response::{
-        Sse,
+        IntoResponse, Json as ResponseJson, Response, Sse,
         sse::{Event, KeepAlive},
     },
     routing::get,
 };
 use deployment::Deployment;
 use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use utils::{
+    msg_store::{MsgStore, SequencedMsg},
+    response::ApiResponse,
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_POLL_TIMEOUT_MS: u64 = 30_000;
+const MAX_POLL_TIMEOUT_MS: u64 = 60_000;
+
+/// Long-poll parameters for `GET /api/events`. When neither is given the
+/// request is served as a server-sent event stream instead.
+#[derive(Debug, Deserialize)]
+pub struct EventsPollQuery {
+    /// Sequence number of the last event the client has seen; empty or `0`
+    /// starts from the oldest event still held
+    pub cursor: Option<String>,
+    /// How long to wait for an event after `cursor`, in milliseconds
+    pub timeout_ms: Option<u64>,
+}
 
-use crate::DeploymentImpl;
+#[derive(Debug, Default, Serialize)]
+pub struct MsgStoreStats {
+    pub entries: usize,
+    pub memory_estimate_bytes: usize,
+}
+
+impl MsgStoreStats {
+    fn add(&mut self, store: &MsgStore) {
+        self.entries += store.len();
+        self.memory_estimate_bytes += store.memory_estimate_bytes();
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventStoreStatsResponse {
+    /// The global event stream
+    pub events: MsgStoreStats,
+    /// All execution process log stores combined
+    pub execution_processes: MsgStoreStats,
+    pub execution_process_stores: usize,
+}
 
 pub async fn events(
     State(deployment): State<DeploymentImpl>,
-) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, BoxError>>>, axum::http::StatusCode>
-{
+    Query(query): Query<EventsPollQuery>,
+) -> Result<Response, ApiError> {
+    if query.cursor.is_some() || query.timeout_ms.is_some() {
+        return Ok(poll_events(&deployment, query).await?.into_response());
+    }
+
     // Ask the container service for a combined "history + live" stream
     let stream = deployment.stream_events().await;
-    Ok(Sse::new(stream.map_err(|e| -> BoxError { e.into() })).keep_alive(KeepAlive::default()))
+    Ok(Sse::new(stream.map_err(|e| -> BoxError { e.into() }))
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+/// Long-poll fallback for clients that cannot hold a WebSocket or SSE stream
+/// open. Returns the events after `cursor` as soon as there are any, or an
+/// empty list once `timeout_ms` has passed.
+async fn poll_events(
+    deployment: &DeploymentImpl,
+    query: EventsPollQuery,
+) -> Result<ResponseJson<ApiResponse<Vec<SequencedMsg>>>, ApiError> {
+    let cursor = match query.cursor.as_deref().map(str::trim) {
+        None | Some("") => 0,
+        Some(cursor) => cursor.parse::<u64>().map_err(|_| {
+            ApiError::BadRequest("cursor must be an event sequence number".to_string())
+        })?,
+    };
+    let timeout = Duration::from_millis(
+        query
+            .timeout_ms
+            .unwrap_or(DEFAULT_POLL_TIMEOUT_MS)
+            .min(MAX_POLL_TIMEOUT_MS),
+    );
+
+    let events = tokio::time::timeout(
+        timeout,
+        deployment.events().msg_store().wait_for_new_after(cursor),
+    )
+    .await
+    .unwrap_or_default();
+
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
+
+/// Report in-memory message store sizes. Only mounted in debug builds.
+pub async fn store_stats(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<EventStoreStatsResponse>> {
+    let mut events = MsgStoreStats::default();
+    events.add(deployment.events().msg_store());
+
+    let stores = deployment.container().msg_stores().read().await;
+    let mut execution_processes = MsgStoreStats::default();
+    for store in stores.values() {
+        execution_processes.add(store);
+    }
+
+    ResponseJson(ApiResponse::success(EventStoreStatsResponse {
+        events,
+        execution_processes,
+        execution_process_stores: stores.len(),
+    }))
 }
 
 pub fn router(_: &DeploymentImpl) -> Router<DeploymentImpl> {
-    let events_router = Router::new().route("/", get(events));
+    let mut events_router = Router::new().route("/", get(events));
+    if cfg!(debug_assertions) {
+        events_router = events_router.route("/store-stats", get(store_stats));
+    }
 
     Router::new().nest("/events", events_router)
 }
@@ -1,26 +1,41 @@
+use std::time::Duration;
+
 use anyhow;
 use axum::{
-    Extension, Router,
+    BoxError, Extension, Json, Router,
     extract::{
         Path, Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
+        ws::{Message, WebSocket, WebSocketUpgrade, rejection::WebSocketUpgradeRejection},
     },
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{
+        IntoResponse, Json as ResponseJson, Response, Sse,
+        sse::{Event, KeepAlive},
+    },
     routing::{get, post},
 };
+use chrono::{DateTime, Utc};
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_logs::{ExecutionProcessLogRow, ExecutionProcessLogs},
     execution_process_repo_state::ExecutionProcessRepoState,
+    session::Session,
 };
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt, stream::SplitSink};
+use serde::{Deserialize, Serialize};
 use services::services::container::ContainerService;
+use sqlx::SqlitePool;
+use tokio::sync::broadcast;
+use ts_rs::TS;
 use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext, load_execution_process_middleware},
+};
 
 #[derive(Debug, Deserialize)]
 pub struct SessionExecutionProcessQuery {
@@ -30,6 +45,36 @@ pub struct SessionExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+const DEFAULT_LOG_TAIL: i64 = 100;
+const MAX_LOG_TAIL: i64 = 1000;
+const LOG_FOLLOW_BATCH: i64 = 500;
+const LOG_FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Deserialize)]
+pub struct ExecutionProcessLogsQuery {
+    /// Number of most recent log rows to return (or replay before following)
+    pub tail: Option<i64>,
+    /// Return rows inserted after this sequence number instead of the tail
+    pub after_seq: Option<i64>,
+    /// If true, upgrade to a WebSocket and keep pushing rows as they are stored
+    #[serde(default)]
+    pub follow: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExecutionProcessLogStreamQuery {
+    /// Number of most recent log rows to replay before following
+    pub tail: Option<i64>,
+}
+
+/// Payload of a `log` event on the SSE log stream
+#[derive(Debug, Serialize)]
+pub struct LogStreamEntry {
+    pub seq: i64,
+    pub timestamp: DateTime<Utc>,
+    pub content: LogMsg,
+}
+
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(_deployment): State<DeploymentImpl>,
@@ -166,6 +211,232 @@ async fn handle_normalized_logs_ws(
     Ok(())
 }
 
+/// In K8s mode only the owner of a process's session may read its output.
+async fn ensure_process_owner(
+    pool: &SqlitePool,
+    execution_process: &ExecutionProcess,
+    user_ctx: Option<&UserContext>,
+) -> Result<(), ApiError> {
+    if let Some(ctx) = user_ctx {
+        let owner = Session::find_owner(pool, execution_process.session_id).await?;
+        ctx.ensure_owner(owner, "execution process")?;
+    }
+    Ok(())
+}
+
+/// Read persisted log rows for an execution process. Without `follow` the
+/// rows are returned as JSON; with `follow=true` the request must be a
+/// WebSocket upgrade and new rows are pushed until the process finishes.
+pub async fn get_execution_process_logs(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Query(query): Query<ExecutionProcessLogsQuery>,
+    ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Result<Response, ApiError> {
+    ensure_process_owner(&deployment.db().pool, &execution_process, user_ctx.as_ref()).await?;
+
+    let tail = query
+        .tail
+        .unwrap_or(DEFAULT_LOG_TAIL)
+        .clamp(1, MAX_LOG_TAIL);
+
+    if query.follow {
+        let ws = ws.map_err(|_| {
+            ApiError::BadRequest("follow=true requires a WebSocket upgrade".to_string())
+        })?;
+        let exec_id = execution_process.id;
+        return Ok(ws
+            .on_upgrade(move |socket| async move {
+                if let Err(e) = handle_process_logs_ws(socket, deployment, exec_id, tail).await {
+                    tracing::warn!("process logs WS closed: {}", e);
+                }
+            })
+            .into_response());
+    }
+
+    let pool = &deployment.db().pool;
+    let rows = match query.after_seq {
+        Some(after_seq) => {
+            ExecutionProcessLogs::find_paginated(pool, execution_process.id, Some(after_seq), tail)
+                .await?
+        }
+        None => ExecutionProcessLogs::find_tail(pool, execution_process.id, tail).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(rows)).into_response())
+}
+
+async fn handle_process_logs_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    exec_id: Uuid,
+    tail: i64,
+) -> anyhow::Result<()> {
+    let pool = deployment.db().pool.clone();
+
+    // Split socket into sender and receiver
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    let mut batches = std::pin::pin!(follow_log_rows(pool, exec_id, tail));
+    while let Some(rows) = batches.try_next().await? {
+        send_log_rows(&mut sender, &rows).await?;
+    }
+
+    sender
+        .send(LogMsg::Finished.to_ws_message_unchecked())
+        .await?;
+    Ok(())
+}
+
+/// Stream persisted log rows in batches: the last `tail` rows first, then
+/// newly stored rows until the process is no longer running.
+fn follow_log_rows(
+    pool: SqlitePool,
+    exec_id: Uuid,
+    tail: i64,
+) -> impl Stream<Item = anyhow::Result<Vec<ExecutionProcessLogRow>>> + Send + 'static {
+    // State: `None` before the backlog is sent, then the last sequence number sent
+    futures_util::stream::try_unfold(None::<Option<i64>>, move |state| {
+        let pool = pool.clone();
+        async move {
+            let Some(mut last_seq) = state else {
+                let backlog = ExecutionProcessLogs::find_tail(&pool, exec_id, tail).await?;
+                let last_seq = backlog.last().map(|row| row.seq);
+                return Ok(Some((backlog, Some(last_seq))));
+            };
+
+            loop {
+                // Check status before reading so rows written just before completion are not missed
+                let running = ExecutionProcess::find_by_id(&pool, exec_id)
+                    .await?
+                    .is_some_and(|process| process.status == ExecutionProcessStatus::Running);
+                let rows = ExecutionProcessLogs::find_paginated(
+                    &pool,
+                    exec_id,
+                    last_seq,
+                    LOG_FOLLOW_BATCH,
+                )
+                .await?;
+
+                if rows.is_empty() {
+                    if !running {
+                        return Ok(None);
+                    }
+                    tokio::time::sleep(LOG_FOLLOW_POLL_INTERVAL).await;
+                    continue;
+                }
+
+                last_seq = rows.last().map(|row| row.seq).or(last_seq);
+                return Ok(Some((rows, Some(last_seq))));
+            }
+        }
+    })
+}
+
+/// Send each JSONL line of `rows` as a log message
+async fn send_log_rows(
+    sender: &mut SplitSink<WebSocket, Message>,
+    rows: &[ExecutionProcessLogRow],
+) -> anyhow::Result<()> {
+    for row in rows {
+        for line in row.logs.lines().filter(|line| !line.trim().is_empty()) {
+            let msg: LogMsg = serde_json::from_str(line)?;
+            sender.send(msg.to_ws_message_unchecked()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Server-sent events alternative to `/logs?follow=true` for clients whose
+/// proxies block WebSocket upgrades. Each stored log message is sent as a
+/// `log` event carrying a [`LogStreamEntry`]; a `finished` event follows once
+/// the process has stopped and every row has been sent.
+pub async fn stream_execution_process_logs_sse(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Query(query): Query<ExecutionProcessLogStreamQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    ensure_process_owner(&deployment.db().pool, &execution_process, user_ctx.as_ref()).await?;
+
+    let tail = query
+        .tail
+        .unwrap_or(DEFAULT_LOG_TAIL)
+        .clamp(1, MAX_LOG_TAIL);
+    let pool = deployment.db().pool.clone();
+
+    let events = follow_log_rows(pool, execution_process.id, tail)
+        .and_then(|rows| async move { log_events(&rows) })
+        .map_ok(|events| futures_util::stream::iter(events.into_iter().map(Ok)))
+        .try_flatten()
+        .chain(futures_util::stream::once(async {
+            Ok(LogMsg::Finished.to_sse_event())
+        }))
+        .map_err(|e: anyhow::Error| -> BoxError { e.into() });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Live text of the agent's reply while the process runs. Each delta is sent
+/// as a `token` event carrying an
+/// [`AgentToken`](services::services::agent_tokens::AgentToken); a `finished`
+/// event follows once the process has stopped, or straight away if it is not
+/// a running coding agent.
+pub async fn stream_agent_tokens_sse(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    ensure_process_owner(&deployment.db().pool, &execution_process, user_ctx.as_ref()).await?;
+
+    let receiver = deployment
+        .container()
+        .subscribe_agent_tokens(&execution_process.id)
+        .await;
+
+    let tokens = futures_util::stream::unfold(receiver, |receiver| async move {
+        let mut receiver = receiver?;
+        loop {
+            match receiver.recv().await {
+                Ok(token) => return Some((token, Some(receiver))),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "Agent token subscriber lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let events = tokens
+        .map(|token| -> Result<Event, BoxError> {
+            Ok(Event::default().event("token").json_data(&token)?)
+        })
+        .chain(futures_util::stream::once(async {
+            Ok(LogMsg::Finished.to_sse_event())
+        }));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Build a `log` event for each JSONL line of `rows`
+fn log_events(rows: &[ExecutionProcessLogRow]) -> anyhow::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    for row in rows {
+        for line in row.logs.lines().filter(|line| !line.trim().is_empty()) {
+            let entry = LogStreamEntry {
+                seq: row.seq,
+                timestamp: row.inserted_at,
+                content: serde_json::from_str(line)?,
+            };
+            events.push(Event::default().event("log").json_data(&entry)?);
+        }
+    }
+    Ok(events)
+}
+
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -178,6 +449,51 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct CancelExecutionProcessRequest {
+    pub reason: Option<String>,
+}
+
+pub async fn cancel_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<CancelExecutionProcessRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let (workspace, session) = execution_process
+        .parent_workspace_and_session(pool)
+        .await?
+        .ok_or(ApiError::ExecutionProcess(
+            ExecutionProcessError::ExecutionProcessNotFound,
+        ))?;
+
+    // In K8s mode only the session owner may cancel its processes
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(Session::find_owner(pool, session.id).await?, "session")?;
+    }
+
+    deployment
+        .container()
+        .stop_process(&workspace, execution_process.id)
+        .await?;
+
+    let reason = payload
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    ExecutionProcess::cancel(pool, execution_process.id, reason).await?;
+
+    let process = ExecutionProcess::find_by_id(pool, execution_process.id)
+        .await?
+        .ok_or(ApiError::ExecutionProcess(
+            ExecutionProcessError::ExecutionProcessNotFound,
+        ))?;
+    Ok(ResponseJson(ApiResponse::success(process)))
+}
+
 pub async fn stream_execution_processes_by_session_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -247,7 +563,11 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let workspace_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
+        .route("/cancel", post(cancel_execution_process))
         .route("/repo-states", get(get_execution_process_repo_states))
+        .route("/logs", get(get_execution_process_logs))
+        .route("/logs/stream", get(stream_execution_process_logs_sse))
+        .route("/tokens/stream", get(stream_agent_tokens_sse))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
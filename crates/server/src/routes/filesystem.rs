@@ -1,12 +1,17 @@
+use std::path::PathBuf;
+
 use axum::{
-    Router,
+    Json, Router,
     extract::{Query, State},
     response::Json as ResponseJson,
-    routing::get,
+    routing::{get, post},
 };
 use deployment::Deployment;
-use serde::Deserialize;
-use services::services::filesystem::{DirectoryEntry, DirectoryListResponse, FilesystemError};
+use serde::{Deserialize, Serialize};
+use services::services::filesystem::{
+    DirectoryEntry, DirectoryListResponse, FileMetadata, FilesystemError,
+};
+use ts_rs::TS;
 use utils::response::ApiResponse;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::OptionalUserContext};
@@ -49,6 +54,11 @@ pub async fn list_directory(
             );
             Err(ApiError::Unauthorized)
         }
+        Err(
+            e @ (FilesystemError::FileDoesNotExist
+            | FilesystemError::PathIsNotFile
+            | FilesystemError::NotInGitRepo(_)),
+        ) => Ok(ResponseJson(ApiResponse::error(&e.to_string()))),
         Err(FilesystemError::Io(e)) => {
             tracing::error!("Failed to read directory: {}", e);
             Ok(ResponseJson(ApiResponse::error(&format!(
@@ -102,6 +112,11 @@ pub async fn list_git_repos(
             );
             Err(ApiError::Unauthorized)
         }
+        Err(
+            e @ (FilesystemError::FileDoesNotExist
+            | FilesystemError::PathIsNotFile
+            | FilesystemError::NotInGitRepo(_)),
+        ) => Ok(ResponseJson(ApiResponse::error(&e.to_string()))),
         Err(FilesystemError::Io(e)) => {
             tracing::error!("Failed to read directory: {}", e);
             Ok(ResponseJson(ApiResponse::error(&format!(
@@ -112,8 +127,154 @@ pub async fn list_git_repos(
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct ReadFileRequest {
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ReadFileResponse {
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct WriteFileRequest {
+    pub path: String,
+    pub content: String,
+}
+
+pub async fn read_file(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<ReadFileRequest>,
+) -> Result<ResponseJson<ApiResponse<ReadFileResponse>>, ApiError> {
+    let user_id = user_ctx.as_ref().map(|ctx| ctx.user_id);
+    let path = PathBuf::from(&payload.path);
+
+    match deployment
+        .filesystem()
+        .read_file(user_id.as_ref(), &path)
+        .await
+    {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(content) => Ok(ResponseJson(ApiResponse::success(ReadFileResponse {
+                path: payload.path,
+                content,
+            }))),
+            Err(_) => Ok(ResponseJson(ApiResponse::error(
+                "File is not valid UTF-8 text",
+            ))),
+        },
+        Err(FilesystemError::Unauthorized(msg)) => {
+            tracing::warn!(
+                action = "unauthorized_filesystem_access",
+                user_id = ?user_id,
+                path = %payload.path,
+                security_event = true,
+                "Unauthorized file read attempt: {}", msg
+            );
+            Err(ApiError::Unauthorized)
+        }
+        Err(FilesystemError::Io(e)) => {
+            tracing::error!("Failed to read file: {}", e);
+            Ok(ResponseJson(ApiResponse::error(&format!(
+                "Failed to read file: {}",
+                e
+            ))))
+        }
+        Err(e) => Ok(ResponseJson(ApiResponse::error(&e.to_string()))),
+    }
+}
+
+pub async fn write_file(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<WriteFileRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let user_id = user_ctx.as_ref().map(|ctx| ctx.user_id);
+    let path = PathBuf::from(&payload.path);
+
+    match deployment
+        .filesystem()
+        .write_file(user_id.as_ref(), &path, payload.content.as_bytes())
+        .await
+    {
+        Ok(()) => Ok(ResponseJson(ApiResponse::success(()))),
+        Err(FilesystemError::Unauthorized(msg)) => {
+            tracing::warn!(
+                action = "unauthorized_filesystem_access",
+                user_id = ?user_id,
+                path = %payload.path,
+                security_event = true,
+                "Unauthorized file write attempt: {}", msg
+            );
+            Err(ApiError::Unauthorized)
+        }
+        Err(e @ FilesystemError::NotInGitRepo(_)) => {
+            tracing::warn!(
+                user_id = ?user_id,
+                path = %payload.path,
+                "Rejected file write outside a git repository"
+            );
+            Err(ApiError::Forbidden(e.to_string()))
+        }
+        Err(FilesystemError::Io(e)) => {
+            tracing::error!("Failed to write file: {}", e);
+            Ok(ResponseJson(ApiResponse::error(&format!(
+                "Failed to write file: {}",
+                e
+            ))))
+        }
+        Err(e) => Ok(ResponseJson(ApiResponse::error(&e.to_string()))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileMetadataQuery {
+    path: String,
+}
+
+pub async fn get_file_metadata(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<FileMetadataQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<FileMetadata>>, ApiError> {
+    let user_id = user_ctx.as_ref().map(|ctx| ctx.user_id);
+    let path = PathBuf::from(&query.path);
+
+    match deployment
+        .filesystem()
+        .get_file_metadata(user_id.as_ref(), &path)
+        .await
+    {
+        Ok(metadata) => Ok(ResponseJson(ApiResponse::success(metadata))),
+        Err(FilesystemError::Unauthorized(msg)) => {
+            tracing::warn!(
+                action = "unauthorized_filesystem_access",
+                user_id = ?user_id,
+                path = %query.path,
+                security_event = true,
+                "Unauthorized file metadata access attempt: {}", msg
+            );
+            Err(ApiError::Unauthorized)
+        }
+        Err(FilesystemError::Io(e)) => {
+            tracing::error!("Failed to read file metadata: {}", e);
+            Ok(ResponseJson(ApiResponse::error(&format!(
+                "Failed to read file metadata: {}",
+                e
+            ))))
+        }
+        Err(e) => Ok(ResponseJson(ApiResponse::error(&e.to_string()))),
+    }
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/filesystem/directory", get(list_directory))
         .route("/filesystem/git-repos", get(list_git_repos))
+        .route("/filesystem/read", post(read_file))
+        .route("/filesystem/write", post(write_file))
+        .route("/filesystem/metadata", get(get_file_metadata))
 }
@@ -1,6 +1,75 @@
-use axum::response::Json;
+use axum::{extract::State, http::StatusCode, response::Json};
+use db::DeploymentMode;
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
 use utils::response::ApiResponse;
 
-pub async fn health_check() -> Json<ApiResponse<String>> {
-    Json(ApiResponse::success("OK".to_string()))
+use crate::DeploymentImpl;
+
+/// Response from GET /api/health - deployment mode, database reachability, pool
+/// usage and event stream load
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct HealthResponse {
+    pub db_backend: String,
+    pub db_healthy: bool,
+    pub db_active_connections: u32,
+    pub db_idle_connections: u32,
+    pub db_max_connections: u32,
+    /// Event stream subscribers (WebSocket clients) currently connected
+    pub event_subscribers: usize,
+    pub deployment_mode: DeploymentMode,
+    /// Whether API requests need a bearer token, so clients know to show a login screen
+    pub auth_required: bool,
+}
+
+/// Report database health for liveness/readiness probes.
+///
+/// Returns 200 when the database answers a trivial query and 503 otherwise
+/// (or while the PostgreSQL health monitor has its last check marked failed),
+/// with pool statistics in the body either way so probes can spot pool
+/// exhaustion before requests start timing out.
+pub async fn health_check(
+    State(deployment): State<DeploymentImpl>,
+) -> (StatusCode, Json<ApiResponse<HealthResponse>>) {
+    let backend = deployment.db_backend();
+    // Skip the live query while the background monitor already reports an outage
+    let db_healthy = if backend.last_health_check() == Some(false) {
+        false
+    } else {
+        match backend.health_check().await {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(
+                    db_backend = backend.name(),
+                    "Database health check failed: {}",
+                    e
+                );
+                false
+            }
+        }
+    };
+    let (active, idle, max) = backend.pool_stats();
+    let mode = deployment.mode();
+
+    let response = HealthResponse {
+        db_backend: backend.name().to_string(),
+        db_healthy,
+        db_active_connections: active,
+        db_idle_connections: idle,
+        db_max_connections: max,
+        event_subscribers: deployment.events().subscriber_count(),
+        deployment_mode: mode,
+        auth_required: mode.is_multi_user(),
+    };
+
+    if db_healthy {
+        (StatusCode::OK, Json(ApiResponse::success(response)))
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::error_with_data(response)),
+        )
+    }
 }
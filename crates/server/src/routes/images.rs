@@ -6,7 +6,7 @@ use axum::{
     extract::{DefaultBodyLimit, Multipart, Path, Query, State},
     http::{StatusCode, header},
     response::{Json as ResponseJson, Response},
-    routing::{delete, get, post},
+    routing::{get, post},
 };
 use chrono::{DateTime, Utc};
 use db::models::{
@@ -23,7 +23,11 @@ use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ImageResponse {
@@ -172,13 +176,62 @@ pub async fn serve_image(
     Ok(response)
 }
 
+/// Get the metadata of an image by ID. In K8s mode the image must be attached
+/// to a task the user owns.
+pub async fn get_image(
+    Path(image_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<ImageResponse>>, ApiError> {
+    let image = deployment
+        .image()
+        .get_image(image_id)
+        .await?
+        .ok_or(ApiError::Image(ImageError::NotFound))?;
+    ensure_image_owner(&deployment, image_id, user_ctx.as_ref()).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        ImageResponse::from_image(image),
+    )))
+}
+
+/// Delete an image and its file. In K8s mode the image must be attached to a
+/// task the user owns.
 pub async fn delete_image(
     Path(image_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<StatusCode, ApiError> {
     let image_service = deployment.image();
+    image_service
+        .get_image(image_id)
+        .await?
+        .ok_or(ApiError::Image(ImageError::NotFound))?;
+    ensure_image_owner(&deployment, image_id, user_ctx.as_ref()).await?;
+
     image_service.delete_image(image_id).await?;
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// In K8s mode, reject access to an image that is not attached to one of the
+/// user's tasks.
+async fn ensure_image_owner(
+    deployment: &DeploymentImpl,
+    image_id: Uuid,
+    user_ctx: Option<&UserContext>,
+) -> Result<(), ApiError> {
+    if let Some(ctx) = user_ctx
+        && !TaskImage::is_referenced_by_user(&deployment.db().pool, image_id, ctx.user_id).await?
+    {
+        tracing::warn!(
+            user_id = %ctx.user_id,
+            image_id = %image_id,
+            "User attempted to access an image not attached to their tasks"
+        );
+        return Err(ApiError::Forbidden(
+            "You do not have access to this image".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 pub async fn get_task_images(
@@ -260,7 +313,7 @@ pub fn routes() -> Router<DeploymentImpl> {
             post(upload_image).layer(DefaultBodyLimit::max(20 * 1024 * 1024)), // 20MB limit
         )
         .route("/{id}/file", get(serve_image))
-        .route("/{id}", delete(delete_image))
+        .route("/{id}", get(get_image).delete(delete_image))
         .route("/task/{task_id}", get(get_task_images))
         .route("/task/{task_id}/metadata", get(get_task_image_metadata))
         .route(
@@ -1,14 +1,17 @@
+use std::net::SocketAddr;
+
 use axum::{
-    Router,
-    middleware as axum_middleware,
-    routing::{IntoMakeService, get},
+    Router, extract::connect_info::IntoMakeServiceWithConnectInfo, middleware as axum_middleware,
+    routing::get,
 };
 use db::DeploymentMode;
 use tower_http::validate_request::ValidateRequestHeaderLayer;
 
 use crate::{DeploymentImpl, middleware};
 
+pub mod admin;
 pub mod approvals;
+pub mod auth;
 pub mod config;
 pub mod containers;
 pub mod filesystem;
@@ -20,6 +23,7 @@ pub mod health;
 pub mod images;
 pub mod oauth;
 pub mod organizations;
+pub mod pagination;
 pub mod projects;
 pub mod repo;
 pub mod scratch;
@@ -28,8 +32,9 @@ pub mod tags;
 pub mod task_attempts;
 pub mod tasks;
 pub mod terminal;
+pub mod workspaces;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+pub fn router(deployment: DeploymentImpl) -> IntoMakeServiceWithConnectInfo<Router, SocketAddr> {
     let mode = DeploymentMode::detect();
 
     // Routes that require authentication in K8s mode
@@ -41,7 +46,7 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
-        .merge(oauth::router())
+        .merge(oauth::router().layer(middleware::RateLimitLayer::from_env()))
         .merge(organizations::router())
         .merge(filesystem::router())
         .merge(repo::router())
@@ -50,35 +55,51 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(scratch::router(&deployment))
         .merge(sessions::router(&deployment))
         .merge(terminal::router())
+        .merge(workspaces::router(&deployment))
         .nest("/images", images::routes());
 
     // Apply auth middleware conditionally based on deployment mode
-    let protected_routes = if mode.is_kubernetes() {
+    let protected_routes = if mode.is_multi_user() {
         tracing::info!(
-            mode = "kubernetes",
+            mode = mode.as_str(),
             "Applying authentication middleware to protected routes"
         );
         protected_routes.layer(axum_middleware::from_fn(middleware::require_user))
     } else {
         tracing::info!(
-            mode = "desktop",
+            mode = mode.as_str(),
             "Skipping authentication middleware for desktop mode"
         );
         protected_routes
     };
 
-    // Health check is always public (unprotected)
+    // Health check and token refresh are always public (unprotected); admin
+    // routes authorize with their own shared secret
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
-        .merge(protected_routes)
-        .layer(ValidateRequestHeaderLayer::custom(
+        .merge(auth::router().layer(middleware::RateLimitLayer::from_env()))
+        .merge(admin::router().layer(middleware::RateLimitLayer::from_env()))
+        .merge(protected_routes);
+
+    // The K8s frontend may be served from another origin, so allow configured
+    // cross-origin requests there; desktop mode only accepts same-origin requests
+    let base_routes = if mode.is_kubernetes() {
+        base_routes.layer(middleware::CorsConfig::from_env().layer())
+    } else {
+        base_routes.layer(ValidateRequestHeaderLayer::custom(
             middleware::validate_origin,
         ))
-        .with_state(deployment);
+    }
+    .with_state(deployment);
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
-        .into_make_service()
+        .layer(middleware::compression_layer())
+        // Outermost, so every request (including rejected ones) gets a request ID and span
+        .layer(middleware::trace_layer())
+        .layer(axum_middleware::from_fn(middleware::trace_request))
+        // The connection address identifies clients in audit logs
+        .into_make_service_with_connect_info::<SocketAddr>()
 }
@@ -13,7 +13,7 @@ use services::services::{config::save_config_to_file, oauth_credentials::Credent
 use sha2::{Digest, Sha256};
 use ts_rs::TS;
 use utils::{
-    api::oauth::{HandoffInitRequest, HandoffRedeemRequest, StatusResponse},
+    api::oauth::{HandoffInitRequest, HandoffRedeemRequest, ProfileResponse, StatusResponse},
     assets::config_path,
     jwt::extract_expiration,
     response::ApiResponse,
@@ -42,6 +42,7 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/auth/handoff/init", post(handoff_init))
         .route("/auth/handoff/complete", get(handoff_complete))
         .route("/auth/logout", post(logout))
+        .route("/auth/refresh-profile", post(refresh_profile))
         .route("/auth/status", get(status))
         .route("/auth/token", get(get_token))
         .route("/auth/user", get(get_current_user))
@@ -149,7 +150,7 @@ async fn handoff_complete(
 
     deployment
         .auth_context()
-        .save_credentials(&credentials)
+        .save_provider_credentials(&provider, &credentials)
         .await
         .map_err(|e| {
             tracing::error!(?e, "failed to save credentials");
@@ -229,6 +230,18 @@ async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode,
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Re-fetch the profile from the remote, bypassing the cache
+async fn refresh_profile(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProfileResponse>>, ApiError> {
+    let remote_client = deployment.remote_client()?;
+    let profile = deployment
+        .auth_context()
+        .refresh_profile(&remote_client)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(profile)))
+}
+
 async fn status(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<StatusResponse>>, ApiError> {
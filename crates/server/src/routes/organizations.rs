@@ -2,7 +2,7 @@ use axum::{
     Router,
     extract::{Json, Path, State},
     http::StatusCode,
-    response::Json as ResponseJson,
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{delete, get, patch, post},
 };
 use deployment::Deployment;
@@ -23,6 +23,9 @@ use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
+/// Marks responses served locally because no remote API is configured
+const OFFLINE_MODE_HEADER: &str = "x-offline-mode";
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/organizations", get(list_organizations))
@@ -67,14 +70,25 @@ async fn list_organization_projects(
     Ok(ResponseJson(ApiResponse::success(response.projects)))
 }
 
+/// List the caller's organizations. Without a remote API (desktop offline
+/// mode) this is an empty list flagged with `X-Offline-Mode: true`.
 async fn list_organizations(
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<ListOrganizationsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+) -> Result<Response, ApiError> {
+    let Ok(client) = deployment.remote_client() else {
+        let empty = ListOrganizationsResponse {
+            organizations: vec![],
+        };
+        return Ok((
+            [(OFFLINE_MODE_HEADER, "true")],
+            ResponseJson(ApiResponse::success(empty)),
+        )
+            .into_response());
+    };
 
     let response = client.list_organizations().await?;
 
-    Ok(ResponseJson(ApiResponse::success(response)))
+    Ok(ResponseJson(ApiResponse::success(response)).into_response())
 }
 
 async fn get_organization(
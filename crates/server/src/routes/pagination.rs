@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Default page size when `limit` is not given.
+pub const DEFAULT_PAGE_LIMIT: u32 = 50;
+
+/// Largest page size a client may request.
+pub const MAX_PAGE_LIMIT: u32 = 200;
+
+/// Query parameters for cursor-paginated listings.
+///
+/// `after` is the `next_cursor` returned by the previous page.
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct PaginationQuery {
+    pub after: Option<Uuid>,
+    pub limit: Option<u32>,
+}
+
+impl PaginationQuery {
+    /// The requested page size, defaulted and clamped to `1..=MAX_PAGE_LIMIT`.
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_defaults() {
+        assert_eq!(PaginationQuery::default().limit(), DEFAULT_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn test_limit_is_clamped() {
+        let query = |limit| PaginationQuery {
+            after: None,
+            limit: Some(limit),
+        };
+        assert_eq!(query(0).limit(), 1);
+        assert_eq!(query(25).limit(), 25);
+        assert_eq!(query(10_000).limit(), MAX_PAGE_LIMIT);
+    }
+}
@@ -1,37 +1,50 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use anyhow;
 use axum::{
     Extension, Json, Router,
+    body::Body,
     extract::{
-        Path, Query, State,
+        DefaultBodyLimit, Multipart, Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
 };
+use chrono::{DateTime, Utc};
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo},
     repo::Repo,
+    session::Session,
+    task::{Task, TaskStatus},
+    workspace::Workspace,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use services::services::{
-    file_search::SearchQuery, project::ProjectServiceError,
+    file_search::SearchQuery,
+    project::ProjectServiceError,
     remote_client::CreateRemoteProjectPayload,
+    task_import::{TaskImportFormat, TaskImportRowError, parse_task_import},
 };
+use tokio::sync::mpsc;
 use ts_rs::TS;
 use utils::{
     api::projects::{RemoteProject, RemoteProjectMembersResponse},
-    response::ApiResponse,
+    response::{ApiResponse, PaginatedResponse},
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::{OptionalUserContext, load_project_middleware}};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, load_project_middleware},
+    routes::pagination::PaginationQuery,
+};
 
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
@@ -44,17 +57,89 @@ pub struct CreateRemoteProjectRequest {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct DuplicateProjectRequest {
+    pub name: String,
+    /// Also copy each task's workspaces
+    #[serde(default)]
+    pub deep: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectQuery {
+    /// Also list archived projects
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Response from GET /api/projects/{id}/stats - counts for the project summary card
+#[derive(Debug, Serialize, TS)]
+pub struct ProjectStatsResponse {
+    /// Non-deleted tasks in the project
+    pub total: u64,
+    /// Task counts per status; statuses without tasks are omitted
+    pub by_status: HashMap<TaskStatus, u64>,
+    pub workspaces: u64,
+    pub sessions: u64,
+}
+
+/// Response from GET /api/projects/{id}/sync-status
+#[derive(Debug, Serialize, TS)]
+pub struct ProjectSyncStatus {
+    /// When the project last pulled metadata from its remote project
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub is_linked: bool,
+    /// Remote project name seen at the last sync
+    pub remote_project_name: Option<String>,
+}
+
+/// Response from POST /api/projects/{id}/import
+#[derive(Debug, Serialize, TS)]
+pub struct ImportTasksResponse {
+    pub imported: u32,
+    /// Rows that were skipped, numbered from 1 excluding the CSV header
+    pub errors: Vec<TaskImportRowError>,
+}
+
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
     OptionalUserContext(user_ctx): OptionalUserContext,
-) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
-    // Log user context for tracing in multi-user mode
-    if let Some(ref ctx) = user_ctx {
-        tracing::debug!(user_id = %ctx.user_id, "Fetching projects for user");
+    Query(query): Query<ProjectQuery>,
+    Query(page): Query<PaginationQuery>,
+) -> Result<ResponseJson<ApiResponse<PaginatedResponse<Project>>>, ApiError> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    if let Some(user_id) = user_id {
+        tracing::debug!(user_id = %user_id, "Fetching projects for user");
     }
-    // TODO: In K8s mode, filter projects by user_id once DB schema supports it
-    let projects = Project::find_all(&deployment.db().pool).await?;
-    Ok(ResponseJson(ApiResponse::success(projects)))
+
+    let limit = page.limit();
+    // Fetch one extra row to learn whether another page exists
+    let projects = Project::find_paginated(
+        &deployment.db().pool,
+        user_id,
+        page.after,
+        i64::from(limit) + 1,
+        query.include_archived,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        PaginatedResponse::from_rows(projects, limit as usize, |p| p.id),
+    )))
 }
 
 pub async fn stream_projects_ws(
@@ -105,6 +190,25 @@ pub async fn get_project(
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+pub async fn get_project_stats(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<ProjectStatsResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let by_status =
+        Task::count_by_status(pool, Some(project.id), user_ctx.map(|ctx| ctx.user_id)).await?;
+    let response = ProjectStatsResponse {
+        total: by_status.values().sum(),
+        by_status,
+        workspaces: Workspace::count_by_project_id(pool, project.id).await?,
+        sessions: Session::count_by_project_id(pool, project.id).await?,
+    };
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 pub async fn link_project_to_existing_remote(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -169,6 +273,27 @@ pub async fn get_remote_project_by_id(
     Ok(ResponseJson(ApiResponse::success(remote_project)))
 }
 
+/// Find the local project linked to a remote project, for integrations that
+/// only know the `remote_project_id` (e.g. from remote service webhooks).
+pub async fn get_local_project_for_remote(
+    State(deployment): State<DeploymentImpl>,
+    Path(remote_project_id): Path<Uuid>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_remote_project_id(pool, remote_project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    // In K8s mode only the project owner may look it up
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let project = Project::find_by_id_and_user(pool, project.id, user_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
 pub async fn get_project_remote_members(
     State(deployment): State<DeploymentImpl>,
     Extension(project): Extension<Project>,
@@ -193,6 +318,46 @@ pub async fn get_project_remote_members(
     )))
 }
 
+/// Pull the linked remote project's metadata into the local project. Remote
+/// projects only carry a name, which replaces the local one.
+pub async fn sync_remote_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let remote_project_id = project.remote_project_id.ok_or_else(|| {
+        ApiError::Conflict("Project is not linked to a remote project".to_string())
+    })?;
+
+    let client = deployment.remote_client()?;
+    let remote_project = client.get_project(remote_project_id).await?;
+
+    let updated_project = Project::apply_remote_sync(pool, project.id, &remote_project.name)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(updated_project)))
+}
+
+/// Report the last remote sync of a project without calling the remote API.
+pub async fn get_project_sync_status(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectSyncStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let sync = Project::find_remote_sync(pool, project.id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(ProjectSyncStatus {
+        last_synced_at: sync.last_synced_at,
+        is_linked: project.remote_project_id.is_some(),
+        remote_project_name: sync.remote_project_name,
+    })))
+}
+
 async fn apply_remote_project_link(
     deployment: &DeploymentImpl,
     project: Project,
@@ -234,13 +399,16 @@ pub async fn create_project(
     }
     let repo_count = payload.repositories.len();
 
-    // TODO: In K8s mode, associate project with user_id once DB schema supports it
     match deployment
         .project()
         .create_project(&deployment.db().pool, deployment.repo(), payload)
         .await
     {
         Ok(project) => {
+            if let Some(ref ctx) = user_ctx {
+                Project::set_user_id(&deployment.db().pool, project.id, Some(ctx.user_id)).await?;
+            }
+
             // Track project creation event
             deployment
                 .track_if_analytics_allowed(
@@ -296,38 +464,287 @@ pub async fn delete_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
     OptionalUserContext(user_ctx): OptionalUserContext,
-) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     // Log user context for tracing in multi-user mode
     if let Some(ref ctx) = user_ctx {
         tracing::debug!(user_id = %ctx.user_id, project_id = %project.id, "Deleting project for user");
     }
-    // TODO: In K8s mode, verify user owns the project before deletion
-    match deployment
+    let pool = &deployment.db().pool;
+
+    let rows_affected = deployment
         .project()
-        .delete_project(&deployment.db().pool, project.id)
+        .delete_project(pool, project.id)
         .await
-    {
-        Ok(rows_affected) => {
-            if rows_affected == 0 {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                deployment
-                    .track_if_analytics_allowed(
-                        "project_deleted",
-                        serde_json::json!({
-                            "project_id": project.id.to_string(),
-                        }),
-                    )
-                    .await;
-
-                Ok(ResponseJson(ApiResponse::success(())))
+        .inspect_err(|e| tracing::error!("Failed to delete project: {}", e))?;
+    if rows_affected == 0 {
+        return Err(ProjectError::ProjectNotFound.into());
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_deleted",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn archive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let project = Project::archive(pool, project.id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_archived",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+pub async fn unarchive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let project = Project::unarchive(pool, project.id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_unarchived",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Stream the project's tasks as NDJSON (one task per line) or CSV
+pub async fn export_project_tasks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let pool = deployment.db().pool.clone();
+
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+
+    // The row stream borrows the pool, so a task owns it and feeds the body
+    // through a bounded channel; a slow client holds back the query
+    let (tx, rx) = mpsc::channel::<Result<String, sqlx::Error>>(EXPORT_CHANNEL_CAPACITY);
+    let format = query.format;
+    let project_id = project.id;
+    tokio::spawn(async move {
+        if let ExportFormat::Csv = format
+            && tx.send(Ok(csv_row(&TASK_CSV_HEADER))).await.is_err()
+        {
+            return;
+        }
+
+        let mut tasks = Task::stream_by_project(&pool, project_id, user_id);
+        while let Some(task) = tasks.next().await {
+            let line = task.map(|task| match format {
+                ExportFormat::Ndjson => {
+                    let mut line = serde_json::to_string(&task).unwrap_or_default();
+                    line.push('\n');
+                    line
+                }
+                ExportFormat::Csv => csv_row(&task_csv_fields(&task)),
+            });
+            if let Err(e) = &line {
+                tracing::error!("Failed to export tasks for project {}: {}", project_id, e);
+            }
+            let failed = line.is_err();
+            if tx.send(line).await.is_err() || failed {
+                break;
             }
         }
-        Err(e) => {
-            tracing::error!("Failed to delete project: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+    });
+
+    let body = Body::from_stream(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|line| (line, rx))
+    }));
+    let (content_type, extension) = match format {
+        ExportFormat::Ndjson => ("application/x-ndjson", "ndjson"),
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_tasks_exported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "format": extension,
+            }),
+        )
+        .await;
+
+    let disposition = format!(
+        "attachment; filename=\"project-{}-tasks.{}\"",
+        project.id, extension
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+const EXPORT_CHANNEL_CAPACITY: usize = 64;
+
+const TASK_CSV_HEADER: [&str; 8] = [
+    "id",
+    "project_id",
+    "title",
+    "description",
+    "status",
+    "parent_workspace_id",
+    "created_at",
+    "updated_at",
+];
+
+fn task_csv_fields(task: &Task) -> [String; 8] {
+    [
+        task.id.to_string(),
+        task.project_id.to_string(),
+        task.title.clone(),
+        task.description.clone().unwrap_or_default(),
+        task.status.to_string(),
+        task.parent_workspace_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        task.created_at.to_rfc3339(),
+        task.updated_at.to_rfc3339(),
+    ]
+}
+
+/// Join fields into one CSV line, quoting fields that need it (RFC 4180)
+fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+const IMPORT_MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Create tasks from the CSV or JSON file in the `file` multipart field. Valid
+/// rows are inserted in one transaction; invalid rows are reported back.
+pub async fn import_project_tasks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<ImportTasksResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("file") {
+            let file_name = field.file_name().map(str::to_string);
+            let content_type = field.content_type().map(str::to_string);
+            file = Some((file_name, content_type, field.bytes().await?));
+            break;
         }
     }
+    let (file_name, content_type, data) =
+        file.ok_or_else(|| ApiError::BadRequest("Missing file field".to_string()))?;
+
+    let format = TaskImportFormat::detect(file_name.as_deref(), content_type.as_deref(), &data);
+    let parsed = parse_task_import(&data, format, project.id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let imported = Task::bulk_create(pool, &parsed.tasks).await? as u32;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_tasks_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "format": match format {
+                    TaskImportFormat::Csv => "csv",
+                    TaskImportFormat::Json => "json",
+                },
+                "imported": imported,
+                "failed": parsed.errors.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ImportTasksResponse {
+        imported,
+        errors: parsed.errors,
+    })))
+}
+
+pub async fn duplicate_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<DuplicateProjectRequest>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Project name must not be empty",
+        )));
+    }
+
+    let duplicate = deployment
+        .project()
+        .duplicate_project(
+            pool,
+            project.id,
+            name,
+            user_ctx.as_ref().map(|ctx| ctx.user_id),
+            payload.deep,
+        )
+        .await
+        .map_err(|e| ProjectError::CreateFailed(e.to_string()))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_duplicated",
+            serde_json::json!({
+                "project_id": duplicate.id.to_string(),
+                "source_project_id": project.id.to_string(),
+                "deep": payload.deep,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(duplicate)))
 }
 
 #[derive(serde::Deserialize)]
@@ -593,6 +1010,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/",
             get(get_project).put(update_project).delete(delete_project),
         )
+        .route("/archive", post(archive_project).delete(unarchive_project))
+        .route("/stats", get(get_project_stats))
+        .route("/duplicate", post(duplicate_project))
+        .route("/export", get(export_project_tasks))
+        .route(
+            "/import",
+            post(import_project_tasks).layer(DefaultBodyLimit::max(IMPORT_MAX_BODY_BYTES)),
+        )
         .route("/remote/members", get(get_project_remote_members))
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
@@ -601,6 +1026,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             post(link_project_to_existing_remote).delete(unlink_project),
         )
         .route("/link/create", post(create_and_link_remote_project))
+        .route("/sync-remote", post(sync_remote_project))
+        .route("/sync-status", get(get_project_sync_status))
         .route(
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
@@ -619,8 +1046,28 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/stream/ws", get(stream_projects_ws))
         .nest("/{id}", project_id_router);
 
-    Router::new().nest("/projects", projects_router).route(
-        "/remote-projects/{remote_project_id}",
-        get(get_remote_project_by_id),
-    )
+    Router::new()
+        .nest("/projects", projects_router)
+        .route(
+            "/remote-projects/{remote_project_id}",
+            get(get_remote_project_by_id),
+        )
+        .route(
+            "/remote-projects/{remote_project_id}/local",
+            get(get_local_project_for_remote),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_row_quotes_special_fields() {
+        assert_eq!(csv_row(&["a", "b c"]), "a,b c\r\n");
+        assert_eq!(
+            csv_row(&["x,y", "say \"hi\"", "two\nlines"]),
+            "\"x,y\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
 }
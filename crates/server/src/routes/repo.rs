@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use axum::{
     Router,
     extract::{Path, Query, State},
@@ -11,7 +13,13 @@ use db::models::{
 };
 use deployment::Deployment;
 use serde::Deserialize;
-use services::services::{file_search::SearchQuery, git::GitBranch};
+use services::services::{
+    container::ContainerError,
+    file_search::{CacheStats, SearchQuery},
+    filesystem::{FilesystemError, FilesystemService},
+    repo::BranchInfo,
+    workspace_manager::WorkspaceManager,
+};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -19,6 +27,7 @@ use uuid::Uuid;
 use crate::{
     DeploymentImpl,
     error::ApiError,
+    middleware::{OptionalUserContext, UserContext},
     routes::projects::{OpenEditorRequest, OpenEditorResponse},
 };
 
@@ -36,6 +45,17 @@ pub struct InitRepoRequest {
     pub folder_name: String,
 }
 
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CloneRepoRequest {
+    pub git_url: String,
+    /// Folder to clone into, created inside the user's workspace (K8s) or home directory
+    pub destination_name: String,
+    /// Only fetch the latest commit
+    #[serde(default)]
+    pub shallow: bool,
+}
+
 #[derive(Debug, Deserialize, TS)]
 #[ts(export)]
 pub struct BatchRepoRequest {
@@ -75,19 +95,98 @@ pub async fn init_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+pub async fn clone_repo(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    ResponseJson(payload): ResponseJson<CloneRepoRequest>,
+) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    let destination_name = payload.destination_name.trim();
+    deployment.repo().validate_folder_name(destination_name)?;
+
+    let user_id = user_ctx.as_ref().map(|ctx| ctx.user_id);
+    let dest =
+        FilesystemService::get_home_directory_for_user(user_id.as_ref()).join(destination_name);
+
+    // In K8s mode the clone must land inside the caller's workspace and fit their quota
+    let dest = match &user_ctx {
+        Some(ctx) => {
+            let dest = validate_path_for_user(&deployment, ctx, &dest, "Clone")?;
+            WorkspaceManager::enforce_user_quota(&ctx.user_id)
+                .await
+                .map_err(|e| ApiError::Container(ContainerError::WorkspaceManager(e)))?;
+            dest
+        }
+        None => dest,
+    };
+
+    let repo = deployment
+        .repo()
+        .clone_repo(
+            &deployment.db().pool,
+            &payload.git_url,
+            &dest,
+            payload.shallow,
+        )
+        .await?;
+
+    tracing::info!(
+        repo_id = %repo.id,
+        path = %repo.path.display(),
+        shallow = payload.shallow,
+        "Cloned repository"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(repo)))
+}
+
 pub async fn get_repo_branches(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
-) -> Result<ResponseJson<ApiResponse<Vec<GitBranch>>>, ApiError> {
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<BranchInfo>>>, ApiError> {
     let repo = deployment
         .repo()
         .get_by_id(&deployment.db().pool, repo_id)
         .await?;
 
-    let branches = deployment.git().get_all_branches(&repo.path)?;
+    // In K8s mode the repo must live inside the caller's workspace
+    let repo_path = match &user_ctx {
+        Some(ctx) => validate_path_for_user(&deployment, ctx, &repo.path, "Branch listing")?,
+        None => repo.path.clone(),
+    };
+
+    let branches = deployment.repo().list_branches(&repo_path)?;
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+/// Check that `path` is inside the user's workspace, logging a security event
+/// for `action` when it is not.
+fn validate_path_for_user(
+    deployment: &DeploymentImpl,
+    ctx: &UserContext,
+    path: &std::path::Path,
+    action: &str,
+) -> Result<PathBuf, ApiError> {
+    deployment
+        .filesystem()
+        .validate_path_for_user(&ctx.user_id, path)
+        .map_err(|err| match err {
+            FilesystemError::Unauthorized(path) => {
+                tracing::warn!(
+                    action = "unauthorized_repo_access",
+                    user_id = %ctx.user_id,
+                    path,
+                    security_event = true,
+                    "{} outside user workspace",
+                    action
+                );
+                ApiError::Forbidden("Repository is outside your workspace".to_string())
+            }
+            FilesystemError::Io(e) => ApiError::Io(e),
+            other => ApiError::BadRequest(other.to_string()),
+        })
+}
+
 pub async fn get_repos_batch(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<BatchRepoRequest>,
@@ -195,7 +294,7 @@ pub async fn search_repo(
 
     match deployment
         .file_search_cache()
-        .search_repo(&repo.path, &search_query.q, search_query.mode)
+        .search_repo(&repo, &search_query.q, search_query.mode)
         .await
     {
         Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
@@ -206,13 +305,27 @@ pub async fn search_repo(
     }
 }
 
+/// Report file search cache hit rate and size. Only mounted in debug builds.
+pub async fn file_search_cache_stats(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<CacheStats>> {
+    ResponseJson(ApiResponse::success(deployment.file_search_cache().stats()))
+}
+
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new()
+    let router = Router::new()
         .route("/repos", get(get_repos).post(register_repo))
         .route("/repos/init", post(init_repo))
+        .route("/repos/clone", post(clone_repo))
         .route("/repos/batch", post(get_repos_batch))
         .route("/repos/{repo_id}", get(get_repo).put(update_repo))
         .route("/repos/{repo_id}/branches", get(get_repo_branches))
         .route("/repos/{repo_id}/search", get(search_repo))
-        .route("/repos/{repo_id}/open-editor", post(open_repo_in_editor))
+        .route("/repos/{repo_id}/open-editor", post(open_repo_in_editor));
+
+    if cfg!(debug_assertions) {
+        router.route("/file-search/cache-stats", get(file_search_cache_stats))
+    } else {
+        router
+    }
 }
@@ -1,20 +1,27 @@
 use axum::{
     Json, Router,
     extract::{
-        Path, State,
+        Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     response::{IntoResponse, Json as ResponseJson},
     routing::get,
 };
-use db::models::scratch::{CreateScratch, Scratch, ScratchType, UpdateScratch};
+use db::models::{
+    scratch::{CreateScratch, Scratch, ScratchType, UpdateScratch},
+    session::{Session, SessionError},
+};
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext},
+};
 
 /// Path parameters for scratch routes with composite key
 #[derive(Deserialize)]
@@ -100,6 +107,76 @@ pub async fn delete_scratch(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Query parameters for session scratch routes
+#[derive(Deserialize)]
+pub struct SessionScratchQuery {
+    /// Scratch type, e.g. `DRAFT_FOLLOW_UP` (the default)
+    #[serde(rename = "type", default = "default_session_scratch_type")]
+    scratch_type: ScratchType,
+}
+
+fn default_session_scratch_type() -> ScratchType {
+    ScratchType::DraftFollowUp
+}
+
+/// Check the session exists and, in K8s mode, belongs to the caller.
+async fn ensure_session_access(
+    deployment: &DeploymentImpl,
+    session_id: Uuid,
+    user_ctx: Option<&UserContext>,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    Session::find_by_id(pool, session_id)
+        .await?
+        .ok_or(SessionError::NotFound)?;
+    if let Some(ctx) = user_ctx {
+        ctx.ensure_owner(Session::find_owner(pool, session_id).await?, "session")?;
+    }
+    Ok(())
+}
+
+pub async fn get_session_scratch(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+    Query(SessionScratchQuery { scratch_type }): Query<SessionScratchQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Option<Scratch>>>, ApiError> {
+    ensure_session_access(&deployment, session_id, user_ctx.as_ref()).await?;
+    let scratch = Scratch::find_by_id(&deployment.db().pool, session_id, &scratch_type).await?;
+    Ok(ResponseJson(ApiResponse::success(scratch)))
+}
+
+pub async fn put_session_scratch(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+    Query(SessionScratchQuery { scratch_type }): Query<SessionScratchQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<UpdateScratch>,
+) -> Result<ResponseJson<ApiResponse<Scratch>>, ApiError> {
+    ensure_session_access(&deployment, session_id, user_ctx.as_ref()).await?;
+    update_scratch(
+        State(deployment),
+        Path(ScratchPath {
+            scratch_type,
+            id: session_id,
+        }),
+        Json(payload),
+    )
+    .await
+}
+
+pub async fn delete_session_scratch(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+    Query(SessionScratchQuery { scratch_type }): Query<SessionScratchQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_session_access(&deployment, session_id, user_ctx.as_ref()).await?;
+    // Deleting a draft that was never saved is not an error
+    Scratch::delete(&deployment.db().pool, session_id, &scratch_type).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub async fn stream_scratch_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -147,6 +224,12 @@ async fn handle_scratch_ws(
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     Router::new()
         .route("/scratch", get(list_scratch))
+        .route(
+            "/scratch/{session_id}",
+            get(get_session_scratch)
+                .put(put_session_scratch)
+                .delete(delete_session_scratch),
+        )
         .route(
             "/scratch/{scratch_type}/{id}",
             get(get_scratch)
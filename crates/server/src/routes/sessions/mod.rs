@@ -5,17 +5,20 @@ use std::str::FromStr;
 
 use axum::{
     Extension, Json, Router,
-    extract::{Query, State},
+    extract::{
+        Query, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    http::StatusCode,
     middleware::from_fn_with_state,
-    response::Json as ResponseJson,
+    response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
 use db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session},
     workspace::{Workspace, WorkspaceError},
-    workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
 use executors::{
@@ -23,22 +26,28 @@ use executors::{
         ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
     },
     executors::BaseCodingAgent,
-    profile::ExecutorProfileId,
+    profile::{ExecutorConfigs, ExecutorProfileId},
 };
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::container::ContainerService;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::response::{ApiResponse, PaginatedResponse};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::{OptionalUserContext, load_session_middleware},
-    routes::task_attempts::util::restore_worktrees_to_process,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext, load_session_middleware},
+    routes::{pagination::PaginationQuery, task_attempts::util::restore_worktrees_to_process},
 };
 
 #[derive(Debug, Deserialize)]
 pub struct SessionQuery {
     pub workspace_id: Uuid,
+    /// Only list sessions that are running or have not started yet
+    #[serde(default)]
+    pub active: bool,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -60,9 +69,13 @@ pub async fn get_sessions(
             "Fetching sessions for user"
         );
     }
-    // TODO: In K8s mode, verify user owns the workspace before listing sessions
     let pool = &deployment.db().pool;
-    let sessions = Session::find_by_workspace_id(pool, query.workspace_id).await?;
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let sessions = if query.active {
+        Session::find_active_for_workspace(pool, query.workspace_id, user_id).await?
+    } else {
+        Session::find_by_workspace_id_and_user(pool, query.workspace_id, user_id).await?
+    };
     Ok(ResponseJson(ApiResponse::success(sessions)))
 }
 
@@ -72,6 +85,38 @@ pub async fn get_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
+/// List a session's execution processes, newest first, one page at a time.
+pub async fn get_session_processes(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Query(page): Query<PaginationQuery>,
+) -> Result<ResponseJson<ApiResponse<PaginatedResponse<ExecutionProcess>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // In K8s mode only the owner of the session's workspace may list its processes
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, session.workspace_id).await?,
+            "session",
+        )?;
+    }
+
+    let limit = page.limit();
+    // Fetch one extra row to learn whether another page exists
+    let processes = ExecutionProcess::find_by_session_paginated(
+        pool,
+        session.id,
+        page.after,
+        i64::from(limit) + 1,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        PaginatedResponse::from_rows(processes, limit as usize, |p| p.id),
+    )))
+}
+
 pub async fn create_session(
     State(deployment): State<DeploymentImpl>,
     OptionalUserContext(user_ctx): OptionalUserContext,
@@ -85,7 +130,6 @@ pub async fn create_session(
             "Creating session for user"
         );
     }
-    // TODO: In K8s mode, verify user owns the workspace before creating session
 
     let pool = &deployment.db().pool;
 
@@ -96,10 +140,35 @@ pub async fn create_session(
             "Workspace not found".to_string(),
         )))?;
 
+    // In K8s mode sessions may only be started in the caller's own workspaces
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, payload.workspace_id).await?,
+            "workspace",
+        )?;
+    }
+
+    // Refuse executors that could not be started on this machine
+    if let Some(executor) = payload.executor.as_deref() {
+        let profile_id = ExecutorProfileId::from_str(executor)
+            .map_err(|e| ApiError::UnprocessableEntity(e.to_string()))?;
+        let validation = ExecutorConfigs::get_cached()
+            .validate_profile(&profile_id)
+            .await;
+        if !validation.available {
+            return Err(ApiError::UnprocessableEntity(
+                validation
+                    .reason
+                    .unwrap_or_else(|| format!("Executor '{executor}' is not available")),
+            ));
+        }
+    }
+
     let session = Session::create(
         pool,
         &CreateSession {
             executor: payload.executor,
+            user_id: user_ctx.map(|ctx| ctx.user_id),
         },
         Uuid::new_v4(),
         payload.workspace_id,
@@ -109,6 +178,116 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
+pub async fn stream_sessions_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SessionQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<impl IntoResponse, ApiError> {
+    // In K8s mode only the workspace owner may watch its sessions
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    Workspace::find_by_id_and_user(&deployment.db().pool, query.workspace_id, user_id)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )))?;
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_sessions_ws(socket, deployment, query.workspace_id).await {
+            tracing::warn!("sessions WS closed: {}", e);
+        }
+    }))
+}
+
+async fn handle_sessions_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    workspace_id: Uuid,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_sessions_raw(workspace_id)
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    // Split socket into sender and receiver
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    // Forward server messages
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break; // client disconnected
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn stream_session_ws(
+    ws: WebSocketUpgrade,
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<impl IntoResponse, ApiError> {
+    // In K8s mode only the owner of the session's workspace may watch it
+    if let Some(ref ctx) = user_ctx {
+        let owner = Workspace::find_owner(&deployment.db().pool, session.workspace_id).await?;
+        ctx.ensure_owner(owner, "session")?;
+    }
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_session_ws(socket, deployment, session.id).await {
+            tracing::warn!("session WS closed: {}", e);
+        }
+    }))
+}
+
+async fn handle_session_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    session_id: Uuid,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_execution_processes_for_session_raw(session_id, false)
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    // Split socket into sender and receiver
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    // Forward server messages
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break; // client disconnected
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
@@ -133,10 +312,17 @@ pub async fn follow_up(
             "Processing follow-up for user"
         );
     }
-    // TODO: In K8s mode, verify user owns the session before processing
 
     let pool = &deployment.db().pool;
 
+    // In K8s mode only the owner of the session's workspace may send follow-ups
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, session.workspace_id).await?,
+            "session",
+        )?;
+    }
+
     // Load workspace from session
     let workspace = Workspace::find_by_id(pool, session.workspace_id)
         .await?
@@ -217,7 +403,10 @@ pub async fn follow_up(
 
     let prompt = payload.prompt;
 
-    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let repos = deployment
+        .workspace_repo_cache()
+        .find_repos_for_workspace(pool, workspace.id)
+        .await?;
     let cleanup_action = deployment.container().cleanup_actions_for_repos(&repos);
 
     let working_dir = workspace
@@ -269,10 +458,123 @@ pub async fn follow_up(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+/// Load the session's workspace and its most recent running execution process.
+async fn running_process_for_session(
+    deployment: &DeploymentImpl,
+    session: &Session,
+    user_ctx: Option<&UserContext>,
+) -> Result<(Workspace, ExecutionProcess), ApiError> {
+    let pool = &deployment.db().pool;
+
+    // In K8s mode only the owner of the session's workspace may pause or resume it
+    if let Some(ctx) = user_ctx {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, session.workspace_id).await?,
+            "session",
+        )?;
+    }
+
+    let workspace = Workspace::find_by_id(pool, session.workspace_id)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )))?;
+
+    let process = ExecutionProcess::find_by_session_id(pool, session.id, false)
+        .await?
+        .into_iter()
+        .filter(|p| p.status == ExecutionProcessStatus::Running)
+        .max_by_key(|p| p.created_at)
+        .ok_or_else(|| {
+            ApiError::Conflict("Session has no running execution process".to_string())
+        })?;
+
+    Ok((workspace, process))
+}
+
+pub async fn pause_session(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let (workspace, process) =
+        running_process_for_session(&deployment, &session, user_ctx.as_ref()).await?;
+    let process = deployment
+        .container()
+        .pause_execution(&workspace, process.id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(process)))
+}
+
+pub async fn resume_session(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let (workspace, process) =
+        running_process_for_session(&deployment, &session, user_ctx.as_ref()).await?;
+    let process = deployment
+        .container()
+        .resume_execution(&workspace, process.id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(process)))
+}
+
+pub async fn delete_session(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<StatusCode, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // In K8s mode only the owner of the session's workspace may delete it
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, session.workspace_id).await?,
+            "session",
+        )?;
+    }
+
+    // Stop any running processes for this session only; other sessions in the
+    // same workspace keep running
+    let processes = ExecutionProcess::find_by_session_id(pool, session.id, false).await?;
+    for process in processes
+        .iter()
+        .filter(|p| p.status == ExecutionProcessStatus::Running)
+    {
+        if let Err(e) = deployment
+            .container()
+            .stop_execution(process, ExecutionProcessStatus::Killed)
+            .await
+        {
+            tracing::error!(
+                "Failed to stop execution process {} for session {}: {}",
+                process.id,
+                session.id,
+                e
+            );
+            return Err(e.into());
+        }
+    }
+
+    deployment
+        .queued_message_service()
+        .cancel_queued(session.id);
+
+    // Execution processes are removed via ON DELETE CASCADE
+    Session::delete(pool, session.id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
-        .route("/", get(get_session))
+        .route("/", get(get_session).delete(delete_session))
+        .route("/stream/ws", get(stream_session_ws))
+        .route("/processes", get(get_session_processes))
         .route("/follow-up", post(follow_up))
+        .route("/pause", post(pause_session))
+        .route("/resume", post(resume_session))
         .route("/review", post(review::start_review))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -281,6 +583,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let sessions_router = Router::new()
         .route("/", get(get_sessions).post(create_session))
+        .route("/stream/ws", get(stream_sessions_ws))
         .nest("/{session_id}", session_id_router)
         .nest("/{session_id}/queue", queue::router(deployment));
 
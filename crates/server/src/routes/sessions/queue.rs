@@ -29,9 +29,10 @@ pub async fn queue_message(
         variant: payload.variant,
     };
 
-    let queued = deployment
-        .queued_message_service()
-        .queue_message(session.id, data);
+    let queued =
+        deployment
+            .queued_message_service()
+            .queue_message(session.id, session.user_id, data);
 
     deployment
         .track_if_analytics_allowed(
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
 use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
 use db::models::{
@@ -13,7 +13,7 @@ use executors::{
         ExecutorAction, ExecutorActionType,
         review::{RepoReviewContext as ExecutorRepoReviewContext, ReviewRequest as ReviewAction},
     },
-    executors::build_review_prompt,
+    executors::{BaseCodingAgent, build_review_prompt},
     profile::ExecutorProfileId,
 };
 use serde::{Deserialize, Serialize};
@@ -21,11 +21,44 @@ use services::services::container::ContainerService;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::OptionalUserContext};
+
+/// What the reviewing agent should focus on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ReviewType {
+    CodeQuality,
+    SecurityAudit,
+    FullReview,
+}
+
+impl ReviewType {
+    /// Instructions added to the review prompt.
+    fn instructions(self) -> &'static str {
+        match self {
+            Self::CodeQuality => {
+                "Focus on code quality: correctness, readability, naming, duplication, \
+                 error handling and test coverage."
+            }
+            Self::SecurityAudit => {
+                "Focus on security: injection, authentication and authorization flaws, \
+                 secrets in code, unsafe input handling and vulnerable dependencies."
+            }
+            Self::FullReview => {
+                "Review correctness, code quality, security and performance, and point \
+                 out missing tests."
+            }
+        }
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct StartReviewRequest {
-    pub executor_profile_id: ExecutorProfileId,
+    /// Executor to review with, e.g. "CLAUDE_CODE". Defaults to the executor
+    /// of the session's latest coding agent turn, else the session's executor.
+    pub executor: Option<String>,
+    pub review_type: ReviewType,
     pub additional_prompt: Option<String>,
     #[serde(default)]
     pub use_all_workspace_commits: bool,
@@ -42,10 +75,19 @@ pub enum ReviewError {
 pub async fn start_review(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
     Json(payload): Json<StartReviewRequest>,
 ) -> Result<ResponseJson<ApiResponse<ExecutionProcess, ReviewError>>, ApiError> {
     let pool = &deployment.db().pool;
 
+    // In K8s mode only the owner of the session's workspace may start a review
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Workspace::find_owner(pool, session.workspace_id).await?,
+            "session",
+        )?;
+    }
+
     let workspace = Workspace::find_by_id(pool, session.workspace_id)
         .await?
         .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
@@ -60,6 +102,11 @@ pub async fn start_review(
         )));
     }
 
+    let executor_profile_id = match payload.executor.as_deref() {
+        Some(executor) => ExecutorProfileId::new(parse_executor(executor)?),
+        None => session_executor_profile(pool, &session).await?,
+    };
+
     let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
@@ -97,12 +144,19 @@ pub async fn start_review(
         None
     };
 
-    let prompt = build_review_prompt(context.as_deref(), payload.additional_prompt.as_deref());
+    let review_type = payload.review_type;
+    let instructions = match payload.additional_prompt.as_deref() {
+        Some(additional) if !additional.trim().is_empty() => {
+            format!("{}\n\n{}", review_type.instructions(), additional)
+        }
+        _ => review_type.instructions().to_string(),
+    };
+    let prompt = build_review_prompt(context.as_deref(), Some(&instructions));
     let resumed_session = agent_session_id.is_some();
 
     let action = ExecutorAction::new(
         ExecutorActionType::ReviewRequest(ReviewAction {
-            executor_profile_id: payload.executor_profile_id.clone(),
+            executor_profile_id: executor_profile_id.clone(),
             context,
             prompt,
             session_id: agent_session_id,
@@ -117,7 +171,7 @@ pub async fn start_review(
             &workspace,
             &session,
             &action,
-            &ExecutionProcessRunReason::CodingAgent,
+            &ExecutionProcessRunReason::Review,
         )
         .await?;
 
@@ -127,8 +181,9 @@ pub async fn start_review(
             serde_json::json!({
                 "workspace_id": workspace.id.to_string(),
                 "session_id": session.id.to_string(),
-                "executor": payload.executor_profile_id.executor.to_string(),
-                "variant": payload.executor_profile_id.variant,
+                "executor": executor_profile_id.executor.to_string(),
+                "variant": executor_profile_id.variant,
+                "review_type": review_type,
                 "resumed_session": resumed_session,
             }),
         )
@@ -136,3 +191,33 @@ pub async fn start_review(
 
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
+
+/// The executor of the session's latest coding agent turn, else the executor
+/// the session was created with.
+async fn session_executor_profile(
+    pool: &sqlx::SqlitePool,
+    session: &Session,
+) -> Result<ExecutorProfileId, ApiError> {
+    if let Some(profile) =
+        ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?
+    {
+        return Ok(profile);
+    }
+
+    let executor = session.executor.as_deref().ok_or_else(|| {
+        ApiError::Workspace(WorkspaceError::ValidationError(
+            "No executor given and none configured on session".to_string(),
+        ))
+    })?;
+    Ok(ExecutorProfileId::new(parse_executor(executor)?))
+}
+
+/// Parse an executor name such as "CLAUDE_CODE" or "claude-code".
+fn parse_executor(executor: &str) -> Result<BaseCodingAgent, ApiError> {
+    BaseCodingAgent::from_str(&executor.replace('-', "_").to_ascii_uppercase()).map_err(|_| {
+        ApiError::Workspace(WorkspaceError::ValidationError(format!(
+            "Invalid executor: {}",
+            executor
+        )))
+    })
+}
@@ -56,7 +56,9 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, load_workspace_middleware},
     routes::task_attempts::gh_cli_setup::GhCliSetupError,
 };
 
@@ -171,6 +173,7 @@ pub struct RunAgentSetupResponse {}
 #[axum::debug_handler]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
     let executor_profile_id = payload.executor_profile_id.clone();
@@ -215,6 +218,10 @@ pub async fn create_task_attempt(
     )
     .await?;
 
+    if let Some(ref ctx) = user_ctx {
+        Workspace::set_user_id(pool, workspace.id, Some(ctx.user_id)).await?;
+    }
+
     let workspace_repos: Vec<CreateWorkspaceRepo> = payload
         .repos
         .iter()
@@ -1257,6 +1264,7 @@ pub async fn start_dev_server(
                 pool,
                 &CreateSession {
                     executor: Some("dev-server".to_string()),
+                    user_id: None,
                 },
                 Uuid::new_v4(),
                 workspace.id,
@@ -1408,6 +1416,7 @@ pub async fn run_setup_script(
                 pool,
                 &CreateSession {
                     executor: Some("setup-script".to_string()),
+                    user_id: None,
                 },
                 Uuid::new_v4(),
                 workspace.id,
@@ -1489,6 +1498,7 @@ pub async fn run_cleanup_script(
                 pool,
                 &CreateSession {
                     executor: Some("cleanup-script".to_string()),
+                    user_id: None,
                 },
                 Uuid::new_v4(),
                 workspace.id,
@@ -1693,6 +1703,7 @@ pub async fn delete_workspace(
     // Spawn background cleanup task for filesystem resources
     if let Some(workspace_dir) = workspace_dir {
         let workspace_id = workspace.id;
+        let file_search_cache = deployment.file_search_cache().clone();
         tokio::spawn(async move {
             tracing::info!(
                 "Starting background cleanup for workspace {} at {}",
@@ -1700,7 +1711,12 @@ pub async fn delete_workspace(
                 workspace_dir.display()
             );
 
-            if let Err(e) = WorkspaceManager::cleanup_workspace(&workspace_dir, &repositories).await
+            if let Err(e) = WorkspaceManager::cleanup_workspace(
+                &workspace_dir,
+                &repositories,
+                &file_search_cache,
+            )
+            .await
             {
                 tracing::error!(
                     "Background workspace cleanup failed for {} at {}: {}",
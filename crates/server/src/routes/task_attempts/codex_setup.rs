@@ -55,6 +55,7 @@ pub async fn run_codex_setup(
                     &deployment.db().pool,
                     &CreateSession {
                         executor: Some("codex".to_string()),
+                        user_id: None,
                     },
                     Uuid::new_v4(),
                     workspace.id,
@@ -128,7 +128,10 @@ async fn trigger_pr_description_follow_up(
             None => {
                 Session::create(
                     &deployment.db().pool,
-                    &CreateSession { executor: None },
+                    &CreateSession {
+                        executor: None,
+                        user_id: None,
+                    },
                     Uuid::new_v4(),
                     workspace.id,
                 )
@@ -4,7 +4,7 @@ use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
@@ -14,8 +14,9 @@ use axum::{
 };
 use db::models::{
     image::TaskImage,
+    project::{Project, ProjectError},
     repo::{Repo, RepoError},
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task::{CreateTask, Task, TaskHistoryEntry, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -26,32 +27,91 @@ use serde::{Deserialize, Serialize};
 use services::services::{container::ContainerService, workspace_manager::WorkspaceManager};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::response::{ApiResponse, PaginatedResponse};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::{OptionalUserContext, load_task_middleware},
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, load_task_middleware},
+    routes::{pagination::PaginationQuery, task_attempts::WorkspaceRepoInput},
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Also list soft-deleted tasks (multi-user mode only)
+    #[serde(default)]
+    pub include_deleted: bool,
 }
 
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
     OptionalUserContext(user_ctx): OptionalUserContext,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
+    Query(page): Query<PaginationQuery>,
+) -> Result<ResponseJson<ApiResponse<PaginatedResponse<TaskWithAttemptStatus>>>, ApiError> {
     // Log user context for tracing in multi-user mode
     if let Some(ref ctx) = user_ctx {
         tracing::debug!(user_id = %ctx.user_id, project_id = %query.project_id, "Fetching tasks for user");
     }
-    // TODO: In K8s mode, verify user owns the project before listing tasks
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
+    let pool = &deployment.db().pool;
+
+    // In K8s mode only the project owner may list its tasks
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Project::find_owner(pool, query.project_id).await?,
+            "project",
+        )?;
+    }
+
+    // Deleted tasks are only exposed in K8s mode
+    if query.include_deleted && user_ctx.is_none() {
+        return Err(ApiError::BadRequest(
+            "include_deleted is only supported in multi-user mode".to_string(),
+        ));
+    }
+
+    let limit = page.limit();
+    // Fetch one extra row to learn whether another page exists
+    let tasks = Task::find_by_project_id_with_attempt_status_paginated(
+        pool,
+        query.project_id,
+        page.after,
+        i64::from(limit) + 1,
+        query.include_deleted,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        PaginatedResponse::from_rows(tasks, limit as usize, |t| t.task.id),
+    )))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaskSearchQuery {
+    pub q: String,
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn search_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskSearchQuery>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    if query.q.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Query parameter 'q' is required and cannot be empty",
+        )));
+    }
+
+    let tasks = Task::search(
+        &deployment.db().pool,
+        user_ctx.map(|ctx| ctx.user_id),
+        query.q.trim(),
+        query.project_id,
+    )
+    .await?;
 
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
@@ -132,7 +192,12 @@ pub async fn create_task(
             "Creating task (desktop mode)"
         );
     }
-    // TODO: In K8s mode, verify user owns the project before creating task
+
+    // In K8s mode tasks may only be added to the caller's own projects
+    if let Some(ref ctx) = user_ctx {
+        let owner = Project::find_owner(&deployment.db().pool, payload.project_id).await?;
+        ctx.ensure_owner(owner, "project")?;
+    }
 
     let task = Task::create(&deployment.db().pool, &payload, id).await?;
 
@@ -182,10 +247,17 @@ pub async fn create_task_and_start(
             "Creating and starting task for user"
         );
     }
-    // TODO: In K8s mode, verify user owns the project before creating task
 
     let pool = &deployment.db().pool;
 
+    // In K8s mode tasks may only be added to the caller's own projects
+    if let Some(ref ctx) = user_ctx {
+        ctx.ensure_owner(
+            Project::find_owner(pool, payload.task.project_id).await?,
+            "project",
+        )?;
+    }
+
     let task_id = Uuid::new_v4();
     let task = Task::create(pool, &payload.task, task_id).await?;
 
@@ -234,6 +306,10 @@ pub async fn create_task_and_start(
     )
     .await?;
 
+    if let Some(ref ctx) = user_ctx {
+        Workspace::set_user_id(pool, workspace.id, Some(ctx.user_id)).await?;
+    }
+
     let workspace_repos: Vec<CreateWorkspaceRepo> = payload
         .repos
         .iter()
@@ -278,7 +354,7 @@ pub async fn create_task_and_start(
 pub async fn update_task(
     Extension(existing_task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
-
+    OptionalUserContext(user_ctx): OptionalUserContext,
     Json(payload): Json<UpdateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     // Use existing values if not provided in update
@@ -301,6 +377,7 @@ pub async fn update_task(
         description,
         status,
         parent_workspace_id,
+        user_ctx.map(|ctx| ctx.user_id),
     )
     .await?;
 
@@ -312,6 +389,129 @@ pub async fn update_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct MoveTaskRequest {
+    pub project_id: Uuid,
+}
+
+/// Move a task, together with its workspaces, to another project. In K8s
+/// mode the target project must belong to the task's owner; otherwise the
+/// move is refused with 409 Conflict.
+pub async fn move_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<MoveTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let target = Project::find_by_id(pool, payload.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    if let Some(ref ctx) = user_ctx {
+        if Project::find_owner(pool, target.id).await? != Some(ctx.user_id) {
+            return Err(ApiError::Conflict(
+                "Target project belongs to another user".to_string(),
+            ));
+        }
+    }
+
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let moved = Task::move_to_project(pool, task.id, target.id, user_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_moved",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "from_project_id": task.project_id.to_string(),
+                "to_project_id": target.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(moved)))
+}
+
+/// Maximum number of tasks a single bulk update may touch
+const MAX_BULK_UPDATE_IDS: usize = 100;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct BulkUpdateTaskStatusRequest {
+    pub ids: Vec<Uuid>,
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct BulkUpdateTaskStatusResponse {
+    pub updated: u64,
+    /// Requested tasks that were missing, deleted, or not owned by the caller
+    pub skipped: u64,
+}
+
+/// Move several tasks to the same status in one request
+pub async fn bulk_update_task_status(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<BulkUpdateTaskStatusRequest>,
+) -> Result<ResponseJson<ApiResponse<BulkUpdateTaskStatusResponse>>, ApiError> {
+    if payload.ids.len() > MAX_BULK_UPDATE_IDS {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Cannot update more than {} tasks at once (got {})",
+            MAX_BULK_UPDATE_IDS,
+            payload.ids.len()
+        )));
+    }
+
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let updated = Task::bulk_update_status(
+        &deployment.db().pool,
+        &payload.ids,
+        payload.status.clone(),
+        user_id,
+    )
+    .await?;
+    let skipped = (payload.ids.len() as u64).saturating_sub(updated);
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_bulk_updated",
+            serde_json::json!({
+                "status": payload.status.to_string(),
+                "requested": payload.ids.len(),
+                "updated": updated,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        BulkUpdateTaskStatusResponse { updated, skipped },
+    )))
+}
+
+/// List the recorded field changes of a task, oldest first
+pub async fn get_task_history(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskHistoryEntry>>>, ApiError> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let history = Task::find_history(&deployment.db().pool, task.id, user_id).await?;
+    Ok(ResponseJson(ApiResponse::success(history)))
+}
+
+pub async fn get_task_workspaces(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<Workspace>>>, ApiError> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let workspaces = Workspace::find_by_task_id(&deployment.db().pool, task.id, user_id).await?;
+    Ok(ResponseJson(ApiResponse::success(workspaces)))
+}
+
 pub async fn delete_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -326,11 +526,9 @@ pub async fn delete_task(
             "Deleting task for user"
         );
     }
-    // TODO: In K8s mode, verify user owns the task before deletion
 
     let pool = &deployment.db().pool;
 
-    // Gather task attempts data needed for background cleanup
     let attempts = Workspace::fetch_all(pool, Some(task.id))
         .await
         .map_err(|e| {
@@ -343,6 +541,51 @@ pub async fn delete_task(
         deployment.container().try_stop(workspace, true).await;
     }
 
+    // Soft-delete: the task row, its workspaces and their directories on disk
+    // stay so it can be restored. Child tasks keep their parent link too; the
+    // parent workspace rows still exist, and `purge_task` detaches them.
+    let rows_affected = Task::delete(pool, task.id).await?;
+
+    if rows_affected == 0 {
+        return Err(ApiError::Database(SqlxError::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_deleted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "attempt_count": attempts.len(),
+            }),
+        )
+        .await;
+
+    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+}
+
+/// Permanently delete a soft-deleted task, its workspaces and their
+/// directories on disk. In multi-user mode only tasks in the caller's
+/// projects can be purged.
+pub async fn purge_task(
+    Path(task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<(StatusCode, ResponseJson<ApiResponse<()>>), ApiError> {
+    let pool = &deployment.db().pool;
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let task = Task::find_deleted_by_id(pool, task_id, user_id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    // Gather task attempts data needed for background cleanup
+    let attempts = Workspace::fetch_all(pool, Some(task.id))
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch task attempts for task {}: {}", task.id, e);
+            ApiError::Workspace(e)
+        })?;
+
     let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
 
     // Collect workspace directories that need cleanup
@@ -364,7 +607,7 @@ pub async fn delete_task(
     }
 
     // Delete task from database (FK CASCADE will handle task_attempts)
-    let rows_affected = Task::delete(&mut *tx, task.id).await?;
+    let rows_affected = Task::purge(&mut *tx, task.id).await?;
 
     if rows_affected == 0 {
         return Err(ApiError::Database(SqlxError::RowNotFound));
@@ -375,7 +618,7 @@ pub async fn delete_task(
 
     if total_children_affected > 0 {
         tracing::info!(
-            "Nullified {} child task references before deleting task {}",
+            "Nullified {} child task references before purging task {}",
             total_children_affected,
             task.id
         );
@@ -383,7 +626,7 @@ pub async fn delete_task(
 
     deployment
         .track_if_analytics_allowed(
-            "task_deleted",
+            "task_purged",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": task.project_id.to_string(),
@@ -394,6 +637,7 @@ pub async fn delete_task(
 
     let task_id = task.id;
     let pool = pool.clone();
+    let file_search_cache = deployment.file_search_cache().clone();
     tokio::spawn(async move {
         tracing::info!(
             "Starting background cleanup for task {} ({} workspaces, {} repos)",
@@ -403,7 +647,12 @@ pub async fn delete_task(
         );
 
         for workspace_dir in &workspace_dirs {
-            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
+            if let Err(e) = WorkspaceManager::cleanup_workspace(
+                workspace_dir,
+                &repositories,
+                &file_search_cache,
+            )
+            .await
             {
                 tracing::error!(
                     "Background workspace cleanup failed for task {} at {}: {}",
@@ -431,6 +680,31 @@ pub async fn delete_task(
     Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
 }
 
+/// Undo a soft delete. In multi-user mode only tasks in the caller's projects
+/// can be restored.
+pub async fn restore_task(
+    Path(task_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let task = Task::restore(&deployment.db().pool, task_id, user_id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_restored",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
@@ -438,13 +712,21 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
+        .route("/history", get(get_task_history))
+        .route("/workspaces", get(get_task_workspaces))
+        .route("/move", post(move_task))
         .merge(task_actions_router)
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
+        .route("/search", get(search_tasks))
+        .route("/bulk", put(bulk_update_task_status))
         .route("/create-and-start", post(create_task_and_start))
+        // Outside task_id_router: the task loader does not see soft-deleted tasks
+        .route("/{task_id}/restore", post(restore_task))
+        .route("/{task_id}/purge", post(purge_task))
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
@@ -14,13 +14,20 @@ use db::{DeploymentMode, models::{workspace::Workspace, workspace_repo::Workspac
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use services::services::workspace_manager::WorkspaceManager;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::{verify_jwt, UserContext}};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{AuthError, UserContext, verify_configured_jwt},
+};
 
 #[derive(Debug, Deserialize)]
 pub struct TerminalQuery {
-    pub workspace_id: Uuid,
+    /// Workspace to open the terminal in. Without one the terminal starts in
+    /// the caller's workspace base directory.
+    pub workspace_id: Option<Uuid>,
     #[serde(default = "default_cols")]
     pub cols: u16,
     #[serde(default = "default_rows")]
@@ -62,23 +69,23 @@ enum TerminalMessage {
 fn validate_ws_auth(token: Option<&str>) -> Result<Option<UserContext>, ApiError> {
     let mode = DeploymentMode::detect();
 
-    if mode.is_kubernetes() {
+    if mode.is_multi_user() {
         // In K8s mode, require authentication
         let token = token.ok_or_else(|| {
             tracing::warn!("Terminal WebSocket connection missing auth token in K8s mode");
             ApiError::Unauthorized
         })?;
 
-        // Get JWT secret from environment
-        let secret = std::env::var("JWT_SECRET").map_err(|_| {
-            tracing::error!("JWT_SECRET not configured for terminal auth");
-            ApiError::BadRequest("Authentication not configured".to_string())
-        })?;
-
-        // Verify the token
-        let user_ctx = verify_jwt(token, secret.as_bytes()).map_err(|e| {
-            tracing::warn!(error = %e, "Terminal WebSocket auth failed");
-            ApiError::Unauthorized
+        // Verify the token against JWT_SECRET or the JWKS keys
+        let user_ctx = verify_configured_jwt(token).map_err(|e| match e {
+            AuthError::SecretNotConfigured => {
+                tracing::error!("JWT_SECRET not configured for terminal auth");
+                ApiError::BadRequest("Authentication not configured".to_string())
+            }
+            e => {
+                tracing::warn!(error = %e, "Terminal WebSocket auth failed");
+                ApiError::Unauthorized
+            }
         })?;
 
         tracing::debug!(
@@ -104,15 +111,61 @@ pub async fn terminal_ws(
     if let Some(ref ctx) = user_ctx {
         tracing::debug!(
             user_id = %ctx.user_id,
-            workspace_id = %query.workspace_id,
+            workspace_id = ?query.workspace_id,
             "Opening terminal for user"
         );
     }
-    // TODO: In K8s mode, verify user owns the workspace before allowing terminal access
 
-    let attempt = Workspace::find_by_id(&deployment.db().pool, query.workspace_id)
-        .await?
-        .ok_or_else(|| ApiError::BadRequest("Attempt not found".to_string()))?;
+    // Get user_id for PTY session (use a nil UUID for desktop mode)
+    let user_id = user_ctx.as_ref().map(|ctx| ctx.user_id).unwrap_or(Uuid::nil());
+
+    let working_dir = match query.workspace_id {
+        Some(workspace_id) => {
+            workspace_working_dir(&deployment, workspace_id, user_ctx.as_ref()).await?
+        }
+        None => {
+            let dir = WorkspaceManager::get_workspace_base_dir_for_user(&user_id);
+            tokio::fs::create_dir_all(&dir).await?;
+            dir
+        }
+    };
+
+    Ok(ws.on_upgrade(move |socket| {
+        handle_terminal_ws(
+            socket,
+            deployment,
+            query.workspace_id,
+            working_dir,
+            query.cols,
+            query.rows,
+            user_id,
+        )
+    }))
+}
+
+/// Resolve the directory a workspace terminal starts in: the single repo's
+/// checkout when there is exactly one, otherwise the workspace root.
+///
+/// In K8s mode the workspace must belong to the caller.
+async fn workspace_working_dir(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    user_ctx: Option<&UserContext>,
+) -> Result<PathBuf, ApiError> {
+    let pool = &deployment.db().pool;
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let Some(attempt) = Workspace::find_by_id_and_user(pool, workspace_id, user_id).await? else {
+        // Distinguish a workspace owned by someone else from a missing one
+        if let Some(ctx) = user_ctx
+            && Workspace::find_by_id(pool, workspace_id).await?.is_some()
+        {
+            ctx.ensure_owner(
+                Workspace::find_owner(pool, workspace_id).await?,
+                "workspace",
+            )?;
+        }
+        return Err(ApiError::NotFound("Workspace not found".to_string()));
+    };
 
     let container_ref = attempt
         .container_ref
@@ -126,7 +179,7 @@ pub async fn terminal_ws(
     }
 
     let mut working_dir = base_dir.clone();
-    match WorkspaceRepo::find_repos_for_workspace(&deployment.db().pool, query.workspace_id).await {
+    match WorkspaceRepo::find_repos_for_workspace(pool, workspace_id).await {
         Ok(repos) if repos.len() == 1 => {
             let repo_dir = base_dir.join(&repos[0].name);
             if repo_dir.exists() {
@@ -143,17 +196,13 @@ pub async fn terminal_ws(
         }
     }
 
-    // Get user_id for PTY session (use a nil UUID for desktop mode)
-    let user_id = user_ctx.as_ref().map(|ctx| ctx.user_id).unwrap_or(Uuid::nil());
-
-    Ok(ws.on_upgrade(move |socket| {
-        handle_terminal_ws(socket, deployment, working_dir, query.cols, query.rows, user_id)
-    }))
+    Ok(working_dir)
 }
 
 async fn handle_terminal_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
+    workspace_id: Option<Uuid>,
     working_dir: PathBuf,
     cols: u16,
     rows: u16,
@@ -161,7 +210,7 @@ async fn handle_terminal_ws(
 ) {
     let (session_id, mut output_rx) = match deployment
         .pty()
-        .create_session(user_id, working_dir, cols, rows)
+        .create_session(user_id, workspace_id, working_dir, cols, rows)
         .await
     {
         Ok(result) => result,
@@ -204,7 +253,9 @@ async fn handle_terminal_ws(
                             }
                         }
                         TerminalCommand::Resize { cols, rows } => {
-                            let _ = pty_service.resize(user_id, session_id_for_input, cols, rows).await;
+                            if let Err(e) = pty_service.resize(user_id, session_id_for_input, cols, rows).await {
+                                tracing::debug!(session_id = %session_id_for_input, "Ignoring terminal resize: {}", e);
+                            }
                         }
                     }
                 }
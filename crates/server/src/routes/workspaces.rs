@@ -0,0 +1,659 @@
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+
+use axum::{
+    Extension, Router,
+    extract::{Json, Query, State},
+    http::StatusCode,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, patch, post},
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    merge::{Merge, PullRequestInfo},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use local_deployment::pty::PtySessionInfo;
+use serde::Deserialize;
+use services::services::{
+    container::ContainerError,
+    git::GitServiceError,
+    git_host::{GitHostError, gitlab::GITLAB_OAUTH_PROVIDER},
+    workspace_manager::{
+        CommitResult, RepoDiff, WorkspaceError as WorkspaceManagerError, WorkspaceManager,
+        WorkspaceQuota, WorktreeStatus,
+    },
+};
+use sqlx::{Error as SqlxError, SqlitePool};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{OptionalUserContext, UserContext, load_workspace_middleware},
+};
+
+/// List workspaces, pinned first and then most recently updated. In
+/// multi-user mode only the caller's workspaces are returned.
+pub async fn get_workspaces(
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<Workspace>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspaces = match user_ctx {
+        Some(ctx) => Workspace::find_by_user(pool, ctx.user_id).await?,
+        None => Workspace::fetch_all(pool, None).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(workspaces)))
+}
+
+/// Pin a workspace so it is listed first
+pub async fn pin_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    set_workspace_pinned(&deployment, workspace.id, true).await
+}
+
+pub async fn unpin_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    set_workspace_pinned(&deployment, workspace.id, false).await
+}
+
+async fn set_workspace_pinned(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    pinned: bool,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace = if pinned {
+        Workspace::pin(pool, workspace_id).await?
+    } else {
+        Workspace::unpin(pool, workspace_id).await?
+    }
+    .ok_or(SqlxError::RowNotFound)?;
+
+    Ok(ResponseJson(ApiResponse::success(workspace)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateAgentWorkingDirRequest {
+    /// Worktree directory of one of the workspace's repos, relative to the
+    /// workspace root or absolute; empty or null runs agents in the root
+    pub agent_working_dir: Option<String>,
+}
+
+/// Set the directory agents of a workspace run in. The directory is stored
+/// relative to the workspace root. A path that is not a repo worktree is
+/// rejected with 422 and the valid directories.
+pub async fn update_agent_working_dir(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateAgentWorkingDirRequest>,
+) -> Result<
+    (
+        StatusCode,
+        ResponseJson<ApiResponse<Workspace, Vec<String>>>,
+    ),
+    ApiError,
+> {
+    let pool = &deployment.db().pool;
+
+    let valid_dirs: Vec<String> = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id)
+        .await?
+        .into_iter()
+        .map(|repo| repo.name)
+        .collect();
+    let requested = payload.agent_working_dir.unwrap_or_default();
+    let agent_working_dir = match relative_working_dir(
+        &requested,
+        workspace.container_ref.as_deref().map(Path::new),
+    ) {
+        Some(dir) if dir.is_empty() => None,
+        Some(dir) if valid_dirs.contains(&dir) => Some(dir),
+        _ => {
+            return Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ResponseJson(ApiResponse::error_with_data(valid_dirs)),
+            ));
+        }
+    };
+
+    let workspace =
+        Workspace::set_agent_working_dir(pool, workspace.id, agent_working_dir.as_deref())
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+
+    Ok((
+        StatusCode::OK,
+        ResponseJson(ApiResponse::success(workspace)),
+    ))
+}
+
+/// `requested` relative to `workspace_dir`, without `.` components or a
+/// trailing slash. `None` for an absolute path outside the workspace.
+fn relative_working_dir(requested: &str, workspace_dir: Option<&Path>) -> Option<String> {
+    let path = Path::new(requested.trim());
+    let relative = if path.is_absolute() {
+        path.strip_prefix(workspace_dir?).ok()?
+    } else {
+        path
+    };
+    let normalized: PathBuf = relative
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect();
+    Some(normalized.to_string_lossy().into_owned())
+}
+
+/// Report the on-disk state of each repo worktree in a workspace. A workspace
+/// whose container has not been created yet has no worktrees to report.
+/// Worktrees found on another branch than the recorded `active_branch`, e.g.
+/// because the agent created one, have the new branch recorded.
+pub async fn get_workspace_worktrees(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorktreeStatus>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let Some(container_ref) = workspace.container_ref.as_deref() else {
+        return Ok(ResponseJson(ApiResponse::success(vec![])));
+    };
+
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let mut worktrees = WorkspaceManager::list_worktrees(Path::new(container_ref), &repos);
+    record_active_branches(pool, workspace.id, &mut worktrees).await?;
+
+    Ok(ResponseJson(ApiResponse::success(worktrees)))
+}
+
+/// Fill in `active_branch` for each worktree, recording the checked-out
+/// branch for worktrees whose branch changed since it was last recorded.
+async fn record_active_branches(
+    pool: &SqlitePool,
+    workspace_id: Uuid,
+    worktrees: &mut [WorktreeStatus],
+) -> Result<(), sqlx::Error> {
+    let recorded: HashMap<Uuid, String> = WorkspaceRepo::find_by_workspace_id(pool, workspace_id)
+        .await?
+        .into_iter()
+        .map(|workspace_repo| (workspace_repo.repo_id, workspace_repo.active_branch))
+        .collect();
+
+    for worktree in worktrees {
+        let recorded = recorded.get(&worktree.repo_id).cloned().unwrap_or_default();
+        worktree.active_branch = match &worktree.branch {
+            Some(branch) if *branch != recorded => {
+                WorkspaceRepo::update_active_branch(pool, workspace_id, worktree.repo_id, branch)
+                    .await?;
+                branch.clone()
+            }
+            _ => recorded,
+        };
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceDiffQuery {
+    /// Only return the per-file summary, without the diff text
+    #[serde(default = "default_stat")]
+    pub stat: bool,
+}
+
+fn default_stat() -> bool {
+    true
+}
+
+/// Report the uncommitted changes in every repo of a workspace. With
+/// `?stat=false` the diff text is included, capped at `DIFF_MAX_BYTES`.
+pub async fn get_workspace_diff(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceDiffQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepoDiff>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let Some(container_ref) = workspace.container_ref else {
+        return Ok(ResponseJson(ApiResponse::success(vec![])));
+    };
+
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let diffs = tokio::task::spawn_blocking(move || {
+        WorkspaceManager::aggregate_diff(Path::new(&container_ref), &repos, !query.stat)
+    })
+    .await
+    .map_err(|e| ApiError::Io(std::io::Error::other(e)))?
+    .map_err(ContainerError::from)?;
+
+    Ok(ResponseJson(ApiResponse::success(diffs)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CommitWorkspaceRequest {
+    pub message: String,
+}
+
+/// Commit all uncommitted changes in every repo of a workspace. In
+/// multi-user mode the commits are authored by the calling user.
+pub async fn commit_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<CommitWorkspaceRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<CommitResult>>>, ApiError> {
+    let message = payload.message.trim().to_string();
+    if message.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Commit message must not be empty".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+
+    let Some(container_ref) = workspace.container_ref else {
+        return Ok(ResponseJson(ApiResponse::success(vec![])));
+    };
+
+    let (author_name, author_email) = match user_ctx.as_ref() {
+        Some(ctx) => commit_author(&deployment, ctx).await,
+        None => (None, None),
+    };
+
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let results = tokio::task::spawn_blocking(move || {
+        WorkspaceManager::commit_workspace(
+            Path::new(&container_ref),
+            &repos,
+            &message,
+            author_name.as_deref(),
+            author_email.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| ApiError::Io(std::io::Error::other(e)))?
+    .map_err(ContainerError::from)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_committed",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_count": results.len(),
+                "files_committed": results.iter().map(|r| r.files_committed).sum::<u32>(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+/// Git author for a multi-user commit: the token's email, with the name taken
+/// from the loaded profile when it belongs to the same user.
+async fn commit_author(
+    deployment: &DeploymentImpl,
+    ctx: &UserContext,
+) -> (Option<String>, Option<String>) {
+    let profile = deployment
+        .auth_context()
+        .cached_profile()
+        .await
+        .filter(|profile| profile.user_id == ctx.user_id);
+    let email = ctx
+        .email
+        .clone()
+        .or_else(|| profile.as_ref().map(|p| p.email.clone()));
+    let name = profile.and_then(|p| p.username).or_else(|| email.clone());
+    (name, email)
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PushWorkspaceRequest {
+    /// Remote to push to; defaults to the repo's push remote
+    pub remote: Option<String>,
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Push the branch of every repo in a workspace. Failures are reported per
+/// repo. In multi-user mode the caller's stored OAuth token authenticates
+/// HTTPS remotes; otherwise native git credentials are used.
+pub async fn push_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<PushWorkspaceRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<PushResult>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+
+    let Some(container_ref) = workspace.container_ref else {
+        return Ok(ResponseJson(ApiResponse::success(vec![])));
+    };
+
+    let token = match (deployment.config_service(), user_id) {
+        (Some(config_service), Some(user_id)) => config_service
+            .get_credentials(user_id)
+            .await?
+            .and_then(|creds| creds.access_token),
+        _ => None,
+    };
+
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let remote = payload
+        .remote
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    let force = payload.force;
+    let results = tokio::task::spawn_blocking(move || {
+        WorkspaceManager::push_workspace(
+            Path::new(&container_ref),
+            &repos,
+            remote.as_deref(),
+            force,
+            token.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| ApiError::Io(std::io::Error::other(e)))?
+    .map_err(ContainerError::from)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_pushed",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_count": results.len(),
+                "failed_count": results.iter().filter(|r| r.error.is_some()).count(),
+                "force": force,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ResetWorkspaceRequest {
+    /// Discard uncommitted changes instead of refusing when there are any
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Reset every repo worktree of a workspace to `HEAD`, discarding the agent's
+/// uncommitted changes. Without `force`, a workspace with uncommitted changes
+/// is left alone and the dirty files are returned with 409 Conflict.
+pub async fn reset_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResetWorkspaceRequest>,
+) -> Result<(StatusCode, ResponseJson<ApiResponse<(), Vec<String>>>), ApiError> {
+    let pool = &deployment.db().pool;
+
+    let Some(container_ref) = workspace.container_ref else {
+        return Ok((StatusCode::OK, ResponseJson(ApiResponse::success(()))));
+    };
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Stop the running agent before resetting the workspace".to_string(),
+        ));
+    }
+
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let force = payload.force;
+    let workspace_dir = Path::new(&container_ref).to_path_buf();
+    let worktree_repos = repos.clone();
+    let (dirty_files, mut worktrees) = tokio::task::spawn_blocking(move || {
+        if !force {
+            let dirty_files = WorkspaceManager::list_dirty_files(&workspace_dir, &worktree_repos)?;
+            if !dirty_files.is_empty() {
+                return Ok((dirty_files, vec![]));
+            }
+        }
+        WorkspaceManager::reset_workspace(&workspace_dir, &worktree_repos)?;
+        Ok::<_, WorkspaceManagerError>((
+            vec![],
+            WorkspaceManager::list_worktrees(&workspace_dir, &worktree_repos),
+        ))
+    })
+    .await
+    .map_err(|e| ApiError::Io(std::io::Error::other(e)))?
+    .map_err(ContainerError::from)?;
+
+    if !dirty_files.is_empty() {
+        return Ok((
+            StatusCode::CONFLICT,
+            ResponseJson(ApiResponse::error_with_data(dirty_files)),
+        ));
+    }
+
+    record_active_branches(pool, workspace.id, &mut worktrees).await?;
+    for repo in &repos {
+        deployment
+            .file_search_cache()
+            .invalidate_for_repo(repo.id)
+            .await;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_reset",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_count": repos.len(),
+                "force": force,
+            }),
+        )
+        .await;
+
+    Ok((StatusCode::OK, ResponseJson(ApiResponse::success(()))))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateWorkspacePullRequest {
+    /// Repo to open the pull request for; may be omitted when the workspace
+    /// has a single repo
+    pub repo_id: Option<Uuid>,
+    /// Branch to merge into; defaults to the repo's target branch
+    pub base_branch: Option<String>,
+    pub title: String,
+    pub body: Option<String>,
+}
+
+/// Open a pull request from the workspace branch of one repo. The branch must
+/// have been pushed, e.g. with `POST /workspaces/{id}/push`.
+pub async fn create_workspace_pull_request(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+    Json(payload): Json<CreateWorkspacePullRequest>,
+) -> Result<ResponseJson<ApiResponse<PullRequestInfo>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+
+    let title = payload.title.trim();
+    if title.is_empty() {
+        return Err(ApiError::BadRequest("Title must not be empty".to_string()));
+    }
+
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let repo = match payload.repo_id {
+        Some(repo_id) => repos.into_iter().find(|repo| repo.id == repo_id),
+        None if repos.len() == 1 => repos.into_iter().next(),
+        None => {
+            return Err(ApiError::BadRequest(
+                "repo_id is required for workspaces with several repos".to_string(),
+            ));
+        }
+    }
+    .ok_or_else(|| ApiError::BadRequest("Repo is not part of this workspace".to_string()))?;
+
+    let base_branch = match payload
+        .base_branch
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty())
+    {
+        Some(branch) => branch,
+        None => WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo.id)
+            .await?
+            .map(|workspace_repo| workspace_repo.target_branch)
+            .unwrap_or_default(),
+    };
+
+    // Only GitLab remotes use the stored OAuth credentials
+    let gitlab_credentials = match (deployment.config_service(), user_id) {
+        (Some(config_service), Some(user_id)) => {
+            config_service
+                .get_provider_credentials(user_id, GITLAB_OAUTH_PROVIDER)
+                .await?
+        }
+        _ => {
+            deployment
+                .auth_context()
+                .get_provider_credentials(GITLAB_OAUTH_PROVIDER)
+                .await
+        }
+    };
+
+    let body = payload
+        .body
+        .as_deref()
+        .filter(|body| !body.trim().is_empty());
+    let pr_info = deployment
+        .git()
+        .create_pull_request(
+            &repo.path,
+            &workspace.branch,
+            &base_branch,
+            title,
+            body,
+            gitlab_credentials.as_ref(),
+        )
+        .await
+        .map_err(|e| match e {
+            GitServiceError::GitHost(GitHostError::UnsupportedProvider) => {
+                ApiError::UnprocessableEntity(
+                    "Pull requests are supported for GitHub, GitLab and Azure DevOps remotes"
+                        .to_string(),
+                )
+            }
+            GitServiceError::GitHost(GitHostError::AuthFailed(msg)) => {
+                ApiError::UnprocessableEntity(msg)
+            }
+            GitServiceError::GitHost(e) => ApiError::GitHost(e),
+            e => ApiError::GitService(e),
+        })?;
+
+    if let Err(e) = Merge::create_pr(
+        pool,
+        workspace.id,
+        repo.id,
+        &base_branch,
+        pr_info.number,
+        &pr_info.url,
+    )
+    .await
+    {
+        tracing::error!("Failed to record pull request for workspace: {}", e);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_pull_request_created",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(pr_info)))
+}
+
+/// List the open terminal sessions of a workspace, oldest first. In
+/// multi-user mode only the caller's own sessions are returned.
+pub async fn get_workspace_terminals(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<Vec<PtySessionInfo>>>, ApiError> {
+    let user_id = user_ctx.map(|ctx| ctx.user_id);
+    let sessions = deployment
+        .pty()
+        .list_sessions_for_workspace(workspace.id)
+        .into_iter()
+        .filter(|session| user_id.is_none_or(|uid| session.user_id == uid))
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+/// Report the caller's workspace disk usage against `WORKSPACE_QUOTA_BYTES`.
+pub async fn get_workspace_quota(
+    OptionalUserContext(user_ctx): OptionalUserContext,
+) -> Result<ResponseJson<ApiResponse<WorkspaceQuota>>, ApiError> {
+    let base_dir = match user_ctx {
+        Some(ctx) => WorkspaceManager::get_workspace_base_dir_for_user(&ctx.user_id),
+        None => WorkspaceManager::get_workspace_base_dir(),
+    };
+    let quota = WorkspaceManager::quota_for_dir(base_dir).await?;
+
+    Ok(ResponseJson(ApiResponse::success(quota)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let workspace_id_router = Router::new()
+        .route("/", patch(update_agent_working_dir))
+        .route("/pin", post(pin_workspace).delete(unpin_workspace))
+        .route("/worktrees", get(get_workspace_worktrees))
+        .route("/diff", get(get_workspace_diff))
+        .route("/commit", post(commit_workspace))
+        .route("/push", post(push_workspace))
+        .route("/reset", post(reset_workspace))
+        .route("/pull-request", post(create_workspace_pull_request))
+        .route("/terminals", get(get_workspace_terminals))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_workspace_middleware,
+        ));
+
+    Router::new()
+        .route("/workspaces", get(get_workspaces))
+        .route("/workspaces/quota", get(get_workspace_quota))
+        .nest("/workspaces/{workspace_id}", workspace_id_router)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_working_dir_normalizes_paths() {
+        let workspace_dir = Path::new("/workspaces/abc");
+        let relative = |requested| relative_working_dir(requested, Some(workspace_dir));
+
+        assert_eq!(relative("frontend").as_deref(), Some("frontend"));
+        assert_eq!(relative("./frontend/").as_deref(), Some("frontend"));
+        assert_eq!(
+            relative("/workspaces/abc/frontend").as_deref(),
+            Some("frontend")
+        );
+        assert_eq!(relative("/workspaces/abc").as_deref(), Some(""));
+        assert_eq!(relative("").as_deref(), Some(""));
+        assert_eq!(relative("/workspaces/other/frontend"), None);
+        assert_eq!(relative_working_dir("/workspaces/abc/frontend", None), None);
+    }
+}
@@ -0,0 +1,105 @@
+//! Bulk user maintenance for operators of multi-user deployments.
+//!
+//! Every [`AdminService`] method takes the caller's admin token and checks it
+//! against the `ADMIN_SECRET` environment variable before touching any user.
+//! When `ADMIN_SECRET` is unset every call is rejected.
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::config_db::{ConfigDbError, ConfigKeySummary, ConfigServicePg};
+
+/// Environment variable holding the shared admin secret.
+pub const ADMIN_SECRET_ENV: &str = "ADMIN_SECRET";
+
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("Admin API is disabled")]
+    Disabled,
+    #[error("Invalid admin token")]
+    InvalidToken,
+    #[error(transparent)]
+    ConfigDb(#[from] ConfigDbError),
+}
+
+/// Check `admin_token` against `ADMIN_SECRET`.
+///
+/// Both values are hashed before comparing so the comparison time does not
+/// depend on how much of the secret matched.
+pub fn verify_admin_token(admin_token: &str) -> Result<(), AdminError> {
+    let expected = std::env::var(ADMIN_SECRET_ENV)
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .ok_or(AdminError::Disabled)?;
+
+    if Sha256::digest(admin_token.as_bytes()) != Sha256::digest(expected.as_bytes()) {
+        tracing::warn!(security_event = true, "Admin request rejected: bad secret");
+        return Err(AdminError::InvalidToken);
+    }
+
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct AdminService {
+    config: ConfigServicePg,
+}
+
+impl AdminService {
+    pub fn new(config: ConfigServicePg) -> Self {
+        Self { config }
+    }
+
+    /// Every user with a stored configuration, oldest first.
+    pub async fn list_all_user_ids(&self, admin_token: &str) -> Result<Vec<Uuid>, AdminError> {
+        verify_admin_token(admin_token)?;
+        Ok(self.config.list_all_user_ids().await?)
+    }
+
+    /// Which configuration fields `user_id` has set, without their values.
+    pub async fn config_summary(
+        &self,
+        admin_token: &str,
+        user_id: Uuid,
+    ) -> Result<ConfigKeySummary, AdminError> {
+        verify_admin_token(admin_token)?;
+        Ok(self.config.list_config_keys(user_id).await?)
+    }
+
+    /// Reset the configuration of `user_ids` to the defaults. Returns the
+    /// number of users reset.
+    pub async fn bulk_reset_configs(
+        &self,
+        admin_token: &str,
+        user_ids: &[Uuid],
+    ) -> Result<u64, AdminError> {
+        verify_admin_token(admin_token)?;
+        let reset = self.config.reset_configs(user_ids).await?;
+        tracing::warn!(
+            requested = user_ids.len(),
+            reset,
+            security_event = true,
+            "User configs reset via admin API"
+        );
+        Ok(reset)
+    }
+
+    /// Delete the stored OAuth credentials of `user_ids`, signing them out of
+    /// the remote service. Returns the number of users that had credentials.
+    pub async fn bulk_delete_credentials(
+        &self,
+        admin_token: &str,
+        user_ids: &[Uuid],
+    ) -> Result<u64, AdminError> {
+        verify_admin_token(admin_token)?;
+        let deleted = self.config.delete_credentials_for_users(user_ids).await?;
+        tracing::warn!(
+            requested = user_ids.len(),
+            deleted,
+            security_event = true,
+            "User credentials deleted via admin API"
+        );
+        Ok(deleted)
+    }
+}
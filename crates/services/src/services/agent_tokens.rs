@@ -0,0 +1,182 @@
+//! Live text deltas of a coding agent's replies, for
+//! `GET /api/execution-processes/{id}/tokens/stream`.
+//!
+//! Agents stream their reply by adding an assistant (or thinking) entry to the
+//! normalized conversation and then replacing it with longer text. Each
+//! running coding agent process gets a broadcast channel, fed from its
+//! [`MsgStore`], carrying only the newly appended text of those entries.
+
+use std::{collections::HashMap, sync::Arc};
+
+use executors::logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{RwLock, broadcast},
+    task::JoinHandle,
+};
+use ts_rs::TS;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+/// Tokens buffered per subscriber before it starts missing them.
+pub const AGENT_TOKEN_CHANNEL_SIZE: usize = 1024;
+
+/// Token channels of the running coding agent processes, by process ID.
+pub type AgentTokenSenders = Arc<RwLock<HashMap<Uuid, broadcast::Sender<AgentToken>>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum AgentTokenRole {
+    Assistant,
+    Thinking,
+}
+
+/// Text the agent appended to its reply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct AgentToken {
+    pub text: String,
+    pub role: AgentTokenRole,
+    /// The model configured for the agent, if known
+    pub model: Option<String>,
+}
+
+/// Turns conversation patches into [`AgentToken`]s, remembering how much of
+/// each entry has been sent.
+#[derive(Debug)]
+pub struct AgentTokenExtractor {
+    model: Option<String>,
+    sent: HashMap<usize, String>,
+}
+
+impl AgentTokenExtractor {
+    pub fn new(model: Option<String>) -> Self {
+        Self {
+            model,
+            sent: HashMap::new(),
+        }
+    }
+
+    /// The text `patch` appends to an assistant or thinking entry. When an
+    /// entry is rewritten rather than extended, its whole new text is sent.
+    pub fn extract(&mut self, patch: &json_patch::Patch) -> Option<AgentToken> {
+        let (index, entry) = extract_normalized_entry_from_patch(patch)?;
+        let role = match entry.entry_type {
+            NormalizedEntryType::AssistantMessage => AgentTokenRole::Assistant,
+            NormalizedEntryType::Thinking => AgentTokenRole::Thinking,
+            _ => return None,
+        };
+
+        let previous = self.sent.insert(index, entry.content.clone());
+        let text = match previous {
+            Some(previous) => match entry.content.strip_prefix(previous.as_str()) {
+                Some(appended) => appended.to_string(),
+                None => entry.content,
+            },
+            None => entry.content,
+        };
+        (!text.is_empty()).then(|| AgentToken {
+            text,
+            role,
+            model: self.model.clone(),
+        })
+    }
+}
+
+/// Forward the tokens of `msg_store` into `senders[process_id]` until the
+/// process finishes, then drop the channel so subscribers see it close.
+pub async fn spawn_token_forwarder(
+    senders: AgentTokenSenders,
+    process_id: Uuid,
+    msg_store: Arc<MsgStore>,
+    model: Option<String>,
+) -> JoinHandle<()> {
+    // Subscribe and register before spawning so neither an early patch nor an
+    // early subscriber is missed
+    let mut receiver = msg_store.get_receiver();
+    let (sender, _) = broadcast::channel(AGENT_TOKEN_CHANNEL_SIZE);
+    senders.write().await.insert(process_id, sender.clone());
+
+    tokio::spawn(async move {
+        let mut extractor = AgentTokenExtractor::new(model);
+        loop {
+            match receiver.recv().await {
+                Ok(LogMsg::JsonPatch(patch)) => {
+                    if let Some(token) = extractor.extract(&patch) {
+                        // No subscribers is not an error
+                        let _ = sender.send(token);
+                    }
+                }
+                Ok(LogMsg::Finished) | Err(broadcast::error::RecvError::Closed) => break,
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(%process_id, skipped, "Agent token forwarder lagged");
+                }
+            }
+        }
+
+        senders.write().await.remove(&process_id);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{NormalizedEntry, utils::patch::ConversationPatch};
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn extracts_appended_text() {
+        let mut extractor = AgentTokenExtractor::new(Some("sonnet".to_string()));
+
+        let first = ConversationPatch::add_normalized_entry(
+            3,
+            entry(NormalizedEntryType::AssistantMessage, "Hello"),
+        );
+        let token = extractor.extract(&first).unwrap();
+        assert_eq!(token.text, "Hello");
+        assert_eq!(token.role, AgentTokenRole::Assistant);
+        assert_eq!(token.model.as_deref(), Some("sonnet"));
+
+        let second = ConversationPatch::replace(
+            3,
+            entry(NormalizedEntryType::AssistantMessage, "Hello, world"),
+        );
+        assert_eq!(extractor.extract(&second).unwrap().text, ", world");
+
+        let rewritten =
+            ConversationPatch::replace(3, entry(NormalizedEntryType::AssistantMessage, "Hi"));
+        assert_eq!(extractor.extract(&rewritten).unwrap().text, "Hi");
+
+        assert!(extractor.extract(&rewritten).is_none());
+    }
+
+    #[test]
+    fn ignores_other_entries() {
+        let mut extractor = AgentTokenExtractor::new(None);
+
+        let user = ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::UserMessage, "Fix the bug"),
+        );
+        assert!(extractor.extract(&user).is_none());
+
+        let thinking = ConversationPatch::add_normalized_entry(
+            1,
+            entry(NormalizedEntryType::Thinking, "Looking at main.rs"),
+        );
+        assert_eq!(
+            extractor.extract(&thinking).unwrap().role,
+            AgentTokenRole::Thinking
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{VecDeque, hash_map::DefaultHasher},
     hash::{Hash, Hasher},
     time::Duration,
 };
 
 use os_info;
 use serde_json::{Value, json};
+use tokio::sync::{mpsc, oneshot};
 
 #[derive(Debug, Clone)]
 pub struct AnalyticsContext {
@@ -35,83 +36,275 @@ impl AnalyticsConfig {
     }
 }
 
+/// Largest number of events sent in a single batch request.
+const MAX_BATCH_SIZE: usize = 50;
+
+/// Events held in memory before the oldest are dropped.
+const MAX_QUEUED_EVENTS: usize = 1000;
+
+/// How often queued events are sent when a full batch has not accumulated.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Retries after the first failed batch request.
+const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry; doubled on every following retry.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug)]
+enum QueueMessage {
+    Event(Value),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Handle to the background task that batches analytics events and sends
+/// them to the PostHog batch endpoint.
 #[derive(Clone, Debug)]
-pub struct AnalyticsService {
+pub struct AnalyticsEventQueue {
+    sender: mpsc::Sender<QueueMessage>,
+}
+
+impl AnalyticsEventQueue {
+    /// Spawn the background sender. Must be called from within a Tokio runtime.
+    fn spawn(config: AnalyticsConfig, client: reqwest::Client) -> Self {
+        let (sender, receiver) = mpsc::channel(MAX_QUEUED_EVENTS);
+        let worker = QueueWorker {
+            receiver,
+            config,
+            client,
+            pending: VecDeque::new(),
+            flush_waiters: Vec::new(),
+            dropped: 0,
+        };
+        tokio::spawn(worker.run());
+        Self { sender }
+    }
+
+    fn push(&self, event: Value) {
+        match self.sender.try_send(QueueMessage::Event(event)) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                tracing::warn!("Analytics queue is full, dropping event");
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                tracing::debug!("Analytics queue is closed, dropping event");
+            }
+        }
+    }
+
+    async fn flush(&self) {
+        let (done_tx, done_rx) = oneshot::channel();
+        if self.sender.send(QueueMessage::Flush(done_tx)).await.is_ok() {
+            let _ = done_rx.await;
+        }
+    }
+}
+
+struct QueueWorker {
+    receiver: mpsc::Receiver<QueueMessage>,
     config: AnalyticsConfig,
     client: reqwest::Client,
+    pending: VecDeque<Value>,
+    flush_waiters: Vec<oneshot::Sender<()>>,
+    dropped: usize,
 }
 
-impl AnalyticsService {
-    pub fn new(config: AnalyticsConfig) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .unwrap();
+impl QueueWorker {
+    async fn run(mut self) {
+        let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            tokio::select! {
+                message = self.receiver.recv() => {
+                    let Some(message) = message else {
+                        // Every handle was dropped; send what is left and stop
+                        self.send_pending().await;
+                        break;
+                    };
+                    self.accept(message);
+                    let batch_ready = self.pending.len() >= MAX_BATCH_SIZE;
+                    if batch_ready || !self.flush_waiters.is_empty() {
+                        self.send_pending().await;
+                    }
+                }
+                _ = ticker.tick() => self.send_pending().await,
+            }
+        }
+    }
 
-        Self { config, client }
+    fn accept(&mut self, message: QueueMessage) {
+        match message {
+            QueueMessage::Event(event) => {
+                if push_bounded(&mut self.pending, event, MAX_QUEUED_EVENTS) {
+                    self.dropped += 1;
+                }
+            }
+            QueueMessage::Flush(done) => self.flush_waiters.push(done),
+        }
     }
 
-    pub fn track_event(&self, user_id: &str, event_name: &str, properties: Option<Value>) {
+    /// Send every pending event in batches, then answer any flush requests.
+    async fn send_pending(&mut self) {
+        while !self.pending.is_empty() {
+            let batch_len = self.pending.len().min(MAX_BATCH_SIZE);
+            let batch: Vec<Value> = self.pending.drain(..batch_len).collect();
+            self.send_batch(batch).await;
+
+            // Pick up events queued while the request was in flight so the
+            // oldest are the ones dropped if the backlog overflows
+            while let Ok(message) = self.receiver.try_recv() {
+                self.accept(message);
+            }
+        }
+
+        if self.dropped > 0 {
+            tracing::warn!(
+                dropped = self.dropped,
+                "Analytics queue exceeded {} events, dropped oldest",
+                MAX_QUEUED_EVENTS
+            );
+            self.dropped = 0;
+        }
+
+        for done in self.flush_waiters.drain(..) {
+            let _ = done.send(());
+        }
+    }
+
+    async fn send_batch(&self, batch: Vec<Value>) {
         let endpoint = format!(
-            "{}/capture/",
+            "{}/batch/",
             self.config.posthog_api_endpoint.trim_end_matches('/')
         );
-
-        let mut payload = json!({
+        let batch_len = batch.len();
+        let payload = json!({
             "api_key": self.config.posthog_api_key,
-            "event": event_name,
-            "distinct_id": user_id,
+            "batch": batch,
         });
-        if event_name == "$identify" {
-            // For $identify, set person properties in $set
-            if let Some(props) = properties {
-                payload["$set"] = props;
-            }
-        } else {
-            // For other events, use properties as before
-            let mut event_properties = properties.unwrap_or_else(|| json!({}));
-            if let Some(props) = event_properties.as_object_mut() {
-                props.insert(
-                    "timestamp".to_string(),
-                    json!(chrono::Utc::now().to_rfc3339()),
-                );
-                props.insert("version".to_string(), json!(env!("CARGO_PKG_VERSION")));
-                props.insert("device".to_string(), get_device_info());
-                props.insert("source".to_string(), json!("backend"));
-            }
-            payload["properties"] = event_properties;
-        }
 
-        let client = self.client.clone();
-        let event_name = event_name.to_string();
+        let mut delay = INITIAL_RETRY_DELAY;
+        for attempt in 0..=MAX_RETRIES {
+            if attempt > 0 {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
 
-        tokio::spawn(async move {
-            match client
+            match self
+                .client
                 .post(&endpoint)
                 .header("Content-Type", "application/json")
                 .json(&payload)
                 .send()
                 .await
             {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!("Sent batch of {} analytics events", batch_len);
+                    return;
+                }
                 Ok(response) => {
-                    if response.status().is_success() {
-                        tracing::debug!("Event '{}' sent successfully", event_name);
-                    } else {
-                        let status = response.status();
-                        let response_text = response.text().await.unwrap_or_default();
-                        tracing::error!(
-                            "Failed to send event. Status: {}. Response: {}",
-                            status,
-                            response_text
-                        );
+                    let status = response.status();
+                    let response_text = response.text().await.unwrap_or_default();
+                    tracing::error!(
+                        "Failed to send analytics batch (attempt {}). Status: {}. Response: {}",
+                        attempt + 1,
+                        status,
+                        response_text
+                    );
+                    if !is_retryable_status(status) {
+                        return;
                     }
                 }
                 Err(e) => {
-                    tracing::error!("Error sending event '{}': {}", event_name, e);
+                    tracing::error!(
+                        "Error sending analytics batch (attempt {}): {}",
+                        attempt + 1,
+                        e
+                    );
                 }
             }
-        });
+        }
+
+        tracing::warn!(
+            "Giving up on batch of {} analytics events after {} retries",
+            batch_len,
+            MAX_RETRIES
+        );
+    }
+}
+
+/// Append `event`, dropping the oldest event if the queue grows past `capacity`.
+/// Returns `true` if an event was dropped.
+fn push_bounded(queue: &mut VecDeque<Value>, event: Value, capacity: usize) -> bool {
+    queue.push_back(event);
+    if queue.len() > capacity {
+        queue.pop_front();
+        true
+    } else {
+        false
+    }
+}
+
+/// Server errors and rate limiting are worth retrying; other client errors are not.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+#[derive(Clone, Debug)]
+pub struct AnalyticsService {
+    queue: AnalyticsEventQueue,
+}
+
+impl AnalyticsService {
+    /// Create the service and spawn its background sender. Must be called from
+    /// within a Tokio runtime.
+    pub fn new(config: AnalyticsConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self {
+            queue: AnalyticsEventQueue::spawn(config, client),
+        }
+    }
+
+    /// Queue an event; it is sent with the next batch.
+    pub fn track_event(&self, user_id: &str, event_name: &str, properties: Option<Value>) {
+        self.queue.push(build_event(user_id, event_name, properties));
+    }
+
+    /// Send every queued event now and wait until that is done. Call before
+    /// shutdown so events tracked at exit are not lost.
+    pub async fn flush(&self) {
+        self.queue.flush().await;
+    }
+}
+
+fn build_event(user_id: &str, event_name: &str, properties: Option<Value>) -> Value {
+    let mut event = json!({
+        "event": event_name,
+        "distinct_id": user_id,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    });
+    if event_name == "$identify" {
+        // For $identify, set person properties in $set
+        if let Some(props) = properties {
+            event["$set"] = props;
+        }
+    } else {
+        // For other events, use properties as before
+        let mut event_properties = properties.unwrap_or_else(|| json!({}));
+        if let Some(props) = event_properties.as_object_mut() {
+            props.insert(
+                "timestamp".to_string(),
+                json!(chrono::Utc::now().to_rfc3339()),
+            );
+            props.insert("version".to_string(), json!(env!("CARGO_PKG_VERSION")));
+            props.insert("device".to_string(), get_device_info());
+            props.insert("source".to_string(), json!("backend"));
+        }
+        event["properties"] = event_properties;
     }
+    event
 }
 
 /// Generates a consistent, anonymous user ID for npm package telemetry.
@@ -198,4 +391,37 @@ mod tests {
         let id2 = generate_user_id();
         assert_eq!(id1, id2, "ID should be consistent across calls");
     }
+
+    #[test]
+    fn test_push_bounded_drops_oldest() {
+        let mut queue = VecDeque::new();
+        assert!(!push_bounded(&mut queue, json!(1), 2));
+        assert!(!push_bounded(&mut queue, json!(2), 2));
+        assert!(push_bounded(&mut queue, json!(3), 2));
+        assert_eq!(queue, VecDeque::from([json!(2), json!(3)]));
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(reqwest::StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn test_build_identify_event_sets_person_properties() {
+        let event = build_event("user", "$identify", Some(json!({ "email": "a@b.c" })));
+        assert_eq!(event["distinct_id"], "user");
+        assert_eq!(event["$set"]["email"], "a@b.c");
+        assert!(event.get("properties").is_none());
+    }
+
+    #[test]
+    fn test_build_event_adds_backend_properties() {
+        let event = build_event("user", "task_created", Some(json!({ "task_id": "t" })));
+        assert_eq!(event["event"], "task_created");
+        assert_eq!(event["properties"]["task_id"], "t");
+        assert_eq!(event["properties"]["source"], "backend");
+        assert!(event.get("api_key").is_none());
+    }
 }
@@ -3,12 +3,14 @@ pub mod executor_approvals;
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
-    time::Duration as StdDuration,
+    time::{Duration as StdDuration, Instant},
 };
 
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use db::models::{
     execution_process::ExecutionProcess,
+    session::Session,
     task::{Task, TaskStatus},
 };
 use executors::{
@@ -19,9 +21,11 @@ use executors::{
     },
 };
 use futures::future::{BoxFuture, FutureExt, Shared};
+use serde::Serialize;
 use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
 use tokio::sync::{RwLock, oneshot};
+use ts_rs::TS;
 use utils::{
     approvals::{ApprovalRequest, ApprovalResponse, ApprovalStatus},
     log_msg::LogMsg,
@@ -30,12 +34,28 @@ use utils::{
 use uuid::Uuid;
 
 #[derive(Debug)]
-struct PendingApproval {
+struct PendingRequest {
     entry_index: usize,
     entry: NormalizedEntry,
     execution_process_id: Uuid,
     tool_name: String,
     response_tx: oneshot::Sender<ApprovalStatus>,
+    requested_at: DateTime<Utc>,
+    timeout_at: DateTime<Utc>,
+    expires_at: Instant,
+}
+
+/// An approval request still waiting for a response, as listed by
+/// [`Approvals::list_pending_for_user`]
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PendingApproval {
+    pub id: String,
+    pub execution_process_id: Uuid,
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    pub tool_name: String,
+    pub requested_at: DateTime<Utc>,
+    pub timeout_at: DateTime<Utc>,
 }
 
 type ApprovalWaiter = Shared<BoxFuture<'static, ApprovalStatus>>;
@@ -48,7 +68,7 @@ pub struct ToolContext {
 
 #[derive(Clone)]
 pub struct Approvals {
-    pending: Arc<DashMap<String, PendingApproval>>,
+    pending: Arc<DashMap<String, PendingRequest>>,
     completed: Arc<DashMap<String, ApprovalStatus>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
 }
@@ -105,12 +125,18 @@ impl Approvals {
 
                 self.pending.insert(
                     req_id.clone(),
-                    PendingApproval {
+                    PendingRequest {
                         entry_index: idx,
                         entry: matching_tool,
                         execution_process_id: request.execution_process_id,
                         tool_name: request.tool_name.clone(),
                         response_tx: tx,
+                        requested_at: request.created_at,
+                        timeout_at: request.timeout_at,
+                        expires_at: Instant::now()
+                            + (request.timeout_at - chrono::Utc::now())
+                                .to_std()
+                                .unwrap_or_default(),
                     },
                 );
                 tracing::debug!(
@@ -256,11 +282,119 @@ impl Approvals {
         });
     }
 
+    /// Remove pending approvals past their expiry and deny them so the waiting
+    /// agent can continue. Acts as a backstop for the per-request timeout
+    /// watcher, e.g. when the user closed their browser mid-session.
+    pub async fn expire_old(&self) -> Vec<ApprovalResponse> {
+        let now = Instant::now();
+        let expired_ids: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|entry| entry.value().expires_at <= now)
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        let mut expired = Vec::with_capacity(expired_ids.len());
+        for id in expired_ids {
+            // Skip approvals answered since the scan
+            let Some((_, pending_approval)) = self.pending.remove(&id) else {
+                continue;
+            };
+
+            let status = ApprovalStatus::Denied {
+                reason: Some("Approval request expired".to_string()),
+            };
+            self.completed.insert(id.clone(), status.clone());
+            if pending_approval.response_tx.send(status.clone()).is_err() {
+                tracing::debug!("approval '{}' expiry notification receiver dropped", id);
+            }
+
+            if let Some(store) = self
+                .msg_store_by_id(&pending_approval.execution_process_id)
+                .await
+                && let Some(updated_entry) = ToolStatus::from_approval_status(&status)
+                    .and_then(|tool_status| pending_approval.entry.with_tool_status(tool_status))
+            {
+                store.push_patch(ConversationPatch::replace(
+                    pending_approval.entry_index,
+                    updated_entry,
+                ));
+            }
+
+            tracing::info!(
+                approval_id = %id,
+                execution_process_id = %pending_approval.execution_process_id,
+                tool_name = %pending_approval.tool_name,
+                "Denied expired approval request"
+            );
+            expired.push(ApprovalResponse {
+                execution_process_id: pending_approval.execution_process_id,
+                status,
+            });
+        }
+
+        expired
+    }
+
     async fn msg_store_by_id(&self, execution_process_id: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores.read().await;
         map.get(execution_process_id).cloned()
     }
 
+    /// List pending approvals across all of a user's sessions, oldest first.
+    /// Passing `None` (desktop mode) lists every pending approval. Approvals
+    /// whose execution process no longer exists are skipped.
+    pub async fn list_pending_for_user(
+        &self,
+        pool: &SqlitePool,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<PendingApproval>, ApprovalError> {
+        // Snapshot first so no map guard is held across the queries below
+        let snapshot: Vec<_> = self
+            .pending
+            .iter()
+            .map(|entry| {
+                let p = entry.value();
+                (
+                    entry.key().clone(),
+                    p.execution_process_id,
+                    p.tool_name.clone(),
+                    p.requested_at,
+                    p.timeout_at,
+                )
+            })
+            .collect();
+
+        let mut approvals = Vec::with_capacity(snapshot.len());
+        for (id, execution_process_id, tool_name, requested_at, timeout_at) in snapshot {
+            let Some(process) = ExecutionProcess::find_by_id(pool, execution_process_id).await?
+            else {
+                continue;
+            };
+            let Some(session) = process.parent_session(pool).await? else {
+                continue;
+            };
+            if let Some(user_id) = user_id
+                && Session::find_owner(pool, session.id).await? != Some(user_id)
+            {
+                continue;
+            }
+
+            approvals.push(PendingApproval {
+                id,
+                execution_process_id,
+                session_id: session.id,
+                workspace_id: session.workspace_id,
+                tool_name,
+                requested_at,
+                timeout_at,
+            });
+        }
+
+        approvals.sort_by_key(|approval| approval.requested_at);
+        Ok(approvals)
+    }
+
     /// Check which execution processes have pending approvals.
     /// Returns a set of execution_process_ids that have at least one pending approval.
     pub fn get_pending_execution_process_ids(
@@ -422,4 +556,143 @@ mod tests {
             "Should not match different tool ids"
         );
     }
+    #[tokio::test]
+    async fn test_expire_old_denies_only_expired_approvals() {
+        let approvals = Approvals::new(Arc::new(RwLock::new(HashMap::new())));
+        let execution_process_id = Uuid::new_v4();
+
+        let mut receivers = Vec::new();
+        for (id, expires_at) in [
+            ("expired", Instant::now() - StdDuration::from_secs(1)),
+            ("live", Instant::now() + StdDuration::from_secs(300)),
+        ] {
+            let (tx, rx) = oneshot::channel();
+            receivers.push(rx);
+            approvals.pending.insert(
+                id.to_string(),
+                PendingRequest {
+                    entry_index: 0,
+                    entry: create_tool_use_entry("Read", "foo.rs", id, ToolStatus::Created),
+                    execution_process_id,
+                    tool_name: "Read".to_string(),
+                    response_tx: tx,
+                    requested_at: chrono::Utc::now(),
+                    timeout_at: chrono::Utc::now(),
+                    expires_at,
+                },
+            );
+        }
+
+        let expired = approvals.expire_old().await;
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].execution_process_id, execution_process_id);
+        assert!(matches!(expired[0].status, ApprovalStatus::Denied { .. }));
+
+        assert!(!approvals.pending.contains_key("expired"));
+        assert!(approvals.pending.contains_key("live"));
+        assert!(matches!(
+            receivers.remove(0).await,
+            Ok(ApprovalStatus::Denied { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_pending_for_user_filters_by_session_owner() {
+        use db::models::{
+            project::{CreateProject, Project},
+            session::CreateSession,
+            task::CreateTask,
+            workspace::{CreateWorkspace, Workspace},
+        };
+        use sqlx::sqlite::SqlitePoolOptions;
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+
+        let project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: vec![],
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = Task::create(
+            &pool,
+            &CreateTask::from_title_description(project.id, "task".to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let workspace = Workspace::create(
+            &pool,
+            &CreateWorkspace {
+                branch: "vk/test".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let approvals = Approvals::new(Arc::new(RwLock::new(HashMap::new())));
+        let mut sessions = Vec::new();
+        for (id, owner) in [("alice", alice), ("bob", bob)] {
+            let session = Session::create(
+                &pool,
+                &CreateSession {
+                    executor: None,
+                    user_id: Some(owner),
+                },
+                Uuid::new_v4(),
+                workspace.id,
+            )
+            .await
+            .unwrap();
+            let execution_process_id = Uuid::new_v4();
+            sqlx::query("INSERT INTO execution_processes (id, session_id) VALUES ($1, $2)")
+                .bind(execution_process_id)
+                .bind(session.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let (tx, _rx) = oneshot::channel();
+            approvals.pending.insert(
+                id.to_string(),
+                PendingRequest {
+                    entry_index: 0,
+                    entry: create_tool_use_entry("Read", "foo.rs", id, ToolStatus::Created),
+                    execution_process_id,
+                    tool_name: "Read".to_string(),
+                    response_tx: tx,
+                    requested_at: chrono::Utc::now(),
+                    timeout_at: chrono::Utc::now(),
+                    expires_at: Instant::now() + StdDuration::from_secs(300),
+                },
+            );
+            sessions.push(session);
+        }
+
+        let mine = approvals
+            .list_pending_for_user(&pool, Some(alice))
+            .await
+            .unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].id, "alice");
+        assert_eq!(mine[0].session_id, sessions[0].id);
+        assert_eq!(mine[0].workspace_id, workspace.id);
+
+        let all = approvals.list_pending_for_user(&pool, None).await.unwrap();
+        assert_eq!(all.len(), 2);
+    }
 }
@@ -1,14 +1,34 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use tokio::sync::{Mutex as TokioMutex, OwnedMutexGuard, RwLock};
 use utils::api::oauth::ProfileResponse;
 
-use super::oauth_credentials::{Credentials, OAuthCredentials};
+use super::{
+    oauth_credentials::{Credentials, OAuthCredentials},
+    remote_client::{RemoteClient, RemoteClientError},
+};
+
+/// How long a cached profile is used before it is fetched again, unless
+/// overridden by `PROFILE_CACHE_TTL_SECS`
+const DEFAULT_PROFILE_CACHE_TTL_SECS: u64 = 3600;
+
+fn profile_cache_ttl() -> Duration {
+    let secs = std::env::var("PROFILE_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_PROFILE_CACHE_TTL_SECS);
+    Duration::from_secs(secs)
+}
 
 #[derive(Clone)]
 pub struct AuthContext {
     oauth: Arc<OAuthCredentials>,
     profile: Arc<RwLock<Option<ProfileResponse>>>,
+    profile_cached_at: Arc<RwLock<Option<Instant>>>,
+    profile_ttl: Duration,
     refresh_lock: Arc<TokioMutex<()>>,
 }
 
@@ -20,35 +40,122 @@ impl AuthContext {
         Self {
             oauth,
             profile,
+            profile_cached_at: Arc::new(RwLock::new(None)),
+            profile_ttl: profile_cache_ttl(),
             refresh_lock: Arc::new(TokioMutex::new(())),
         }
     }
 
+    /// Credentials of the provider most recently signed in with.
     pub async fn get_credentials(&self) -> Option<Credentials> {
-        self.oauth.get().await
+        self.oauth.current().await
     }
 
+    pub async fn get_provider_credentials(&self, provider: &str) -> Option<Credentials> {
+        self.oauth.get(provider).await
+    }
+
+    /// Replace the current provider's credentials, e.g. after a token refresh.
     pub async fn save_credentials(&self, creds: &Credentials) -> std::io::Result<()> {
-        self.oauth.save(creds).await
+        self.oauth.set_current(creds).await
+    }
+
+    /// Store credentials from signing in with `provider` and make it current.
+    pub async fn save_provider_credentials(
+        &self,
+        provider: &str,
+        creds: &Credentials,
+    ) -> std::io::Result<()> {
+        self.oauth.set(provider, creds).await
     }
 
+    pub async fn remove_provider_credentials(&self, provider: &str) -> std::io::Result<()> {
+        self.oauth.remove(provider).await
+    }
+
+    /// Remove credentials for every provider.
     pub async fn clear_credentials(&self) -> std::io::Result<()> {
         self.oauth.clear().await
     }
 
+    /// The cached profile, or `None` once it is older than the cache TTL so
+    /// callers fetch it again.
     pub async fn cached_profile(&self) -> Option<ProfileResponse> {
+        let cached_at = (*self.profile_cached_at.read().await)?;
+        if cached_at.elapsed() >= self.profile_ttl {
+            return None;
+        }
         self.profile.read().await.clone()
     }
 
     pub async fn set_profile(&self, profile: ProfileResponse) {
-        *self.profile.write().await = Some(profile)
+        *self.profile.write().await = Some(profile);
+        *self.profile_cached_at.write().await = Some(Instant::now());
     }
 
     pub async fn clear_profile(&self) {
-        *self.profile.write().await = None
+        *self.profile.write().await = None;
+        *self.profile_cached_at.write().await = None;
+    }
+
+    /// Drop the cached profile and fetch it again, e.g. after a plan upgrade
+    /// or name change on the remote.
+    pub async fn refresh_profile(
+        &self,
+        remote_client: &RemoteClient,
+    ) -> Result<ProfileResponse, RemoteClientError> {
+        self.clear_profile().await;
+        let profile = remote_client.profile().await?;
+        self.set_profile(profile.clone()).await;
+        Ok(profile)
     }
 
     pub async fn refresh_guard(&self) -> OwnedMutexGuard<()> {
         self.refresh_lock.clone().lock_owned().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn profile() -> ProfileResponse {
+        ProfileResponse {
+            user_id: Uuid::new_v4(),
+            username: Some("dev".to_string()),
+            email: "dev@example.com".to_string(),
+            providers: vec![],
+        }
+    }
+
+    fn auth_context(ttl: Duration) -> AuthContext {
+        let oauth = Arc::new(OAuthCredentials::new(
+            std::env::temp_dir().join("vk-auth-test-credentials.json"),
+        ));
+        AuthContext {
+            profile_ttl: ttl,
+            ..AuthContext::new(oauth, Arc::new(RwLock::new(None)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_profile_expires_after_ttl() {
+        let fresh = auth_context(Duration::from_secs(3600));
+        fresh.set_profile(profile()).await;
+        assert!(fresh.cached_profile().await.is_some());
+
+        let expired = auth_context(Duration::ZERO);
+        expired.set_profile(profile()).await;
+        assert!(expired.cached_profile().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clear_profile_drops_cache() {
+        let ctx = auth_context(Duration::from_secs(3600));
+        ctx.set_profile(profile()).await;
+        ctx.clear_profile().await;
+        assert!(ctx.cached_profile().await.is_none());
+    }
+}
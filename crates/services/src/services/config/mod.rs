@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
+use executors::profile::ExecutorProfileId;
+use serde::Deserialize;
+use serde_with::rust::double_option;
 use thiserror::Error;
+use ts_rs::TS;
 
 pub mod editor;
 mod versions;
@@ -27,6 +31,109 @@ pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
 
+/// Partial config update: only the fields present in the request are changed.
+/// Nullable fields distinguish "omitted" from an explicit `null`.
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct UpdateConfig {
+    #[ts(optional)]
+    pub theme: Option<ThemeMode>,
+    #[ts(optional)]
+    pub executor_profile: Option<ExecutorProfileId>,
+    #[ts(optional)]
+    pub disclaimer_acknowledged: Option<bool>,
+    #[ts(optional)]
+    pub onboarding_acknowledged: Option<bool>,
+    #[ts(optional)]
+    pub notifications: Option<NotificationConfig>,
+    #[ts(optional)]
+    pub editor: Option<EditorConfig>,
+    #[ts(optional)]
+    pub github: Option<GitHubConfig>,
+    #[ts(optional)]
+    pub analytics_enabled: Option<bool>,
+    #[serde(default, with = "double_option")]
+    #[ts(optional, type = "string | null")]
+    pub workspace_dir: Option<Option<String>>,
+    #[serde(default, with = "double_option")]
+    #[ts(optional, type = "string | null")]
+    pub last_app_version: Option<Option<String>>,
+    #[ts(optional)]
+    pub show_release_notes: Option<bool>,
+    #[ts(optional)]
+    pub language: Option<UiLanguage>,
+    #[ts(optional)]
+    pub git_branch_prefix: Option<String>,
+    #[ts(optional)]
+    pub showcases: Option<ShowcaseState>,
+    #[ts(optional)]
+    pub pr_auto_description_enabled: Option<bool>,
+    #[serde(default, with = "double_option")]
+    #[ts(optional, type = "string | null")]
+    pub pr_auto_description_prompt: Option<Option<String>>,
+    #[ts(optional)]
+    pub beta_workspaces: Option<bool>,
+    #[ts(optional)]
+    pub beta_workspaces_invitation_sent: Option<bool>,
+    #[ts(optional)]
+    pub commit_reminder: Option<bool>,
+}
+
+impl UpdateConfig {
+    /// Return a copy of `config` with every field present in this update applied.
+    pub fn merge_into(self, config: &Config) -> Config {
+        let Self {
+            theme,
+            executor_profile,
+            disclaimer_acknowledged,
+            onboarding_acknowledged,
+            notifications,
+            editor,
+            github,
+            analytics_enabled,
+            workspace_dir,
+            last_app_version,
+            show_release_notes,
+            language,
+            git_branch_prefix,
+            showcases,
+            pr_auto_description_enabled,
+            pr_auto_description_prompt,
+            beta_workspaces,
+            beta_workspaces_invitation_sent,
+            commit_reminder,
+        } = self;
+
+        Config {
+            config_version: config.config_version.clone(),
+            theme: theme.unwrap_or_else(|| config.theme.clone()),
+            executor_profile: executor_profile.unwrap_or_else(|| config.executor_profile.clone()),
+            disclaimer_acknowledged: disclaimer_acknowledged
+                .unwrap_or(config.disclaimer_acknowledged),
+            onboarding_acknowledged: onboarding_acknowledged
+                .unwrap_or(config.onboarding_acknowledged),
+            notifications: notifications.unwrap_or_else(|| config.notifications.clone()),
+            editor: editor.unwrap_or_else(|| config.editor.clone()),
+            github: github.unwrap_or_else(|| config.github.clone()),
+            analytics_enabled: analytics_enabled.unwrap_or(config.analytics_enabled),
+            workspace_dir: workspace_dir.unwrap_or_else(|| config.workspace_dir.clone()),
+            last_app_version: last_app_version.unwrap_or_else(|| config.last_app_version.clone()),
+            show_release_notes: show_release_notes.unwrap_or(config.show_release_notes),
+            language: language.unwrap_or(config.language),
+            git_branch_prefix: git_branch_prefix
+                .unwrap_or_else(|| config.git_branch_prefix.clone()),
+            showcases: showcases.unwrap_or_else(|| config.showcases.clone()),
+            pr_auto_description_enabled: pr_auto_description_enabled
+                .unwrap_or(config.pr_auto_description_enabled),
+            pr_auto_description_prompt: pr_auto_description_prompt
+                .unwrap_or_else(|| config.pr_auto_description_prompt.clone()),
+            beta_workspaces: beta_workspaces.unwrap_or(config.beta_workspaces),
+            beta_workspaces_invitation_sent: beta_workspaces_invitation_sent
+                .unwrap_or(config.beta_workspaces_invitation_sent),
+            commit_reminder: commit_reminder.unwrap_or(config.commit_reminder),
+        }
+    }
+}
+
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
     match std::fs::read_to_string(config_path) {
@@ -47,3 +154,51 @@ pub async fn save_config_to_file(
     std::fs::write(config_path, raw_config)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_config_only_changes_present_fields() {
+        let config = Config {
+            workspace_dir: Some("/work".to_string()),
+            ..Config::default()
+        };
+
+        let update: UpdateConfig =
+            serde_json::from_str(r#"{ "analytics_enabled": false, "git_branch_prefix": "feat" }"#)
+                .unwrap();
+        let merged = update.merge_into(&config);
+
+        assert!(!merged.analytics_enabled);
+        assert_eq!(merged.git_branch_prefix, "feat");
+        assert_eq!(merged.workspace_dir.as_deref(), Some("/work"));
+        assert_eq!(merged.config_version, config.config_version);
+    }
+
+    #[test]
+    fn update_config_explicit_null_clears_nullable_field() {
+        let config = Config {
+            workspace_dir: Some("/work".to_string()),
+            ..Config::default()
+        };
+
+        let update: UpdateConfig = serde_json::from_str(r#"{ "workspace_dir": null }"#).unwrap();
+        assert_eq!(update.merge_into(&config).workspace_dir, None);
+    }
+
+    #[test]
+    fn update_config_accepts_full_config() {
+        let config = Config {
+            onboarding_acknowledged: true,
+            ..Config::default()
+        };
+
+        let update: UpdateConfig =
+            serde_json::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+        let merged = update.merge_into(&Config::default());
+
+        assert!(merged.onboarding_acknowledged);
+    }
+}
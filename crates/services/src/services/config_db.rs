@@ -9,16 +9,19 @@
 //! - AES-256-GCM encryption for sensitive OAuth credentials
 //! - Automatic config upsert with timestamp tracking
 //! - Graceful handling of missing configurations (returns defaults)
+//! - Refresh token tracking for rotation and revocation
+//! - One-time import of an existing desktop config file
+//! - Re-encryption of stored credentials when the encryption key is rotated
 //!
 //! # Example
 //!
 //! ```ignore
+//! use db::DBServicePg;
 //! use services::services::config_db::ConfigServicePg;
-//! use sqlx::PgPool;
 //! use uuid::Uuid;
 //!
-//! let pool: PgPool = /* ... */;
-//! let config_service = ConfigServicePg::new(pool)?;
+//! let db = DBServicePg::new().await?;
+//! let config_service = ConfigServicePg::new(db);
 //!
 //! let user_id = Uuid::new_v4();
 //!
@@ -29,18 +32,23 @@
 //! config_service.save_config(user_id, &config).await?;
 //! ```
 
+use std::path::Path;
+
 use aes_gcm::{
     Aes256Gcm, Key, Nonce,
     aead::{Aead, KeyInit},
 };
+use chrono::{DateTime, Utc};
+use db::{DBServicePg, pg::InstrumentedPool};
 use rand::RngCore;
+use serde::Serialize;
 use sqlx::PgPool;
 use thiserror::Error;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use super::config::Config;
-use super::oauth_credentials::Credentials;
+use super::config::{Config, load_config_from_file};
+use super::oauth_credentials::{CredentialStore, Credentials};
 
 /// Nonce size for AES-256-GCM (96 bits / 12 bytes).
 const NONCE_SIZE: usize = 12;
@@ -48,6 +56,15 @@ const NONCE_SIZE: usize = 12;
 /// Environment variable for the config encryption key.
 const CONFIG_ENCRYPTION_KEY_ENV: &str = "CONFIG_ENCRYPTION_KEY";
 
+/// Environment variable for the key being rotated out, still accepted for decryption.
+const CONFIG_ENCRYPTION_KEY_PREVIOUS_ENV: &str = "CONFIG_ENCRYPTION_KEY_PREVIOUS";
+
+/// Number of rows re-encrypted per batch during key rotation.
+const ROTATION_BATCH_SIZE: i64 = 500;
+
+/// Marker stored in `config_json` once a desktop config file has been imported.
+pub const MIGRATED_FROM_FILE_KEY: &str = "_migrated_from_file";
+
 /// Errors that can occur during configuration operations.
 #[derive(Debug, Error)]
 pub enum ConfigDbError {
@@ -74,6 +91,21 @@ pub enum ConfigDbError {
     /// Invalid encrypted data format.
     #[error("Invalid encrypted data format")]
     InvalidEncryptedData,
+
+    /// The user has no configuration row.
+    #[error("No configuration stored for user {0}")]
+    ConfigNotFound(Uuid),
+}
+
+/// Which parts of a user's configuration are set, without their values.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigKeySummary {
+    /// Whether encrypted OAuth credentials are stored.
+    pub has_oauth_credentials: bool,
+    /// Top-level keys of the stored `config_json`, sorted.
+    pub config_keys: Vec<String>,
+    /// When the configuration row was last written.
+    pub updated_at: DateTime<Utc>,
 }
 
 /// PostgreSQL-backed configuration service for multi-user deployments.
@@ -82,11 +114,15 @@ pub enum ConfigDbError {
 /// with OAuth credentials encrypted using AES-256-GCM.
 #[derive(Clone)]
 pub struct ConfigServicePg {
-    /// The PostgreSQL connection pool.
-    pool: PgPool,
+    /// The PostgreSQL database service. The pool is fetched on each use so a
+    /// pool recreated by the health monitor is picked up.
+    db: DBServicePg,
 
     /// The encryption key for OAuth credentials (32 bytes for AES-256).
     encryption_key: Option<[u8; 32]>,
+
+    /// The previous encryption key, tried when decryption with the current key fails.
+    previous_encryption_key: Option<[u8; 32]>,
 }
 
 impl ConfigServicePg {
@@ -94,10 +130,12 @@ impl ConfigServicePg {
     ///
     /// The encryption key is read from the `CONFIG_ENCRYPTION_KEY` environment
     /// variable. The key should be a 64-character hex string (32 bytes).
+    /// During a key rotation, `CONFIG_ENCRYPTION_KEY_PREVIOUS` may hold the old
+    /// key so credentials not yet re-encrypted can still be read.
     ///
     /// # Arguments
     ///
-    /// * `pool` - The PostgreSQL connection pool
+    /// * `db` - The PostgreSQL database service
     ///
     /// # Returns
     ///
@@ -107,9 +145,9 @@ impl ConfigServicePg {
     ///
     /// ```ignore
     /// std::env::set_var("CONFIG_ENCRYPTION_KEY", "0123456789abcdef...");
-    /// let service = ConfigServicePg::new(pool);
+    /// let service = ConfigServicePg::new(db);
     /// ```
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(db: DBServicePg) -> Self {
         let encryption_key = Self::load_encryption_key();
 
         if encryption_key.is_none() {
@@ -120,8 +158,9 @@ impl ConfigServicePg {
         }
 
         Self {
-            pool,
+            db,
             encryption_key,
+            previous_encryption_key: Self::load_key_from_env(CONFIG_ENCRYPTION_KEY_PREVIOUS_ENV),
         }
     }
 
@@ -131,12 +170,13 @@ impl ConfigServicePg {
     ///
     /// # Arguments
     ///
-    /// * `pool` - The PostgreSQL connection pool
+    /// * `db` - The PostgreSQL database service
     /// * `encryption_key` - A 32-byte encryption key
-    pub fn new_with_key(pool: PgPool, encryption_key: [u8; 32]) -> Self {
+    pub fn new_with_key(db: DBServicePg, encryption_key: [u8; 32]) -> Self {
         Self {
-            pool,
+            db,
             encryption_key: Some(encryption_key),
+            previous_encryption_key: None,
         }
     }
 
@@ -144,25 +184,22 @@ impl ConfigServicePg {
     ///
     /// The key should be a 64-character hex string (representing 32 bytes).
     fn load_encryption_key() -> Option<[u8; 32]> {
-        let key_hex = std::env::var(CONFIG_ENCRYPTION_KEY_ENV).ok()?;
+        Self::load_key_from_env(CONFIG_ENCRYPTION_KEY_ENV)
+    }
+
+    fn load_key_from_env(var: &str) -> Option<[u8; 32]> {
+        let key_hex = std::env::var(var).ok()?;
 
         if key_hex.len() != 64 {
             warn!(
-                "CONFIG_ENCRYPTION_KEY must be 64 hex characters (32 bytes), got {} chars",
+                "{} must be 64 hex characters (32 bytes), got {} chars",
+                var,
                 key_hex.len()
             );
             return None;
         }
 
-        let key_bytes = hex::decode(&key_hex).ok()?;
-
-        if key_bytes.len() != 32 {
-            return None;
-        }
-
-        let mut key_array = [0u8; 32];
-        key_array.copy_from_slice(&key_bytes);
-        Some(key_array)
+        parse_encryption_key(&key_hex)
     }
 
     /// Load a user's configuration from the database.
@@ -183,16 +220,16 @@ impl ConfigServicePg {
     pub async fn load_config(&self, user_id: Uuid) -> Result<Config, ConfigDbError> {
         debug!(user_id = %user_id, "Loading config from database");
 
-        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+        let query = sqlx::query_as(
             r#"
             SELECT config_json
             FROM user_configs
             WHERE user_id = $1
             "#,
         )
-        .bind(user_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        .bind(user_id);
+        let row: Option<(serde_json::Value,)> =
+            self.instrumented_pool().fetch_optional_as(query).await?;
 
         match row {
             Some((config_json,)) => {
@@ -225,7 +262,7 @@ impl ConfigServicePg {
 
         let config_json = serde_json::to_value(config)?;
 
-        sqlx::query(
+        let query = sqlx::query(
             r#"
             INSERT INTO user_configs (user_id, config_json, created_at, updated_at)
             VALUES ($1, $2, NOW(), NOW())
@@ -236,97 +273,137 @@ impl ConfigServicePg {
             "#,
         )
         .bind(user_id)
-        .bind(config_json)
-        .execute(&self.pool)
-        .await?;
+        .bind(config_json);
+        self.instrumented_pool().execute(query).await?;
 
         info!(user_id = %user_id, "Config saved successfully");
         Ok(())
     }
 
-    /// Encrypt OAuth credentials for storage.
+    /// Import a desktop config file as a user's database configuration.
     ///
-    /// Uses AES-256-GCM with a random nonce. The output format is:
-    /// `nonce (12 bytes) || ciphertext`
+    /// The import only happens once: it is skipped when the file does not
+    /// exist or when the user already has a configuration row, so settings
+    /// changed in K8s mode are never overwritten by a stale file. The imported
+    /// row is tagged with `_migrated_from_file: true` in `config_json`.
     ///
     /// # Arguments
     ///
-    /// * `credentials` - The OAuth credentials to encrypt
+    /// * `user_id` - The UUID of the user to seed
+    /// * `file_path` - Path to the desktop `config.json`
     ///
     /// # Returns
     ///
-    /// The encrypted credentials as a byte vector.
-    pub fn encrypt_credentials(
+    /// `true` if the file was imported, `false` if the import was skipped.
+    pub async fn migrate_from_file(
         &self,
-        credentials: &Credentials,
-    ) -> Result<Vec<u8>, ConfigDbError> {
-        let encryption_key = self
-            .encryption_key
-            .ok_or(ConfigDbError::EncryptionKeyError)?;
-
-        let key = Key::<Aes256Gcm>::from_slice(&encryption_key);
-        let cipher = Aes256Gcm::new(key);
+        user_id: Uuid,
+        file_path: &Path,
+    ) -> Result<bool, ConfigDbError> {
+        if !file_path.exists() {
+            debug!(path = %file_path.display(), "No config file to migrate");
+            return Ok(false);
+        }
 
-        // Generate random nonce
-        let mut nonce_bytes = [0u8; NONCE_SIZE];
-        rand::thread_rng().fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
+        if self.has_config(user_id).await? {
+            debug!(user_id = %user_id, "User already has a config, skipping file migration");
+            return Ok(false);
+        }
 
-        // Serialize credentials to JSON
-        let plaintext = serde_json::to_vec(credentials)?;
+        let config = load_config_from_file(&file_path.to_path_buf()).await;
+        self.save_config(user_id, &config).await?;
 
-        // Encrypt
-        let ciphertext = cipher
-            .encrypt(nonce, plaintext.as_ref())
-            .map_err(|e| ConfigDbError::EncryptionFailed(e.to_string()))?;
+        let query = sqlx::query(
+            r#"
+            UPDATE user_configs
+            SET config_json = config_json || jsonb_build_object($2::text, true)
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(MIGRATED_FROM_FILE_KEY);
+        self.instrumented_pool().execute(query).await?;
+
+        info!(
+            user_id = %user_id,
+            path = %file_path.display(),
+            "Migrated file-based config to database"
+        );
+        Ok(true)
+    }
 
-        // Prepend nonce to ciphertext
-        let mut output = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
-        output.extend_from_slice(&nonce_bytes);
-        output.extend_from_slice(&ciphertext);
+    /// Check whether a configuration row exists for a user.
+    async fn has_config(&self, user_id: Uuid) -> Result<bool, ConfigDbError> {
+        let query = sqlx::query_as(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM user_configs WHERE user_id = $1)
+            "#,
+        )
+        .bind(user_id);
+        let (exists,): (bool,) = self.instrumented_pool().fetch_one_as(query).await?;
 
-        Ok(output)
+        Ok(exists)
     }
 
-    /// Decrypt OAuth credentials from storage.
+    /// Encrypt a user's OAuth credentials for storage.
     ///
-    /// Expects input in the format: `nonce (12 bytes) || ciphertext`
+    /// Uses AES-256-GCM with a random nonce. The output format is:
+    /// `nonce (12 bytes) || ciphertext`, where the plaintext is the JSON map of
+    /// credentials keyed by provider.
     ///
     /// # Arguments
     ///
-    /// * `encrypted` - The encrypted credentials
+    /// * `store` - The OAuth credentials to encrypt
     ///
     /// # Returns
     ///
-    /// The decrypted OAuth credentials.
-    pub fn decrypt_credentials(&self, encrypted: &[u8]) -> Result<Credentials, ConfigDbError> {
+    /// The encrypted credentials as a byte vector.
+    pub fn encrypt_credentials(&self, store: &CredentialStore) -> Result<Vec<u8>, ConfigDbError> {
         let encryption_key = self
             .encryption_key
             .ok_or(ConfigDbError::EncryptionKeyError)?;
 
-        if encrypted.len() <= NONCE_SIZE {
-            return Err(ConfigDbError::InvalidEncryptedData);
-        }
+        // Serialize credentials to JSON
+        let plaintext = serde_json::to_vec(store)?;
 
-        let key = Key::<Aes256Gcm>::from_slice(&encryption_key);
-        let cipher = Aes256Gcm::new(key);
+        encrypt_with_key(&encryption_key, &plaintext)
+    }
 
-        // Extract nonce and ciphertext
-        let nonce = Nonce::from_slice(&encrypted[..NONCE_SIZE]);
-        let ciphertext = &encrypted[NONCE_SIZE..];
+    /// Decrypt a user's OAuth credentials from storage.
+    ///
+    /// Expects input in the format: `nonce (12 bytes) || ciphertext`. Blobs
+    /// written before credentials were stored per provider decrypt to a single
+    /// entry under [`DEFAULT_PROVIDER`](super::oauth_credentials::DEFAULT_PROVIDER).
+    /// If the current key cannot decrypt the blob, the previous key is tried.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted` - The encrypted credentials
+    ///
+    /// # Returns
+    ///
+    /// The decrypted credentials, or an error if decryption fails.
+    pub fn decrypt_credentials(&self, encrypted: &[u8]) -> Result<CredentialStore, ConfigDbError> {
+        let encryption_key = self
+            .encryption_key
+            .ok_or(ConfigDbError::EncryptionKeyError)?;
 
-        // Decrypt
-        let plaintext = cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|e| ConfigDbError::DecryptionFailed(e.to_string()))?;
+        let plaintext = match (
+            decrypt_with_key(&encryption_key, encrypted),
+            self.previous_encryption_key,
+        ) {
+            (Err(ConfigDbError::DecryptionFailed(_)), Some(previous_key)) => {
+                debug!("Decrypting credentials with the previous encryption key");
+                decrypt_with_key(&previous_key, encrypted)?
+            }
+            (result, _) => result?,
+        };
 
         // Deserialize
-        let credentials: Credentials = serde_json::from_slice(&plaintext)?;
-
-        Ok(credentials)
+        Ok(CredentialStore::from_json(&plaintext)?)
     }
 
-    /// Get OAuth credentials for a user from the database.
+    /// Get all OAuth credentials for a user from the database.
     ///
     /// # Arguments
     ///
@@ -334,48 +411,83 @@ impl ConfigServicePg {
     ///
     /// # Returns
     ///
-    /// The user's OAuth credentials if they exist and can be decrypted.
-    pub async fn get_credentials(
+    /// The user's credentials keyed by provider; empty if none are stored.
+    pub async fn get_credential_store(
         &self,
         user_id: Uuid,
-    ) -> Result<Option<Credentials>, ConfigDbError> {
+    ) -> Result<CredentialStore, ConfigDbError> {
         debug!(user_id = %user_id, "Getting credentials from database");
 
-        let row: Option<(Option<Vec<u8>>,)> = sqlx::query_as(
+        let query = sqlx::query_as(
             r#"
             SELECT oauth_credentials
             FROM user_configs
             WHERE user_id = $1
             "#,
         )
-        .bind(user_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        .bind(user_id);
+        let row: Option<(Option<Vec<u8>>,)> =
+            self.instrumented_pool().fetch_optional_as(query).await?;
 
         match row {
             Some((Some(encrypted),)) => {
                 debug!(user_id = %user_id, "Found encrypted credentials");
-                let credentials = self.decrypt_credentials(&encrypted)?;
-                Ok(Some(credentials))
+                self.decrypt_credentials(&encrypted)
             }
             Some((None,)) => {
                 debug!(user_id = %user_id, "No credentials stored");
-                Ok(None)
+                Ok(CredentialStore::default())
             }
             None => {
                 debug!(user_id = %user_id, "No user config found");
-                Ok(None)
+                Ok(CredentialStore::default())
             }
         }
     }
 
-    /// Save OAuth credentials for a user to the database.
+    /// Get the OAuth credentials of the provider a user most recently signed in with.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    ///
+    /// # Returns
+    ///
+    /// The user's current OAuth credentials if they exist and can be decrypted.
+    pub async fn get_credentials(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<Credentials>, ConfigDbError> {
+        Ok(self.get_credential_store(user_id).await?.current().cloned())
+    }
+
+    /// Get a user's OAuth credentials for one provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    /// * `provider` - The OAuth provider name
+    pub async fn get_provider_credentials(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+    ) -> Result<Option<Credentials>, ConfigDbError> {
+        Ok(self
+            .get_credential_store(user_id)
+            .await?
+            .get(provider)
+            .cloned())
+    }
+
+    /// Save a user's OAuth credentials for one provider and make it current.
     ///
-    /// The credentials are encrypted before storage using AES-256-GCM.
+    /// Credentials for other providers are kept. The whole map is encrypted
+    /// before storage using AES-256-GCM.
     ///
     /// # Arguments
     ///
     /// * `user_id` - The UUID of the user
+    /// * `provider` - The OAuth provider name
     /// * `credentials` - The OAuth credentials to save
     ///
     /// # Returns
@@ -384,11 +496,78 @@ impl ConfigServicePg {
     pub async fn save_credentials(
         &self,
         user_id: Uuid,
+        provider: &str,
         credentials: &Credentials,
     ) -> Result<(), ConfigDbError> {
-        debug!(user_id = %user_id, "Saving credentials to database");
+        debug!(user_id = %user_id, provider, "Saving credentials to database");
 
-        let encrypted = self.encrypt_credentials(credentials)?;
+        self.update_credential_store(user_id, |store| {
+            store.set(provider, credentials.clone())
+        })
+        .await?;
+
+        info!(user_id = %user_id, provider, "Credentials saved successfully");
+        Ok(())
+    }
+
+    /// Remove a user's OAuth credentials for one provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    /// * `provider` - The OAuth provider name
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, including when no credentials were stored.
+    pub async fn remove_credentials(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+    ) -> Result<(), ConfigDbError> {
+        debug!(user_id = %user_id, provider, "Removing credentials from database");
+
+        self.update_credential_store(user_id, |store| {
+            store.remove(provider);
+        })
+        .await?;
+
+        info!(user_id = %user_id, provider, "Credentials removed successfully");
+        Ok(())
+    }
+
+    /// Read, modify and write back a user's credentials in one transaction so
+    /// concurrent updates for different providers are not lost.
+    async fn update_credential_store(
+        &self,
+        user_id: Uuid,
+        update: impl FnOnce(&mut CredentialStore),
+    ) -> Result<(), ConfigDbError> {
+        let mut tx = self.pool().begin().await?;
+
+        let row: Option<(Option<Vec<u8>>,)> = sqlx::query_as(
+            r#"
+            SELECT oauth_credentials
+            FROM user_configs
+            WHERE user_id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let mut store = match row {
+            Some((Some(encrypted),)) => self.decrypt_credentials(&encrypted)?,
+            _ => CredentialStore::default(),
+        };
+        update(&mut store);
+
+        let encrypted = if store.is_empty() {
+            None
+        } else {
+            Some(self.encrypt_credentials(&store)?)
+        };
 
         sqlx::query(
             r#"
@@ -402,14 +581,14 @@ impl ConfigServicePg {
         )
         .bind(user_id)
         .bind(encrypted)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        info!(user_id = %user_id, "Credentials saved successfully");
+        tx.commit().await?;
         Ok(())
     }
 
-    /// Delete OAuth credentials for a user.
+    /// Delete OAuth credentials for every provider of a user.
     ///
     /// # Arguments
     ///
@@ -421,21 +600,320 @@ impl ConfigServicePg {
     pub async fn delete_credentials(&self, user_id: Uuid) -> Result<(), ConfigDbError> {
         debug!(user_id = %user_id, "Deleting credentials from database");
 
-        sqlx::query(
+        let query = sqlx::query(
             r#"
             UPDATE user_configs
             SET oauth_credentials = NULL, updated_at = NOW()
             WHERE user_id = $1
             "#,
         )
-        .bind(user_id)
-        .execute(&self.pool)
-        .await?;
+        .bind(user_id);
+        self.instrumented_pool().execute(query).await?;
 
         info!(user_id = %user_id, "Credentials deleted successfully");
         Ok(())
     }
 
+    /// Record an issued refresh token for a user.
+    ///
+    /// Only the token's JWT ID is stored, keyed in the `refresh_tokens` JSONB
+    /// map alongside its expiry. Expired entries for the user are pruned on
+    /// each write so the map does not grow without bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user the token was issued to
+    /// * `jti` - The JWT ID identifying the refresh token
+    /// * `expires_at` - When the refresh token stops being accepted
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or an error if the database operation fails.
+    pub async fn store_refresh_token(
+        &self,
+        user_id: Uuid,
+        jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), ConfigDbError> {
+        debug!(user_id = %user_id, "Storing refresh token");
+
+        let entry = serde_json::json!({ jti: expires_at.to_rfc3339() });
+
+        let query = sqlx::query(
+            r#"
+            INSERT INTO user_configs (user_id, refresh_tokens, created_at, updated_at)
+            VALUES ($1, $2, NOW(), NOW())
+            ON CONFLICT (user_id)
+            DO UPDATE SET
+                refresh_tokens = COALESCE(
+                    (
+                        SELECT jsonb_object_agg(key, value)
+                        FROM jsonb_each_text(user_configs.refresh_tokens)
+                        WHERE value::timestamptz > NOW()
+                    ),
+                    '{}'::jsonb
+                ) || EXCLUDED.refresh_tokens,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(entry);
+        self.instrumented_pool().execute(query).await?;
+
+        Ok(())
+    }
+
+    /// Look up the expiry of a user's refresh token.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user
+    /// * `jti` - The JWT ID identifying the refresh token
+    ///
+    /// # Returns
+    ///
+    /// The token's expiry if it has been stored and not revoked, `None` otherwise.
+    pub async fn get_refresh_token_expiry(
+        &self,
+        user_id: Uuid,
+        jti: &str,
+    ) -> Result<Option<DateTime<Utc>>, ConfigDbError> {
+        let query = sqlx::query_as(
+            r#"
+            SELECT refresh_tokens ->> $2
+            FROM user_configs
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(jti);
+        let row: Option<(Option<String>,)> =
+            self.instrumented_pool().fetch_optional_as(query).await?;
+
+        Ok(row
+            .and_then(|(expires_at,)| expires_at)
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
+
+    /// Revoke a refresh token by its JWT ID.
+    ///
+    /// Revocation is a single atomic update, so when two requests race to
+    /// redeem the same token only one of them observes `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `jti` - The JWT ID identifying the refresh token
+    ///
+    /// # Returns
+    ///
+    /// `true` if an active token was revoked, `false` if it was unknown or
+    /// already revoked.
+    pub async fn revoke_refresh_token(&self, jti: &str) -> Result<bool, ConfigDbError> {
+        debug!("Revoking refresh token");
+
+        let query = sqlx::query(
+            r#"
+            UPDATE user_configs
+            SET refresh_tokens = refresh_tokens - $1, updated_at = NOW()
+            WHERE refresh_tokens ? $1
+            "#,
+        )
+        .bind(jti);
+        let result = self.instrumented_pool().execute(query).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Re-encrypt every stored OAuth credential blob from `old_key` to `new_key`.
+    ///
+    /// Rows are read in batches and rewritten inside a single transaction, so
+    /// either every row is rotated or none is. Rows that already decrypt with
+    /// `new_key` (for example written by an instance that was switched to the
+    /// new key first) are left untouched. Serving instances keep working while
+    /// this runs if they have the new key as `CONFIG_ENCRYPTION_KEY` and the old
+    /// one as `CONFIG_ENCRYPTION_KEY_PREVIOUS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_key` - The key the credentials are currently encrypted with
+    /// * `new_key` - The key to re-encrypt the credentials with
+    ///
+    /// # Returns
+    ///
+    /// The number of rows that were re-encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigDbError::DecryptionFailed`] if a row decrypts with
+    /// neither key; no rows are changed in that case.
+    pub async fn rotate_encryption_key(
+        &self,
+        old_key: [u8; 32],
+        new_key: [u8; 32],
+    ) -> Result<u32, ConfigDbError> {
+        info!("Rotating OAuth credentials encryption key");
+
+        let mut tx = self.pool().begin().await?;
+        let mut rotated = 0u32;
+        let mut last_user_id: Option<Uuid> = None;
+
+        loop {
+            let rows: Vec<(Uuid, Vec<u8>)> = sqlx::query_as(
+                r#"
+                SELECT user_id, oauth_credentials
+                FROM user_configs
+                WHERE oauth_credentials IS NOT NULL
+                  AND ($1::uuid IS NULL OR user_id > $1)
+                ORDER BY user_id
+                LIMIT $2
+                FOR UPDATE
+                "#,
+            )
+            .bind(last_user_id)
+            .bind(ROTATION_BATCH_SIZE)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            let Some((batch_last, _)) = rows.last() else {
+                break;
+            };
+            last_user_id = Some(*batch_last);
+
+            for (user_id, encrypted) in rows {
+                let plaintext = match decrypt_with_key(&old_key, &encrypted) {
+                    Ok(plaintext) => plaintext,
+                    Err(err) => {
+                        if decrypt_with_key(&new_key, &encrypted).is_ok() {
+                            debug!(user_id = %user_id, "Credentials already use the new key");
+                            continue;
+                        }
+                        warn!(user_id = %user_id, "Credentials decrypt with neither key");
+                        return Err(err);
+                    }
+                };
+
+                sqlx::query(
+                    r#"
+                    UPDATE user_configs
+                    SET oauth_credentials = $2, updated_at = NOW()
+                    WHERE user_id = $1
+                    "#,
+                )
+                .bind(user_id)
+                .bind(encrypt_with_key(&new_key, &plaintext)?)
+                .execute(&mut *tx)
+                .await?;
+
+                rotated += 1;
+            }
+        }
+
+        tx.commit().await?;
+
+        info!(rotated, "OAuth credentials encryption key rotated");
+        Ok(rotated)
+    }
+
+    /// List every user with a configuration row, oldest first.
+    ///
+    /// Meant for admin maintenance; use it through
+    /// [`AdminService`](super::admin::AdminService).
+    pub async fn list_all_user_ids(&self) -> Result<Vec<Uuid>, ConfigDbError> {
+        let query = sqlx::query_scalar(
+            r#"
+            SELECT user_id
+            FROM user_configs
+            ORDER BY created_at, user_id
+            "#,
+        );
+        let user_ids = self.instrumented_pool().fetch_all_scalar(query).await?;
+
+        Ok(user_ids)
+    }
+
+    /// Summarize which configuration fields `user_id` has set, without
+    /// reading any values or decrypting credentials.
+    ///
+    /// Meant for admin maintenance; use it through
+    /// [`AdminService`](super::admin::AdminService).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigDbError::ConfigNotFound`] if the user has no
+    /// configuration row.
+    pub async fn list_config_keys(&self, user_id: Uuid) -> Result<ConfigKeySummary, ConfigDbError> {
+        let query = sqlx::query_as(
+            r#"
+            SELECT
+                ARRAY(SELECT jsonb_object_keys(config_json) ORDER BY 1),
+                oauth_credentials IS NOT NULL,
+                updated_at
+            FROM user_configs
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id);
+        let row: Option<(Vec<String>, bool, DateTime<Utc>)> =
+            self.instrumented_pool().fetch_optional_as(query).await?;
+
+        let (config_keys, has_oauth_credentials, updated_at) =
+            row.ok_or(ConfigDbError::ConfigNotFound(user_id))?;
+
+        Ok(ConfigKeySummary {
+            has_oauth_credentials,
+            config_keys,
+            updated_at,
+        })
+    }
+
+    /// Replace the configuration of each of `user_ids` with the default one.
+    /// Credentials and refresh tokens are kept.
+    ///
+    /// # Returns
+    ///
+    /// The number of users whose configuration was reset.
+    pub async fn reset_configs(&self, user_ids: &[Uuid]) -> Result<u64, ConfigDbError> {
+        let config_json = serde_json::to_value(Config::default())?;
+
+        let query = sqlx::query(
+            r#"
+            UPDATE user_configs
+            SET config_json = $2, updated_at = NOW()
+            WHERE user_id = ANY($1)
+            "#,
+        )
+        .bind(user_ids)
+        .bind(config_json);
+        let result = self.instrumented_pool().execute(query).await?;
+
+        info!(reset = result.rows_affected(), "Configs reset to defaults");
+        Ok(result.rows_affected())
+    }
+
+    /// Delete the stored OAuth credentials of each of `user_ids`.
+    ///
+    /// # Returns
+    ///
+    /// The number of users that had credentials.
+    pub async fn delete_credentials_for_users(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<u64, ConfigDbError> {
+        let query = sqlx::query(
+            r#"
+            UPDATE user_configs
+            SET oauth_credentials = NULL, updated_at = NOW()
+            WHERE user_id = ANY($1) AND oauth_credentials IS NOT NULL
+            "#,
+        )
+        .bind(user_ids);
+        let result = self.instrumented_pool().execute(query).await?;
+
+        info!(deleted = result.rows_affected(), "Credentials deleted");
+        Ok(result.rows_affected())
+    }
+
     /// Check if the encryption key is configured.
     ///
     /// # Returns
@@ -445,10 +923,59 @@ impl ConfigServicePg {
         self.encryption_key.is_some()
     }
 
-    /// Get the database pool reference.
-    pub fn pool(&self) -> &PgPool {
-        &self.pool
+    /// Get the current database pool.
+    pub fn pool(&self) -> PgPool {
+        self.db.pool()
     }
+
+    /// Get the current database pool, tracing each query run through it.
+    fn instrumented_pool(&self) -> InstrumentedPool {
+        self.db.instrumented_pool()
+    }
+}
+
+/// Parse a 64-character hex string into a 32-byte encryption key.
+pub fn parse_encryption_key(key_hex: &str) -> Option<[u8; 32]> {
+    let key_bytes = hex::decode(key_hex.trim()).ok()?;
+    key_bytes.try_into().ok()
+}
+
+/// Encrypt `plaintext` with AES-256-GCM, returning `nonce || ciphertext`.
+fn encrypt_with_key(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>, ConfigDbError> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    // Generate random nonce
+    let mut nonce_bytes = [0u8; NONCE_SIZE];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| ConfigDbError::EncryptionFailed(e.to_string()))?;
+
+    // Prepend nonce to ciphertext
+    let mut output = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    output.extend_from_slice(&nonce_bytes);
+    output.extend_from_slice(&ciphertext);
+
+    Ok(output)
+}
+
+/// Decrypt a `nonce || ciphertext` blob produced by [`encrypt_with_key`].
+fn decrypt_with_key(key: &[u8; 32], encrypted: &[u8]) -> Result<Vec<u8>, ConfigDbError> {
+    if encrypted.len() <= NONCE_SIZE {
+        return Err(ConfigDbError::InvalidEncryptedData);
+    }
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    // Extract nonce and ciphertext
+    let nonce = Nonce::from_slice(&encrypted[..NONCE_SIZE]);
+    let ciphertext = &encrypted[NONCE_SIZE..];
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| ConfigDbError::DecryptionFailed(e.to_string()))
 }
 
 #[cfg(test)]
@@ -601,6 +1128,35 @@ mod tests {
         assert_eq!(credentials.expires_at, decrypted.expires_at);
     }
 
+    #[test]
+    fn test_rotated_blob_only_decrypts_with_new_key() {
+        let old_key = TEST_KEY;
+        let new_key = [0x42u8; 32];
+        let plaintext = b"{\"github\":{}}";
+
+        let old_blob = encrypt_with_key(&old_key, plaintext).unwrap();
+        let new_blob =
+            encrypt_with_key(&new_key, &decrypt_with_key(&old_key, &old_blob).unwrap()).unwrap();
+
+        assert_eq!(decrypt_with_key(&new_key, &new_blob).unwrap(), plaintext);
+        assert!(matches!(
+            decrypt_with_key(&old_key, &new_blob),
+            Err(ConfigDbError::DecryptionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_encryption_key() {
+        assert_eq!(
+            parse_encryption_key(
+                "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+            ),
+            Some(TEST_KEY)
+        );
+        assert!(parse_encryption_key("0001").is_none());
+        assert!(parse_encryption_key("not hex").is_none());
+    }
+
     #[test]
     fn test_load_encryption_key_from_env() {
         // SAFETY: This test modifies environment variables, which is safe in a single-threaded
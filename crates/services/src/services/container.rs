@@ -6,6 +6,7 @@ use std::{
 
 use anyhow::{Error as AnyhowError, anyhow};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
@@ -40,9 +41,11 @@ use executors::{
     profile::ExecutorProfileId,
 };
 use futures::{StreamExt, future};
+use serde::Serialize;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
+use ts_rs::TS;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
@@ -51,6 +54,7 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    agent_tokens::{AgentToken, AgentTokenSenders},
     git::{GitService, GitServiceError},
     notification::NotificationService,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
@@ -58,6 +62,24 @@ use crate::services::{
 };
 pub type ContainerRef = String;
 
+/// An execution process that currently has a live child process.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RunningProcess {
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+    pub process_id: Uuid,
+    pub started_at: DateTime<Utc>,
+}
+
+/// CPU and memory currently used by the processes of a workspace.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ResourceUsage {
+    /// Summed over processes, so it can exceed 100 on multi-core machines
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    pub memory_limit_bytes: Option<u64>,
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -80,6 +102,8 @@ pub enum ContainerError {
     KillFailed(std::io::Error),
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+    #[error("Execution process {0} is not running")]
+    ProcessNotRunning(Uuid),
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
@@ -88,6 +112,8 @@ pub enum ContainerError {
 pub trait ContainerService {
     fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>;
 
+    fn agent_token_senders(&self) -> &AgentTokenSenders;
+
     fn db(&self) -> &DBService;
 
     fn git(&self) -> &GitService;
@@ -150,10 +176,12 @@ pub trait ContainerService {
             return false;
         }
 
-        // Always finalize failed or killed executions, regardless of next action
+        // Always finalize failed, killed or cancelled executions, regardless of next action
         if matches!(
             ctx.execution_process.status,
-            ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
+            ExecutionProcessStatus::Failed
+                | ExecutionProcessStatus::Killed
+                | ExecutionProcessStatus::Cancelled
         ) {
             return true;
         }
@@ -170,8 +198,11 @@ pub trait ContainerService {
             tracing::error!("Failed to update task status to InReview: {e}");
         }
 
-        // Skip notification if process was intentionally killed by user
-        if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
+        // Skip notification if process was intentionally killed or cancelled by user
+        if matches!(
+            ctx.execution_process.status,
+            ExecutionProcessStatus::Killed | ExecutionProcessStatus::Cancelled
+        ) {
             return;
         }
 
@@ -252,6 +283,7 @@ pub trait ContainerService {
             if matches!(
                 process.run_reason,
                 ExecutionProcessRunReason::CodingAgent
+                    | ExecutionProcessRunReason::Review
                     | ExecutionProcessRunReason::SetupScript
                     | ExecutionProcessRunReason::CleanupScript
             ) && let Ok(Some(session)) =
@@ -510,6 +542,40 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Stop a running execution process of `workspace`, marking it cancelled.
+    ///
+    /// The process is interrupted first and force-killed if it does not exit
+    /// in time, like `stop_execution`.
+    async fn stop_process(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError>;
+
+    /// Suspend a running execution process of `workspace` and record
+    /// `paused_at`. Pausing a process that is already paused is a no-op.
+    async fn pause_execution(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError>;
+
+    /// Continue an execution process suspended by `pause_execution` and
+    /// clear `paused_at`.
+    async fn resume_execution(
+        &self,
+        workspace: &Workspace,
+        process_id: Uuid,
+    ) -> Result<ExecutionProcess, ContainerError>;
+
+    /// List execution processes that currently have a live child process,
+    /// oldest first.
+    async fn list_running(&self) -> Vec<RunningProcess>;
+
+    /// CPU and memory used by the running processes of a workspace.
+    async fn get_resource_usage(&self, workspace_id: Uuid)
+    -> Result<ResourceUsage, ContainerError>;
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -532,6 +598,16 @@ pub trait ContainerService {
         map.get(uuid).cloned()
     }
 
+    /// Subscribe to the reply tokens of a running coding agent process.
+    /// Returns `None` once the process has finished.
+    async fn subscribe_agent_tokens(
+        &self,
+        id: &Uuid,
+    ) -> Option<tokio::sync::broadcast::Receiver<AgentToken>> {
+        let map = self.agent_token_senders().read().await;
+        map.get(id).map(|sender| sender.subscribe())
+    }
+
     async fn git_branch_prefix(&self) -> String;
 
     async fn git_branch_from_workspace(&self, workspace_id: &Uuid, task_title: &str) -> String {
@@ -867,6 +943,7 @@ pub trait ContainerService {
             &self.db().pool,
             &CreateSession {
                 executor: Some(executor_profile_id.executor.to_string()),
+                user_id: None,
             },
             Uuid::new_v4(),
             workspace.id,
@@ -1146,9 +1223,9 @@ pub trait ContainerService {
             (
                 _,
                 ExecutorActionType::CodingAgentFollowUpRequest(_)
-                | ExecutorActionType::CodingAgentInitialRequest(_)
-                | ExecutorActionType::ReviewRequest(_),
+                | ExecutorActionType::CodingAgentInitialRequest(_),
             ) => ExecutionProcessRunReason::CodingAgent,
+            (_, ExecutorActionType::ReviewRequest(_)) => ExecutionProcessRunReason::Review,
         };
 
         self.start_execution(&ctx.workspace, &ctx.session, next_action, &next_run_reason)
@@ -1,18 +1,33 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    str::FromStr,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
 use db::{
     DBService,
     models::{
-        execution_process::ExecutionProcess, project::Project, scratch::Scratch, session::Session,
-        task::Task, workspace::Workspace,
+        execution_process::{ExecutionProcess, ExecutionProcessStatus},
+        project::Project,
+        scratch::Scratch,
+        session::Session,
+        task::Task,
+        workspace::Workspace,
+        workspace_repo::WorkspaceRepo,
     },
 };
+use futures::{StreamExt, stream::BoxStream};
 use serde_json::json;
 use sqlx::{Error as SqlxError, Sqlite, SqlitePool, decode::Decode, sqlite::SqliteOperation};
-use tokio::sync::RwLock;
-use utils::msg_store::MsgStore;
+use tokio::sync::{RwLock, mpsc, mpsc::error::TrySendError};
+use tokio_stream::wrappers::ReceiverStream;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
 use uuid::Uuid;
 
+use super::{file_search::FileSearchCache, workspace_repo_cache::WorkspaceRepoCache};
+
 #[path = "events/patches.rs"]
 pub mod patches;
 #[path = "events/streams.rs"]
@@ -25,12 +40,29 @@ pub use patches::{
 };
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
+const DEFAULT_EVENT_CHANNEL_SIZE: usize = 256;
+
+/// Smallest accepted buffer; a subscriber must at least fit the initial snapshot
+const MIN_EVENT_CHANNEL_SIZE: usize = 16;
+
+/// Messages buffered per stream subscriber before it is evicted, from `EVENT_CHANNEL_SIZE`
+static EVENT_CHANNEL_SIZE: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("EVENT_CHANNEL_SIZE")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .map(|size| size.max(MIN_EVENT_CHANNEL_SIZE))
+        .unwrap_or(DEFAULT_EVENT_CHANNEL_SIZE)
+});
+
+type EventStream = BoxStream<'static, Result<LogMsg, std::io::Error>>;
+
 #[derive(Clone)]
 pub struct EventService {
     msg_store: Arc<MsgStore>,
     db: DBService,
     #[allow(dead_code)]
     entry_count: Arc<RwLock<usize>>,
+    subscribers: Arc<AtomicUsize>,
 }
 
 impl EventService {
@@ -40,9 +72,57 @@ impl EventService {
             msg_store,
             db,
             entry_count,
+            subscribers: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Number of event stream subscribers currently connected
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.load(Ordering::Relaxed)
+    }
+
+    /// Hand `stream` to its subscriber through a bounded channel.
+    ///
+    /// A forwarding task drains the source so a slow client never holds up the
+    /// shared broadcast. When the subscriber's buffer is full it is evicted: its
+    /// stream ends and the client is expected to reconnect for a fresh snapshot.
+    fn bounded_subscriber(
+        &self,
+        stream_name: &'static str,
+        mut stream: EventStream,
+    ) -> EventStream {
+        let capacity = *EVENT_CHANNEL_SIZE;
+        let (tx, rx) = mpsc::channel(capacity);
+        let subscribers = self.subscribers.clone();
+        subscribers.fetch_add(1, Ordering::Relaxed);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    item = stream.next() => {
+                        let Some(item) = item else { break };
+                        match tx.try_send(item) {
+                            Ok(()) => {}
+                            Err(TrySendError::Full(_)) => {
+                                tracing::warn!(
+                                    stream = stream_name,
+                                    capacity,
+                                    "Evicting slow event subscriber"
+                                );
+                                break;
+                            }
+                            Err(TrySendError::Closed(_)) => break,
+                        }
+                    }
+                }
+            }
+            subscribers.fetch_sub(1, Ordering::Relaxed);
+        });
+
+        ReceiverStream::new(rx).boxed()
+    }
+
     async fn push_task_update_for_task(
         pool: &SqlitePool,
         msg_store: Arc<MsgStore>,
@@ -90,11 +170,29 @@ impl EventService {
         Ok(())
     }
 
+    /// Drop cached file indexes for the repos of a session's workspace, since
+    /// a finished agent run has likely changed files in them
+    async fn invalidate_file_search_for_session(
+        pool: &SqlitePool,
+        file_search_cache: &FileSearchCache,
+        session_id: Uuid,
+    ) -> Result<(), SqlxError> {
+        if let Some(session) = Session::find_by_id(pool, session_id).await? {
+            let repos = WorkspaceRepo::find_repos_for_workspace(pool, session.workspace_id).await?;
+            for repo in repos {
+                file_search_cache.invalidate_for_repo(repo.id).await;
+            }
+        }
+        Ok(())
+    }
+
     /// Creates the hook function that should be used with DBService::new_with_after_connect
     pub fn create_hook(
         msg_store: Arc<MsgStore>,
         entry_count: Arc<RwLock<usize>>,
         db_service: DBService,
+        file_search_cache: Arc<FileSearchCache>,
+        workspace_repo_cache: Arc<WorkspaceRepoCache>,
     ) -> impl for<'a> Fn(
         &'a mut sqlx::sqlite::SqliteConnection,
     ) -> std::pin::Pin<
@@ -106,12 +204,16 @@ impl EventService {
             let msg_store_for_hook = msg_store.clone();
             let entry_count_for_hook = entry_count.clone();
             let db_for_hook = db_service.clone();
+            let file_search_cache_for_hook = file_search_cache.clone();
+            let workspace_repo_cache_for_hook = workspace_repo_cache.clone();
             Box::pin(async move {
                 let mut handle = conn.lock_handle().await?;
                 let runtime_handle = tokio::runtime::Handle::current();
                 handle.set_preupdate_hook({
                     let msg_store_for_preupdate = msg_store_for_hook.clone();
                     move |preupdate: sqlx::sqlite::PreupdateHookResult<'_>| {
+                        invalidate_workspace_repo_cache(&preupdate, &workspace_repo_cache_for_hook);
+
                         if preupdate.operation != SqliteOperation::Delete {
                             return;
                         }
@@ -172,6 +274,7 @@ impl EventService {
                     let entry_count_for_hook = entry_count_for_hook.clone();
                     let msg_store_for_hook = msg_store_for_hook.clone();
                     let db = db_for_hook.clone();
+                    let file_search_cache = file_search_cache_for_hook.clone();
 
                     if let Ok(table) = HookTables::from_str(hook.table) {
                         let rowid = hook.rowid;
@@ -270,6 +373,11 @@ impl EventService {
 
                             // Handle task-related operations with direct patches
                             match &record_type {
+                                RecordTypes::Task(task) if task.deleted_at.is_some() => {
+                                    // A soft delete is an UPDATE; clients see it as a removal
+                                    msg_store_for_hook.push_patch(task_patch::remove(task.id));
+                                    return;
+                                }
                                 RecordTypes::Task(task) => {
                                     // Convert Task to TaskWithAttemptStatus
                                     if let Ok(task_list) =
@@ -285,8 +393,10 @@ impl EventService {
                                             SqliteOperation::Insert => {
                                                 task_patch::add(&task_with_status)
                                             }
+                                            // A restored task is absent from client state, and
+                                            // add overwrites an existing key, so updates use add
                                             SqliteOperation::Update => {
-                                                task_patch::replace(&task_with_status)
+                                                task_patch::add(&task_with_status)
                                             }
                                             _ => task_patch::replace(&task_with_status), // fallback
                                         };
@@ -302,10 +412,16 @@ impl EventService {
                                     msg_store_for_hook.push_patch(patch);
                                     return;
                                 }
+                                RecordTypes::Project(project) if project.is_archived => {
+                                    // Archived projects drop out of the default listing
+                                    msg_store_for_hook.push_patch(project_patch::remove(project.id));
+                                    return;
+                                }
                                 RecordTypes::Project(project) => {
+                                    // `add` also covers unarchive, where the key is absent
                                     let patch = match hook.operation {
                                         SqliteOperation::Insert => project_patch::add(project),
-                                        SqliteOperation::Update => project_patch::replace(project),
+                                        SqliteOperation::Update => project_patch::add(project),
                                         _ => project_patch::replace(project),
                                     };
                                     msg_store_for_hook.push_patch(patch);
@@ -420,6 +536,26 @@ impl EventService {
                                         );
                                     }
 
+                                    if matches!(hook.operation, SqliteOperation::Update)
+                                        && matches!(
+                                            process.status,
+                                            ExecutionProcessStatus::Completed
+                                                | ExecutionProcessStatus::Failed
+                                        )
+                                        && let Err(err) =
+                                            EventService::invalidate_file_search_for_session(
+                                                &db.pool,
+                                                &file_search_cache,
+                                                process.session_id,
+                                            )
+                                            .await
+                                    {
+                                        tracing::error!(
+                                            "Failed to invalidate file search cache after execution process change: {:?}",
+                                            err
+                                        );
+                                    }
+
                                     return;
                                 }
                                 RecordTypes::DeletedExecutionProcess {
@@ -501,3 +637,28 @@ impl EventService {
         &self.msg_store
     }
 }
+
+/// Drop cached workspace repos affected by a write to `workspaces`,
+/// `workspace_repos` or `repos`.
+fn invalidate_workspace_repo_cache(
+    preupdate: &sqlx::sqlite::PreupdateHookResult<'_>,
+    cache: &WorkspaceRepoCache,
+) {
+    let value = match (preupdate.table, &preupdate.operation) {
+        // Nothing is cached for a new workspace or repo yet
+        ("workspaces" | "repos", SqliteOperation::Insert) => return,
+        ("repos", _) => {
+            cache.invalidate_all();
+            return;
+        }
+        ("workspaces", _) => preupdate.get_old_column_value(0),
+        ("workspace_repos", SqliteOperation::Insert) => preupdate.get_new_column_value(1),
+        ("workspace_repos", _) => preupdate.get_old_column_value(1),
+        _ => return,
+    };
+    if let Ok(value) = value
+        && let Ok(workspace_id) = <Uuid as Decode<Sqlite>>::decode(value)
+    {
+        cache.invalidate(workspace_id);
+    }
+}
@@ -2,6 +2,7 @@ use db::models::{
     execution_process::ExecutionProcess,
     project::Project,
     scratch::Scratch,
+    session::Session,
     task::{Task, TaskWithAttemptStatus},
     workspace::Workspace,
 };
@@ -142,7 +143,8 @@ impl EventService {
 
         // Start with initial snapshot, Ready signal, then live updates
         let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+        let combined_stream =
+            self.bounded_subscriber("tasks", initial_stream.chain(filtered_stream).boxed());
 
         Ok(combined_stream)
     }
@@ -221,7 +223,8 @@ impl EventService {
 
         // Start with initial snapshot, Ready signal, then live updates
         let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+        let combined_stream =
+            self.bounded_subscriber("projects", initial_stream.chain(filtered_stream).boxed());
 
         Ok(combined_stream)
     }
@@ -351,7 +354,109 @@ impl EventService {
 
         // Start with initial snapshot, Ready signal, then live updates
         let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+        let combined_stream = self.bounded_subscriber(
+            "execution_processes",
+            initial_stream.chain(filtered_stream).boxed(),
+        );
+
+        Ok(combined_stream)
+    }
+
+    /// Stream sessions for a workspace with initial snapshot (raw LogMsg format for WebSocket)
+    ///
+    /// The snapshot carries `/sessions` and their non-dropped `/execution_processes`; live
+    /// updates are the execution process patches of any session in the workspace, including
+    /// sessions created after the stream was opened.
+    pub async fn stream_sessions_raw(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let sessions = Session::find_by_workspace_id(&self.db.pool, workspace_id).await?;
+
+        let mut processes_map = serde_json::Map::new();
+        for session in &sessions {
+            for process in
+                ExecutionProcess::find_by_session_id(&self.db.pool, session.id, false).await?
+            {
+                processes_map.insert(process.id.to_string(), serde_json::to_value(process)?);
+            }
+        }
+
+        // Convert sessions array to object keyed by session ID
+        let sessions_map: serde_json::Map<String, serde_json::Value> = sessions
+            .into_iter()
+            .map(|session| {
+                (
+                    session.id.to_string(),
+                    serde_json::to_value(session).unwrap(),
+                )
+            })
+            .collect();
+
+        let initial_patch = json!([
+            {
+                "op": "replace",
+                "path": "/sessions",
+                "value": sessions_map
+            },
+            {
+                "op": "replace",
+                "path": "/execution_processes",
+                "value": processes_map
+            }
+        ]);
+        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+
+        let db_pool = self.db.pool.clone();
+
+        // Get filtered event stream (execution processes of this workspace's sessions)
+        let filtered_stream =
+            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
+                let db_pool = db_pool.clone();
+                async move {
+                    match msg_result {
+                        Ok(LogMsg::JsonPatch(patch)) => {
+                            let patch_op = patch.0.first()?;
+                            if !patch_op.path().starts_with("/execution_processes/") {
+                                return None;
+                            }
+
+                            let value = match patch_op {
+                                json_patch::PatchOperation::Add(op) => &op.value,
+                                json_patch::PatchOperation::Replace(op) => &op.value,
+                                json_patch::PatchOperation::Remove(_) => {
+                                    // The removed process is gone, so its session can't be
+                                    // checked; let the client ignore unknown IDs
+                                    return Some(Ok(LogMsg::JsonPatch(patch)));
+                                }
+                                _ => return None,
+                            };
+
+                            let process =
+                                serde_json::from_value::<ExecutionProcess>(value.clone()).ok()?;
+                            match Session::find_by_id(&db_pool, process.session_id).await {
+                                Ok(Some(session)) if session.workspace_id == workspace_id => {
+                                    if process.dropped {
+                                        return Some(Ok(LogMsg::JsonPatch(
+                                            execution_process_patch::remove(process.id),
+                                        )));
+                                    }
+                                    Some(Ok(LogMsg::JsonPatch(patch)))
+                                }
+                                _ => None,
+                            }
+                        }
+                        Ok(other) => Some(Ok(other)), // Pass through non-patch messages
+                        Err(_) => None,               // Filter out broadcast errors
+                    }
+                }
+            });
+
+        // Start with initial snapshot, Ready signal, then live updates
+        let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
+        let combined_stream =
+            self.bounded_subscriber("sessions", initial_stream.chain(filtered_stream).boxed());
 
         Ok(combined_stream)
     }
@@ -430,7 +535,8 @@ impl EventService {
             });
 
         let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+        let combined_stream =
+            self.bounded_subscriber("scratch", initial_stream.chain(filtered_stream).boxed());
         Ok(combined_stream)
     }
 
@@ -520,6 +626,6 @@ impl EventService {
         );
 
         let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        Ok(initial_stream.chain(filtered_stream).boxed())
+        Ok(self.bounded_subscriber("workspaces", initial_stream.chain(filtered_stream).boxed()))
     }
 }
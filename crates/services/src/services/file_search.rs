@@ -1,6 +1,9 @@
 use std::{
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
     time::{Duration, Instant},
 };
 
@@ -8,6 +11,7 @@ use dashmap::DashMap;
 use db::models::{
     project::{Project, SearchMatchType, SearchResult},
     project_repo::ProjectRepo,
+    repo::Repo,
 };
 use fst::{Map, MapBuilder};
 use ignore::WalkBuilder;
@@ -18,14 +22,21 @@ use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use thiserror::Error;
 use tokio::sync::mpsc;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 use ts_rs::TS;
+use uuid::Uuid;
 
 use super::{
     file_ranker::{FileRanker, FileStats},
     git::GitService,
 };
 
+/// Default maximum number of repository indexes kept in the cache.
+const DEFAULT_MAX_ENTRIES: u64 = 256;
+
+/// Default lifetime of a cached repository index, in seconds.
+const DEFAULT_TTL_SECS: u64 = 300;
+
 /// Search mode for different use cases
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "lowercase")]
@@ -91,23 +102,41 @@ pub enum CacheError {
     BuildError(String),
 }
 
+/// Hit/miss counters and size of the file search cache
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+    /// Fraction of searches served from the cache, `0.0` before any search
+    pub hit_rate: f64,
+}
+
 /// File search cache with FST indexing
 pub struct FileSearchCache {
     cache: Cache<PathBuf, CachedRepo>,
+    hits: AtomicU64,
+    misses: AtomicU64,
     git_service: GitService,
     file_ranker: FileRanker,
     build_queue: mpsc::UnboundedSender<PathBuf>,
     watchers: DashMap<PathBuf, RecommendedWatcher>,
+    /// Source path of every repo searched or warmed by ID, for invalidation
+    repo_paths: DashMap<Uuid, PathBuf>,
 }
 
 impl FileSearchCache {
     pub fn new() -> Self {
         let (build_sender, build_receiver) = mpsc::unbounded_channel();
 
-        // Create cache with 100MB limit and 1 hour TTL
+        // Bounded by FILE_SEARCH_CACHE_MAX_ENTRIES repos (least recently used
+        // are evicted first); entries expire FILE_SEARCH_CACHE_TTL_SECS after
+        // they were built
+        let max_entries = env_u64("FILE_SEARCH_CACHE_MAX_ENTRIES", DEFAULT_MAX_ENTRIES);
+        let ttl_secs = env_u64("FILE_SEARCH_CACHE_TTL_SECS", DEFAULT_TTL_SECS);
         let cache = Cache::builder()
-            .max_capacity(50) // Max 50 repos
-            .time_to_live(Duration::from_secs(3600)) // 1 hour TTL
+            .max_capacity(max_entries)
+            .time_to_live(Duration::from_secs(ttl_secs))
             .build();
 
         let cache_for_worker = cache.clone();
@@ -129,10 +158,13 @@ impl FileSearchCache {
 
         Self {
             cache,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
             git_service,
             file_ranker,
             build_queue: build_sender,
             watchers: DashMap::new(),
+            repo_paths: DashMap::new(),
         }
     }
 
@@ -151,10 +183,12 @@ impl FileSearchCache {
             && head_info.oid == cached.head_sha
         {
             // Cache hit - perform fast search with mode-based filtering
+            self.hits.fetch_add(1, Ordering::Relaxed);
             return Ok(self.search_in_cache(&cached, query, mode).await);
         }
 
         // Cache miss - trigger background refresh and return error
+        self.misses.fetch_add(1, Ordering::Relaxed);
         if let Err(e) = self.build_queue.send(repo_path_buf) {
             warn!("Failed to enqueue cache build: {}", e);
         }
@@ -162,6 +196,23 @@ impl FileSearchCache {
         Err(CacheError::Miss)
     }
 
+    /// Hit/miss counts since startup and the current number of cached repos
+    pub fn stats(&self) -> CacheStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let total = hits + misses;
+        CacheStats {
+            hits,
+            misses,
+            entries: self.cache.entry_count(),
+            hit_rate: if total == 0 {
+                0.0
+            } else {
+                hits as f64 / total as f64
+            },
+        }
+    }
+
     /// Pre-warm cache for given repositories
     pub async fn warm_repos(&self, repo_paths: Vec<PathBuf>) -> Result<(), String> {
         for repo_path in repo_paths {
@@ -196,6 +247,7 @@ impl FileSearchCache {
                 .await
                 .map_err(|e| format!("Failed to fetch repositories for project: {e}"))?;
             for repo in repos {
+                self.repo_paths.insert(repo.id, repo.path.clone());
                 repo_paths.push(repo.path);
             }
         }
@@ -279,7 +331,7 @@ impl FileSearchCache {
     /// Search files in a single repository with cache + fallback
     pub async fn search_repo(
         &self,
-        repo_path: &Path,
+        repo: &Repo,
         query: &str,
         mode: SearchMode,
     ) -> Result<Vec<SearchResult>, String> {
@@ -288,6 +340,9 @@ impl FileSearchCache {
             return Ok(vec![]);
         }
 
+        self.repo_paths.insert(repo.id, repo.path.clone());
+        let repo_path = repo.path.as_path();
+
         // Try cache first
         match self.search(repo_path, query, mode.clone()).await {
             Ok(results) => Ok(results),
@@ -298,6 +353,18 @@ impl FileSearchCache {
         }
     }
 
+    /// Drop the cached index for a repository after its files changed outside
+    /// of a commit (worktree created, restored or removed, or an agent run
+    /// finished). The next search falls back to the filesystem and queues a
+    /// rebuild. Repos that were never searched or warmed are ignored.
+    pub async fn invalidate_for_repo(&self, repo_id: Uuid) {
+        let Some(repo_path) = self.repo_paths.get(&repo_id).map(|p| p.clone()) else {
+            return;
+        };
+        self.cache.invalidate(&repo_path).await;
+        debug!("Invalidated file search cache for repo: {:?}", repo_path);
+    }
+
     /// Fallback filesystem search when cache is not available
     async fn search_files_no_cache(
         &self,
@@ -588,10 +655,13 @@ impl FileSearchCache {
         while let Some(repo_path) = build_receiver.recv().await {
             let cache_builder = FileSearchCache {
                 cache: cache.clone(),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
                 git_service: git_service.clone(),
                 file_ranker: file_ranker.clone(),
                 build_queue: mpsc::unbounded_channel().0, // Dummy sender
                 watchers: DashMap::new(),
+                repo_paths: DashMap::new(),
             };
 
             match cache_builder.build_repo_cache(&repo_path).await {
@@ -664,8 +734,39 @@ impl FileSearchCache {
     }
 }
 
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(default)
+}
+
 impl Default for FileSearchCache {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stats_count_cache_misses() {
+        let cache = FileSearchCache::new();
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (0, 0));
+        assert_eq!(stats.hit_rate, 0.0);
+
+        let missing = tempfile::tempdir().unwrap().path().join("missing");
+        assert!(matches!(
+            cache.search(&missing, "main", SearchMode::TaskForm).await,
+            Err(CacheError::Miss)
+        ));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (0, 1));
+        assert_eq!(stats.entries, 0);
+    }
+}
@@ -5,6 +5,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use chrono::{DateTime, Utc};
 #[cfg(not(feature = "qa-mode"))]
 use ignore::WalkBuilder;
 use serde::Serialize;
@@ -29,6 +30,12 @@ pub enum FilesystemError {
     Io(#[from] std::io::Error),
     #[error("Unauthorized: path {0} is outside user workspace boundary")]
     Unauthorized(String),
+    #[error("File does not exist")]
+    FileDoesNotExist,
+    #[error("Path is not a file")]
+    PathIsNotFile,
+    #[error("Path {0} is not inside a git repository")]
+    NotInGitRepo(String),
 }
 
 impl From<WorkspaceError> for FilesystemError {
@@ -54,6 +61,16 @@ pub struct DirectoryEntry {
     pub last_modified: Option<u64>,
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct FileMetadata {
+    pub size_bytes: u64,
+    pub modified_at: DateTime<Utc>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub mime_type: String,
+    pub is_readable: bool,
+    pub is_writable: bool,
+}
+
 impl Default for FilesystemService {
     fn default() -> Self {
         Self::new()
@@ -611,4 +628,144 @@ impl FilesystemService {
             current_path: resolved_path.to_string_lossy().to_string(),
         })
     }
+
+    /// Resolve a path for file access, validating it against the user's
+    /// workspace boundary in Kubernetes mode.
+    fn resolve_path_for_user(
+        &self,
+        user_id: Option<&Uuid>,
+        path: &Path,
+    ) -> Result<PathBuf, FilesystemError> {
+        match user_id {
+            Some(uid) => self.validate_path_for_user(uid, path),
+            None => Ok(path.to_path_buf()),
+        }
+    }
+
+    /// Read the contents of a file.
+    ///
+    /// In Kubernetes mode, the path must be within the user's workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - Optional user UUID for workspace restriction
+    /// * `path` - The file to read
+    pub async fn read_file(
+        &self,
+        user_id: Option<&Uuid>,
+        path: &Path,
+    ) -> Result<Vec<u8>, FilesystemError> {
+        let resolved_path = self.resolve_path_for_user(user_id, path)?;
+
+        if !resolved_path.exists() {
+            return Err(FilesystemError::FileDoesNotExist);
+        }
+        if !resolved_path.is_file() {
+            return Err(FilesystemError::PathIsNotFile);
+        }
+
+        Ok(tokio::fs::read(&resolved_path).await?)
+    }
+
+    /// Write the contents of a file, creating it if it does not exist.
+    ///
+    /// The file must live inside an existing git repository (and not inside
+    /// its `.git` directory) so the API cannot be used to write arbitrary
+    /// files to the container filesystem. Symlinks are never written through.
+    /// In Kubernetes mode, the path must also be within the user's workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - Optional user UUID for workspace restriction
+    /// * `path` - The file to write
+    /// * `contents` - The bytes to write
+    pub async fn write_file(
+        &self,
+        user_id: Option<&Uuid>,
+        path: &Path,
+        contents: &[u8],
+    ) -> Result<(), FilesystemError> {
+        let resolved_path = self.resolve_path_for_user(user_id, path)?;
+
+        if resolved_path.is_dir() {
+            return Err(FilesystemError::PathIsNotFile);
+        }
+
+        let parent = resolved_path
+            .parent()
+            .ok_or(FilesystemError::DirectoryDoesNotExist)?;
+        Self::verify_directory(parent)?;
+
+        // Canonicalize the parent so symlinks cannot escape the repository
+        let canonical_parent = dunce::canonicalize(parent)?;
+        let inside_git_dir = canonical_parent
+            .components()
+            .any(|component| component.as_os_str() == ".git");
+        let in_repo = canonical_parent
+            .ancestors()
+            .any(|ancestor| ancestor.join(".git").exists());
+        if inside_git_dir || !in_repo {
+            return Err(FilesystemError::NotInGitRepo(
+                resolved_path.display().to_string(),
+            ));
+        }
+
+        let target = match resolved_path.file_name() {
+            Some(name) => canonical_parent.join(name),
+            None => return Err(FilesystemError::PathIsNotFile),
+        };
+        // Writing through a symlink would follow it out of the repository
+        if tokio::fs::symlink_metadata(&target)
+            .await
+            .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        {
+            return Err(FilesystemError::PathIsNotFile);
+        }
+        tokio::fs::write(&target, contents).await?;
+        Ok(())
+    }
+
+    /// Get size, timestamps, MIME type and access flags for a file.
+    ///
+    /// The MIME type is guessed from the file extension. In Kubernetes mode,
+    /// the path must be within the user's workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - Optional user UUID for workspace restriction
+    /// * `path` - The file to inspect
+    pub async fn get_file_metadata(
+        &self,
+        user_id: Option<&Uuid>,
+        path: &Path,
+    ) -> Result<FileMetadata, FilesystemError> {
+        let resolved_path = self.resolve_path_for_user(user_id, path)?;
+
+        if !resolved_path.exists() {
+            return Err(FilesystemError::FileDoesNotExist);
+        }
+        if !resolved_path.is_file() {
+            return Err(FilesystemError::PathIsNotFile);
+        }
+
+        let metadata = tokio::fs::metadata(&resolved_path).await?;
+        let is_readable = tokio::fs::File::open(&resolved_path).await.is_ok();
+        // Opening for write without truncate leaves the file untouched
+        let is_writable = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&resolved_path)
+            .await
+            .is_ok();
+
+        Ok(FileMetadata {
+            size_bytes: metadata.len(),
+            modified_at: DateTime::<Utc>::from(metadata.modified()?),
+            created_at: metadata.created().ok().map(DateTime::<Utc>::from),
+            mime_type: mime_guess::from_path(&resolved_path)
+                .first_or_octet_stream()
+                .to_string(),
+            is_readable,
+            is_writable,
+        })
+    }
 }
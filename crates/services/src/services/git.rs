@@ -4,6 +4,7 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
+use db::models::merge::PullRequestInfo;
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
     Repository, Sort,
@@ -21,6 +22,9 @@ pub use cli::{GitCli, GitCliError};
 
 use super::config_db::{ConfigDbError, ConfigServicePg};
 use super::file_ranker::FileStat;
+use super::git_host::{
+    CreatePrRequest, GitHostError, GitHostProvider, GitHostService, ProviderKind, gitlab,
+};
 use super::oauth_credentials::Credentials;
 use super::workspace_manager::{WorkspaceError, WorkspaceManager};
 
@@ -48,6 +52,8 @@ pub enum GitServiceError {
     Unauthorized(String),
     #[error("Credential error: {0}")]
     CredentialError(String),
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
 }
 
 impl From<WorkspaceError> for GitServiceError {
@@ -1240,6 +1246,17 @@ impl GitService {
         Ok(())
     }
 
+    pub fn repair_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.worktree_repair(repo_path, worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
     pub fn prune_worktrees(&self, repo_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
         git.worktree_prune(repo_path)
@@ -1988,6 +2005,62 @@ impl GitService {
             })
     }
 
+    /// Open a pull request from `head_branch` into `base_branch` on the host
+    /// of the repo's remote. GitHub and Azure DevOps are authenticated by
+    /// their CLI; GitLab merge requests use `gitlab_credentials`, the user's
+    /// stored GitLab OAuth credentials. The head branch must already be
+    /// pushed. `base_branch` may name a remote-tracking branch such as
+    /// `upstream/main` to target another remote.
+    pub async fn create_pull_request(
+        &self,
+        repo_path: &Path,
+        head_branch: &str,
+        base_branch: &str,
+        title: &str,
+        body: Option<&str>,
+        gitlab_credentials: Option<&Credentials>,
+    ) -> Result<PullRequestInfo, GitServiceError> {
+        let push_remote = self.resolve_remote_name_for_branch(repo_path, head_branch)?;
+        let (target_remote, base_branch) =
+            match self.get_remote_name_from_branch_name(repo_path, base_branch) {
+                Ok(remote) => {
+                    let branch = base_branch
+                        .strip_prefix(&format!("{remote}/"))
+                        .unwrap_or(base_branch);
+                    (remote, branch.to_string())
+                }
+                Err(_) => (push_remote.clone(), base_branch.to_string()),
+            };
+        let head_repo_url = self.get_remote_url(repo_path, &push_remote)?;
+        let remote_url = self.get_remote_url(repo_path, &target_remote)?;
+
+        let request = CreatePrRequest {
+            title: title.to_string(),
+            body: body.map(str::to_string),
+            head_branch: head_branch.to_string(),
+            base_branch,
+            draft: None,
+            head_repo_url: Some(head_repo_url),
+        };
+
+        match GitHostService::provider_for_repo(repo_path, &remote_url) {
+            ProviderKind::GitLab => {
+                let token = gitlab_credentials
+                    .and_then(|c| c.access_token.as_deref())
+                    .ok_or_else(|| {
+                        GitHostError::AuthFailed(
+                            "Sign in with GitLab to open merge requests".to_string(),
+                        )
+                    })?;
+                Ok(gitlab::create_merge_request(&remote_url, token, &request).await?)
+            }
+            kind => {
+                let git_host = GitHostService::from_kind(kind)?;
+                Ok(git_host.create_pr(repo_path, &remote_url, &request).await?)
+            }
+        }
+    }
+
     fn get_remote_from_branch_ref<'a>(
         &self,
         repo: &'a Repository,
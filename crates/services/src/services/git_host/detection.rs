@@ -8,6 +8,8 @@ use super::types::ProviderKind;
 /// - GitHub.com: `https://github.com/owner/repo` or `git@github.com:owner/repo.git`
 /// - GitHub Enterprise: URLs containing `github.` (e.g., `https://github.company.com/owner/repo`)
 /// - Azure DevOps: `https://dev.azure.com/org/project/_git/repo` or legacy `https://org.visualstudio.com/...`
+/// - GitLab: `https://gitlab.com/group/repo`, `git@gitlab.com:group/repo.git` or
+///   self-managed hosts containing `gitlab.` (e.g., `https://gitlab.company.com/group/repo`)
 pub fn detect_provider_from_url(url: &str) -> ProviderKind {
     let url_lower = url.to_lowercase();
 
@@ -33,6 +35,10 @@ pub fn detect_provider_from_url(url: &str) -> ProviderKind {
         return ProviderKind::GitHub;
     }
 
+    if url_lower.contains("gitlab.") {
+        return ProviderKind::GitLab;
+    }
+
     ProviderKind::Unknown
 }
 
@@ -137,11 +143,23 @@ mod tests {
     }
 
     #[test]
-    fn test_unknown_provider() {
+    fn test_gitlab() {
         assert_eq!(
             detect_provider_from_url("https://gitlab.com/owner/repo"),
-            ProviderKind::Unknown
+            ProviderKind::GitLab
+        );
+        assert_eq!(
+            detect_provider_from_url("git@gitlab.com:group/subgroup/repo.git"),
+            ProviderKind::GitLab
         );
+        assert_eq!(
+            detect_provider_from_url("https://gitlab.company.com/group/repo"),
+            ProviderKind::GitLab
+        );
+    }
+
+    #[test]
+    fn test_unknown_provider() {
         assert_eq!(
             detect_provider_from_url("https://bitbucket.org/owner/repo"),
             ProviderKind::Unknown
@@ -0,0 +1,229 @@
+//! GitLab merge requests through the REST API.
+//!
+//! Unlike the GitHub and Azure DevOps providers no CLI is involved: requests
+//! are authenticated with the user's stored GitLab OAuth token.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PullRequestInfo};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::types::{CreatePrRequest, GitHostError};
+
+/// Provider name the GitLab OAuth credentials are stored under.
+pub const GITLAB_OAUTH_PROVIDER: &str = "gitlab";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A GitLab project, parsed from a remote URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitLabProject {
+    /// Base URL of the GitLab instance, e.g. `https://gitlab.com`.
+    pub base_url: String,
+    /// Project path including its groups, e.g. `group/subgroup/repo`.
+    pub path: String,
+}
+
+impl GitLabProject {
+    /// Parse an HTTPS, SSH or scp-style (`git@host:group/repo.git`) remote URL.
+    pub fn from_remote_url(remote_url: &str) -> Result<Self, GitHostError> {
+        let invalid = || GitHostError::Repository(format!("Invalid GitLab remote: {remote_url}"));
+
+        let (base_url, path) = match Url::parse(remote_url) {
+            Ok(url) => {
+                let host = url.host_str().ok_or_else(invalid)?;
+                let base_url = match url.scheme() {
+                    "http" | "https" => match url.port() {
+                        Some(port) => format!("{}://{host}:{port}", url.scheme()),
+                        None => format!("{}://{host}", url.scheme()),
+                    },
+                    // The SSH port says nothing about where the API is served
+                    _ => format!("https://{host}"),
+                };
+                (base_url, url.path().to_string())
+            }
+            Err(_) => {
+                let (user_host, path) = remote_url.split_once(':').ok_or_else(invalid)?;
+                let host = user_host.rsplit('@').next().unwrap_or(user_host);
+                (format!("https://{host}"), path.to_string())
+            }
+        };
+
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if !path.contains('/') {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            base_url,
+            path: path.to_string(),
+        })
+    }
+
+    fn merge_requests_url(&self) -> String {
+        format!(
+            "{}/api/v4/projects/{}/merge_requests",
+            self.base_url,
+            self.path.replace('/', "%2F")
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CreateMergeRequestBody<'a> {
+    source_branch: &'a str,
+    target_branch: &'a str,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: i64,
+    web_url: String,
+    state: String,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+}
+
+impl From<MergeRequest> for PullRequestInfo {
+    fn from(mr: MergeRequest) -> Self {
+        Self {
+            number: mr.iid,
+            url: mr.web_url,
+            status: merge_status(&mr.state),
+            merged_at: mr.merged_at,
+            merge_commit_sha: mr.merge_commit_sha,
+        }
+    }
+}
+
+fn merge_status(state: &str) -> MergeStatus {
+    match state {
+        "opened" => MergeStatus::Open,
+        "merged" => MergeStatus::Merged,
+        "closed" | "locked" => MergeStatus::Closed,
+        _ => MergeStatus::Unknown,
+    }
+}
+
+/// Open a merge request in the project of `remote_url`. The head branch must
+/// already be pushed to that project; merge requests from forks are rejected.
+pub async fn create_merge_request(
+    remote_url: &str,
+    access_token: &str,
+    request: &CreatePrRequest,
+) -> Result<PullRequestInfo, GitHostError> {
+    let project = GitLabProject::from_remote_url(remote_url)?;
+    if let Some(head_repo_url) = request.head_repo_url.as_deref()
+        && GitLabProject::from_remote_url(head_repo_url)? != project
+    {
+        return Err(GitHostError::PullRequest(
+            "Merge requests from another GitLab project are not supported".to_string(),
+        ));
+    }
+
+    // GitLab marks drafts by title prefix
+    let title = if request.draft == Some(true) {
+        format!("Draft: {}", request.title)
+    } else {
+        request.title.clone()
+    };
+    let body = CreateMergeRequestBody {
+        source_branch: &request.head_branch,
+        target_branch: &request.base_branch,
+        title,
+        description: request.body.as_deref(),
+    };
+
+    let client = Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| GitHostError::PullRequest(format!("Failed to build GitLab client: {e}")))?;
+    let response = client
+        .post(project.merge_requests_url())
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| GitHostError::PullRequest(format!("Failed to reach GitLab: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(match status {
+            StatusCode::UNAUTHORIZED => GitHostError::AuthFailed(message),
+            StatusCode::FORBIDDEN => GitHostError::InsufficientPermissions(message),
+            StatusCode::NOT_FOUND => GitHostError::RepoNotFoundOrNoAccess(project.path),
+            _ => GitHostError::PullRequest(format!("GitLab returned {status}: {message}")),
+        });
+    }
+
+    let merge_request: MergeRequest = response.json().await.map_err(|e| {
+        GitHostError::UnexpectedOutput(format!("Failed to parse GitLab merge request: {e}"))
+    })?;
+    Ok(merge_request.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(base_url: &str, path: &str) -> GitLabProject {
+        GitLabProject {
+            base_url: base_url.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_https_remote() {
+        assert_eq!(
+            GitLabProject::from_remote_url("https://gitlab.com/group/repo.git").unwrap(),
+            project("https://gitlab.com", "group/repo")
+        );
+        assert_eq!(
+            GitLabProject::from_remote_url("https://gitlab.company.com:8443/a/b/repo").unwrap(),
+            project("https://gitlab.company.com:8443", "a/b/repo")
+        );
+    }
+
+    #[test]
+    fn test_parse_ssh_remote() {
+        assert_eq!(
+            GitLabProject::from_remote_url("git@gitlab.com:group/subgroup/repo.git").unwrap(),
+            project("https://gitlab.com", "group/subgroup/repo")
+        );
+        assert_eq!(
+            GitLabProject::from_remote_url("ssh://git@gitlab.com:2222/group/repo.git").unwrap(),
+            project("https://gitlab.com", "group/repo")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_remote_without_project() {
+        assert!(GitLabProject::from_remote_url("https://gitlab.com/repo").is_err());
+        assert!(GitLabProject::from_remote_url("not a remote").is_err());
+    }
+
+    #[test]
+    fn test_merge_requests_url_encodes_project_path() {
+        assert_eq!(
+            project("https://gitlab.com", "group/subgroup/repo").merge_requests_url(),
+            "https://gitlab.com/api/v4/projects/group%2Fsubgroup%2Frepo/merge_requests"
+        );
+    }
+
+    #[test]
+    fn test_merge_status() {
+        assert!(matches!(merge_status("opened"), MergeStatus::Open));
+        assert!(matches!(merge_status("merged"), MergeStatus::Merged));
+        assert!(matches!(merge_status("locked"), MergeStatus::Closed));
+        assert!(matches!(merge_status("unexpected"), MergeStatus::Unknown));
+    }
+}
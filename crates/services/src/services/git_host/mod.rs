@@ -3,8 +3,13 @@ mod types;
 
 pub mod azure;
 pub mod github;
+pub mod gitlab;
 
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+};
 
 use async_trait::async_trait;
 use db::models::merge::PullRequestInfo;
@@ -17,6 +22,11 @@ pub use types::{
 
 use self::{azure::AzureDevOpsProvider, github::GitHubProvider};
 
+/// Provider detected for each repository path, so the remote URL of a repo is
+/// only inspected once.
+static PROVIDER_BY_REPO: LazyLock<RwLock<HashMap<PathBuf, ProviderKind>>> =
+    LazyLock::new(Default::default);
+
 #[async_trait]
 #[enum_dispatch(GitHostService)]
 pub trait GitHostProvider: Send + Sync {
@@ -54,10 +64,39 @@ pub enum GitHostService {
 
 impl GitHostService {
     pub fn from_url(url: &str) -> Result<Self, GitHostError> {
-        match detect_provider_from_url(url) {
+        Self::from_kind(detect_provider_from_url(url))
+    }
+
+    /// Like [`Self::from_url`], but remembers the provider detected for
+    /// `repo_path`.
+    pub fn for_repo(repo_path: &Path, remote_url: &str) -> Result<Self, GitHostError> {
+        Self::from_kind(Self::provider_for_repo(repo_path, remote_url))
+    }
+
+    /// The provider hosting `remote_url`, cached per `repo_path`.
+    pub fn provider_for_repo(repo_path: &Path, remote_url: &str) -> ProviderKind {
+        let cached = PROVIDER_BY_REPO
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(repo_path)
+            .copied();
+        cached.unwrap_or_else(|| {
+            let kind = detect_provider_from_url(remote_url);
+            PROVIDER_BY_REPO
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(repo_path.to_path_buf(), kind);
+            kind
+        })
+    }
+
+    /// GitLab has no CLI-backed provider; merge requests go through
+    /// [`gitlab::create_merge_request`] instead.
+    pub fn from_kind(kind: ProviderKind) -> Result<Self, GitHostError> {
+        match kind {
             ProviderKind::GitHub => Ok(Self::GitHub(GitHubProvider::new()?)),
             ProviderKind::AzureDevOps => Ok(Self::AzureDevOps(AzureDevOpsProvider::new()?)),
-            ProviderKind::Unknown => Err(GitHostError::UnsupportedProvider),
+            ProviderKind::GitLab | ProviderKind::Unknown => Err(GitHostError::UnsupportedProvider),
         }
     }
 }
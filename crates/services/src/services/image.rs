@@ -1,6 +1,7 @@
 use std::{
     fs,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use db::models::image::{CreateImage, Image};
@@ -171,6 +172,31 @@ impl ImageService {
         Ok(())
     }
 
+    /// Delete images older than `max_age`, along with their cached files.
+    /// Images attached to a task with a running execution are kept.
+    /// Returns the number of images deleted.
+    pub async fn prune_by_age(&self, max_age: Duration) -> Result<u32, ImageError> {
+        let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        let cutoff = chrono::Utc::now()
+            .checked_sub_signed(max_age)
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+
+        let stale_images = Image::find_unused_created_before(&self.pool, cutoff).await?;
+        let mut deleted_count = 0;
+
+        for image in stale_images {
+            match self.delete_image(image.id).await {
+                Ok(_) => {
+                    deleted_count += 1;
+                    tracing::debug!("Deleted stale image: {}", image.id);
+                }
+                Err(e) => tracing::error!("Failed to delete stale image {}: {}", image.id, e),
+            }
+        }
+
+        Ok(deleted_count)
+    }
+
     pub fn get_absolute_path(&self, image: &Image) -> PathBuf {
         self.cache_dir.join(&image.file_path)
     }
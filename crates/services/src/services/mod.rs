@@ -1,3 +1,5 @@
+pub mod admin;
+pub mod agent_tokens;
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
@@ -22,5 +24,7 @@ pub mod qa_repos;
 pub mod queued_message;
 pub mod remote_client;
 pub mod repo;
+pub mod task_import;
 pub mod workspace_manager;
+pub mod workspace_repo_cache;
 pub mod worktree_manager;
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use serde::{Deserialize, Serialize};
@@ -22,6 +22,74 @@ impl Credentials {
     }
 }
 
+/// Provider name for credentials saved before they were stored per provider.
+pub const DEFAULT_PROVIDER: &str = "default";
+
+/// OAuth credentials for every provider the user has signed in with, keyed by
+/// provider name. `current` is the provider most recently signed in with; its
+/// credentials authenticate calls to the remote API.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CredentialStore {
+    #[serde(default)]
+    pub providers: HashMap<String, Credentials>,
+    #[serde(default)]
+    pub current: Option<String>,
+}
+
+impl CredentialStore {
+    /// Parse a serialized store, accepting the older single-credentials
+    /// format as the [`DEFAULT_PROVIDER`] entry.
+    pub fn from_json(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Format {
+            // Tried first: a store has no required fields and would match anything
+            Legacy(Credentials),
+            Store(CredentialStore),
+        }
+
+        Ok(match serde_json::from_slice(bytes)? {
+            Format::Legacy(creds) => Self::single(DEFAULT_PROVIDER, creds),
+            Format::Store(store) => store,
+        })
+    }
+
+    fn single(provider: &str, creds: Credentials) -> Self {
+        let mut store = Self::default();
+        store.set(provider, creds);
+        store
+    }
+
+    pub fn get(&self, provider: &str) -> Option<&Credentials> {
+        self.providers.get(provider)
+    }
+
+    /// Store credentials for `provider` and make it the current provider.
+    pub fn set(&mut self, provider: &str, creds: Credentials) {
+        self.providers.insert(provider.to_string(), creds);
+        self.current = Some(provider.to_string());
+    }
+
+    /// Remove `provider`. If it was current, another remaining provider (if
+    /// any) becomes current.
+    pub fn remove(&mut self, provider: &str) -> Option<Credentials> {
+        let removed = self.providers.remove(provider);
+        if self.current.as_deref() == Some(provider) {
+            self.current = self.providers.keys().min().cloned();
+        }
+        removed
+    }
+
+    /// Credentials of the current provider.
+    pub fn current(&self) -> Option<&Credentials> {
+        self.current.as_deref().and_then(|p| self.providers.get(p))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StoredCredentials {
     refresh_token: String,
@@ -37,50 +105,161 @@ impl From<StoredCredentials> for Credentials {
     }
 }
 
+/// On-disk form of [`CredentialStore`]; only refresh tokens are persisted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoredCredentialStore {
+    #[serde(default)]
+    providers: HashMap<String, StoredCredentials>,
+    #[serde(default)]
+    current: Option<String>,
+}
+
+impl StoredCredentialStore {
+    /// Parse the stored form, accepting the older single-token format.
+    fn from_json(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Format {
+            Legacy(StoredCredentials),
+            Store(StoredCredentialStore),
+        }
+
+        Ok(match serde_json::from_slice(bytes)? {
+            Format::Legacy(creds) => Self {
+                providers: HashMap::from([(DEFAULT_PROVIDER.to_string(), creds)]),
+                current: Some(DEFAULT_PROVIDER.to_string()),
+            },
+            Format::Store(store) => store,
+        })
+    }
+}
+
+impl From<&CredentialStore> for StoredCredentialStore {
+    fn from(value: &CredentialStore) -> Self {
+        Self {
+            providers: value
+                .providers
+                .iter()
+                .map(|(provider, creds)| {
+                    (
+                        provider.clone(),
+                        StoredCredentials {
+                            refresh_token: creds.refresh_token.clone(),
+                        },
+                    )
+                })
+                .collect(),
+            current: value.current.clone(),
+        }
+    }
+}
+
+impl From<StoredCredentialStore> for CredentialStore {
+    fn from(value: StoredCredentialStore) -> Self {
+        Self {
+            providers: value
+                .providers
+                .into_iter()
+                .map(|(provider, creds)| (provider, creds.into()))
+                .collect(),
+            current: value.current,
+        }
+    }
+}
+
 /// Service for managing OAuth credentials (JWT tokens) in memory and persistent storage.
-/// The token is loaded into memory on startup and persisted to disk/keychain on save.
+/// Tokens are loaded into memory on startup and persisted to disk/keychain on every change.
 pub struct OAuthCredentials {
     backend: Backend,
-    inner: RwLock<Option<Credentials>>,
+    inner: RwLock<CredentialStore>,
 }
 
 impl OAuthCredentials {
     pub fn new(path: PathBuf) -> Self {
         Self {
             backend: Backend::detect(path),
-            inner: RwLock::new(None),
+            inner: RwLock::new(CredentialStore::default()),
         }
     }
 
     pub async fn load(&self) -> std::io::Result<()> {
-        let creds = self.backend.load().await?.map(Credentials::from);
-        *self.inner.write().await = creds;
+        let store = self
+            .backend
+            .load()
+            .await?
+            .map(CredentialStore::from)
+            .unwrap_or_default();
+        *self.inner.write().await = store;
         Ok(())
     }
 
-    pub async fn save(&self, creds: &Credentials) -> std::io::Result<()> {
-        let stored = StoredCredentials {
-            refresh_token: creds.refresh_token.clone(),
-        };
-        self.backend.save(&stored).await?;
-        *self.inner.write().await = Some(creds.clone());
+    /// Persist the in-memory credentials for every provider.
+    pub async fn save(&self) -> std::io::Result<()> {
+        let store = self.inner.read().await;
+        self.persist(&store).await
+    }
+
+    async fn persist(&self, store: &CredentialStore) -> std::io::Result<()> {
+        if store.is_empty() {
+            self.backend.clear().await
+        } else {
+            self.backend.save(&store.into()).await
+        }
+    }
+
+    /// Credentials for `provider`.
+    pub async fn get(&self, provider: &str) -> Option<Credentials> {
+        self.inner.read().await.get(provider).cloned()
+    }
+
+    /// Credentials of the provider most recently signed in with.
+    pub async fn current(&self) -> Option<Credentials> {
+        self.inner.read().await.current().cloned()
+    }
+
+    /// Store credentials for `provider`, make it current, and persist.
+    pub async fn set(&self, provider: &str, creds: &Credentials) -> std::io::Result<()> {
+        let mut store = self.inner.write().await;
+        let mut updated = store.clone();
+        updated.set(provider, creds.clone());
+        self.persist(&updated).await?;
+        *store = updated;
         Ok(())
     }
 
-    pub async fn clear(&self) -> std::io::Result<()> {
-        self.backend.clear().await?;
-        *self.inner.write().await = None;
+    /// Replace the current provider's credentials, e.g. after a token refresh.
+    pub async fn set_current(&self, creds: &Credentials) -> std::io::Result<()> {
+        let provider = self
+            .inner
+            .read()
+            .await
+            .current
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROVIDER.to_string());
+        self.set(&provider, creds).await
+    }
+
+    /// Remove credentials for `provider` and persist.
+    pub async fn remove(&self, provider: &str) -> std::io::Result<()> {
+        let mut store = self.inner.write().await;
+        let mut updated = store.clone();
+        updated.remove(provider);
+        self.persist(&updated).await?;
+        *store = updated;
         Ok(())
     }
 
-    pub async fn get(&self) -> Option<Credentials> {
-        self.inner.read().await.clone()
+    /// Remove credentials for every provider.
+    pub async fn clear(&self) -> std::io::Result<()> {
+        self.backend.clear().await?;
+        *self.inner.write().await = CredentialStore::default();
+        Ok(())
     }
 }
 
 trait StoreBackend {
-    async fn load(&self) -> std::io::Result<Option<StoredCredentials>>;
-    async fn save(&self, creds: &StoredCredentials) -> std::io::Result<()>;
+    async fn load(&self) -> std::io::Result<Option<StoredCredentialStore>>;
+    async fn save(&self, creds: &StoredCredentialStore) -> std::io::Result<()>;
     async fn clear(&self) -> std::io::Result<()>;
 }
 
@@ -116,7 +295,7 @@ impl Backend {
 }
 
 impl StoreBackend for Backend {
-    async fn load(&self) -> std::io::Result<Option<StoredCredentials>> {
+    async fn load(&self) -> std::io::Result<Option<StoredCredentialStore>> {
         match self {
             Backend::File(b) => b.load().await,
             #[cfg(target_os = "macos")]
@@ -124,7 +303,7 @@ impl StoreBackend for Backend {
         }
     }
 
-    async fn save(&self, creds: &StoredCredentials) -> std::io::Result<()> {
+    async fn save(&self, creds: &StoredCredentialStore) -> std::io::Result<()> {
         match self {
             Backend::File(b) => b.save(creds).await,
             #[cfg(target_os = "macos")]
@@ -146,7 +325,7 @@ struct FileBackend {
 }
 
 impl FileBackend {
-    async fn load(&self) -> std::io::Result<Option<StoredCredentials>> {
+    async fn load(&self) -> std::io::Result<Option<StoredCredentialStore>> {
         if !self.path.exists() {
             return Ok(None);
         }
@@ -163,11 +342,11 @@ impl FileBackend {
         }
     }
 
-    fn parse_credentials(bytes: &[u8]) -> Result<StoredCredentials, serde_json::Error> {
-        serde_json::from_slice::<StoredCredentials>(bytes)
+    fn parse_credentials(bytes: &[u8]) -> Result<StoredCredentialStore, serde_json::Error> {
+        StoredCredentialStore::from_json(bytes)
     }
 
-    async fn save(&self, creds: &StoredCredentials) -> std::io::Result<()> {
+    async fn save(&self, creds: &StoredCredentialStore) -> std::io::Result<()> {
         let tmp = self.path.with_extension("tmp");
 
         let file = {
@@ -206,11 +385,11 @@ impl KeychainBackend {
     const ACCOUNT_NAME: &'static str = "default";
     const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
 
-    async fn load(&self) -> std::io::Result<Option<StoredCredentials>> {
+    async fn load(&self) -> std::io::Result<Option<StoredCredentialStore>> {
         use security_framework::passwords::get_generic_password;
 
         match get_generic_password(Self::SERVICE_NAME, Self::ACCOUNT_NAME) {
-            Ok(bytes) => match serde_json::from_slice::<StoredCredentials>(&bytes) {
+            Ok(bytes) => match StoredCredentialStore::from_json(&bytes) {
                 Ok(creds) => Ok(Some(creds)),
                 Err(error) => {
                     tracing::warn!(
@@ -225,7 +404,7 @@ impl KeychainBackend {
         }
     }
 
-    async fn save(&self, creds: &StoredCredentials) -> std::io::Result<()> {
+    async fn save(&self, creds: &StoredCredentialStore) -> std::io::Result<()> {
         use security_framework::passwords::set_generic_password;
 
         let bytes = serde_json::to_vec_pretty(creds).map_err(std::io::Error::other)?;
@@ -243,3 +422,69 @@ impl KeychainBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creds(refresh_token: &str) -> Credentials {
+        Credentials {
+            access_token: None,
+            refresh_token: refresh_token.to_string(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_store_keeps_each_provider() {
+        let mut store = CredentialStore::default();
+        store.set("github", creds("gh"));
+        store.set("google", creds("gg"));
+
+        assert_eq!(store.get("github").unwrap().refresh_token, "gh");
+        assert_eq!(store.get("google").unwrap().refresh_token, "gg");
+        assert_eq!(store.current().unwrap().refresh_token, "gg");
+    }
+
+    #[test]
+    fn test_removing_current_provider_falls_back() {
+        let mut store = CredentialStore::default();
+        store.set("github", creds("gh"));
+        store.set("google", creds("gg"));
+
+        assert!(store.remove("google").is_some());
+        assert_eq!(store.current.as_deref(), Some("github"));
+
+        store.remove("github");
+        assert!(store.current().is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_single_credentials_parse_as_default_provider() {
+        let legacy = br#"{"access_token":null,"refresh_token":"old","expires_at":null}"#;
+        let store = CredentialStore::from_json(legacy).unwrap();
+        assert_eq!(store.get(DEFAULT_PROVIDER).unwrap().refresh_token, "old");
+        assert_eq!(store.current().unwrap().refresh_token, "old");
+
+        let stored = StoredCredentialStore::from_json(br#"{"refresh_token":"old"}"#).unwrap();
+        let store = CredentialStore::from(stored);
+        assert_eq!(store.current().unwrap().refresh_token, "old");
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let mut store = CredentialStore::default();
+        store.set("github", creds("gh"));
+
+        let bytes = serde_json::to_vec(&store).unwrap();
+        let parsed = CredentialStore::from_json(&bytes).unwrap();
+        assert_eq!(parsed.current.as_deref(), Some("github"));
+        assert_eq!(parsed.get("github").unwrap().refresh_token, "gh");
+
+        let stored = StoredCredentialStore::from(&store);
+        let bytes = serde_json::to_vec(&stored).unwrap();
+        let parsed = CredentialStore::from(StoredCredentialStore::from_json(&bytes).unwrap());
+        assert_eq!(parsed.get("github").unwrap().refresh_token, "gh");
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
@@ -7,6 +7,9 @@ use db::models::{
     project::{CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo},
     repo::Repo,
+    task::{CreateTask, Task, TaskWithAttemptStatus},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
 use sqlx::SqlitePool;
 use thiserror::Error;
@@ -105,9 +108,12 @@ impl ProjectService {
             .map_err(|e| ProjectServiceError::Project(ProjectError::CreateFailed(e.to_string())))?;
 
         for repo in &normalized_repos {
-            let repo_entity =
-                Repo::find_or_create(pool, Path::new(&repo.git_repo_path), &repo.display_name)
-                    .await?;
+            let repo_entity = Self::find_or_register_repo(
+                pool,
+                Path::new(&repo.git_repo_path),
+                &repo.display_name,
+            )
+            .await?;
             ProjectRepo::create(pool, project.id, repo_entity.id).await?;
         }
 
@@ -174,22 +180,14 @@ impl ProjectService {
         let path = repo_service.normalize_path(&payload.git_repo_path)?;
         repo_service.validate_git_repo_path(&path)?;
 
-        let repository = ProjectRepo::add_repo_to_project(
-            pool,
-            project_id,
-            &path.to_string_lossy(),
-            &payload.display_name,
-        )
-        .await
-        .map_err(|e| match e {
-            db::models::project_repo::ProjectRepoError::AlreadyExists => {
-                ProjectServiceError::DuplicateGitRepoPath
-            }
-            db::models::project_repo::ProjectRepoError::Database(e) => {
-                ProjectServiceError::Database(e)
-            }
-            _ => ProjectServiceError::RepositoryNotFound,
-        })?;
+        let repository = Self::find_or_register_repo(pool, &path, &payload.display_name).await?;
+        if ProjectRepo::find_by_project_and_repo(pool, project_id, repository.id)
+            .await?
+            .is_some()
+        {
+            return Err(ProjectServiceError::DuplicateGitRepoPath);
+        }
+        ProjectRepo::create(pool, project_id, repository.id).await?;
 
         tracing::info!(
             "Added repository {} to project {} (path: {})",
@@ -201,6 +199,25 @@ impl ProjectService {
         Ok(repository)
     }
 
+    /// Reuse the repo already registered for `path`, if any, so that users
+    /// adding the same checkout share one `repos` row. Only the
+    /// `project_repos` link is per project.
+    async fn find_or_register_repo(
+        pool: &SqlitePool,
+        path: &Path,
+        display_name: &str,
+    ) -> Result<Repo> {
+        if let Some(existing) = Repo::find_by_path(pool, path).await? {
+            tracing::debug!(
+                "Reusing registered repository {} for {}",
+                existing.id,
+                path.display()
+            );
+            return Ok(existing);
+        }
+        Ok(Repo::find_or_create(pool, path, display_name).await?)
+    }
+
     pub async fn delete_repository(
         &self,
         pool: &SqlitePool,
@@ -244,6 +261,159 @@ impl ProjectService {
         Ok(rows_affected)
     }
 
+    /// Copy a project under a new name, including its repository links and
+    /// non-deleted tasks. With `deep`, each task's workspaces (and their repo
+    /// target branches) are copied too; copies have no container, so their
+    /// worktrees are created on the next start. Sessions and execution history
+    /// are never copied.
+    pub async fn duplicate_project(
+        &self,
+        pool: &SqlitePool,
+        source_id: Uuid,
+        new_name: &str,
+        user_id: Option<Uuid>,
+        deep: bool,
+    ) -> Result<Project> {
+        let source = Project::find_by_id(pool, source_id)
+            .await?
+            .ok_or(ProjectError::ProjectNotFound)?;
+
+        let project = Project::create(
+            pool,
+            &CreateProject {
+                name: new_name.to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .map_err(|e| ProjectServiceError::Project(ProjectError::CreateFailed(e.to_string())))?;
+
+        // Undo the partial copy so a failure doesn't leave a half-filled project
+        if let Err(e) =
+            Self::copy_project_contents(pool, source.id, project.id, user_id, deep).await
+        {
+            tracing::error!(
+                "Failed to duplicate project {} into {}: {}",
+                source.id,
+                project.id,
+                e
+            );
+            if let Err(cleanup_err) = Project::delete(pool, project.id).await {
+                tracing::error!(
+                    "Failed to remove partially duplicated project {}: {}",
+                    project.id,
+                    cleanup_err
+                );
+            }
+            return Err(e);
+        }
+
+        tracing::info!(
+            "Duplicated project {} into {} (deep: {})",
+            source.id,
+            project.id,
+            deep
+        );
+
+        Ok(Project::find_by_id(pool, project.id)
+            .await?
+            .ok_or(ProjectError::ProjectNotFound)?)
+    }
+
+    async fn copy_project_contents(
+        pool: &SqlitePool,
+        source_id: Uuid,
+        target_id: Uuid,
+        user_id: Option<Uuid>,
+        deep: bool,
+    ) -> Result<()> {
+        if user_id.is_some() {
+            Project::set_user_id(pool, target_id, user_id).await?;
+        }
+
+        for project_repo in ProjectRepo::find_by_project_id(pool, source_id).await? {
+            ProjectRepo::create(pool, target_id, project_repo.repo_id).await?;
+        }
+
+        let tasks = Task::find_by_project_id_with_attempt_status(pool, source_id).await?;
+        let mut workspace_ids = HashMap::new();
+        let mut new_task_ids = Vec::with_capacity(tasks.len());
+
+        for TaskWithAttemptStatus { task, .. } in &tasks {
+            let new_task = Task::create(
+                pool,
+                &CreateTask {
+                    project_id: target_id,
+                    title: task.title.clone(),
+                    description: task.description.clone(),
+                    status: Some(task.status.clone()),
+                    parent_workspace_id: None,
+                    image_ids: None,
+                },
+                Uuid::new_v4(),
+            )
+            .await?;
+            new_task_ids.push(new_task.id);
+
+            if !deep {
+                continue;
+            }
+
+            let workspaces = Workspace::fetch_all(pool, Some(task.id))
+                .await
+                .map_err(|e| ProjectError::CreateFailed(e.to_string()))?;
+            for workspace in workspaces {
+                let new_workspace = Workspace::create(
+                    pool,
+                    &CreateWorkspace {
+                        branch: workspace.branch.clone(),
+                        agent_working_dir: workspace.agent_working_dir.clone(),
+                    },
+                    Uuid::new_v4(),
+                    new_task.id,
+                )
+                .await
+                .map_err(|e| ProjectError::CreateFailed(e.to_string()))?;
+                Workspace::update(
+                    pool,
+                    new_workspace.id,
+                    Some(workspace.archived),
+                    Some(workspace.pinned),
+                    workspace.name.as_deref(),
+                )
+                .await?;
+                if user_id.is_some() {
+                    Workspace::set_user_id(pool, new_workspace.id, user_id).await?;
+                }
+
+                let repos: Vec<CreateWorkspaceRepo> =
+                    WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+                        .await?
+                        .into_iter()
+                        .map(|wr| CreateWorkspaceRepo {
+                            repo_id: wr.repo_id,
+                            target_branch: wr.target_branch,
+                        })
+                        .collect();
+                WorkspaceRepo::create_many(pool, new_workspace.id, &repos).await?;
+
+                workspace_ids.insert(workspace.id, new_workspace.id);
+            }
+        }
+
+        // Subtask links only survive when their parent workspace was copied
+        for (TaskWithAttemptStatus { task, .. }, new_task_id) in tasks.iter().zip(new_task_ids) {
+            if let Some(parent) = task.parent_workspace_id
+                && let Some(new_parent) = workspace_ids.get(&parent)
+            {
+                Task::update_parent_workspace_id(pool, new_task_id, Some(*new_parent)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn get_repositories(&self, pool: &SqlitePool, project_id: Uuid) -> Result<Vec<Repo>> {
         let repos = ProjectRepo::find_repos_for_project(pool, project_id).await?;
         Ok(repos)
@@ -265,12 +435,11 @@ impl ProjectService {
             .iter()
             .map(|repo| {
                 let repo_name = repo.name.clone();
-                let repo_path = repo.path.clone();
                 let mode = query.mode.clone();
                 let query_str = query_str.to_string();
                 async move {
                     let results = cache
-                        .search_repo(&repo_path, &query_str, mode)
+                        .search_repo(repo, &query_str, mode)
                         .await
                         .unwrap_or_else(|e| {
                             tracing::warn!("Search failed for repo {}: {}", repo_name, e);
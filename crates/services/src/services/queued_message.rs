@@ -2,8 +2,9 @@ use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use db::models::scratch::DraftFollowUpData;
+use db::{DBServicePg, models::scratch::DraftFollowUpData, pg::InstrumentedPool};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -32,31 +33,113 @@ pub enum QueueStatus {
 
 /// In-memory service for managing queued follow-up messages.
 /// One queued message per session.
+///
+/// When constructed with a PostgreSQL database (K8s mode), queue changes are
+/// mirrored to the `queued_messages` table so pending messages can be
+/// restored after a pod restart.
 #[derive(Clone)]
 pub struct QueuedMessageService {
     queue: Arc<DashMap<Uuid, QueuedMessage>>,
+    /// PostgreSQL database for persisting queued messages (K8s mode only).
+    db: Option<DBServicePg>,
 }
 
 impl QueuedMessageService {
     pub fn new() -> Self {
         Self {
             queue: Arc::new(DashMap::new()),
+            db: None,
         }
     }
 
+    /// Create a service that persists queued messages to PostgreSQL.
+    pub fn new_with_db(db: DBServicePg) -> Self {
+        Self {
+            queue: Arc::new(DashMap::new()),
+            db: Some(db),
+        }
+    }
+
+    /// The current PostgreSQL pool, fetched on each use so a pool recreated by
+    /// the health monitor is picked up. Queries run through it are traced.
+    fn pool(&self) -> Option<InstrumentedPool> {
+        self.db.as_ref().map(DBServicePg::instrumented_pool)
+    }
+
+    /// Load undelivered messages from PostgreSQL into the in-memory queue.
+    /// Called once on startup; a no-op without a database.
+    pub async fn restore_pending(&self) -> Result<usize, sqlx::Error> {
+        let Some(pool) = self.pool() else {
+            return Ok(0);
+        };
+
+        let query = sqlx::query_as(
+            r#"
+            SELECT message_json FROM queued_messages
+            WHERE delivered_at IS NULL
+            ORDER BY created_at ASC
+            "#,
+        );
+        let rows: Vec<(Json<QueuedMessage>,)> = pool.fetch_all_as(query).await?;
+
+        let count = rows.len();
+        for (Json(message),) in rows {
+            // Later rows win, matching the replace-on-queue semantics
+            self.queue.insert(message.session_id, message);
+        }
+        Ok(count)
+    }
+
     /// Queue a message for a session. Replaces any existing queued message.
-    pub fn queue_message(&self, session_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
+    ///
+    /// `user_id` is recorded with the persisted row; messages for sessions
+    /// without an owner are kept in memory only.
+    pub fn queue_message(
+        &self,
+        session_id: Uuid,
+        user_id: Option<Uuid>,
+        data: DraftFollowUpData,
+    ) -> QueuedMessage {
         let queued = QueuedMessage {
             session_id,
             data,
             queued_at: Utc::now(),
         };
         self.queue.insert(session_id, queued.clone());
+
+        if let Some(pool) = self.pool() {
+            match user_id {
+                Some(user_id) => {
+                    let message = queued.clone();
+                    spawn_persistence(async move {
+                        if let Err(e) =
+                            Self::enqueue_persistent(&pool, session_id, user_id, &message).await
+                        {
+                            tracing::warn!(
+                                session_id = %session_id,
+                                "Failed to persist queued message: {}",
+                                e
+                            );
+                        }
+                    });
+                }
+                None => tracing::warn!(
+                    session_id = %session_id,
+                    "Session has no owner; queued message will not be persisted"
+                ),
+            }
+        }
+
         queued
     }
 
     /// Cancel/remove a queued message for a session
     pub fn cancel_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
+        if let Some(pool) = self.pool() {
+            spawn_persistence(async move {
+                delete_pending(&pool, session_id).await;
+            });
+        }
         self.queue.remove(&session_id).map(|(_, v)| v)
     }
 
@@ -68,6 +151,17 @@ impl QueuedMessageService {
     /// Take (remove and return) the queued message for a session.
     /// Used by finalization flow to consume the queued message.
     pub fn take_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
+        if let Some(pool) = self.pool() {
+            spawn_persistence(async move {
+                if let Err(e) = Self::dequeue_pending(&pool, session_id).await {
+                    tracing::warn!(
+                        session_id = %session_id,
+                        "Failed to mark queued message delivered: {}",
+                        e
+                    );
+                }
+            });
+        }
         self.queue.remove(&session_id).map(|(_, v)| v)
     }
 
@@ -83,6 +177,83 @@ impl QueuedMessageService {
             None => QueueStatus::Empty,
         }
     }
+
+    /// Persist a queued message, replacing any pending message for the session.
+    pub async fn enqueue_persistent(
+        pool: &InstrumentedPool,
+        session_id: Uuid,
+        user_id: Uuid,
+        message: &QueuedMessage,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.inner().begin().await?;
+
+        sqlx::query("DELETE FROM queued_messages WHERE session_id = $1 AND delivered_at IS NULL")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO queued_messages (id, session_id, user_id, message_json, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(session_id)
+        .bind(user_id)
+        .bind(Json(message))
+        .bind(message.queued_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await
+    }
+
+    /// Atomically mark all pending messages for a session as delivered and
+    /// return them, oldest first.
+    pub async fn dequeue_pending(
+        pool: &InstrumentedPool,
+        session_id: Uuid,
+    ) -> Result<Vec<QueuedMessage>, sqlx::Error> {
+        let query = sqlx::query_as(
+            r#"
+            UPDATE queued_messages
+            SET delivered_at = NOW()
+            WHERE session_id = $1 AND delivered_at IS NULL
+            RETURNING message_json, created_at
+            "#,
+        )
+        .bind(session_id);
+        let mut rows: Vec<(Json<QueuedMessage>, DateTime<Utc>)> = pool.fetch_all_as(query).await?;
+
+        // RETURNING does not guarantee order
+        rows.sort_by_key(|(_, created_at)| *created_at);
+        Ok(rows.into_iter().map(|(Json(message), _)| message).collect())
+    }
+}
+
+/// Run a persistence task in the background from synchronous code.
+fn spawn_persistence<F>(task: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(task);
+        }
+        Err(_) => tracing::warn!("No async runtime available to persist queued message changes"),
+    }
+}
+
+async fn delete_pending(pool: &InstrumentedPool, session_id: Uuid) {
+    let query =
+        sqlx::query("DELETE FROM queued_messages WHERE session_id = $1 AND delivered_at IS NULL")
+            .bind(session_id);
+    let result = pool.execute(query).await;
+
+    if let Err(e) = result {
+        tracing::warn!(session_id = %session_id, "Failed to delete queued message: {}", e);
+    }
 }
 
 impl Default for QueuedMessageService {
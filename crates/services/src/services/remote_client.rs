@@ -8,7 +8,7 @@ use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
-use tracing::warn;
+use tracing::{Instrument, debug, debug_span, warn};
 use url::Url;
 use utils::{
     api::{
@@ -97,10 +97,30 @@ struct ApiErrorResponse {
 }
 
 /// HTTP client for the remote OAuth server with automatic retries.
+/// Retry policy for transient remote failures (5xx, timeouts, connection errors).
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_retries: usize,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+/// HTTP client for the remote API. Clones share the underlying connection pool.
 pub struct RemoteClient {
     base: Url,
     http: Client,
     auth_context: AuthContext,
+    retry: RetryConfig,
 }
 
 impl std::fmt::Debug for RemoteClient {
@@ -109,6 +129,7 @@ impl std::fmt::Debug for RemoteClient {
             .field("base", &self.base)
             .field("http", &self.http)
             .field("auth_context", &"<present>")
+            .field("retry", &self.retry)
             .finish()
     }
 }
@@ -119,18 +140,52 @@ impl Clone for RemoteClient {
             base: self.base.clone(),
             http: self.http.clone(),
             auth_context: self.auth_context.clone(),
+            retry: self.retry,
         }
     }
 }
 
+/// Connect timeout in seconds, overridden by `REMOTE_CLIENT_CONNECT_TIMEOUT_SECS`
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Whole-request timeout in seconds, overridden by `REMOTE_CLIENT_REQUEST_TIMEOUT_SECS`
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Seconds from `var`, or `default` when it is unset, invalid or zero.
+fn timeout_from_env(var: &str, default: u64) -> Duration {
+    let secs = std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default);
+    Duration::from_secs(secs)
+}
+
 impl RemoteClient {
-    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
     const TOKEN_REFRESH_LEEWAY_SECS: i64 = 20;
+    const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+    const POOL_MAX_IDLE_PER_HOST: usize = 8;
 
     pub fn new(base_url: &str, auth_context: AuthContext) -> Result<Self, RemoteClientError> {
         let base = Url::parse(base_url).map_err(|e| RemoteClientError::Url(e.to_string()))?;
+        let connect_timeout = timeout_from_env(
+            "REMOTE_CLIENT_CONNECT_TIMEOUT_SECS",
+            DEFAULT_CONNECT_TIMEOUT_SECS,
+        );
+        let request_timeout = timeout_from_env(
+            "REMOTE_CLIENT_REQUEST_TIMEOUT_SECS",
+            DEFAULT_REQUEST_TIMEOUT_SECS,
+        );
+        tracing::info!(
+            connect_timeout_secs = connect_timeout.as_secs(),
+            request_timeout_secs = request_timeout.as_secs(),
+            "Creating remote client"
+        );
+
         let http = Client::builder()
-            .timeout(Self::REQUEST_TIMEOUT)
+            .connect_timeout(connect_timeout)
+            .timeout(request_timeout)
+            .pool_idle_timeout(Self::POOL_IDLE_TIMEOUT)
+            .pool_max_idle_per_host(Self::POOL_MAX_IDLE_PER_HOST)
             .user_agent(concat!("remote-client/", env!("CARGO_PKG_VERSION")))
             .build()
             .map_err(|e| RemoteClientError::Transport(e.to_string()))?;
@@ -138,9 +193,18 @@ impl RemoteClient {
             base,
             http,
             auth_context,
+            retry: RetryConfig::default(),
         })
     }
 
+    /// Overrides the retry policy for transient failures. The maximum backoff
+    /// delay is kept from the current configuration.
+    pub fn with_retry_config(mut self, max_retries: usize, initial_delay: Duration) -> Self {
+        self.retry.max_retries = max_retries;
+        self.retry.initial_delay = initial_delay;
+        self
+    }
+
     /// Returns a valid access token, refreshing when it's about to expire.
     fn require_token(
         &self,
@@ -275,6 +339,9 @@ impl RemoteClient {
             .join(path)
             .map_err(|e| RemoteClientError::Url(e.to_string()))?;
 
+        let span = debug_span!("remote_request", method = %method, path);
+        let mut retries = 0usize;
+
         (|| async {
             let mut req = self.http.request(method.clone(), url.clone());
 
@@ -301,19 +368,26 @@ impl RemoteClient {
         })
         .retry(
             &ExponentialBuilder::default()
-                .with_min_delay(Duration::from_secs(1))
-                .with_max_delay(Duration::from_secs(30))
-                .with_max_times(3)
+                .with_min_delay(self.retry.initial_delay)
+                .with_max_delay(self.retry.max_delay)
+                .with_max_times(self.retry.max_retries)
                 .with_jitter(),
         )
         .when(|e: &RemoteClientError| e.should_retry())
         .notify(|e, dur| {
+            retries += 1;
+            debug!(
+                retry = retries,
+                max_retries = self.retry.max_retries,
+                "Retrying remote call"
+            );
             warn!(
                 "Remote call failed, retrying after {:.2}s: {}",
                 dur.as_secs_f64(),
                 e
             )
         })
+        .instrument(span)
         .await
     }
 
@@ -325,9 +399,7 @@ impl RemoteClient {
         let res = self
             .send(reqwest::Method::GET, path, false, None::<&()>)
             .await?;
-        res.json::<T>()
-            .await
-            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+        res.json::<T>().await.map_err(map_body_error)
     }
 
     async fn post_public<T, B>(&self, path: &str, body: Option<&B>) -> Result<T, RemoteClientError>
@@ -336,9 +408,7 @@ impl RemoteClient {
         B: Serialize,
     {
         let res = self.send(reqwest::Method::POST, path, false, body).await?;
-        res.json::<T>()
-            .await
-            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+        res.json::<T>().await.map_err(map_body_error)
     }
 
     // Authenticated endpoint helpers (require token)
@@ -349,9 +419,7 @@ impl RemoteClient {
         let res = self
             .send(reqwest::Method::GET, path, true, None::<&()>)
             .await?;
-        res.json::<T>()
-            .await
-            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+        res.json::<T>().await.map_err(map_body_error)
     }
 
     async fn post_authed<T, B>(&self, path: &str, body: Option<&B>) -> Result<T, RemoteClientError>
@@ -360,9 +428,7 @@ impl RemoteClient {
         B: Serialize,
     {
         let res = self.send(reqwest::Method::POST, path, true, body).await?;
-        res.json::<T>()
-            .await
-            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+        res.json::<T>().await.map_err(map_body_error)
     }
 
     async fn patch_authed<T, B>(&self, path: &str, body: &B) -> Result<T, RemoteClientError>
@@ -373,9 +439,7 @@ impl RemoteClient {
         let res = self
             .send(reqwest::Method::PATCH, path, true, Some(body))
             .await?;
-        res.json::<T>()
-            .await
-            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+        res.json::<T>().await.map_err(map_body_error)
     }
 
     async fn delete_authed(&self, path: &str) -> Result<(), RemoteClientError> {
@@ -557,3 +621,55 @@ fn map_reqwest_error(e: reqwest::Error) -> RemoteClientError {
         RemoteClientError::Transport(e.to_string())
     }
 }
+
+/// Like [`map_reqwest_error`], for errors while reading a response body,
+/// which can also hit the request timeout.
+fn map_body_error(e: reqwest::Error) -> RemoteClientError {
+    if e.is_timeout() {
+        RemoteClientError::Timeout
+    } else {
+        RemoteClientError::Serde(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_only_transient_errors() {
+        assert!(RemoteClientError::Timeout.should_retry());
+        assert!(RemoteClientError::Transport("reset".into()).should_retry());
+        assert!(
+            RemoteClientError::Http {
+                status: 503,
+                body: String::new(),
+            }
+            .should_retry()
+        );
+        assert!(
+            !RemoteClientError::Http {
+                status: 404,
+                body: String::new(),
+            }
+            .should_retry()
+        );
+        assert!(!RemoteClientError::Auth.should_retry());
+    }
+
+    #[test]
+    fn timeout_from_env_falls_back_to_default() {
+        assert_eq!(
+            timeout_from_env("REMOTE_CLIENT_TIMEOUT_TEST_UNSET", 7),
+            Duration::from_secs(7)
+        );
+    }
+
+    #[test]
+    fn default_retry_config_matches_backoff_policy() {
+        let config = RetryConfig::default();
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.initial_delay, Duration::from_millis(100));
+        assert_eq!(config.max_delay, Duration::from_secs(5));
+    }
+}
@@ -1,12 +1,20 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+};
 
+use chrono::{DateTime, Utc};
 use db::models::repo::Repo as RepoModel;
+use git2::{BranchType, Repository};
+use serde::Serialize;
 use sqlx::SqlitePool;
 use thiserror::Error;
+use ts_rs::TS;
 use utils::path::expand_tilde;
 use uuid::Uuid;
 
-use super::git::{GitService, GitServiceError};
+use super::git::{GitCli, GitService, GitServiceError};
 
 #[derive(Debug, Error)]
 pub enum RepoError {
@@ -28,10 +36,30 @@ pub enum RepoError {
     Git(#[from] GitServiceError),
     #[error("Invalid folder name: {0}")]
     InvalidFolderName(String),
+    #[error("Not a remote git URL: {0}")]
+    InvalidGitUrl(String),
+    #[error("Branch not found: {0}")]
+    BranchNotFound(String),
 }
 
 pub type Result<T> = std::result::Result<T, RepoError>;
 
+/// Default branch detected for each repo, so the remote is only asked once.
+static DEFAULT_BRANCHES: LazyLock<RwLock<HashMap<Uuid, String>>> = LazyLock::new(Default::default);
+
+/// A local or remote branch together with its tip commit
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BranchInfo {
+    pub name: String,
+    pub is_current: bool,
+    pub is_remote: bool,
+    pub last_commit_sha: String,
+    /// First line of the tip commit's message
+    pub last_commit_message: Option<String>,
+    #[ts(type = "Date")]
+    pub last_commit_date: DateTime<Utc>,
+}
+
 #[derive(Clone, Default)]
 pub struct RepoService;
 
@@ -56,6 +84,91 @@ impl RepoService {
         Ok(())
     }
 
+    /// List the local and remote branches of the repository at `repo_path`.
+    ///
+    /// The checked-out branch comes first, then the rest by most recent tip
+    /// commit. Remote `HEAD` aliases and branches without a commit are skipped.
+    pub fn list_branches(&self, repo_path: &Path) -> Result<Vec<BranchInfo>> {
+        let repo = Repository::open(repo_path)
+            .map_err(|_| RepoError::NotGitRepository(repo_path.to_path_buf()))?;
+        let current = repo
+            .head()
+            .ok()
+            .filter(|head| head.is_branch())
+            .and_then(|head| head.shorthand().map(str::to_string));
+
+        let mut branches = Vec::new();
+        for entry in repo.branches(None).map_err(GitServiceError::from)? {
+            let (branch, branch_type) = entry.map_err(GitServiceError::from)?;
+            let Some(name) = branch.name().map_err(GitServiceError::from)? else {
+                continue;
+            };
+            let is_remote = branch_type == BranchType::Remote;
+            if is_remote && name.ends_with("/HEAD") {
+                continue;
+            }
+            let Ok(commit) = branch.get().peel_to_commit() else {
+                continue;
+            };
+
+            branches.push(BranchInfo {
+                name: name.to_string(),
+                is_current: !is_remote && current.as_deref() == Some(name),
+                is_remote,
+                last_commit_sha: commit.id().to_string(),
+                last_commit_message: commit.summary().map(str::to_string),
+                last_commit_date: DateTime::from_timestamp(commit.time().seconds(), 0)
+                    .unwrap_or_else(Utc::now),
+            });
+        }
+
+        branches.sort_by(|a, b| {
+            b.is_current
+                .cmp(&a.is_current)
+                .then_with(|| b.last_commit_date.cmp(&a.last_commit_date))
+        });
+        Ok(branches)
+    }
+
+    /// The default branch of repo `repo_id` at `repo_path`: the branch `HEAD`
+    /// points at, else the `HEAD branch` reported by `git remote show origin`.
+    /// The result is cached per repo.
+    pub fn detect_default_branch(&self, repo_id: Uuid, repo_path: &Path) -> Result<String> {
+        if let Some(branch) = DEFAULT_BRANCHES
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&repo_id)
+        {
+            return Ok(branch.clone());
+        }
+
+        let repo = Repository::open(repo_path)
+            .map_err(|_| RepoError::NotGitRepository(repo_path.to_path_buf()))?;
+        // Read HEAD as a symbolic ref, which also works before the first commit
+        let head_branch = repo
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(str::to_string))
+            .and_then(|target| target.strip_prefix("refs/heads/").map(str::to_string));
+        let branch = match head_branch {
+            Some(branch) => branch,
+            None => {
+                let output = GitCli::new()
+                    .git(repo_path, ["remote", "show", "origin"])
+                    .map_err(GitServiceError::from)?;
+                parse_remote_head_branch(&output).ok_or_else(|| {
+                    RepoError::BranchNotFound("default branch of origin".to_string())
+                })?
+            }
+        };
+
+        DEFAULT_BRANCHES
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(repo_id, branch.clone());
+        Ok(branch)
+    }
+
     pub fn normalize_path(&self, path: &str) -> std::io::Result<PathBuf> {
         std::path::absolute(expand_tilde(path))
     }
@@ -91,13 +204,8 @@ impl RepoService {
             .ok_or(RepoError::NotFound)
     }
 
-    pub async fn init_repo(
-        &self,
-        pool: &SqlitePool,
-        git: &GitService,
-        parent_path: &str,
-        folder_name: &str,
-    ) -> Result<RepoModel> {
+    /// Reject folder names that are empty or would escape their parent directory.
+    pub fn validate_folder_name(&self, folder_name: &str) -> Result<()> {
         if folder_name.is_empty()
             || folder_name.contains('/')
             || folder_name.contains('\\')
@@ -106,6 +214,17 @@ impl RepoService {
         {
             return Err(RepoError::InvalidFolderName(folder_name.to_string()));
         }
+        Ok(())
+    }
+
+    pub async fn init_repo(
+        &self,
+        pool: &SqlitePool,
+        git: &GitService,
+        parent_path: &str,
+        folder_name: &str,
+    ) -> Result<RepoModel> {
+        self.validate_folder_name(folder_name)?;
 
         let normalized_parent = self.normalize_path(parent_path)?;
         if !normalized_parent.exists() {
@@ -125,4 +244,71 @@ impl RepoService {
         let repo = RepoModel::find_or_create(pool, &repo_path, folder_name).await?;
         Ok(repo)
     }
+
+    /// Clone the remote repository at `url` into `dest` and register it.
+    ///
+    /// Only network URLs are accepted (see [`GitCli::is_remote_url`]), so a
+    /// clone can never copy another local directory. Callers are responsible
+    /// for checking that `dest` is somewhere the user may write to.
+    pub async fn clone_repo(
+        &self,
+        pool: &SqlitePool,
+        url: &str,
+        dest: &Path,
+        shallow: bool,
+    ) -> Result<RepoModel> {
+        let url = url.trim();
+        if !GitCli::is_remote_url(url) {
+            return Err(RepoError::InvalidGitUrl(url.to_string()));
+        }
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| RepoError::InvalidFolderName(dest.display().to_string()))?;
+        if dest.exists() {
+            return Err(RepoError::DirectoryAlreadyExists(dest.to_path_buf()));
+        }
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let clone_url = url.to_string();
+        let clone_dest = dest.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            GitCli::new().clone_repo(&clone_url, &clone_dest, shallow)
+        })
+        .await
+        .map_err(std::io::Error::other)?
+        .map_err(GitServiceError::from)?;
+
+        let repo = RepoModel::find_or_create(pool, dest, &name).await?;
+        Ok(repo)
+    }
+}
+
+/// The branch on the `HEAD branch:` line of `git remote show` output. Remotes
+/// without a default branch report `(unknown)`.
+fn parse_remote_head_branch(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("HEAD branch:"))
+        .map(str::trim)
+        .filter(|branch| !branch.is_empty() && *branch != "(unknown)")
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_remote_head_branch() {
+        let output = "* remote origin\n  Fetch URL: git@example.com:org/app.git\n  HEAD branch: develop\n  Remote branches:\n";
+        assert_eq!(parse_remote_head_branch(output).as_deref(), Some("develop"));
+        assert_eq!(
+            parse_remote_head_branch("* remote origin\n  HEAD branch: (unknown)\n"),
+            None
+        );
+        assert_eq!(parse_remote_head_branch(""), None);
+    }
 }
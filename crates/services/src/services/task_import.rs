@@ -0,0 +1,226 @@
+//! Parsing of task import files for `POST /api/projects/{id}/import`.
+//!
+//! CSV files need a header row with a `task_title` (or `title`) column and may
+//! have `description`, `status` and `tags` columns. JSON files hold an array of
+//! `{ title, description, status, tags }` objects. Tasks have no labels in this
+//! schema, so `tags` is accepted for compatibility with other tools and ignored.
+
+use std::str::FromStr;
+
+use db::models::task::{CreateTask, TaskStatus};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Maximum number of rows accepted in a single import.
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+#[derive(Debug, Error)]
+pub enum TaskImportError {
+    #[error("Import file has {rows} rows; at most {limit} are allowed")]
+    TooManyRows { rows: usize, limit: usize },
+    #[error("CSV file must have a task_title or title column")]
+    MissingTitleColumn,
+    #[error("Invalid CSV file: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Invalid JSON file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskImportFormat {
+    Csv,
+    Json,
+}
+
+impl TaskImportFormat {
+    /// Pick the format from the file extension or content type, falling back
+    /// to JSON when the content starts with `[`.
+    pub fn detect(file_name: Option<&str>, content_type: Option<&str>, data: &[u8]) -> Self {
+        let extension = file_name
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        match (extension.as_deref(), content_type) {
+            (Some("json"), _) => Self::Json,
+            (Some("csv"), _) => Self::Csv,
+            (_, Some(content_type)) if content_type.contains("json") => Self::Json,
+            (_, Some(content_type)) if content_type.contains("csv") => Self::Csv,
+            _ if data.trim_ascii_start().starts_with(b"[") => Self::Json,
+            _ => Self::Csv,
+        }
+    }
+}
+
+/// A row that was skipped, numbered from 1 excluding the CSV header.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskImportRowError {
+    pub row: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedTaskImport {
+    pub tasks: Vec<CreateTask>,
+    pub errors: Vec<TaskImportRowError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportRow {
+    #[serde(alias = "task_title")]
+    title: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+}
+
+/// Parse and validate an import file. Invalid rows are reported in
+/// [`ParsedTaskImport::errors`]; only unreadable files and files over
+/// [`MAX_IMPORT_ROWS`] rows fail as a whole.
+pub fn parse_task_import(
+    data: &[u8],
+    format: TaskImportFormat,
+    project_id: Uuid,
+) -> Result<ParsedTaskImport, TaskImportError> {
+    let rows = match format {
+        TaskImportFormat::Csv => read_csv(data)?,
+        TaskImportFormat::Json => serde_json::from_slice::<Vec<serde_json::Value>>(data)?
+            .into_iter()
+            .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+            .collect(),
+    };
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(TaskImportError::TooManyRows {
+            rows: rows.len(),
+            limit: MAX_IMPORT_ROWS,
+        });
+    }
+
+    let mut parsed = ParsedTaskImport::default();
+    for (index, row) in rows.into_iter().enumerate() {
+        match row.and_then(|row| validate_row(row, project_id)) {
+            Ok(task) => parsed.tasks.push(task),
+            Err(message) => parsed.errors.push(TaskImportRowError {
+                row: index as u32 + 1,
+                message,
+            }),
+        }
+    }
+    Ok(parsed)
+}
+
+fn read_csv(data: &[u8]) -> Result<Vec<Result<ImportRow, String>>, TaskImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::Headers)
+        .from_reader(data);
+    let has_title = reader
+        .headers()?
+        .iter()
+        .any(|header| header == "task_title" || header == "title");
+    if !has_title {
+        return Err(TaskImportError::MissingTitleColumn);
+    }
+    Ok(reader
+        .deserialize::<ImportRow>()
+        .map(|row| row.map_err(|e| e.to_string()))
+        .collect())
+}
+
+fn validate_row(row: ImportRow, project_id: Uuid) -> Result<CreateTask, String> {
+    let title = row.title.as_deref().map(str::trim).unwrap_or_default();
+    if title.is_empty() {
+        return Err("Title must not be empty".to_string());
+    }
+    let description = row
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    let status = match row.status.as_deref().map(str::trim) {
+        None | Some("") => TaskStatus::default(),
+        Some(status) => parse_status(status)?,
+    };
+
+    Ok(CreateTask {
+        project_id,
+        title: title.to_string(),
+        description,
+        status: Some(status),
+        parent_workspace_id: None,
+        image_ids: None,
+    })
+}
+
+/// Accept `inprogress` as well as `in_progress`, `in-progress` and `In Progress`.
+fn parse_status(status: &str) -> Result<TaskStatus, String> {
+    let normalized: String = status
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .collect::<String>()
+        .to_ascii_lowercase();
+    TaskStatus::from_str(&normalized).map_err(|_| format!("Unknown status '{status}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_rows_and_reports_invalid_ones() {
+        let csv = "task_title,description,status,tags\n\
+                   Write docs,\"Cover the, API\",in_progress,docs\n\
+                   ,missing title,todo,\n\
+                   Ship it,,shipped,\n\
+                   Plain task,,,\n";
+        let parsed =
+            parse_task_import(csv.as_bytes(), TaskImportFormat::Csv, Uuid::new_v4()).unwrap();
+
+        assert_eq!(parsed.tasks.len(), 2);
+        assert_eq!(parsed.tasks[0].title, "Write docs");
+        assert_eq!(
+            parsed.tasks[0].description.as_deref(),
+            Some("Cover the, API")
+        );
+        assert_eq!(parsed.tasks[0].status, Some(TaskStatus::InProgress));
+        assert_eq!(parsed.tasks[1].status, Some(TaskStatus::Todo));
+        let rows: Vec<u32> = parsed.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![2, 3]);
+    }
+
+    #[test]
+    fn parses_json_rows() {
+        let json = r#"[
+            { "title": "First", "status": "done", "tags": ["a"] },
+            { "title": 42 }
+        ]"#;
+        let parsed =
+            parse_task_import(json.as_bytes(), TaskImportFormat::Json, Uuid::new_v4()).unwrap();
+
+        assert_eq!(parsed.tasks.len(), 1);
+        assert_eq!(parsed.tasks[0].status, Some(TaskStatus::Done));
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].row, 2);
+    }
+
+    #[test]
+    fn rejects_files_over_the_row_limit() {
+        let json = format!("[{}]", vec!["{}"; MAX_IMPORT_ROWS + 1].join(","));
+        let result = parse_task_import(json.as_bytes(), TaskImportFormat::Json, Uuid::new_v4());
+        assert!(matches!(result, Err(TaskImportError::TooManyRows { .. })));
+    }
+
+    #[test]
+    fn detects_format() {
+        assert_eq!(
+            TaskImportFormat::detect(Some("tasks.JSON"), None, b""),
+            TaskImportFormat::Json
+        );
+        assert_eq!(
+            TaskImportFormat::detect(None, Some("text/csv"), b"[]"),
+            TaskImportFormat::Csv
+        );
+        assert_eq!(
+            TaskImportFormat::detect(None, None, b"  [{}]"),
+            TaskImportFormat::Json
+        );
+    }
+}
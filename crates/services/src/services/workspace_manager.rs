@@ -1,13 +1,21 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use db::models::{repo::Repo, workspace::Workspace as DbWorkspace};
 use db::DeploymentMode;
+use ignore::WalkBuilder;
+use serde::Serialize;
 use sqlx::{Pool, Sqlite};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
+use ts_rs::TS;
 use uuid::Uuid;
 
-use super::worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
+use super::{
+    file_search::FileSearchCache,
+    git::{GitCli, GitCliError, GitService},
+    repo::{RepoError, RepoService},
+    worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager},
+};
 
 #[derive(Debug, Clone)]
 pub struct RepoWorkspaceInput {
@@ -36,8 +44,21 @@ pub enum WorkspaceError {
     PartialCreation(String),
     #[error("Unauthorized: path {0} is outside user workspace boundary")]
     Unauthorized(String),
+    #[error("Workspace quota exceeded: {used} of {limit} bytes used")]
+    QuotaExceeded { used: u64, limit: u64 },
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Cannot move workspace: {0}")]
+    MoveFailed(String),
+    #[error(transparent)]
+    GitCli(#[from] GitCliError),
+    #[error(transparent)]
+    Repo(#[from] RepoError),
 }
 
+/// Default cap on the diff text returned by [`WorkspaceManager::aggregate_diff`]
+const DEFAULT_DIFF_MAX_BYTES: usize = 1024 * 1024;
+
 /// Info about a single repo's worktree within a workspace
 #[derive(Debug, Clone)]
 pub struct RepoWorktree {
@@ -54,6 +75,72 @@ pub struct WorktreeContainer {
     pub worktrees: Vec<RepoWorktree>,
 }
 
+/// On-disk state of a single repo's worktree, as reported by
+/// [`WorkspaceManager::list_worktrees`]
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WorktreeStatus {
+    pub repo_id: Uuid,
+    pub path: PathBuf,
+    pub exists: bool,
+    pub branch: Option<String>,
+    /// Branch last recorded for the worktree in the database. Left empty by
+    /// [`WorkspaceManager::list_worktrees`], which does not read the database.
+    pub active_branch: String,
+    pub is_dirty: bool,
+}
+
+/// A file with uncommitted changes. Line counts are `None` for binary files.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ChangedFile {
+    pub path: String,
+    pub additions: Option<u32>,
+    pub deletions: Option<u32>,
+}
+
+/// Uncommitted changes in one repo's worktree, as reported by
+/// [`WorkspaceManager::aggregate_diff`]
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RepoDiff {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    /// `git diff --stat` summary
+    pub diff_stat: String,
+    pub files_changed: Vec<ChangedFile>,
+    /// Full diff text, only included when requested
+    pub diff: Option<String>,
+    /// True when `diff` was cut short by `DIFF_MAX_BYTES`
+    pub truncated: bool,
+}
+
+/// A commit made in one repo's worktree by [`WorkspaceManager::commit_workspace`]
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CommitResult {
+    pub repo_name: String,
+    pub sha: String,
+    pub files_committed: u32,
+}
+
+/// Outcome of pushing one repo's worktree branch, as reported by
+/// [`WorkspaceManager::push_workspace`]. A failed push sets `error` and keeps
+/// whatever was resolved before the failure.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PushResult {
+    pub repo_name: String,
+    pub remote_url: Option<String>,
+    pub branch: Option<String>,
+    pub sha: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Disk usage of a user's workspace directory against `WORKSPACE_QUOTA_BYTES`.
+/// `limit_bytes` and `remaining_bytes` are `None` when no quota is configured.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WorkspaceQuota {
+    pub used_bytes: u64,
+    pub limit_bytes: Option<u64>,
+    pub remaining_bytes: Option<u64>,
+}
+
 pub struct WorkspaceManager;
 
 impl WorkspaceManager {
@@ -63,6 +150,7 @@ impl WorkspaceManager {
         workspace_dir: &Path,
         repos: &[RepoWorkspaceInput],
         branch_name: &str,
+        file_search_cache: &FileSearchCache,
     ) -> Result<WorktreeContainer, WorkspaceError> {
         if repos.is_empty() {
             return Err(WorkspaceError::NoRepositories);
@@ -74,11 +162,16 @@ impl WorkspaceManager {
             repos.len()
         );
 
+        let mut target_branches = Vec::with_capacity(repos.len());
+        for input in repos {
+            target_branches.push(Self::resolve_target_branch(input).await?);
+        }
+
         tokio::fs::create_dir_all(workspace_dir).await?;
 
         let mut created_worktrees: Vec<RepoWorktree> = Vec::new();
 
-        for input in repos {
+        for (input, target_branch) in repos.iter().zip(&target_branches) {
             let worktree_path = workspace_dir.join(&input.repo.name);
 
             debug!(
@@ -91,7 +184,7 @@ impl WorkspaceManager {
                 &input.repo.path,
                 branch_name,
                 &worktree_path,
-                &input.target_branch,
+                target_branch,
                 true,
             )
             .await
@@ -134,17 +227,45 @@ impl WorkspaceManager {
             created_worktrees.len()
         );
 
+        for worktree in &created_worktrees {
+            file_search_cache
+                .invalidate_for_repo(worktree.repo_id)
+                .await;
+        }
+
         Ok(WorktreeContainer {
             workspace_dir: workspace_dir.to_path_buf(),
             worktrees: created_worktrees,
         })
     }
 
+    /// The branch to base `input`'s worktree on. An empty target branch means
+    /// the repo's default branch.
+    async fn resolve_target_branch(input: &RepoWorkspaceInput) -> Result<String, WorkspaceError> {
+        if !input.target_branch.trim().is_empty() {
+            return Ok(input.target_branch.clone());
+        }
+
+        let repo_id = input.repo.id;
+        let repo_path = input.repo.path.clone();
+        let branch = tokio::task::spawn_blocking(move || {
+            RepoService::new().detect_default_branch(repo_id, &repo_path)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        debug!(
+            "Using default branch '{}' for repo '{}'",
+            branch, input.repo.name
+        );
+        Ok(branch)
+    }
+
     /// Ensure all worktrees in a workspace exist (for cold restart scenarios)
     pub async fn ensure_workspace_exists(
         workspace_dir: &Path,
         repos: &[Repo],
         branch_name: &str,
+        file_search_cache: &FileSearchCache,
     ) -> Result<(), WorkspaceError> {
         if repos.is_empty() {
             return Err(WorkspaceError::NoRepositories);
@@ -153,6 +274,7 @@ impl WorkspaceManager {
         // Try legacy migration first (single repo projects only)
         // Old layout had worktree directly at workspace_dir; new layout has it at workspace_dir/{repo_name}
         if repos.len() == 1 && Self::migrate_legacy_worktree(workspace_dir, &repos[0]).await? {
+            file_search_cache.invalidate_for_repo(repos[0].id).await;
             return Ok(());
         }
 
@@ -171,15 +293,400 @@ impl WorkspaceManager {
 
             WorktreeManager::ensure_worktree_exists(&repo.path, branch_name, &worktree_path)
                 .await?;
+            file_search_cache.invalidate_for_repo(repo.id).await;
+        }
+
+        Ok(())
+    }
+
+    /// Inspect the worktree of every repo in a workspace without modifying anything.
+    /// Missing worktrees are reported with `exists: false` rather than recreated.
+    pub fn list_worktrees(workspace_dir: &Path, repos: &[Repo]) -> Vec<WorktreeStatus> {
+        let git_service = GitService::new();
+        let git_cli = GitCli::new();
+
+        repos
+            .iter()
+            .map(|repo| {
+                let worktree_path = workspace_dir.join(&repo.name);
+                if !worktree_path.exists() {
+                    return WorktreeStatus {
+                        repo_id: repo.id,
+                        path: worktree_path,
+                        exists: false,
+                        branch: None,
+                        active_branch: String::new(),
+                        is_dirty: false,
+                    };
+                }
+
+                let branch = match git_service.get_current_branch(&worktree_path) {
+                    Ok(branch) => Some(branch),
+                    Err(e) => {
+                        debug!(
+                            "Could not read HEAD for worktree {}: {}",
+                            worktree_path.display(),
+                            e
+                        );
+                        None
+                    }
+                };
+                let is_dirty = git_cli.has_changes(&worktree_path).unwrap_or_else(|e| {
+                    warn!(
+                        "Could not read status for worktree {}: {}",
+                        worktree_path.display(),
+                        e
+                    );
+                    false
+                });
+
+                WorktreeStatus {
+                    repo_id: repo.id,
+                    path: worktree_path,
+                    exists: true,
+                    branch,
+                    active_branch: String::new(),
+                    is_dirty,
+                }
+            })
+            .collect()
+    }
+
+    /// Diff every repo worktree in a workspace against its HEAD, i.e. the
+    /// changes not yet committed. With `include_patch` the full diff text is
+    /// returned as well, capped at `DIFF_MAX_BYTES` across all repos. Repos
+    /// whose worktree does not exist are skipped.
+    pub fn aggregate_diff(
+        workspace_dir: &Path,
+        repos: &[Repo],
+        include_patch: bool,
+    ) -> Result<Vec<RepoDiff>, WorkspaceError> {
+        let git_cli = GitCli::new();
+        let mut remaining_bytes = Self::diff_max_bytes();
+        let mut diffs = Vec::with_capacity(repos.len());
+
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            if !worktree_path.exists() {
+                continue;
+            }
+
+            let diff_stat = git_cli.git(
+                &worktree_path,
+                ["-c", "core.quotepath=false", "diff", "HEAD", "--stat"],
+            )?;
+            let numstat = git_cli.git(
+                &worktree_path,
+                ["-c", "core.quotepath=false", "diff", "HEAD", "--numstat"],
+            )?;
+            let files_changed = numstat
+                .lines()
+                .filter_map(Self::parse_numstat_line)
+                .collect();
+
+            let (diff, truncated) = if include_patch {
+                let patch = git_cli.git(&worktree_path, ["diff", "HEAD"])?;
+                let (patch, truncated) = Self::truncate_diff(patch, remaining_bytes);
+                remaining_bytes = remaining_bytes.saturating_sub(patch.len());
+                (Some(patch), truncated)
+            } else {
+                (None, false)
+            };
+
+            diffs.push(RepoDiff {
+                repo_id: repo.id,
+                repo_name: repo.name.clone(),
+                diff_stat,
+                files_changed,
+                diff,
+                truncated,
+            });
+        }
+
+        Ok(diffs)
+    }
+
+    /// Limit on diff text read from `DIFF_MAX_BYTES`, 1 MiB by default.
+    pub fn diff_max_bytes() -> usize {
+        let Ok(value) = std::env::var("DIFF_MAX_BYTES") else {
+            return DEFAULT_DIFF_MAX_BYTES;
+        };
+        match value.trim().parse::<usize>() {
+            Ok(limit) => limit,
+            Err(_) => {
+                warn!("Ignoring invalid DIFF_MAX_BYTES value: {}", value);
+                DEFAULT_DIFF_MAX_BYTES
+            }
+        }
+    }
+
+    /// Parse one `git diff --numstat` line: `<added>\t<deleted>\t<path>`,
+    /// where binary files report `-` for both counts.
+    fn parse_numstat_line(line: &str) -> Option<ChangedFile> {
+        let mut parts = line.splitn(3, '\t');
+        let additions = parts.next()?;
+        let deletions = parts.next()?;
+        let path = parts.next()?;
+        Some(ChangedFile {
+            path: path.to_string(),
+            additions: additions.parse().ok(),
+            deletions: deletions.parse().ok(),
+        })
+    }
+
+    fn truncate_diff(mut diff: String, max_bytes: usize) -> (String, bool) {
+        if diff.len() <= max_bytes {
+            return (diff, false);
+        }
+        let mut end = max_bytes;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        diff.truncate(end);
+        diff.push_str("\n[diff truncated: DIFF_MAX_BYTES limit reached]\n");
+        (diff, true)
+    }
+
+    /// Stage and commit all changes in every repo worktree of a workspace.
+    /// Repos without changes are skipped. When no author is given the repo's
+    /// configured identity is used, falling back to the Vibe Kanban identity.
+    pub fn commit_workspace(
+        workspace_dir: &Path,
+        repos: &[Repo],
+        message: &str,
+        author_name: Option<&str>,
+        author_email: Option<&str>,
+    ) -> Result<Vec<CommitResult>, WorkspaceError> {
+        let git_cli = GitCli::new();
+        let mut results = Vec::new();
+
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            if !worktree_path.exists() || !git_cli.has_changes(&worktree_path)? {
+                continue;
+            }
+
+            git_cli.add_all(&worktree_path)?;
+            let staged = git_cli.git(&worktree_path, ["diff", "--cached", "--name-only"])?;
+            let files_committed = staged.lines().filter(|l| !l.is_empty()).count() as u32;
+            if files_committed == 0 {
+                continue;
+            }
+
+            let name = Self::commit_identity(&git_cli, &worktree_path, "user.name", author_name)
+                .unwrap_or_else(|| "Vibe Kanban".to_string());
+            let email = Self::commit_identity(&git_cli, &worktree_path, "user.email", author_email)
+                .unwrap_or_else(|| "noreply@vibekanban.com".to_string());
+            git_cli.git(
+                &worktree_path,
+                [
+                    "-c",
+                    &format!("user.name={name}"),
+                    "-c",
+                    &format!("user.email={email}"),
+                    "commit",
+                    "-m",
+                    message,
+                ],
+            )?;
+            let sha = git_cli.git(&worktree_path, ["rev-parse", "HEAD"])?;
+
+            info!(
+                "Committed {} files in {} ({})",
+                files_committed,
+                repo.name,
+                sha.trim()
+            );
+            results.push(CommitResult {
+                repo_name: repo.name.clone(),
+                sha: sha.trim().to_string(),
+                files_committed,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Files with uncommitted changes, tracked or untracked, in the repo
+    /// worktrees of a workspace, as `<repo name>/<path>`.
+    pub fn list_dirty_files(
+        workspace_dir: &Path,
+        repos: &[Repo],
+    ) -> Result<Vec<String>, WorkspaceError> {
+        let git_cli = GitCli::new();
+        let mut dirty = Vec::new();
+
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            if !worktree_path.exists() {
+                continue;
+            }
+
+            for entry in git_cli.get_worktree_status(&worktree_path)?.entries {
+                let path = String::from_utf8_lossy(&entry.path);
+                dirty.push(format!("{}/{}", repo.name, path));
+            }
+        }
+
+        Ok(dirty)
+    }
+
+    /// Discard all uncommitted changes in every repo worktree of a workspace:
+    /// tracked files are reset to `HEAD` and untracked files and directories
+    /// are removed. Ignored files are kept.
+    pub fn reset_workspace(workspace_dir: &Path, repos: &[Repo]) -> Result<(), WorkspaceError> {
+        let git_cli = GitCli::new();
+
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            if !worktree_path.exists() {
+                continue;
+            }
+
+            git_cli.git(&worktree_path, ["reset", "--hard", "HEAD"])?;
+            git_cli.git(&worktree_path, ["clean", "-fd"])?;
+            info!("Reset worktree {} to HEAD", worktree_path.display());
         }
 
         Ok(())
     }
 
+    /// Push the checked-out branch of every repo worktree in a workspace.
+    ///
+    /// `remote_name` defaults to `remote.pushDefault`, then the first configured
+    /// remote. With a `token` HTTPS remotes are authenticated with it; otherwise
+    /// native git authentication applies, including any per-repo
+    /// `credential.helper`. Failures are reported per repo rather than aborting
+    /// the remaining pushes.
+    pub fn push_workspace(
+        workspace_dir: &Path,
+        repos: &[Repo],
+        remote_name: Option<&str>,
+        force: bool,
+        token: Option<&str>,
+    ) -> Result<Vec<PushResult>, WorkspaceError> {
+        let git_cli = GitCli::new();
+        let mut results = Vec::new();
+
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            if !worktree_path.exists() {
+                continue;
+            }
+
+            let mut result = PushResult {
+                repo_name: repo.name.clone(),
+                remote_url: None,
+                branch: None,
+                sha: None,
+                error: None,
+            };
+            if let Err(e) = Self::push_worktree(
+                &git_cli,
+                &worktree_path,
+                remote_name,
+                force,
+                token,
+                &mut result,
+            ) {
+                let message = match token {
+                    Some(t) => e.to_string().replace(t, "***"),
+                    None => e.to_string(),
+                };
+                warn!("Push failed for {}: {}", repo.name, message);
+                result.error = Some(message);
+            }
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    fn push_worktree(
+        git_cli: &GitCli,
+        worktree_path: &Path,
+        remote_name: Option<&str>,
+        force: bool,
+        token: Option<&str>,
+        result: &mut PushResult,
+    ) -> Result<(), GitCliError> {
+        let branch = git_cli
+            .git(worktree_path, ["rev-parse", "--abbrev-ref", "HEAD"])?
+            .trim()
+            .to_string();
+        if branch == "HEAD" {
+            return Err(GitCliError::CommandFailed(
+                "worktree is not on a branch".to_string(),
+            ));
+        }
+        result.branch = Some(branch.clone());
+
+        let sha = git_cli.git(worktree_path, ["rev-parse", "HEAD"])?;
+        result.sha = Some(sha.trim().to_string());
+
+        let remote = match remote_name {
+            Some(name) => name.to_string(),
+            None => Self::default_remote(git_cli, worktree_path)?,
+        };
+        let remote_url = git_cli.get_remote_url(worktree_path, &remote)?;
+        result.remote_url = Some(remote_url.clone());
+
+        git_cli.push_with_token(worktree_path, &remote_url, &branch, force, token)?;
+
+        // Pushing to a URL skips the remote-tracking ref, so record it here
+        git_cli.git(
+            worktree_path,
+            [
+                "update-ref",
+                &format!("refs/remotes/{remote}/{branch}"),
+                sha.trim(),
+            ],
+        )?;
+        git_cli.git(
+            worktree_path,
+            [
+                "branch",
+                &format!("--set-upstream-to={remote}/{branch}"),
+                &branch,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// `remote.pushDefault` if set, otherwise the first configured remote.
+    fn default_remote(git_cli: &GitCli, worktree_path: &Path) -> Result<String, GitCliError> {
+        if let Ok(default) = git_cli.git(worktree_path, ["config", "remote.pushDefault"])
+            && !default.trim().is_empty()
+        {
+            return Ok(default.trim().to_string());
+        }
+        git_cli
+            .git(worktree_path, ["remote"])?
+            .lines()
+            .next()
+            .map(|name| name.trim().to_string())
+            .ok_or_else(|| GitCliError::CommandFailed("no remote configured".to_string()))
+    }
+
+    /// The explicit value if given, otherwise the repo's `git config` entry.
+    fn commit_identity(
+        git_cli: &GitCli,
+        worktree_path: &Path,
+        key: &str,
+        explicit: Option<&str>,
+    ) -> Option<String> {
+        explicit
+            .map(str::to_string)
+            .or_else(|| git_cli.git(worktree_path, ["config", key]).ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
     /// Clean up all worktrees in a workspace
     pub async fn cleanup_workspace(
         workspace_dir: &Path,
         repos: &[Repo],
+        file_search_cache: &FileSearchCache,
     ) -> Result<(), WorkspaceError> {
         info!("Cleaning up workspace at {}", workspace_dir.display());
 
@@ -193,6 +700,10 @@ impl WorkspaceManager {
 
         WorktreeManager::batch_cleanup_worktrees(&cleanup_data).await?;
 
+        for repo in repos {
+            file_search_cache.invalidate_for_repo(repo.id).await;
+        }
+
         // Remove the workspace directory itself
         if workspace_dir.exists()
             && let Err(e) = tokio::fs::remove_dir_all(workspace_dir).await
@@ -229,7 +740,7 @@ impl WorkspaceManager {
     pub fn get_workspace_base_dir_for_user(user_id: &Uuid) -> PathBuf {
         let mode = DeploymentMode::detect();
 
-        if mode.is_kubernetes() {
+        if mode.is_multi_user() {
             // In K8s mode, use user-specific subdirectory
             let base = std::env::var("WORKSPACE_BASE_DIR")
                 .map(PathBuf::from)
@@ -241,6 +752,85 @@ impl WorkspaceManager {
         }
     }
 
+    /// Per-user disk quota read from `WORKSPACE_QUOTA_BYTES`. Unset or
+    /// unparsable values mean unlimited.
+    pub fn quota_limit_bytes() -> Option<u64> {
+        let value = std::env::var("WORKSPACE_QUOTA_BYTES").ok()?;
+        match value.trim().parse::<u64>() {
+            Ok(limit) => Some(limit),
+            Err(_) => {
+                warn!("Ignoring invalid WORKSPACE_QUOTA_BYTES value: {}", value);
+                None
+            }
+        }
+    }
+
+    /// Sum the sizes of all files under `dir`, including ignored and hidden
+    /// files since they occupy disk all the same. Symlinks are not followed.
+    pub fn disk_usage(dir: &Path) -> u64 {
+        if !dir.exists() {
+            return 0;
+        }
+
+        WalkBuilder::new(dir)
+            .standard_filters(false)
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+
+    /// Check a user's workspace disk usage against `limit_bytes`.
+    ///
+    /// Walks the user's workspace base directory and returns the bytes used,
+    /// or `WorkspaceError::QuotaExceeded` once usage has reached the limit.
+    /// This walks the filesystem synchronously; call from a blocking context.
+    pub fn check_user_quota(user_id: &Uuid, limit_bytes: u64) -> Result<u64, WorkspaceError> {
+        let used = Self::disk_usage(&Self::get_workspace_base_dir_for_user(user_id));
+        if used >= limit_bytes {
+            return Err(WorkspaceError::QuotaExceeded {
+                used,
+                limit: limit_bytes,
+            });
+        }
+        Ok(used)
+    }
+
+    /// Enforce the configured quota for a user before allocating new worktrees.
+    /// A no-op when `WORKSPACE_QUOTA_BYTES` is not set.
+    pub async fn enforce_user_quota(user_id: &Uuid) -> Result<(), WorkspaceError> {
+        let Some(limit) = Self::quota_limit_bytes() else {
+            return Ok(());
+        };
+
+        let user_id = *user_id;
+        let used = tokio::task::spawn_blocking(move || Self::check_user_quota(&user_id, limit))
+            .await
+            .map_err(std::io::Error::other)??;
+
+        debug!(
+            "User {} workspace usage {} of {} bytes",
+            user_id, used, limit
+        );
+        Ok(())
+    }
+
+    /// Report disk usage of `dir` against the configured quota.
+    pub async fn quota_for_dir(dir: PathBuf) -> Result<WorkspaceQuota, std::io::Error> {
+        let used_bytes = tokio::task::spawn_blocking(move || Self::disk_usage(&dir))
+            .await
+            .map_err(std::io::Error::other)?;
+        let limit_bytes = Self::quota_limit_bytes();
+
+        Ok(WorkspaceQuota {
+            used_bytes,
+            limit_bytes,
+            remaining_bytes: limit_bytes.map(|limit| limit.saturating_sub(used_bytes)),
+        })
+    }
+
     /// Validate that a given path is within the user's workspace boundary.
     ///
     /// This function prevents path traversal attacks and ensures users can only
@@ -266,8 +856,8 @@ impl WorkspaceManager {
     pub fn validate_user_path(user_id: &Uuid, path: &Path) -> Result<PathBuf, WorkspaceError> {
         let mode = DeploymentMode::detect();
 
-        // In desktop mode, skip validation (single-user, no isolation needed)
-        if mode.is_desktop() {
+        // In single-user mode, skip validation (no isolation needed)
+        if !mode.is_multi_user() {
             // Still canonicalize if the path exists, otherwise return as-is
             return Ok(dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
         }
@@ -288,10 +878,16 @@ impl WorkspaceManager {
         let canonical_path = if path.exists() {
             dunce::canonicalize(path)?
         } else {
-            // For non-existent paths, resolve parent components to detect traversal
+            // For non-existent paths, resolve parent components to detect traversal.
+            // `..` is applied lexically: the prefix is either canonical or does not
+            // exist, so it cannot contain a symlink that `..` would escape through.
             let mut resolved = PathBuf::new();
             for component in path.components() {
-                resolved.push(component);
+                if component == Component::ParentDir {
+                    resolved.pop();
+                } else {
+                    resolved.push(component);
+                }
                 // Try to canonicalize what exists so far
                 if resolved.exists() {
                     resolved = dunce::canonicalize(&resolved)?;
@@ -326,6 +922,7 @@ impl WorkspaceManager {
     /// * `workspace_dir` - The directory where the workspace will be created
     /// * `repos` - The repositories to include in the workspace
     /// * `branch_name` - The name of the branch to create worktrees on
+    /// * `file_search_cache` - Search cache to invalidate for the repositories
     ///
     /// # Returns
     ///
@@ -335,16 +932,20 @@ impl WorkspaceManager {
         workspace_dir: &Path,
         repos: &[RepoWorkspaceInput],
         branch_name: &str,
+        file_search_cache: &FileSearchCache,
     ) -> Result<WorktreeContainer, WorkspaceError> {
         // Validate path is within user's workspace boundary
         Self::validate_user_path(user_id, workspace_dir)?;
 
+        // Refuse to allocate new worktrees once the user is over quota
+        Self::enforce_user_quota(user_id).await?;
+
         // Ensure user's base directory exists
         let user_base = Self::get_workspace_base_dir_for_user(user_id);
         tokio::fs::create_dir_all(&user_base).await?;
 
         // Delegate to existing create_workspace logic
-        Self::create_workspace(workspace_dir, repos, branch_name).await
+        Self::create_workspace(workspace_dir, repos, branch_name, file_search_cache).await
     }
 
     /// Ensure all worktrees in a workspace exist, with user-aware path validation.
@@ -355,17 +956,19 @@ impl WorkspaceManager {
     /// * `workspace_dir` - The workspace directory
     /// * `repos` - The repositories in the workspace
     /// * `branch_name` - The branch name for worktrees
+    /// * `file_search_cache` - Search cache to invalidate for the repositories
     pub async fn ensure_workspace_exists_for_user(
         user_id: &Uuid,
         workspace_dir: &Path,
         repos: &[Repo],
         branch_name: &str,
+        file_search_cache: &FileSearchCache,
     ) -> Result<(), WorkspaceError> {
         // Validate path is within user's workspace boundary
         Self::validate_user_path(user_id, workspace_dir)?;
 
         // Delegate to existing ensure_workspace_exists logic
-        Self::ensure_workspace_exists(workspace_dir, repos, branch_name).await
+        Self::ensure_workspace_exists(workspace_dir, repos, branch_name, file_search_cache).await
     }
 
     /// Clean up all worktrees in a workspace, with user-aware path validation.
@@ -375,16 +978,18 @@ impl WorkspaceManager {
     /// * `user_id` - The UUID of the user
     /// * `workspace_dir` - The workspace directory
     /// * `repos` - The repositories in the workspace
+    /// * `file_search_cache` - Search cache to invalidate for the repositories
     pub async fn cleanup_workspace_for_user(
         user_id: &Uuid,
         workspace_dir: &Path,
         repos: &[Repo],
+        file_search_cache: &FileSearchCache,
     ) -> Result<(), WorkspaceError> {
         // Validate path is within user's workspace boundary
         Self::validate_user_path(user_id, workspace_dir)?;
 
         // Delegate to existing cleanup_workspace logic
-        Self::cleanup_workspace(workspace_dir, repos).await
+        Self::cleanup_workspace(workspace_dir, repos, file_search_cache).await
     }
 
     /// Migrate a legacy single-worktree layout to the new workspace layout.
@@ -445,6 +1050,92 @@ impl WorkspaceManager {
         Ok(true)
     }
 
+    /// Relocate a workspace directory, e.g. after `WORKSPACE_BASE_DIR` changed.
+    ///
+    /// Renames `old_dir` to `new_dir`, runs `git worktree repair` for each repo so
+    /// the source repositories point at the new location, then updates the
+    /// workspace's `container_ref`. If any step after the rename fails, the
+    /// directory is moved back and the worktrees are repaired at `old_dir`.
+    pub async fn move_workspace(
+        db: &Pool<Sqlite>,
+        workspace_id: Uuid,
+        old_dir: &Path,
+        new_dir: &Path,
+        repos: &[Repo],
+    ) -> Result<(), WorkspaceError> {
+        if !old_dir.exists() {
+            return Err(WorkspaceError::MoveFailed(format!(
+                "{} does not exist",
+                old_dir.display()
+            )));
+        }
+        if new_dir.exists() {
+            return Err(WorkspaceError::MoveFailed(format!(
+                "{} already exists",
+                new_dir.display()
+            )));
+        }
+
+        info!(
+            "Moving workspace {} from {} to {}",
+            workspace_id,
+            old_dir.display(),
+            new_dir.display()
+        );
+
+        if let Some(parent) = new_dir.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::rename(old_dir, new_dir).await?;
+
+        let result = async {
+            Self::repair_worktrees(new_dir, repos).await?;
+            DbWorkspace::update_container_ref(db, workspace_id, &new_dir.to_string_lossy()).await?;
+            Ok::<(), WorkspaceError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!(
+                "Failed to move workspace {}: {}. Rolling back...",
+                workspace_id, e
+            );
+            if let Err(rename_err) = tokio::fs::rename(new_dir, old_dir).await {
+                error!(
+                    "Could not move workspace {} back to {}: {}",
+                    workspace_id,
+                    old_dir.display(),
+                    rename_err
+                );
+            } else if let Err(repair_err) = Self::repair_worktrees(old_dir, repos).await {
+                error!(
+                    "Could not repair worktrees of workspace {} after rollback: {}",
+                    workspace_id, repair_err
+                );
+            }
+            return Err(e);
+        }
+
+        info!("Moved workspace {} to {}", workspace_id, new_dir.display());
+        Ok(())
+    }
+
+    /// Run `git worktree repair` for every repo worktree present in `workspace_dir`
+    async fn repair_worktrees(workspace_dir: &Path, repos: &[Repo]) -> Result<(), WorkspaceError> {
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            if !worktree_path.exists() {
+                debug!(
+                    "Skipping repair of missing worktree {}",
+                    worktree_path.display()
+                );
+                continue;
+            }
+            WorktreeManager::repair_worktree(&repo.path, &worktree_path).await?;
+        }
+        Ok(())
+    }
+
     /// Helper to cleanup worktrees during rollback
     async fn cleanup_created_worktrees(worktrees: &[RepoWorktree]) {
         for worktree in worktrees {
@@ -462,47 +1153,59 @@ impl WorkspaceManager {
         }
     }
 
-    pub async fn cleanup_orphan_workspaces(db: &Pool<Sqlite>) {
+    /// Prune orphaned workspace directories from the default and configured
+    /// base directories, returning how many were removed.
+    pub async fn cleanup_orphan_workspaces(db: &Pool<Sqlite>) -> u32 {
         if std::env::var("DISABLE_WORKTREE_ORPHAN_CLEANUP").is_ok() {
             debug!(
                 "Orphan workspace cleanup is disabled via DISABLE_WORKTREE_ORPHAN_CLEANUP environment variable"
             );
-            return;
+            return 0;
         }
 
         // Always clean up the default directory
         let default_dir = WorktreeManager::get_default_worktree_base_dir();
-        Self::cleanup_orphans_in_directory(db, &default_dir).await;
+        let mut pruned = Self::cleanup_orphans_in_directory(db, &default_dir).await;
 
         // Also clean up custom directory if it's different from the default
         let current_dir = Self::get_workspace_base_dir();
         if current_dir != default_dir {
-            Self::cleanup_orphans_in_directory(db, &current_dir).await;
+            pruned += Self::cleanup_orphans_in_directory(db, &current_dir).await;
         }
+        pruned
+    }
+
+    async fn cleanup_orphans_in_directory(db: &Pool<Sqlite>, workspace_base_dir: &Path) -> u32 {
+        Self::prune_stale_worktrees(db, workspace_base_dir)
+            .await
+            .unwrap_or_else(|e| {
+                error!(
+                    "Failed to read workspace base directory {}: {}",
+                    workspace_base_dir.display(),
+                    e
+                );
+                0
+            })
     }
 
-    async fn cleanup_orphans_in_directory(db: &Pool<Sqlite>, workspace_base_dir: &Path) {
+    /// Remove every directory under `workspace_base_dir` that no workspace
+    /// record points at, along with the worktrees inside it. Returns how many
+    /// directories were removed; directories that fail to delete are logged
+    /// and skipped.
+    pub async fn prune_stale_worktrees(
+        db: &Pool<Sqlite>,
+        workspace_base_dir: &Path,
+    ) -> Result<u32, WorkspaceError> {
         if !workspace_base_dir.exists() {
             debug!(
                 "Workspace base directory {} does not exist, skipping orphan cleanup",
                 workspace_base_dir.display()
             );
-            return;
+            return Ok(0);
         }
 
-        let entries = match std::fs::read_dir(workspace_base_dir) {
-            Ok(entries) => entries,
-            Err(e) => {
-                error!(
-                    "Failed to read workspace base directory {}: {}",
-                    workspace_base_dir.display(),
-                    e
-                );
-                return;
-            }
-        };
-
-        for entry in entries {
+        let mut pruned = 0;
+        for entry in std::fs::read_dir(workspace_base_dir)? {
             let entry = match entry {
                 Ok(entry) => entry,
                 Err(e) => {
@@ -529,9 +1232,11 @@ impl WorkspaceManager {
                         "Successfully removed orphaned workspace: {}",
                         workspace_path_str
                     );
+                    pruned += 1;
                 }
             }
         }
+        Ok(pruned)
     }
 
     async fn cleanup_workspace_without_repos(workspace_dir: &Path) -> Result<(), WorkspaceError> {
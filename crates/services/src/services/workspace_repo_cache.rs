@@ -0,0 +1,77 @@
+//! In-memory cache of the repos attached to each workspace.
+//!
+//! Follow-ups look up a workspace's repos on every user message. Entries
+//! expire `WORKSPACE_REPO_CACHE_TTL_SECS` after they were loaded and are
+//! dropped earlier by the SQLite hooks in [`super::events::EventService`]
+//! whenever a workspace, its repo links or a repo change.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use db::models::{repo::Repo, workspace_repo::WorkspaceRepo};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Default lifetime of a cached entry, in seconds.
+const DEFAULT_TTL_SECS: u64 = 60;
+
+pub struct WorkspaceRepoCache {
+    entries: Arc<DashMap<Uuid, (Vec<Repo>, Instant)>>,
+    ttl: Duration,
+}
+
+impl WorkspaceRepoCache {
+    pub fn new() -> Self {
+        let ttl_secs = std::env::var("WORKSPACE_REPO_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_TTL_SECS);
+        Self::with_ttl(Duration::from_secs(ttl_secs))
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(DashMap::new()),
+            ttl,
+        }
+    }
+
+    /// The repos of `workspace_id`, loaded with
+    /// [`WorkspaceRepo::find_repos_for_workspace`] on a miss.
+    pub async fn find_repos_for_workspace(
+        &self,
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Repo>, sqlx::Error> {
+        if let Some(entry) = self.entries.get(&workspace_id)
+            && entry.1.elapsed() < self.ttl
+        {
+            return Ok(entry.0.clone());
+        }
+
+        let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace_id).await?;
+        self.entries
+            .insert(workspace_id, (repos.clone(), Instant::now()));
+        Ok(repos)
+    }
+
+    /// Drop the cached repos of one workspace.
+    pub fn invalidate(&self, workspace_id: Uuid) {
+        self.entries.remove(&workspace_id);
+    }
+
+    /// Drop every cached entry, e.g. after a repo was renamed or moved.
+    pub fn invalidate_all(&self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for WorkspaceRepoCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -5,8 +5,29 @@ use std::{
     sync::{Arc, LazyLock, Mutex, OnceLock},
 };
 
+/// Custom workspace directory from the user's config. Set at most once at
+/// startup and read without locking afterwards.
+#[cfg(not(test))]
 static WORKSPACE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
+// Tests run on parallel threads, so each gets its own resettable override
+#[cfg(test)]
+thread_local! {
+    static WORKSPACE_DIR_OVERRIDE: std::cell::RefCell<OnceLock<PathBuf>> =
+        std::cell::RefCell::new(OnceLock::new());
+}
+
+fn with_workspace_dir_override<R>(f: impl FnOnce(&OnceLock<PathBuf>) -> R) -> R {
+    #[cfg(not(test))]
+    {
+        f(&WORKSPACE_DIR_OVERRIDE)
+    }
+    #[cfg(test)]
+    {
+        WORKSPACE_DIR_OVERRIDE.with(|cell| f(&cell.borrow()))
+    }
+}
+
 use db::DeploymentMode;
 use git2::{Error as GitError, Repository};
 use uuid::Uuid;
@@ -14,7 +35,7 @@ use thiserror::Error;
 use tracing::{debug, info, trace};
 use utils::{path::normalize_macos_private_alias, shell::resolve_executable_path};
 
-use super::git::{GitService, GitServiceError};
+use super::git::{GitService, GitServiceError, HeadInfo};
 
 // Global synchronization for worktree creation to prevent race conditions
 static WORKTREE_CREATION_LOCKS: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
@@ -53,13 +74,30 @@ pub enum WorktreeError {
     BranchNotFound(String),
     #[error("Repository error: {0}")]
     Repository(String),
+    #[error("Workspace directory override is already set to a different path")]
+    AlreadyInitialized,
 }
 
 pub struct WorktreeManager;
 
 impl WorktreeManager {
-    pub fn set_workspace_dir_override(path: PathBuf) {
-        let _ = WORKSPACE_DIR_OVERRIDE.set(path);
+    /// Use `path` instead of the default worktree base directory.
+    ///
+    /// The override can only be set once per process. Setting the same path
+    /// again is a no-op; a different path returns `AlreadyInitialized`.
+    pub fn set_workspace_dir_override(path: PathBuf) -> Result<(), WorktreeError> {
+        with_workspace_dir_override(|override_path| {
+            if *override_path.get_or_init(|| path.clone()) == path {
+                Ok(())
+            } else {
+                Err(WorktreeError::AlreadyInitialized)
+            }
+        })
+    }
+
+    #[cfg(test)]
+    pub fn clear_workspace_dir_override() {
+        WORKSPACE_DIR_OVERRIDE.with(|cell| *cell.borrow_mut() = OnceLock::new());
     }
 
     /// Create a worktree with a new branch
@@ -537,9 +575,51 @@ impl WorktreeManager {
         .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
     }
 
+    /// Re-register a worktree with its repository after its directory was moved
+    pub async fn repair_worktree(
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<(), WorktreeError> {
+        let repo_path = repo_path.to_path_buf();
+        let worktree_path = worktree_path.to_path_buf();
+
+        tokio::task::spawn_blocking(move || {
+            let git_service = GitService::new();
+            git_service
+                .repair_worktree(&repo_path, &worktree_path)
+                .map_err(WorktreeError::GitService)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
+    /// Name of the branch checked out in a worktree, or `"HEAD"` when detached
+    pub async fn get_current_branch(worktree_path: &Path) -> Result<String, WorktreeError> {
+        Ok(Self::get_head_info(worktree_path).await?.branch)
+    }
+
+    /// Full SHA of the commit checked out in a worktree
+    pub async fn get_commit_sha(worktree_path: &Path) -> Result<String, WorktreeError> {
+        Ok(Self::get_head_info(worktree_path).await?.oid)
+    }
+
+    async fn get_head_info(worktree_path: &Path) -> Result<HeadInfo, WorktreeError> {
+        let worktree_path = worktree_path.to_path_buf();
+
+        tokio::task::spawn_blocking(move || {
+            GitService::new()
+                .get_head_info(&worktree_path)
+                .map_err(WorktreeError::GitService)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
     /// Get the base directory for vibe-kanban worktrees
     pub fn get_worktree_base_dir() -> std::path::PathBuf {
-        if let Some(override_path) = WORKSPACE_DIR_OVERRIDE.get() {
+        if let Some(override_path) =
+            with_workspace_dir_override(|override_path| override_path.get().cloned())
+        {
             // Always use app-owned subdirectory within custom path for safety.
             // This ensures orphan cleanup never touches user's existing folders.
             return override_path.join(".vibe-kanban-workspaces");
@@ -564,7 +644,7 @@ impl WorktreeManager {
     pub fn get_worktree_base_dir_for_user(user_id: &Uuid) -> std::path::PathBuf {
         let mode = DeploymentMode::detect();
 
-        if mode.is_kubernetes() {
+        if mode.is_multi_user() {
             // In K8s mode, use user-specific subdirectory
             let base = std::env::var("WORKSPACE_BASE_DIR")
                 .map(PathBuf::from)
@@ -638,3 +718,57 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
     .await
     .unwrap();
 }
+
+#[tokio::test]
+async fn worktree_reports_current_branch_and_commit() {
+    use tempfile::TempDir;
+    let td = TempDir::new().unwrap();
+
+    let repo_path = td.path().join("repo");
+    let git_service = GitService::new();
+    git_service
+        .initialize_repo_with_main_branch(&repo_path)
+        .unwrap();
+
+    let worktree_path = td.path().join("wt");
+    WorktreeManager::create_worktree(&repo_path, "wt-branch", &worktree_path, "main", true)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        WorktreeManager::get_current_branch(&worktree_path)
+            .await
+            .unwrap(),
+        "wt-branch"
+    );
+    assert_eq!(
+        WorktreeManager::get_commit_sha(&worktree_path)
+            .await
+            .unwrap(),
+        git_service.get_branch_oid(&repo_path, "main").unwrap()
+    );
+}
+
+#[test]
+fn workspace_dir_override_can_only_be_set_once() {
+    WorktreeManager::clear_workspace_dir_override();
+
+    let custom = PathBuf::from("/tmp/custom-workspaces");
+    WorktreeManager::set_workspace_dir_override(custom.clone()).unwrap();
+    // Re-applying the same path (e.g. on config reload) is fine
+    WorktreeManager::set_workspace_dir_override(custom.clone()).unwrap();
+    assert!(matches!(
+        WorktreeManager::set_workspace_dir_override(PathBuf::from("/tmp/other")),
+        Err(WorktreeError::AlreadyInitialized)
+    ));
+    assert_eq!(
+        WorktreeManager::get_worktree_base_dir(),
+        custom.join(".vibe-kanban-workspaces")
+    );
+
+    WorktreeManager::clear_workspace_dir_override();
+    assert_eq!(
+        WorktreeManager::get_worktree_base_dir(),
+        WorktreeManager::get_default_worktree_base_dir()
+    );
+}
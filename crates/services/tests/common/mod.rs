@@ -0,0 +1,72 @@
+//! Fixtures shared by the service tests that run against an in-memory SQLite
+//! database.
+
+// Each test binary uses a different subset of the helpers
+#![allow(dead_code)]
+
+use db::models::{
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+    workspace::{CreateWorkspace, Workspace},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// A migrated in-memory database.
+pub async fn setup_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory database");
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+    pool
+}
+
+pub async fn create_project(pool: &SqlitePool, name: &str) -> Project {
+    Project::create(
+        pool,
+        &CreateProject {
+            name: name.to_string(),
+            repositories: vec![],
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap()
+}
+
+pub async fn create_workspace(pool: &SqlitePool, task_id: Uuid, branch: &str) -> Workspace {
+    Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch: branch.to_string(),
+            agent_working_dir: None,
+        },
+        Uuid::new_v4(),
+        task_id,
+    )
+    .await
+    .unwrap()
+}
+
+/// A project named `name` with one task, and a workspace for that task on
+/// branch `vk/<name>`.
+pub async fn create_task_with_workspace(
+    pool: &SqlitePool,
+    name: &str,
+) -> (Project, Task, Workspace) {
+    let project = create_project(pool, name).await;
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(project.id, "task".to_string(), None),
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let workspace = create_workspace(pool, task.id, &format!("vk/{name}")).await;
+    (project, task, workspace)
+}
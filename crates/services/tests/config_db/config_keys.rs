@@ -0,0 +1,53 @@
+//! `ConfigServicePg::list_config_keys`.
+
+use services::services::{
+    config::Config, config_db::ConfigDbError, oauth_credentials::Credentials,
+};
+use uuid::Uuid;
+
+use super::setup;
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn list_config_keys_reports_keys_and_credentials() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+
+    service
+        .save_config(user_id, &Config::default())
+        .await
+        .expect("save should succeed");
+
+    let summary = service.list_config_keys(user_id).await.unwrap();
+    assert!(!summary.has_oauth_credentials);
+    assert!(
+        summary
+            .config_keys
+            .iter()
+            .any(|key| key == "git_branch_prefix")
+    );
+    assert!(summary.config_keys.is_sorted());
+
+    let credentials = Credentials {
+        access_token: Some("access".to_string()),
+        refresh_token: "refresh".to_string(),
+        expires_at: None,
+    };
+    service
+        .save_credentials(user_id, "github", &credentials)
+        .await
+        .expect("save should succeed");
+
+    let summary = service.list_config_keys(user_id).await.unwrap();
+    assert!(summary.has_oauth_credentials);
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn list_config_keys_without_config_is_not_found() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+
+    let result = service.list_config_keys(user_id).await;
+    assert!(matches!(result, Err(ConfigDbError::ConfigNotFound(id)) if id == user_id));
+}
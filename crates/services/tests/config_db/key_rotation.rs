@@ -0,0 +1,56 @@
+//! `ConfigServicePg::rotate_encryption_key`.
+//!
+//! Rotation touches every row in `user_configs`, so the test rotates back to
+//! the shared test key before finishing.
+
+use services::services::oauth_credentials::Credentials;
+use uuid::Uuid;
+
+use super::{TEST_KEY, setup_with_key};
+
+const NEW_KEY: [u8; 32] = [9u8; 32];
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn rotate_encryption_key_re_encrypts_credentials() {
+    let old_service = setup_with_key(TEST_KEY).await;
+    let user_id = Uuid::new_v4();
+    let credentials = Credentials {
+        access_token: Some("access".to_string()),
+        refresh_token: "refresh".to_string(),
+        expires_at: None,
+    };
+    old_service
+        .save_credentials(user_id, "github", &credentials)
+        .await
+        .expect("save should succeed");
+
+    let rotated = old_service
+        .rotate_encryption_key(TEST_KEY, NEW_KEY)
+        .await
+        .expect("rotation should succeed");
+    assert!(rotated >= 1);
+
+    // Only the new key can read the credentials now
+    let new_service = setup_with_key(NEW_KEY).await;
+    let stored = new_service
+        .get_provider_credentials(user_id, "github")
+        .await
+        .expect("new key should decrypt")
+        .expect("credentials should be present");
+    assert_eq!(stored.refresh_token, "refresh");
+    assert!(old_service.get_credential_store(user_id).await.is_err());
+
+    // Rows already on the new key are skipped
+    let rotated_again = old_service
+        .rotate_encryption_key(TEST_KEY, NEW_KEY)
+        .await
+        .expect("second rotation should succeed");
+    assert_eq!(rotated_again, 0);
+
+    // Restore the shared test key for the other tests
+    new_service
+        .rotate_encryption_key(NEW_KEY, TEST_KEY)
+        .await
+        .expect("rotating back should succeed");
+}
@@ -0,0 +1,110 @@
+//! Importing a desktop config file into `ConfigServicePg`.
+//!
+//! The config file read is mocked by writing a config to a temporary path.
+
+use std::path::PathBuf;
+
+use services::services::{
+    config::Config,
+    config_db::{ConfigServicePg, MIGRATED_FROM_FILE_KEY},
+};
+use uuid::Uuid;
+
+use super::setup;
+
+/// Write `config` to a unique temporary file standing in for the desktop config.
+fn write_config_file(config: &Config) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("vk-config-{}.json", Uuid::new_v4()));
+    std::fs::write(&path, serde_json::to_string_pretty(config).unwrap())
+        .expect("Failed to write config file");
+    path
+}
+
+async fn migration_marker(service: &ConfigServicePg, user_id: Uuid) -> Option<bool> {
+    let (config_json,): (serde_json::Value,) =
+        sqlx::query_as("SELECT config_json FROM user_configs WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(service.pool())
+            .await
+            .expect("config row should exist");
+    config_json
+        .get(MIGRATED_FROM_FILE_KEY)
+        .and_then(|v| v.as_bool())
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn migrate_from_file_seeds_config() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+
+    let mut file_config = Config::default();
+    file_config.git_branch_prefix = "migrated".to_string();
+    let path = write_config_file(&file_config);
+
+    let migrated = service
+        .migrate_from_file(user_id, &path)
+        .await
+        .expect("migration should succeed");
+    assert!(migrated);
+
+    let loaded = service.load_config(user_id).await.unwrap();
+    assert_eq!(loaded.git_branch_prefix, "migrated");
+    assert_eq!(migration_marker(&service, user_id).await, Some(true));
+
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn migrate_from_file_does_not_overwrite_existing_config() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+
+    let mut db_config = Config::default();
+    db_config.git_branch_prefix = "from-db".to_string();
+    service.save_config(user_id, &db_config).await.unwrap();
+
+    let mut file_config = Config::default();
+    file_config.git_branch_prefix = "from-file".to_string();
+    let path = write_config_file(&file_config);
+
+    let migrated = service.migrate_from_file(user_id, &path).await.unwrap();
+    assert!(!migrated);
+
+    let loaded = service.load_config(user_id).await.unwrap();
+    assert_eq!(loaded.git_branch_prefix, "from-db");
+
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn migrate_from_file_is_idempotent() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+    let path = write_config_file(&Config::default());
+
+    assert!(service.migrate_from_file(user_id, &path).await.unwrap());
+    assert!(!service.migrate_from_file(user_id, &path).await.unwrap());
+
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn migrate_from_missing_file_is_skipped() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+    let path = std::env::temp_dir().join(format!("vk-missing-{}.json", Uuid::new_v4()));
+
+    assert!(!service.migrate_from_file(user_id, &path).await.unwrap());
+
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT EXISTS(SELECT 1 FROM user_configs WHERE user_id = $1)")
+            .bind(user_id)
+            .fetch_one(service.pool())
+            .await
+            .unwrap();
+    assert!(!exists);
+}
@@ -0,0 +1,113 @@
+//! Refresh token storage in `ConfigServicePg`.
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use super::setup;
+
+fn unique_jti() -> String {
+    format!("test-jti-{}", Uuid::new_v4())
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn store_and_lookup_refresh_token() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+    let jti = unique_jti();
+    let expires_at = Utc::now() + Duration::days(1);
+
+    service
+        .store_refresh_token(user_id, &jti, expires_at)
+        .await
+        .expect("store should succeed");
+
+    let stored = service
+        .get_refresh_token_expiry(user_id, &jti)
+        .await
+        .expect("lookup should succeed")
+        .expect("token should be present");
+    assert_eq!(stored.timestamp(), expires_at.timestamp());
+
+    // Tokens are scoped to the user they were issued to
+    let other = service
+        .get_refresh_token_expiry(Uuid::new_v4(), &jti)
+        .await
+        .expect("lookup should succeed");
+    assert!(other.is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn revoke_refresh_token_is_single_use() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+    let jti = unique_jti();
+
+    service
+        .store_refresh_token(user_id, &jti, Utc::now() + Duration::days(1))
+        .await
+        .expect("store should succeed");
+
+    assert!(service.revoke_refresh_token(&jti).await.unwrap());
+    assert!(!service.revoke_refresh_token(&jti).await.unwrap());
+    assert!(
+        service
+            .get_refresh_token_expiry(user_id, &jti)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn store_refresh_token_prunes_expired_entries() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+    let expired = unique_jti();
+    let active = unique_jti();
+
+    service
+        .store_refresh_token(user_id, &expired, Utc::now() - Duration::hours(1))
+        .await
+        .unwrap();
+    service
+        .store_refresh_token(user_id, &active, Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+
+    assert!(
+        service
+            .get_refresh_token_expiry(user_id, &expired)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        service
+            .get_refresh_token_expiry(user_id, &active)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires running PostgreSQL instance"]
+async fn refresh_tokens_do_not_clobber_config() {
+    let service = setup().await;
+    let user_id = Uuid::new_v4();
+
+    let mut config = service.load_config(user_id).await.unwrap();
+    config.analytics_enabled = false;
+    service.save_config(user_id, &config).await.unwrap();
+
+    service
+        .store_refresh_token(user_id, &unique_jti(), Utc::now() + Duration::days(1))
+        .await
+        .unwrap();
+
+    let reloaded = service.load_config(user_id).await.unwrap();
+    assert!(!reloaded.analytics_enabled);
+}
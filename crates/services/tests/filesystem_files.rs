@@ -0,0 +1,131 @@
+//! Tests for `FilesystemService::read_file`, `write_file` and `get_file_metadata`
+//! in desktop mode.
+
+use std::{fs, path::Path};
+
+use services::services::filesystem::{FilesystemError, FilesystemService};
+use tempfile::TempDir;
+
+/// Create a git repository at `base/path` (just creates the .git directory)
+fn create_git_repo(base: &Path, path: &str) {
+    fs::create_dir_all(base.join(path).join(".git")).unwrap();
+}
+
+#[tokio::test]
+async fn test_read_file_returns_contents() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, b"hello").unwrap();
+
+    let service = FilesystemService::new();
+    let contents = service.read_file(None, &file_path).await.unwrap();
+    assert_eq!(contents, b"hello");
+}
+
+#[tokio::test]
+async fn test_read_file_missing_or_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let service = FilesystemService::new();
+
+    let result = service
+        .read_file(None, &temp_dir.path().join("missing.txt"))
+        .await;
+    assert!(matches!(result, Err(FilesystemError::FileDoesNotExist)));
+
+    let result = service.read_file(None, temp_dir.path()).await;
+    assert!(matches!(result, Err(FilesystemError::PathIsNotFile)));
+}
+
+#[tokio::test]
+async fn test_write_file_inside_git_repo() {
+    let temp_dir = TempDir::new().unwrap();
+    create_git_repo(temp_dir.path(), "repo");
+    fs::create_dir_all(temp_dir.path().join("repo/src")).unwrap();
+    let file_path = temp_dir.path().join("repo/src/main.rs");
+
+    let service = FilesystemService::new();
+    service
+        .write_file(None, &file_path, b"fn main() {}")
+        .await
+        .unwrap();
+
+    assert_eq!(fs::read(&file_path).unwrap(), b"fn main() {}");
+}
+
+#[tokio::test]
+async fn test_write_file_outside_git_repo_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("loose.txt");
+
+    let service = FilesystemService::new();
+    let result = service.write_file(None, &file_path, b"data").await;
+
+    assert!(matches!(result, Err(FilesystemError::NotInGitRepo(_))));
+    assert!(!file_path.exists());
+}
+
+#[tokio::test]
+async fn test_write_file_inside_dot_git_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    create_git_repo(temp_dir.path(), "repo");
+    let file_path = temp_dir.path().join("repo/.git/config");
+
+    let service = FilesystemService::new();
+    let result = service.write_file(None, &file_path, b"[core]").await;
+
+    assert!(matches!(result, Err(FilesystemError::NotInGitRepo(_))));
+}
+
+#[tokio::test]
+async fn test_write_file_missing_parent_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    create_git_repo(temp_dir.path(), "repo");
+    let file_path = temp_dir.path().join("repo/missing/file.txt");
+
+    let service = FilesystemService::new();
+    let result = service.write_file(None, &file_path, b"data").await;
+
+    assert!(matches!(result, Err(FilesystemError::DirectoryDoesNotExist)));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_write_file_through_symlink_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    create_git_repo(temp_dir.path(), "repo");
+    let outside = temp_dir.path().join("outside.txt");
+    fs::write(&outside, b"original").unwrap();
+    let link_path = temp_dir.path().join("repo/link.txt");
+    std::os::unix::fs::symlink(&outside, &link_path).unwrap();
+
+    let service = FilesystemService::new();
+    let result = service.write_file(None, &link_path, b"overwritten").await;
+
+    assert!(matches!(result, Err(FilesystemError::PathIsNotFile)));
+    assert_eq!(fs::read(&outside).unwrap(), b"original");
+}
+
+#[tokio::test]
+async fn test_get_file_metadata() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.md");
+    fs::write(&file_path, "# Notes").unwrap();
+
+    let service = FilesystemService::new();
+    let metadata = service.get_file_metadata(None, &file_path).await.unwrap();
+
+    assert_eq!(metadata.size_bytes, 7);
+    assert_eq!(metadata.mime_type, "text/markdown");
+    assert!(metadata.is_readable);
+    assert!(metadata.is_writable);
+}
+
+#[tokio::test]
+async fn test_get_file_metadata_rejects_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let service = FilesystemService::new();
+    let result = service.get_file_metadata(None, temp_dir.path()).await;
+
+    assert!(matches!(result, Err(FilesystemError::PathIsNotFile)));
+}
@@ -463,6 +463,33 @@ fn worktree_diff_permission_only_change() {
     assert_eq!(d.old_content, d.new_content);
 }
 
+#[test]
+fn repair_worktree_reconnects_manually_moved_worktree() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let old_path = td.path().join("old-base").join("wt");
+    let new_path = td.path().join("new-base").join("wt");
+    let s = GitService::new();
+
+    create_branch(&repo_path, "feature");
+    fs::create_dir_all(old_path.parent().unwrap()).unwrap();
+    s.add_worktree(&repo_path, &old_path, "feature", false)
+        .unwrap();
+
+    fs::create_dir_all(new_path.parent().unwrap()).unwrap();
+    fs::rename(&old_path, &new_path).unwrap();
+    s.repair_worktree(&repo_path, &new_path).unwrap();
+
+    let git = GitCli::new();
+    let list = git
+        .git(&repo_path, ["worktree", "list", "--porcelain"])
+        .unwrap();
+    let new_canonical = dunce::canonicalize(&new_path).unwrap();
+    assert!(list.contains(&*new_canonical.to_string_lossy()));
+    assert!(!list.contains("old-base"));
+    assert_eq!(s.get_current_branch(&new_path).unwrap(), "feature");
+}
+
 #[test]
 fn squash_merge_libgit2_sets_author_without_user() {
     // Verify merge_changes (libgit2 path) uses fallback author when no config exists
@@ -520,3 +547,354 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+#[test]
+fn list_worktrees_reports_branch_dirty_and_missing() {
+    use db::models::repo::Repo;
+    use services::services::workspace_manager::WorkspaceManager;
+    use uuid::Uuid;
+
+    let td = TempDir::new().unwrap();
+    // init_repo_main places the repo at <td>/repo, so td acts as the workspace dir
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "scratch.txt", "untracked\n");
+
+    let make_repo = |name: &str| Repo {
+        id: Uuid::new_v4(),
+        path: td.path().join(name),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        setup_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        parallel_setup_script: false,
+        dev_server_script: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+    let present = make_repo("repo");
+    let missing = make_repo("missing");
+
+    let statuses = WorkspaceManager::list_worktrees(td.path(), &[present.clone(), missing.clone()]);
+    assert_eq!(statuses.len(), 2);
+
+    assert_eq!(statuses[0].repo_id, present.id);
+    assert!(statuses[0].exists);
+    assert_eq!(statuses[0].branch.as_deref(), Some("main"));
+    assert!(statuses[0].is_dirty);
+
+    assert_eq!(statuses[1].repo_id, missing.id);
+    assert!(!statuses[1].exists);
+    assert_eq!(statuses[1].branch, None);
+    assert!(!statuses[1].is_dirty);
+}
+
+#[test]
+fn aggregate_diff_reports_uncommitted_changes() {
+    use db::models::repo::Repo;
+    use services::services::workspace_manager::WorkspaceManager;
+    use uuid::Uuid;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "notes.txt", "hello\nworld\n");
+    add_path(&repo_path, "notes.txt");
+
+    let make_repo = |name: &str| Repo {
+        id: Uuid::new_v4(),
+        path: td.path().join(name),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        setup_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        parallel_setup_script: false,
+        dev_server_script: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+    let repos = [make_repo("repo"), make_repo("missing")];
+
+    // Missing worktrees are skipped
+    let summary = WorkspaceManager::aggregate_diff(td.path(), &repos, false).unwrap();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].repo_id, repos[0].id);
+    assert_eq!(summary[0].files_changed.len(), 1);
+    assert_eq!(summary[0].files_changed[0].path, "notes.txt");
+    assert_eq!(summary[0].files_changed[0].additions, Some(2));
+    assert_eq!(summary[0].files_changed[0].deletions, Some(0));
+    assert!(summary[0].diff_stat.contains("notes.txt"));
+    assert_eq!(summary[0].diff, None);
+
+    let full = WorkspaceManager::aggregate_diff(td.path(), &repos, true).unwrap();
+    let diff = full[0].diff.as_deref().unwrap();
+    assert!(diff.contains("+hello"));
+    assert!(!full[0].truncated);
+}
+
+#[test]
+fn commit_workspace_commits_changes_with_author() {
+    use db::models::repo::Repo;
+    use services::services::workspace_manager::WorkspaceManager;
+    use uuid::Uuid;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "a.txt", "a\n");
+    write_file(&repo_path, "b.txt", "b\n");
+
+    let make_repo = |name: &str| Repo {
+        id: Uuid::new_v4(),
+        path: td.path().join(name),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        setup_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        parallel_setup_script: false,
+        dev_server_script: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+    let repos = [make_repo("repo"), make_repo("missing")];
+
+    let results = WorkspaceManager::commit_workspace(
+        td.path(),
+        &repos,
+        "agent changes",
+        Some("Ada"),
+        Some("ada@example.com"),
+    )
+    .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].repo_name, "repo");
+    assert_eq!(results[0].files_committed, 2);
+    assert_eq!(
+        get_commit_author(&repo_path, &results[0].sha),
+        (Some("Ada".to_string()), Some("ada@example.com".to_string()))
+    );
+    assert!(!GitCli::new().has_changes(&repo_path).unwrap());
+
+    // A clean worktree has nothing to commit
+    let again = WorkspaceManager::commit_workspace(td.path(), &repos, "noop", None, None).unwrap();
+    assert!(again.is_empty());
+}
+
+#[test]
+fn reset_workspace_discards_changes() {
+    use db::models::repo::Repo;
+    use services::services::workspace_manager::WorkspaceManager;
+    use uuid::Uuid;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "a.txt", "a\n");
+    let git_cli = GitCli::new();
+    git_cli.git(&repo_path, ["add", "a.txt"]).unwrap();
+    git_cli.git(&repo_path, ["commit", "-m", "add a"]).unwrap();
+
+    write_file(&repo_path, "a.txt", "changed\n");
+    write_file(&repo_path, "new/b.txt", "b\n");
+
+    let repos = [Repo {
+        id: Uuid::new_v4(),
+        path: repo_path.clone(),
+        name: "repo".to_string(),
+        display_name: "repo".to_string(),
+        setup_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        parallel_setup_script: false,
+        dev_server_script: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    }];
+
+    let mut dirty = WorkspaceManager::list_dirty_files(td.path(), &repos).unwrap();
+    dirty.sort();
+    assert_eq!(dirty, vec!["repo/a.txt", "repo/new/"]);
+
+    WorkspaceManager::reset_workspace(td.path(), &repos).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        "a\n"
+    );
+    assert!(!repo_path.join("new").exists());
+    assert!(
+        WorkspaceManager::list_dirty_files(td.path(), &repos)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn push_workspace_pushes_branch_and_reports_failures() {
+    use db::models::repo::Repo;
+    use services::services::workspace_manager::WorkspaceManager;
+    use uuid::Uuid;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let remote_path = td.path().join("remote.git");
+    Repository::init_bare(&remote_path).unwrap();
+    let remote_url = remote_path.to_string_lossy().to_string();
+    GitCli::new()
+        .git(&repo_path, ["remote", "add", "origin", &remote_url])
+        .unwrap();
+    // A second repo without any remote
+    GitService::new()
+        .initialize_repo_with_main_branch(&td.path().join("other"))
+        .unwrap();
+
+    let make_repo = |name: &str| Repo {
+        id: Uuid::new_v4(),
+        path: td.path().join(name),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        setup_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        parallel_setup_script: false,
+        dev_server_script: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+    let repos = [make_repo("repo"), make_repo("other")];
+
+    let results = WorkspaceManager::push_workspace(td.path(), &repos, None, false, None).unwrap();
+    assert_eq!(results.len(), 2);
+
+    let pushed = &results[0];
+    assert_eq!(pushed.error, None);
+    assert_eq!(pushed.branch.as_deref(), Some("main"));
+    assert_eq!(pushed.remote_url.as_deref(), Some(remote_url.as_str()));
+    let remote_head = Repository::open_bare(&remote_path)
+        .unwrap()
+        .refname_to_id("refs/heads/main")
+        .unwrap();
+    assert_eq!(
+        pushed.sha.as_deref(),
+        Some(remote_head.to_string().as_str())
+    );
+
+    let failed = &results[1];
+    assert_eq!(failed.repo_name, "other");
+    assert_eq!(failed.remote_url, None);
+    assert!(failed.error.is_some());
+}
+
+#[tokio::test]
+async fn file_search_cache_invalidate_for_repo_drops_index() {
+    use std::time::Duration;
+
+    use db::models::repo::Repo;
+    use services::services::file_search::{CacheError, FileSearchCache, SearchMode};
+    use uuid::Uuid;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    write_file(&repo_path, "needle.txt", "found\n");
+
+    let repo = Repo {
+        id: Uuid::new_v4(),
+        path: repo_path.clone(),
+        name: "repo".to_string(),
+        display_name: "repo".to_string(),
+        setup_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        parallel_setup_script: false,
+        dev_server_script: None,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    };
+
+    let cache = FileSearchCache::new();
+    // First search misses, falls back to the filesystem and queues an index build
+    let results = cache
+        .search_repo(&repo, "needle", SearchMode::TaskForm)
+        .await
+        .unwrap();
+    assert!(results.iter().any(|r| r.path.ends_with("needle.txt")));
+
+    let mut cached = false;
+    for _ in 0..50 {
+        if cache
+            .search(&repo_path, "needle", SearchMode::TaskForm)
+            .await
+            .is_ok()
+        {
+            cached = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(cached, "index should be built in the background");
+    // Let rebuilds queued by the polling misses above drain first
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    cache.invalidate_for_repo(repo.id).await;
+    assert!(matches!(
+        cache
+            .search(&repo_path, "needle", SearchMode::TaskForm)
+            .await,
+        Err(CacheError::Miss)
+    ));
+
+    // Unknown repos are ignored
+    cache.invalidate_for_repo(Uuid::new_v4()).await;
+}
+
+#[test]
+fn workspace_disk_usage_counts_ignored_and_hidden_files() {
+    use services::services::workspace_manager::WorkspaceManager;
+
+    let td = TempDir::new().unwrap();
+    let root = td.path();
+    write_file(root, ".gitignore", "target/\n");
+    write_file(root, "src/main.rs", "fn main() {}\n");
+    write_file(root, "target/debug/app", "0123456789");
+    write_file(root, ".env", "KEY=1\n");
+
+    let expected: u64 = ["target/\n", "fn main() {}\n", "0123456789", "KEY=1\n"]
+        .iter()
+        .map(|s| s.len() as u64)
+        .sum();
+    assert_eq!(WorkspaceManager::disk_usage(root), expected);
+    assert_eq!(WorkspaceManager::disk_usage(&root.join("missing")), 0);
+}
+
+#[test]
+fn repo_service_list_branches_puts_current_first() {
+    use services::services::repo::RepoService;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "feature.txt", "feature\n");
+    let s = GitService::new();
+    s.commit(&repo_path, "add feature").unwrap();
+
+    let branches = RepoService::new().list_branches(&repo_path).unwrap();
+    let names: Vec<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec!["feature", "main"]);
+    assert!(branches[0].is_current);
+    assert!(!branches[0].is_remote);
+    assert_eq!(
+        branches[0].last_commit_message.as_deref(),
+        Some("add feature")
+    );
+
+    let head = Repository::open(&repo_path)
+        .unwrap()
+        .head()
+        .unwrap()
+        .target()
+        .unwrap();
+    assert_eq!(branches[0].last_commit_sha, head.to_string());
+
+    let not_repo = td.path().join("plain");
+    fs::create_dir_all(&not_repo).unwrap();
+    assert!(RepoService::new().list_branches(&not_repo).is_err());
+}
@@ -0,0 +1,168 @@
+//! Tests for `ProjectService::duplicate_project` against an in-memory SQLite
+//! database.
+
+mod common;
+
+use common::{create_project, create_workspace, setup_pool};
+use db::models::{
+    project::Project,
+    project_repo::ProjectRepo,
+    repo::Repo,
+    task::{CreateTask, Task, TaskStatus},
+    workspace::Workspace,
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use services::services::project::ProjectService;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Project with one repo, a parent task with a workspace, and a subtask of
+/// that workspace.
+async fn seed_project(pool: &SqlitePool) -> (Project, Repo, Workspace) {
+    let project = create_project(pool, "source").await;
+    let repo = Repo::find_or_create(pool, std::path::Path::new("/repos/app"), "app")
+        .await
+        .unwrap();
+    ProjectRepo::create(pool, project.id, repo.id)
+        .await
+        .unwrap();
+
+    let parent = Task::create(
+        pool,
+        &CreateTask {
+            project_id: project.id,
+            title: "parent".to_string(),
+            description: Some("details".to_string()),
+            status: Some(TaskStatus::InProgress),
+            parent_workspace_id: None,
+            image_ids: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    let workspace = create_workspace(pool, parent.id, "vk/parent").await;
+    WorkspaceRepo::create_many(
+        pool,
+        workspace.id,
+        &[CreateWorkspaceRepo {
+            repo_id: repo.id,
+            target_branch: "main".to_string(),
+        }],
+    )
+    .await
+    .unwrap();
+
+    Task::create(
+        pool,
+        &CreateTask {
+            project_id: project.id,
+            title: "child".to_string(),
+            description: None,
+            status: None,
+            parent_workspace_id: Some(workspace.id),
+            image_ids: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+
+    (project, repo, workspace)
+}
+
+async fn tasks_by_title(pool: &SqlitePool, project_id: Uuid) -> Vec<Task> {
+    let mut tasks: Vec<Task> = Task::find_by_project_id_with_attempt_status(pool, project_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.task)
+        .collect();
+    tasks.sort_by(|a, b| a.title.cmp(&b.title));
+    tasks
+}
+
+#[tokio::test]
+async fn duplicate_project_copies_repos_and_tasks() {
+    let pool = setup_pool().await;
+    let (source, repo, _) = seed_project(&pool).await;
+    let user_id = Uuid::new_v4();
+
+    let copy = ProjectService::new()
+        .duplicate_project(&pool, source.id, "copy", Some(user_id), false)
+        .await
+        .unwrap();
+
+    assert_ne!(copy.id, source.id);
+    assert_eq!(copy.name, "copy");
+    assert_eq!(
+        Project::find_owner(&pool, copy.id).await.unwrap(),
+        Some(user_id)
+    );
+
+    let links = ProjectRepo::find_by_project_id(&pool, copy.id)
+        .await
+        .unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].repo_id, repo.id);
+
+    let tasks = tasks_by_title(&pool, copy.id).await;
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[1].title, "parent");
+    assert_eq!(tasks[1].description.as_deref(), Some("details"));
+    assert_eq!(tasks[1].status, TaskStatus::InProgress);
+    // Without workspaces there is nothing for the subtask to point at
+    assert_eq!(tasks[0].parent_workspace_id, None);
+    assert!(
+        Workspace::fetch_all(&pool, Some(tasks[1].id))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The source project is untouched
+    assert_eq!(tasks_by_title(&pool, source.id).await.len(), 2);
+}
+
+#[tokio::test]
+async fn duplicate_project_deep_copies_workspaces() {
+    let pool = setup_pool().await;
+    let (source, repo, workspace) = seed_project(&pool).await;
+
+    let copy = ProjectService::new()
+        .duplicate_project(&pool, source.id, "deep copy", None, true)
+        .await
+        .unwrap();
+
+    let tasks = tasks_by_title(&pool, copy.id).await;
+    let workspaces = Workspace::fetch_all(&pool, Some(tasks[1].id))
+        .await
+        .unwrap();
+    assert_eq!(workspaces.len(), 1);
+    let copied = &workspaces[0];
+    assert_ne!(copied.id, workspace.id);
+    assert_eq!(copied.branch, "vk/parent");
+    assert_eq!(copied.container_ref, None);
+
+    let repos = WorkspaceRepo::find_by_workspace_id(&pool, copied.id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 1);
+    assert_eq!(repos[0].repo_id, repo.id);
+    assert_eq!(repos[0].target_branch, "main");
+
+    // The subtask follows its parent workspace into the copy
+    assert_eq!(tasks[0].parent_workspace_id, Some(copied.id));
+}
+
+#[tokio::test]
+async fn duplicate_project_missing_source_fails() {
+    let pool = setup_pool().await;
+
+    let result = ProjectService::new()
+        .duplicate_project(&pool, Uuid::new_v4(), "copy", None, false)
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(Project::count(&pool).await.unwrap(), 0);
+}
@@ -12,7 +12,7 @@
 //! - URL: URL-encoded path tests
 //! - BND: Boundary validation tests
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use tempfile::TempDir;
 use uuid::Uuid;
@@ -74,7 +74,11 @@ fn validate_user_path_test(
         // For non-existent paths, resolve parent components to detect traversal
         let mut resolved = PathBuf::new();
         for component in path.components() {
-            resolved.push(component);
+            if component == Component::ParentDir {
+                resolved.pop();
+            } else {
+                resolved.push(component);
+            }
             // Try to canonicalize what exists so far
             if resolved.exists() {
                 resolved = dunce::canonicalize(&resolved)
@@ -287,6 +291,35 @@ mod path_traversal_tests {
             "Absolute path outside workspace should be blocked"
         );
     }
+
+    /// PTR-06: `..` after a non-existent component should still be resolved
+    #[test]
+    fn test_ptr_06_dotdot_after_missing_dir_blocked() {
+        let setup = TestSetup::new();
+        let (other_user_id, _) = setup.create_other_user();
+
+        // "missing" does not exist, so the path cannot be canonicalized as a whole
+        let malicious_path = setup
+            .user_workspace
+            .join("missing")
+            .join("..")
+            .join("..")
+            .join(other_user_id.to_string())
+            .join("secret.txt");
+
+        let result = validate_user_path_test(
+            &setup.user_id,
+            &malicious_path,
+            MockDeploymentMode::Kubernetes,
+            &setup.workspace_base,
+        );
+
+        assert!(
+            matches!(result, Err(TestWorkspaceError::Unauthorized(_))),
+            "Traversal through a non-existent directory should be blocked, got: {:?}",
+            result
+        );
+    }
 }
 
 // ========== URL-01: URL-Encoded Path Attack Tests ==========
@@ -0,0 +1,73 @@
+//! Tests that soft-deleting a task leaves its workspaces on disk until it is
+//! purged, against an in-memory SQLite database.
+
+mod common;
+
+use common::{create_task_with_workspace, setup_pool};
+use db::models::{task::Task, workspace::Workspace};
+use services::services::workspace_manager::WorkspaceManager;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn delete_then_restore_keeps_workspace_directory() {
+    let pool = setup_pool().await;
+    let base_dir = TempDir::new().unwrap();
+    let (_, task, workspace) = create_task_with_workspace(&pool, "soft-delete").await;
+
+    let workspace_dir = base_dir.path().join("soft-delete");
+    std::fs::create_dir_all(workspace_dir.join("app")).unwrap();
+    Workspace::update_container_ref(&pool, workspace.id, &workspace_dir.to_string_lossy())
+        .await
+        .unwrap();
+
+    assert_eq!(Task::delete(&pool, task.id).await.unwrap(), 1);
+
+    // The workspace record outlives the soft delete, so the stale worktree
+    // sweep must not treat its directory as orphaned
+    let pruned = WorkspaceManager::prune_stale_worktrees(&pool, base_dir.path())
+        .await
+        .unwrap();
+    assert_eq!(pruned, 0);
+
+    let restored = Task::restore(&pool, task.id, None).await.unwrap().unwrap();
+    assert!(restored.deleted_at.is_none());
+    assert!(workspace_dir.join("app").exists());
+    assert_eq!(
+        Workspace::fetch_all(&pool, Some(task.id))
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn purge_removes_only_soft_deleted_tasks() {
+    let pool = setup_pool().await;
+    let (_, task, workspace) = create_task_with_workspace(&pool, "soft-delete").await;
+
+    assert_eq!(Task::purge(&pool, task.id).await.unwrap(), 0);
+    assert!(Task::find_by_id(&pool, task.id).await.unwrap().is_some());
+
+    Task::delete(&pool, task.id).await.unwrap();
+    assert!(
+        Task::find_deleted_by_id(&pool, task.id, None)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert_eq!(Task::purge(&pool, task.id).await.unwrap(), 1);
+
+    assert!(
+        Task::find_deleted_by_id(&pool, task.id, None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        Workspace::find_by_id(&pool, workspace.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,49 @@
+//! Tests for `WorkspaceManager::prune_stale_worktrees` against an in-memory
+//! SQLite database.
+
+mod common;
+
+use common::{create_task_with_workspace, setup_pool};
+use db::models::workspace::Workspace;
+use services::services::workspace_manager::WorkspaceManager;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn prune_stale_worktrees_removes_only_unknown_directories() {
+    let pool = setup_pool().await;
+    let base_dir = TempDir::new().unwrap();
+
+    let kept_dir = base_dir.path().join("kept");
+    std::fs::create_dir_all(kept_dir.join("app")).unwrap();
+    let (_, _, workspace) = create_task_with_workspace(&pool, "prune").await;
+    Workspace::update_container_ref(&pool, workspace.id, &kept_dir.to_string_lossy())
+        .await
+        .unwrap();
+
+    let stale_dir = base_dir.path().join("stale");
+    std::fs::create_dir_all(stale_dir.join("app")).unwrap();
+    std::fs::write(stale_dir.join("app").join("file.txt"), "left over").unwrap();
+    let stray_file = base_dir.path().join("notes.txt");
+    std::fs::write(&stray_file, "not a workspace").unwrap();
+
+    let pruned = WorkspaceManager::prune_stale_worktrees(&pool, base_dir.path())
+        .await
+        .unwrap();
+
+    assert_eq!(pruned, 1);
+    assert!(!stale_dir.exists());
+    assert!(kept_dir.exists());
+    assert!(stray_file.exists());
+}
+
+#[tokio::test]
+async fn prune_stale_worktrees_ignores_missing_base_dir() {
+    let pool = setup_pool().await;
+    let base_dir = TempDir::new().unwrap();
+
+    let pruned = WorkspaceManager::prune_stale_worktrees(&pool, &base_dir.path().join("missing"))
+        .await
+        .unwrap();
+
+    assert_eq!(pruned, 0);
+}
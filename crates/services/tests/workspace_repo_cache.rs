@@ -0,0 +1,78 @@
+//! Tests for `WorkspaceRepoCache` against an in-memory SQLite database.
+
+mod common;
+
+use std::{path::Path, time::Duration};
+
+use common::{create_task_with_workspace, setup_pool};
+use db::models::{
+    repo::Repo,
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use services::services::workspace_repo_cache::WorkspaceRepoCache;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+async fn attach_repo(pool: &SqlitePool, workspace_id: Uuid, name: &str) {
+    let repo = Repo::find_or_create(pool, Path::new(&format!("/repos/{name}")), name)
+        .await
+        .unwrap();
+    WorkspaceRepo::create_many(
+        pool,
+        workspace_id,
+        &[CreateWorkspaceRepo {
+            repo_id: repo.id,
+            target_branch: "main".to_string(),
+        }],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn serves_cached_repos_until_invalidated() {
+    let pool = setup_pool().await;
+    let (_, _, workspace) = create_task_with_workspace(&pool, "cache").await;
+    attach_repo(&pool, workspace.id, "app").await;
+
+    let cache = WorkspaceRepoCache::with_ttl(Duration::from_secs(60));
+    let repos = cache
+        .find_repos_for_workspace(&pool, workspace.id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 1);
+
+    attach_repo(&pool, workspace.id, "lib").await;
+    let repos = cache
+        .find_repos_for_workspace(&pool, workspace.id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 1);
+
+    cache.invalidate(workspace.id);
+    let repos = cache
+        .find_repos_for_workspace(&pool, workspace.id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 2);
+}
+
+#[tokio::test]
+async fn reloads_expired_entries() {
+    let pool = setup_pool().await;
+    let (_, _, workspace) = create_task_with_workspace(&pool, "cache").await;
+    attach_repo(&pool, workspace.id, "app").await;
+
+    let cache = WorkspaceRepoCache::with_ttl(Duration::ZERO);
+    cache
+        .find_repos_for_workspace(&pool, workspace.id)
+        .await
+        .unwrap();
+
+    attach_repo(&pool, workspace.id, "lib").await;
+    let repos = cache
+        .find_repos_for_workspace(&pool, workspace.id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 2);
+}
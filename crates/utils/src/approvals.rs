@@ -3,7 +3,17 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
-pub const APPROVAL_TIMEOUT_SECONDS: i64 = 3600; // 1 hour
+/// Default approval timeout when `APPROVAL_TIMEOUT_SECS` is not set.
+pub const APPROVAL_TIMEOUT_SECONDS: i64 = 300; // 5 minutes
+
+/// Approval timeout in seconds, configurable via `APPROVAL_TIMEOUT_SECS`.
+pub fn approval_timeout_secs() -> i64 {
+    std::env::var("APPROVAL_TIMEOUT_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|secs: &i64| *secs > 0)
+        .unwrap_or(APPROVAL_TIMEOUT_SECONDS)
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ApprovalRequest {
@@ -26,7 +36,7 @@ impl ApprovalRequest {
             tool_call_id: request.tool_call_id,
             execution_process_id,
             created_at: now,
-            timeout_at: now + Duration::seconds(APPROVAL_TIMEOUT_SECONDS),
+            timeout_at: now + Duration::seconds(approval_timeout_secs()),
         }
     }
 }
@@ -9,8 +9,10 @@ pub mod browser;
 pub mod diff;
 pub mod git;
 pub mod jwt;
+pub mod log_format;
 pub mod log_msg;
 pub mod msg_store;
+pub mod otel;
 pub mod path;
 pub mod port_file;
 pub mod response;
@@ -0,0 +1,306 @@
+//! Log output format, chosen with `LOG_FORMAT`.
+//!
+//! `text` (the default) is tracing's human-readable format. `json` writes one
+//! JSON object per line and `logfmt` writes `key=value` pairs, for log
+//! aggregators such as Loki or Datadog. Both structured formats add the
+//! [`LogContext`] fields to every line.
+
+use std::fmt;
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    Layer,
+    fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
+    registry::LookupSpan,
+};
+
+pub const LOG_FORMAT_ENV: &str = "LOG_FORMAT";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+    Logfmt,
+}
+
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "logfmt" => Some(Self::Logfmt),
+            _ => None,
+        }
+    }
+
+    /// Read `LOG_FORMAT`, falling back to text when it is unset or unknown.
+    ///
+    /// Called before the subscriber is installed, so warnings go to stderr.
+    pub fn from_env() -> Self {
+        match std::env::var(LOG_FORMAT_ENV) {
+            Ok(value) => Self::parse(&value).unwrap_or_else(|| {
+                eprintln!("Unknown {LOG_FORMAT_ENV} '{value}', expected text, json or logfmt");
+                Self::Text
+            }),
+            Err(_) => Self::Text,
+        }
+    }
+}
+
+/// Fields added to every structured log line.
+#[derive(Debug, Clone)]
+pub struct LogContext {
+    pub service: String,
+    pub version: String,
+    pub deployment_mode: String,
+    /// The pod name in Kubernetes, from `HOSTNAME`
+    pub pod_name: Option<String>,
+}
+
+impl LogContext {
+    pub fn new(service: &str, deployment_mode: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            version: crate::version::APP_VERSION.to_string(),
+            deployment_mode: deployment_mode.to_string(),
+            pod_name: std::env::var("HOSTNAME")
+                .ok()
+                .filter(|name| !name.is_empty()),
+        }
+    }
+}
+
+/// Build the formatting layer for `format`.
+pub fn fmt_layer<S>(format: LogFormat, context: LogContext) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json | LogFormat::Logfmt => tracing_subscriber::fmt::layer()
+            .event_format(StructuredFormat { format, context })
+            .boxed(),
+    }
+}
+
+struct StructuredFormat {
+    format: LogFormat,
+    context: LogContext,
+}
+
+impl StructuredFormat {
+    /// Key-value pairs of a line, in output order.
+    fn entries(&self, event: &Event<'_>) -> Vec<(String, Value)> {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let message = visitor.0.remove("message").unwrap_or(Value::Null);
+
+        let mut entries = vec![
+            (
+                "timestamp".to_string(),
+                Value::from(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)),
+            ),
+            (
+                "level".to_string(),
+                Value::from(metadata.level().to_string()),
+            ),
+            ("target".to_string(), Value::from(metadata.target())),
+            ("message".to_string(), message),
+        ];
+        entries.extend(visitor.0);
+        entries.extend([
+            (
+                "service".to_string(),
+                Value::from(self.context.service.as_str()),
+            ),
+            (
+                "version".to_string(),
+                Value::from(self.context.version.as_str()),
+            ),
+            (
+                "deployment_mode".to_string(),
+                Value::from(self.context.deployment_mode.as_str()),
+            ),
+            (
+                "pod_name".to_string(),
+                Value::from(self.context.pod_name.as_deref()),
+            ),
+        ]);
+        entries
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for StructuredFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let entries = self.entries(event);
+        match self.format {
+            LogFormat::Logfmt => writeln!(writer, "{}", logfmt_line(&entries)),
+            _ => {
+                let object: Map<String, Value> = entries.into_iter().collect();
+                writeln!(writer, "{}", Value::Object(object))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor(Map<String, Value>);
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0
+            .insert(field.name().to_string(), Value::from(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
+
+/// Render `key=value` pairs, quoting values with spaces, quotes or `=`.
+/// Null values are left out.
+fn logfmt_line(entries: &[(String, Value)]) -> String {
+    entries
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let needs_quotes = value.is_empty()
+                || value
+                    .chars()
+                    .any(|c| c.is_whitespace() || c == '"' || c == '=' || c == '\\');
+            if needs_quotes {
+                format!("{key}={value:?}")
+            } else {
+                format!("{key}={value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing_subscriber::{fmt::MakeWriter, prelude::*};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn log_line(format: LogFormat) -> String {
+        let buffer = Buffer::default();
+        let context = LogContext {
+            service: "vibe-kanban".to_string(),
+            version: "1.2.3".to_string(),
+            deployment_mode: "kubernetes".to_string(),
+            pod_name: Some("vk-0".to_string()),
+        };
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(buffer.clone())
+            .event_format(StructuredFormat { format, context });
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(workspace_id = 7, "Workspace created");
+        });
+        String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap()
+    }
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!(LogFormat::parse("JSON"), Some(LogFormat::Json));
+        assert_eq!(LogFormat::parse(" logfmt "), Some(LogFormat::Logfmt));
+        assert_eq!(LogFormat::parse("text"), Some(LogFormat::Text));
+        assert_eq!(LogFormat::parse("yaml"), None);
+    }
+
+    #[test]
+    fn json_lines_include_context_fields() {
+        let line = log_line(LogFormat::Json);
+        let value: Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(value["level"], "INFO");
+        assert_eq!(value["message"], "Workspace created");
+        assert_eq!(value["workspace_id"], 7);
+        assert_eq!(value["service"], "vibe-kanban");
+        assert_eq!(value["version"], "1.2.3");
+        assert_eq!(value["deployment_mode"], "kubernetes");
+        assert_eq!(value["pod_name"], "vk-0");
+    }
+
+    #[test]
+    fn logfmt_lines_quote_values_with_spaces() {
+        let line = log_line(LogFormat::Logfmt);
+        assert!(line.contains(" level=INFO "));
+        assert!(line.contains(" message=\"Workspace created\" "));
+        assert!(line.contains(" workspace_id=7 "));
+        assert!(
+            line.trim_end()
+                .ends_with("deployment_mode=kubernetes pod_name=vk-0")
+        );
+    }
+}
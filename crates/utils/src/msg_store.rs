@@ -1,11 +1,15 @@
 use std::{
     collections::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, LazyLock, RwLock},
 };
 
 use axum::response::sse::Event;
 use futures::{StreamExt, TryStreamExt, future};
-use tokio::{sync::broadcast, task::JoinHandle};
+use serde::Serialize;
+use tokio::{
+    sync::{Notify, broadcast},
+    task::JoinHandle,
+};
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
@@ -13,20 +17,57 @@ use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
 // 100 MB Limit
 const HISTORY_BYTES: usize = 100000 * 1024;
 
+const DEFAULT_MAX_ENTRIES: usize = 10000;
+
+/// Average message size assumed by [`MsgStore::memory_estimate_bytes`]
+const ESTIMATED_BYTES_PER_MSG: usize = 256;
+
+/// Maximum number of messages kept in history, from `MSG_STORE_MAX_ENTRIES`
+static MAX_MSG_STORE_ENTRIES: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("MSG_STORE_MAX_ENTRIES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|max| *max > 0)
+        .unwrap_or(DEFAULT_MAX_ENTRIES)
+});
+
 #[derive(Clone)]
 struct StoredMsg {
+    seq: u64,
     msg: LogMsg,
     bytes: usize,
 }
 
+/// A history message with its sequence number, for clients that poll with a
+/// cursor instead of holding a stream open.
+#[derive(Debug, Clone, Serialize)]
+pub struct SequencedMsg {
+    pub seq: u64,
+    pub msg: LogMsg,
+}
+
 struct Inner {
     history: VecDeque<StoredMsg>,
     total_bytes: usize,
+    /// Sequence number of the last pushed message; the first one is 1
+    last_seq: u64,
+}
+
+impl Inner {
+    fn trim_to(&mut self, max: usize) {
+        while self.history.len() > max {
+            if let Some(front) = self.history.pop_front() {
+                self.total_bytes = self.total_bytes.saturating_sub(front.bytes);
+            }
+        }
+    }
 }
 
 pub struct MsgStore {
     inner: RwLock<Inner>,
     sender: broadcast::Sender<LogMsg>,
+    /// Woken once a pushed message is in history
+    pushed: Notify,
 }
 
 impl Default for MsgStore {
@@ -42,8 +83,10 @@ impl MsgStore {
             inner: RwLock::new(Inner {
                 history: VecDeque::with_capacity(32),
                 total_bytes: 0,
+                last_seq: 0,
             }),
             sender,
+            pushed: Notify::new(),
         }
     }
 
@@ -59,8 +102,33 @@ impl MsgStore {
                 break;
             }
         }
-        inner.history.push_back(StoredMsg { msg, bytes });
+        inner.last_seq += 1;
+        let seq = inner.last_seq;
+        inner.history.push_back(StoredMsg { seq, msg, bytes });
         inner.total_bytes = inner.total_bytes.saturating_add(bytes);
+        inner.trim_to(*MAX_MSG_STORE_ENTRIES);
+        drop(inner);
+
+        self.pushed.notify_waiters();
+    }
+
+    /// Drop the oldest messages until at most `max` remain in history.
+    pub fn trim_to(&self, max: usize) {
+        self.inner.write().unwrap().trim_to(max);
+    }
+
+    /// Number of messages currently held in history.
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().history.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Rough memory footprint of the history, for observability only.
+    pub fn memory_estimate_bytes(&self) -> usize {
+        self.len() * ESTIMATED_BYTES_PER_MSG
     }
 
     // Convenience
@@ -97,6 +165,39 @@ impl MsgStore {
             .collect()
     }
 
+    /// Messages in history with a sequence number above `cursor`, oldest
+    /// first. Messages already trimmed from history are not returned.
+    pub fn get_history_after(&self, cursor: u64) -> Vec<SequencedMsg> {
+        self.inner
+            .read()
+            .unwrap()
+            .history
+            .iter()
+            .filter(|s| s.seq > cursor)
+            .map(|s| SequencedMsg {
+                seq: s.seq,
+                msg: s.msg.clone(),
+            })
+            .collect()
+    }
+
+    /// Like [`Self::get_history_after`], but waits for a message to be pushed
+    /// when there is none after `cursor` yet.
+    pub async fn wait_for_new_after(&self, cursor: u64) -> Vec<SequencedMsg> {
+        loop {
+            // Register for the wakeup before reading history so a push in
+            // between is not missed
+            let mut pushed = std::pin::pin!(self.pushed.notified());
+            pushed.as_mut().enable();
+
+            let msgs = self.get_history_after(cursor);
+            if !msgs.is_empty() {
+                return msgs;
+            }
+            pushed.await;
+        }
+    }
+
     /// History then live, as `LogMsg`.
     pub fn history_plus_stream(
         &self,
@@ -175,3 +276,47 @@ impl MsgStore {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_to_drops_oldest_messages() {
+        let store = MsgStore::new();
+        for i in 0..5 {
+            store.push_stdout(format!("line {i}"));
+        }
+
+        store.trim_to(2);
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.memory_estimate_bytes(), 2 * ESTIMATED_BYTES_PER_MSG);
+        let history = store.get_history();
+        assert!(matches!(&history[0], LogMsg::Stdout(s) if s == "line 3"));
+        assert!(matches!(&history[1], LogMsg::Stdout(s) if s == "line 4"));
+    }
+
+    #[tokio::test]
+    async fn wait_for_new_after_returns_messages_past_cursor() {
+        let store = Arc::new(MsgStore::new());
+        store.push_stdout("first");
+        store.push_stdout("second");
+
+        let msgs = store.wait_for_new_after(1).await;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].seq, 2);
+        assert!(matches!(&msgs[0].msg, LogMsg::Stdout(s) if s == "second"));
+
+        let waiter = tokio::spawn({
+            let store = store.clone();
+            async move { store.wait_for_new_after(2).await }
+        });
+        tokio::task::yield_now().await;
+        store.push_stdout("third");
+
+        let msgs = waiter.await.unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].seq, 3);
+    }
+}
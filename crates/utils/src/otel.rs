@@ -0,0 +1,97 @@
+//! OpenTelemetry trace export.
+//!
+//! When `OTEL_EXPORTER_OTLP_ENDPOINT` is set, [`otel_layer`] returns a tracing
+//! layer that exports spans over OTLP/HTTP, including the `db.query` spans of
+//! the PostgreSQL pool. Incoming W3C `traceparent` headers are continued with
+//! [`set_parent_from_headers`], so server spans join the caller's trace.
+
+use std::sync::OnceLock;
+
+use axum::http::HeaderMap;
+use opentelemetry::{global, propagation::Extractor, trace::TracerProvider as _};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    propagation::TraceContextPropagator,
+    trace::{SdkTracer, SdkTracerProvider},
+};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+const SERVICE_NAME: &str = "vibe-kanban";
+
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Build the OTLP export layer, or `None` when `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// is unset or the exporter cannot be created.
+///
+/// Called before the subscriber is installed, so failures go to stderr.
+pub fn otel_layer<S>() -> Option<OpenTelemetryLayer<S, SdkTracer>>
+where
+    S: tracing::Subscriber,
+    S: for<'a> LookupSpan<'a>,
+{
+    let endpoint = std::env::var(OTLP_ENDPOINT_ENV).ok()?;
+    let endpoint = endpoint.trim();
+    if endpoint.is_empty() {
+        return None;
+    }
+
+    let exporter = match SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("Failed to create OTLP span exporter for {endpoint}: {e}");
+            return None;
+        }
+    };
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+        .build();
+    let tracer = provider.tracer(SERVICE_NAME);
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+    let _ = TRACER_PROVIDER.set(provider);
+
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flush buffered spans before the process exits.
+pub fn shutdown() {
+    if let Some(provider) = TRACER_PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+    }
+}
+
+/// Make `span` a child of the trace context carried by `headers`, if any.
+/// A no-op unless [`otel_layer`] is installed.
+pub fn set_parent_from_headers(span: &tracing::Span, headers: &HeaderMap) {
+    if TRACER_PROVIDER.get().is_none() {
+        return;
+    }
+    let parent =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    span.set_parent(parent);
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
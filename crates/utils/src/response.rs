@@ -1,5 +1,59 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use uuid::Uuid;
+
+/// Stable, machine-readable identifiers for API errors.
+///
+/// Clients should switch on these rather than matching `message`, which is
+/// meant for humans and may change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    ProjectNotFound,
+    WorkspaceNotFound,
+    SessionNotFound,
+    Conflict,
+    MergeConflicts,
+    RebaseInProgress,
+    SessionClosed,
+    PayloadTooLarge,
+    UnprocessableEntity,
+    InvalidImageFormat,
+    QuotaExceeded,
+    RemoteUnavailable,
+    RemoteTimeout,
+    InternalError,
+}
+
+impl ErrorCode {
+    /// The wire representation of this code, as sent in `ApiResponse::code`.
+    pub fn to_code_string(&self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::ProjectNotFound => "PROJECT_NOT_FOUND",
+            ErrorCode::WorkspaceNotFound => "WORKSPACE_NOT_FOUND",
+            ErrorCode::SessionNotFound => "SESSION_NOT_FOUND",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::MergeConflicts => "MERGE_CONFLICTS",
+            ErrorCode::RebaseInProgress => "REBASE_IN_PROGRESS",
+            ErrorCode::SessionClosed => "SESSION_CLOSED",
+            ErrorCode::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            ErrorCode::UnprocessableEntity => "UNPROCESSABLE_ENTITY",
+            ErrorCode::InvalidImageFormat => "INVALID_IMAGE_FORMAT",
+            ErrorCode::QuotaExceeded => "QUOTA_EXCEEDED",
+            ErrorCode::RemoteUnavailable => "REMOTE_UNAVAILABLE",
+            ErrorCode::RemoteTimeout => "REMOTE_TIMEOUT",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+        }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct ApiResponse<T, E = T> {
@@ -7,6 +61,9 @@ pub struct ApiResponse<T, E = T> {
     data: Option<T>,
     error_data: Option<E>,
     message: Option<String>,
+    #[serde(default)]
+    #[ts(type = "ErrorCode | null")]
+    code: Option<String>,
 }
 
 impl<T, E> ApiResponse<T, E> {
@@ -17,6 +74,7 @@ impl<T, E> ApiResponse<T, E> {
             data: Some(data),
             message: None,
             error_data: None,
+            code: None,
         }
     }
 
@@ -27,6 +85,18 @@ impl<T, E> ApiResponse<T, E> {
             data: None,
             message: Some(message.to_string()),
             error_data: None,
+            code: None,
+        }
+    }
+
+    /// Creates an error response, with `message`, a machine-readable `code` and no data.
+    pub fn error_with_code(message: &str, code: ErrorCode) -> Self {
+        ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+            error_data: None,
+            code: Some(code.to_code_string().to_string()),
         }
     }
     /// Creates an error response, with no `data`, no `message`, but with arbitrary `error_data`.
@@ -36,6 +106,7 @@ impl<T, E> ApiResponse<T, E> {
             data: None,
             error_data: Some(data),
             message: None,
+            code: None,
         }
     }
 
@@ -53,4 +124,95 @@ impl<T, E> ApiResponse<T, E> {
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
     }
+
+    /// Returns the machine-readable error code if present.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+}
+
+/// A page of results from a cursor-paginated listing.
+///
+/// Pass `next_cursor` as the `after` query parameter to fetch the next page.
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<Uuid>,
+    pub has_more: bool,
+}
+
+impl<T> PaginatedResponse<T> {
+    /// Build a page from up to `limit + 1` rows fetched in cursor order.
+    ///
+    /// The extra row, if present, only signals that another page exists and
+    /// is dropped. `cursor` extracts the keyset column from an item.
+    pub fn from_rows(mut rows: Vec<T>, limit: usize, cursor: impl Fn(&T) -> Uuid) -> Self {
+        let has_more = rows.len() > limit;
+        rows.truncate(limit);
+        let next_cursor = if has_more { rows.last().map(cursor) } else { None };
+
+        PaginatedResponse {
+            items: rows,
+            next_cursor,
+            has_more,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_string_matches_serde() {
+        for code in [
+            ErrorCode::NotFound,
+            ErrorCode::WorkspaceNotFound,
+            ErrorCode::QuotaExceeded,
+        ] {
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::json!(code.to_code_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_with_code_serializes_code() {
+        let response = ApiResponse::<()>::error_with_code("gone", ErrorCode::NotFound);
+        let value = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(value["code"], "NOT_FOUND");
+        assert_eq!(value["message"], "gone");
+        assert_eq!(response.code(), Some("NOT_FOUND"));
+    }
+
+    #[test]
+    fn test_from_rows_with_extra_row_has_more() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let page = PaginatedResponse::from_rows(ids.clone(), 3, |id| *id);
+
+        assert_eq!(page.items, ids[..3]);
+        assert!(page.has_more);
+        assert_eq!(page.next_cursor, Some(ids[2]));
+    }
+
+    #[test]
+    fn test_from_rows_last_page() {
+        let ids: Vec<Uuid> = (0..2).map(|_| Uuid::new_v4()).collect();
+        let page = PaginatedResponse::from_rows(ids.clone(), 3, |id| *id);
+
+        assert_eq!(page.items, ids);
+        assert!(!page.has_more);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn test_from_rows_empty() {
+        let page = PaginatedResponse::<Uuid>::from_rows(vec![], 50, |id| *id);
+
+        assert!(page.items.is_empty());
+        assert!(!page.has_more);
+        assert_eq!(page.next_cursor, None);
+    }
 }
@@ -98,7 +98,9 @@ const ScriptFixerDialogImpl = NiceModal.create<ScriptFixerDialogProps>(
     // Compute status for the latest process
     const isProcessRunning = latestProcess?.status === 'running';
     const isProcessCompleted = latestProcess?.status === 'completed';
-    const isProcessKilled = latestProcess?.status === 'killed';
+    const isProcessKilled =
+      latestProcess?.status === 'killed' ||
+      latestProcess?.status === 'cancelled';
     const isProcessFailed = latestProcess?.status === 'failed';
     // exit_code can be null, number, or BigInt - convert to Number for comparison
     const exitCode = latestProcess?.exit_code;
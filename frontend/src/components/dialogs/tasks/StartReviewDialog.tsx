@@ -108,7 +108,8 @@ const StartReviewDialogImpl = NiceModal.create<StartReviewDialogProps>(
         const combinedPrompt = promptParts.join('\n\n');
 
         await sessionsApi.startReview(targetSessionId, {
-          executor_profile_id: effectiveProfile,
+          executor: effectiveProfile.executor,
+          review_type: 'full_review',
           additional_prompt: combinedPrompt || null,
           use_all_workspace_commits: includeGitContext,
         });
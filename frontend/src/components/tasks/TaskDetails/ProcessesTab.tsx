@@ -73,6 +73,7 @@ function ProcessesTab({ sessionId }: ProcessesTabProps) {
       case 'failed':
         return <AlertCircle className="h-4 w-4 text-destructive" />;
       case 'killed':
+      case 'cancelled':
         return <Square className="h-4 w-4 text-gray-500" />;
       default:
         return <Clock className="h-4 w-4 text-gray-400" />;
@@ -88,6 +89,7 @@ function ProcessesTab({ sessionId }: ProcessesTabProps) {
       case 'failed':
         return 'bg-red-50 border-red-200 text-red-800';
       case 'killed':
+      case 'cancelled':
         return 'bg-gray-50 border-gray-200 text-gray-800';
       default:
         return 'bg-gray-50 border-gray-200 text-gray-800';
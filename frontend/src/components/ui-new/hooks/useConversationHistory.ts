@@ -58,7 +58,8 @@ export const useConversationHistory = ({
       (ep) =>
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
-        ep.run_reason === 'codingagent'
+        ep.run_reason === 'codingagent' ||
+        ep.run_reason === 'review'
     );
   }, [executionProcessesRaw]);
 
@@ -222,7 +223,8 @@ export const useConversationHistory = ({
               liveProcessStatus === ExecutionProcessStatus.running;
             const processFailedOrKilled =
               liveProcessStatus === ExecutionProcessStatus.failed ||
-              liveProcessStatus === ExecutionProcessStatus.killed;
+              liveProcessStatus === ExecutionProcessStatus.killed ||
+              liveProcessStatus === ExecutionProcessStatus.cancelled;
 
             if (isProcessRunning) {
               hasRunningProcess = true;
@@ -284,7 +286,9 @@ export const useConversationHistory = ({
 
             if (
               (executionProcess?.status === ExecutionProcessStatus.failed ||
-                executionProcess?.status === ExecutionProcessStatus.killed) &&
+                executionProcess?.status === ExecutionProcessStatus.killed ||
+                executionProcess?.status ===
+                  ExecutionProcessStatus.cancelled) &&
               index === Object.keys(executionProcessState).length - 1
             ) {
               lastProcessFailedOrKilled = true;
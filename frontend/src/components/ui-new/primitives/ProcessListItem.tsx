@@ -23,6 +23,7 @@ interface ProcessListItemProps {
 
 const RUN_REASON_LABELS: Record<ExecutionProcessRunReason, string> = {
   codingagent: 'Coding Agent',
+  review: 'Review',
   setupscript: 'Setup Script',
   cleanupscript: 'Cleanup Script',
   devserver: 'Dev Server',
@@ -31,6 +32,7 @@ const RUN_REASON_LABELS: Record<ExecutionProcessRunReason, string> = {
 const RUN_REASON_ICONS: Record<ExecutionProcessRunReason, typeof TerminalIcon> =
   {
     codingagent: CodeIcon,
+    review: CodeIcon,
     setupscript: GearIcon,
     cleanupscript: GearIcon,
     devserver: GlobeIcon,
@@ -27,7 +27,12 @@ interface WorkspaceSummaryProps {
   hasRunningDevServer?: boolean;
   hasUnseenActivity?: boolean;
   latestProcessCompletedAt?: string;
-  latestProcessStatus?: 'running' | 'completed' | 'failed' | 'killed';
+  latestProcessStatus?:
+    | 'running'
+    | 'completed'
+    | 'failed'
+    | 'killed'
+    | 'cancelled';
   prStatus?: 'open' | 'merged' | 'closed' | 'unknown';
   onClick?: () => void;
   className?: string;
@@ -59,7 +64,9 @@ export function WorkspaceSummary({
   const { t } = useTranslation('common');
   const hasChanges = filesChanged !== undefined && filesChanged > 0;
   const isFailed =
-    latestProcessStatus === 'failed' || latestProcessStatus === 'killed';
+    latestProcessStatus === 'failed' ||
+    latestProcessStatus === 'killed' ||
+    latestProcessStatus === 'cancelled';
 
   const handleOpenCommandBar = (e: React.MouseEvent) => {
     e.stopPropagation();
@@ -5,13 +5,17 @@ export const PROCESS_RUN_REASONS = {
   SETUP_SCRIPT: 'setupscript' as ExecutionProcessRunReason,
   CLEANUP_SCRIPT: 'cleanupscript' as ExecutionProcessRunReason,
   CODING_AGENT: 'codingagent' as ExecutionProcessRunReason,
+  REVIEW: 'review' as ExecutionProcessRunReason,
   DEV_SERVER: 'devserver' as ExecutionProcessRunReason,
 } as const;
 
 export const isCodingAgent = (
   runReason: ExecutionProcessRunReason
 ): boolean => {
-  return runReason === PROCESS_RUN_REASONS.CODING_AGENT;
+  return (
+    runReason === PROCESS_RUN_REASONS.CODING_AGENT ||
+    runReason === PROCESS_RUN_REASONS.REVIEW
+  );
 };
 
 export const shouldShowInLogs = (
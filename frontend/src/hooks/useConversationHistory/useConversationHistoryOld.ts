@@ -52,7 +52,8 @@ export const useConversationHistoryOld = ({
       (ep) =>
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
-        ep.run_reason === 'codingagent'
+        ep.run_reason === 'codingagent' ||
+        ep.run_reason === 'review'
     );
   }, [executionProcessesRaw]);
 
@@ -56,6 +56,7 @@ export const useExecutionProcesses = (
   const isAttemptRunning = executionProcesses.some(
     (process) =>
       (process.run_reason === 'codingagent' ||
+        process.run_reason === 'review' ||
         process.run_reason === 'setupscript' ||
         process.run_reason === 'cleanupscript') &&
       process.status === 'running'
@@ -3,6 +3,11 @@
 import {
   ApprovalStatus,
   ApiResponse,
+  BranchInfo,
+  CloneRepoRequest,
+  ErrorCode,
+  CommitResult,
+  CommitWorkspaceRequest,
   Config,
   CreateFollowUpAttempt,
   EditorType,
@@ -13,10 +18,20 @@ import {
   CreateTag,
   DirectoryListResponse,
   DirectoryEntry,
+  ReadFileRequest,
+  FileMetadata,
+  ReadFileResponse,
+  WriteFileRequest,
+  CancelExecutionProcessRequest,
   ExecutionProcess,
+  PaginatedResponse,
   ExecutionProcessRepoState,
-  GitBranch,
+  RunningProcess,
+  ResourceUsage,
   Project,
+  ProjectStatsResponse,
+  ProjectSyncStatus,
+  DuplicateProjectRequest,
   Repo,
   RepoWithTargetBranch,
   CreateProject,
@@ -25,6 +40,10 @@ import {
   SearchMode,
   SearchResult,
   Task,
+  TaskHistoryEntry,
+  BulkUpdateTaskStatusRequest,
+  MoveTaskRequest,
+  BulkUpdateTaskStatusResponse,
   TaskRelationships,
   Tag,
   TagSearchParams,
@@ -39,6 +58,7 @@ import {
   ImageResponse,
   GitOperationError,
   ApprovalResponse,
+  PendingApproval,
   RebaseTaskAttemptRequest,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
@@ -52,6 +72,9 @@ import {
   GhCliSetupError,
   RunScriptError,
   StatusResponse,
+  ProfileResponse,
+  ProfileValidation,
+  ImportTasksResponse,
   ListOrganizationsResponse,
   OrganizationMemberWithProfile,
   ListMembersResponse,
@@ -76,6 +99,12 @@ import {
   CreateScratch,
   UpdateScratch,
   PushError,
+  PushResult,
+  PushWorkspaceRequest,
+  ResetWorkspaceRequest,
+  CreateWorkspacePullRequest,
+  UpdateAgentWorkingDirRequest,
+  PullRequestInfo,
   TokenResponse,
   CurrentUserResponse,
   QueueStatus,
@@ -95,17 +124,20 @@ import { createWorkspaceWithSession } from '@/types/attempt';
 export class ApiError<E = unknown> extends Error {
   public status?: number;
   public error_data?: E;
+  public code?: ErrorCode;
 
   constructor(
     message: string,
     public statusCode?: number,
     public response?: Response,
-    error_data?: E
+    error_data?: E,
+    code?: ErrorCode
   ) {
     super(message);
     this.name = 'ApiError';
     this.status = statusCode;
     this.error_data = error_data;
+    this.code = code;
   }
 }
 
@@ -140,12 +172,14 @@ const handleApiResponseAsResult = async <T, E>(
   if (!response.ok) {
     // HTTP error - no structured error data
     let errorMessage = `Request failed with status ${response.status}`;
+    let errorCode: ErrorCode | undefined;
 
     try {
       const errorData = await response.json();
       if (errorData.message) {
         errorMessage = errorData.message;
       }
+      errorCode = errorData.code ?? undefined;
     } catch {
       errorMessage = response.statusText || errorMessage;
     }
@@ -175,12 +209,14 @@ export const handleApiResponse = async <T, E = T>(
 ): Promise<T> => {
   if (!response.ok) {
     let errorMessage = `Request failed with status ${response.status}`;
+    let errorCode: ErrorCode | undefined;
 
     try {
       const errorData = await response.json();
       if (errorData.message) {
         errorMessage = errorData.message;
       }
+      errorCode = errorData.code ?? undefined;
     } catch {
       // Fallback to status text if JSON parsing fails
       errorMessage = response.statusText || errorMessage;
@@ -188,12 +224,19 @@ export const handleApiResponse = async <T, E = T>(
 
     console.error('[API Error]', {
       message: errorMessage,
+      code: errorCode,
       status: response.status,
       response,
       endpoint: response.url,
       timestamp: new Date().toISOString(),
     });
-    throw new ApiError<E>(errorMessage, response.status, response);
+    throw new ApiError<E>(
+      errorMessage,
+      response.status,
+      response,
+      undefined,
+      errorCode
+    );
   }
 
   if (response.status === 204) {
@@ -218,7 +261,8 @@ export const handleApiResponse = async <T, E = T>(
         result.message || 'API request failed',
         response.status,
         response,
-        result.error_data
+        result.error_data,
+        result.code ?? undefined
       );
     }
 
@@ -232,7 +276,9 @@ export const handleApiResponse = async <T, E = T>(
     throw new ApiError<E>(
       result.message || 'API request failed',
       response.status,
-      response
+      response,
+      undefined,
+      result.code ?? undefined
     );
   }
 
@@ -257,6 +303,11 @@ export const projectsApi = {
     return handleApiResponse<Project>(response);
   },
 
+  getStats: async (id: string): Promise<ProjectStatsResponse> => {
+    const response = await makeRequest(`/api/projects/${id}/stats`);
+    return handleApiResponse<ProjectStatsResponse>(response);
+  },
+
   getRemoteMembers: async (
     projectId: string
   ): Promise<RemoteProjectMembersResponse> => {
@@ -273,6 +324,50 @@ export const projectsApi = {
     return handleApiResponse<void>(response);
   },
 
+  archive: async (id: string): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/archive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Project>(response);
+  },
+
+  unarchive: async (id: string): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/archive`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<Project>(response);
+  },
+
+  duplicate: async (
+    id: string,
+    data: DuplicateProjectRequest
+  ): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/duplicate`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Project>(response);
+  },
+
+  importTasks: async (id: string, file: File): Promise<ImportTasksResponse> => {
+    const formData = new FormData();
+    formData.append('file', file);
+
+    const response = await fetch(`/api/projects/${id}/import`, {
+      method: 'POST',
+      body: formData,
+      credentials: 'include',
+    });
+    return handleApiResponse<ImportTasksResponse>(response);
+  },
+
+  getByRemoteProjectId: async (remoteProjectId: string): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/remote-projects/${remoteProjectId}/local`
+    );
+    return handleApiResponse<Project>(response);
+  },
+
   openEditor: async (
     id: string,
     data: OpenEditorRequest
@@ -330,6 +425,23 @@ export const projectsApi = {
     return handleApiResponse<Project>(response);
   },
 
+  syncRemote: async (projectId: string): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/sync-remote`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  getSyncStatus: async (projectId: string): Promise<ProjectSyncStatus> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/sync-status`
+    );
+    return handleApiResponse<ProjectSyncStatus>(response);
+  },
+
   getRepositories: async (projectId: string): Promise<Repo[]> => {
     const response = await makeRequest(
       `/api/projects/${projectId}/repositories`
@@ -372,6 +484,15 @@ export const tasksApi = {
     return handleApiResponse<Task>(response);
   },
 
+  search: async (q: string, projectId?: string): Promise<Task[]> => {
+    const params = new URLSearchParams({ q });
+    if (projectId) params.set('project_id', projectId);
+    const response = await makeRequest(
+      `/api/tasks/search?${params.toString()}`
+    );
+    return handleApiResponse<Task[]>(response);
+  },
+
   create: async (data: CreateTask): Promise<Task> => {
     const response = await makeRequest(`/api/tasks`, {
       method: 'POST',
@@ -404,13 +525,58 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  restore: async (taskId: string): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/restore`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Task>(response);
+  },
+
+  purge: async (taskId: string): Promise<void> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/purge`, {
+      method: 'POST',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getHistory: async (taskId: string): Promise<TaskHistoryEntry[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/history`);
+    return handleApiResponse<TaskHistoryEntry[]>(response);
+  },
+
+  getWorkspaces: async (taskId: string): Promise<Workspace[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/workspaces`);
+    return handleApiResponse<Workspace[]>(response);
+  },
+
+  move: async (taskId: string, data: MoveTaskRequest): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/move`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Task>(response);
+  },
+
+  bulkUpdateStatus: async (
+    data: BulkUpdateTaskStatusRequest
+  ): Promise<BulkUpdateTaskStatusResponse> => {
+    const response = await makeRequest('/api/tasks/bulk', {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<BulkUpdateTaskStatusResponse>(response);
+  },
 };
 
 // Sessions API
 export const sessionsApi = {
-  getByWorkspace: async (workspaceId: string): Promise<Session[]> => {
+  getByWorkspace: async (
+    workspaceId: string,
+    activeOnly = false
+  ): Promise<Session[]> => {
     const response = await makeRequest(
-      `/api/sessions?workspace_id=${workspaceId}`
+      `/api/sessions?workspace_id=${workspaceId}${activeOnly ? '&active=true' : ''}`
     );
     return handleApiResponse<Session[]>(response);
   },
@@ -420,6 +586,21 @@ export const sessionsApi = {
     return handleApiResponse<Session>(response);
   },
 
+  /** Newest first; pass the previous page's `next_cursor` as `after` */
+  getProcesses: async (
+    sessionId: string,
+    after?: string,
+    limit?: number
+  ): Promise<PaginatedResponse<ExecutionProcess>> => {
+    const params = new URLSearchParams();
+    if (after) params.set('after', after);
+    if (limit) params.set('limit', String(limit));
+    const response = await makeRequest(
+      `/api/sessions/${sessionId}/processes?${params.toString()}`
+    );
+    return handleApiResponse<PaginatedResponse<ExecutionProcess>>(response);
+  },
+
   create: async (data: {
     workspace_id: string;
     executor?: string;
@@ -452,6 +633,20 @@ export const sessionsApi = {
     });
     return handleApiResponse<ExecutionProcess, ReviewError>(response);
   },
+
+  pause: async (sessionId: string): Promise<ExecutionProcess> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}/pause`, {
+      method: 'POST',
+    });
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  resume: async (sessionId: string): Promise<ExecutionProcess> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}/resume`, {
+      method: 'POST',
+    });
+    return handleApiResponse<ExecutionProcess>(response);
+  },
 };
 
 // Task Attempts APIs
@@ -496,6 +691,104 @@ export const attemptsApi = {
     return handleApiResponse<Workspace>(response);
   },
 
+  pin: async (attemptId: string): Promise<Workspace> => {
+    const response = await makeRequest(`/api/workspaces/${attemptId}/pin`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Workspace>(response);
+  },
+
+  unpin: async (attemptId: string): Promise<Workspace> => {
+    const response = await makeRequest(`/api/workspaces/${attemptId}/pin`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<Workspace>(response);
+  },
+
+  commit: async (
+    attemptId: string,
+    data: CommitWorkspaceRequest
+  ): Promise<CommitResult[]> => {
+    const response = await makeRequest(`/api/workspaces/${attemptId}/commit`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CommitResult[]>(response);
+  },
+
+  push: async (
+    attemptId: string,
+    data: PushWorkspaceRequest
+  ): Promise<PushResult[]> => {
+    const response = await makeRequest(`/api/workspaces/${attemptId}/push`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PushResult[]>(response);
+  },
+
+  /** An invalid directory is returned as an error with the valid ones */
+  updateAgentWorkingDir: async (
+    attemptId: string,
+    data: UpdateAgentWorkingDirRequest
+  ): Promise<Result<Workspace, string[]>> => {
+    const response = await makeRequest(`/api/workspaces/${attemptId}`, {
+      method: 'PATCH',
+      body: JSON.stringify(data),
+    });
+    if (response.status === 422) {
+      const result: ApiResponse<Workspace, string[]> = await response
+        .clone()
+        .json();
+      if (result.error_data) {
+        return {
+          success: false,
+          error: result.error_data,
+          message: result.message || undefined,
+        };
+      }
+    }
+    return handleApiResponseAsResult<Workspace, string[]>(response);
+  },
+
+  /** The workspace branch must already be pushed */
+  createPullRequest: async (
+    attemptId: string,
+    data: CreateWorkspacePullRequest
+  ): Promise<PullRequestInfo> => {
+    const response = await makeRequest(
+      `/api/workspaces/${attemptId}/pull-request`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PullRequestInfo>(response);
+  },
+
+  /** Without `force`, a workspace with uncommitted changes is not reset and
+   * the dirty files are returned as the error */
+  reset: async (
+    attemptId: string,
+    data: ResetWorkspaceRequest
+  ): Promise<Result<void, string[]>> => {
+    const response = await makeRequest(`/api/workspaces/${attemptId}/reset`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    if (response.status === 409) {
+      const result: ApiResponse<void, string[]> = await response.clone().json();
+      if (result.error_data) {
+        return {
+          success: false,
+          error: result.error_data,
+          message: result.message || undefined,
+        };
+      }
+    }
+    return handleApiResponseAsResult<void, string[]>(response);
+  },
+
   /** Get workspace with latest session */
   getWithSession: async (attemptId: string): Promise<WorkspaceWithSession> => {
     const [workspace, sessions] = await Promise.all([
@@ -790,6 +1083,48 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  cancelExecutionProcess: async (
+    processId: string,
+    data: CancelExecutionProcessRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/cancel`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+};
+
+// Container APIs
+export const containersApi = {
+  listRunning: async (workspaceId?: string): Promise<RunningProcess[]> => {
+    const query = workspaceId
+      ? `?${new URLSearchParams({ workspace_id: workspaceId })}`
+      : '';
+    const response = await makeRequest(`/api/containers/running${query}`);
+    return handleApiResponse<RunningProcess[]>(response);
+  },
+
+  stopRunning: async (workspaceId?: string): Promise<RunningProcess[]> => {
+    const query = workspaceId
+      ? `?${new URLSearchParams({ workspace_id: workspaceId })}`
+      : '';
+    const response = await makeRequest(`/api/containers/running${query}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<RunningProcess[]>(response);
+  },
+
+  getResourceUsage: async (workspaceId: string): Promise<ResourceUsage> => {
+    const response = await makeRequest(
+      `/api/containers/${workspaceId}/resources`
+    );
+    return handleApiResponse<ResourceUsage>(response);
+  },
 };
 
 // File System APIs
@@ -809,6 +1144,29 @@ export const fileSystemApi = {
     );
     return handleApiResponse<DirectoryEntry[]>(response);
   },
+
+  readFile: async (data: ReadFileRequest): Promise<ReadFileResponse> => {
+    const response = await makeRequest('/api/filesystem/read', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ReadFileResponse>(response);
+  },
+
+  writeFile: async (data: WriteFileRequest): Promise<void> => {
+    const response = await makeRequest('/api/filesystem/write', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getMetadata: async (path: string): Promise<FileMetadata> => {
+    const response = await makeRequest(
+      `/api/filesystem/metadata?path=${encodeURIComponent(path)}`
+    );
+    return handleApiResponse<FileMetadata>(response);
+  },
 };
 
 // Repo APIs
@@ -842,9 +1200,9 @@ export const repoApi = {
     return handleApiResponse<Repo>(response);
   },
 
-  getBranches: async (repoId: string): Promise<GitBranch[]> => {
+  getBranches: async (repoId: string): Promise<BranchInfo[]> => {
     const response = await makeRequest(`/api/repos/${repoId}/branches`);
-    return handleApiResponse<GitBranch[]>(response);
+    return handleApiResponse<BranchInfo[]>(response);
   },
 
   init: async (data: {
@@ -858,6 +1216,14 @@ export const repoApi = {
     return handleApiResponse<Repo>(response);
   },
 
+  clone: async (data: CloneRepoRequest): Promise<Repo> => {
+    const response = await makeRequest('/api/repos/clone', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Repo>(response);
+  },
+
   getBatch: async (ids: string[]): Promise<Repo[]> => {
     const response = await makeRequest('/api/repos/batch', {
       method: 'POST',
@@ -921,6 +1287,14 @@ export const configApi = {
     );
     return handleApiResponse<AvailabilityInfo>(response);
   },
+  validateExecutorProfile: async (
+    profileId: string
+  ): Promise<ProfileValidation> => {
+    const response = await makeRequest(
+      `/api/executors/${encodeURIComponent(profileId)}/validate`
+    );
+    return handleApiResponse<ProfileValidation>(response);
+  },
 };
 
 // Task Tags APIs (all tags are global)
@@ -1087,6 +1461,11 @@ export const imagesApi = {
     return handleApiResponse<ImageResponse>(response);
   },
 
+  get: async (imageId: string): Promise<ImageResponse> => {
+    const response = await makeRequest(`/api/images/${imageId}`);
+    return handleApiResponse<ImageResponse>(response);
+  },
+
   delete: async (imageId: string): Promise<void> => {
     const response = await makeRequest(`/api/images/${imageId}`, {
       method: 'DELETE',
@@ -1120,6 +1499,11 @@ export const approvalsApi = {
 
     return handleApiResponse<ApprovalStatus>(res);
   },
+
+  listPending: async (): Promise<PendingApproval[]> => {
+    const res = await makeRequest('/api/approvals/pending');
+    return handleApiResponse<PendingApproval[]>(res);
+  },
 };
 
 // OAuth API
@@ -1144,6 +1528,13 @@ export const oauthApi = {
     return handleApiResponse<StatusResponse>(response);
   },
 
+  refreshProfile: async (): Promise<ProfileResponse> => {
+    const response = await makeRequest('/api/auth/refresh-profile', {
+      method: 'POST',
+    });
+    return handleApiResponse<ProfileResponse>(response);
+  },
+
   logout: async (): Promise<void> => {
     const response = await makeRequest('/api/auth/logout', {
       method: 'POST',
@@ -1315,6 +1706,44 @@ export const scratchApi = {
 
   getStreamUrl: (scratchType: ScratchType, id: string): string =>
     `/api/scratch/${scratchType}/${id}/stream/ws`,
+
+  getForSession: async (
+    sessionId: string,
+    scratchType: ScratchType = ScratchType.DRAFT_FOLLOW_UP
+  ): Promise<Scratch | null> => {
+    const response = await makeRequest(
+      `/api/scratch/${sessionId}?type=${scratchType}`
+    );
+    return handleApiResponse<Scratch | null>(response);
+  },
+
+  saveForSession: async (
+    sessionId: string,
+    data: UpdateScratch,
+    scratchType: ScratchType = ScratchType.DRAFT_FOLLOW_UP
+  ): Promise<Scratch> => {
+    const response = await makeRequest(
+      `/api/scratch/${sessionId}?type=${scratchType}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Scratch>(response);
+  },
+
+  deleteForSession: async (
+    sessionId: string,
+    scratchType: ScratchType = ScratchType.DRAFT_FOLLOW_UP
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/scratch/${sessionId}?type=${scratchType}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
 };
 
 // Queue API for session follow-up messages
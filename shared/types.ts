@@ -4,7 +4,7 @@
 
 // If you are an AI, and you absolutely have to edit this file, please confirm with the user first.
 
-export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, is_archived: boolean, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
@@ -26,11 +26,19 @@ export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
 export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
 
-export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, 
+/**
+ * Branch checked out in the repo's worktree; empty until it is created
+ */
+active_branch: string, created_at: Date, updated_at: Date, };
 
 export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, created_at: Date, updated_at: Date, };
+export type RepoWithTargetBranch = { target_branch: string, 
+/**
+ * Branch checked out in the repo's worktree; empty until it is created
+ */
+active_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, created_at: Date, updated_at: Date, };
 
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
@@ -40,9 +48,11 @@ export type UpdateTag = { tag_name: string | null, content: string | null, };
 
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, created_at: string, updated_at: string, deleted_at: string | null, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, created_at: string, updated_at: string, };
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, created_at: string, updated_at: string, deleted_at: string | null, };
+
+export type TaskHistoryEntry = { id: string, task_id: string, user_id: string | null, changed_at: string, field: string, old_value: JsonValue, new_value: JsonValue, };
 
 export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
@@ -86,14 +96,34 @@ export type ExecutionProcess = { id: string, session_id: string, run_reason: Exe
  * history view (due to restore/trimming). Hidden from logs/timeline;
  * still listed in the Processes tab.
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+dropped: boolean, started_at: string, completed_at: string | null, 
+/**
+ * Set while the process is suspended by a pause request
+ */
+paused_at: string | null, 
+/**
+ * Reason given when the process was cancelled by a user
+ */
+cancelled_reason: string | null, created_at: string, updated_at: string, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed", cancelled = "cancelled" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "review" | "devserver";
+
+export type RunningProcess = { workspace_id: string, session_id: string, process_id: string, started_at: string, };
+
+export type ResourceUsage = { 
+/**
+ * Summed over processes, so it can exceed 100 on multi-core machines
+ */
+cpu_percent: number, memory_bytes: bigint, memory_limit_bytes: bigint | null, };
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
+export type ExecutionProcessLogRow = { seq: bigint, execution_id: string, logs: string, byte_size: bigint, inserted_at: string, };
+
+export type CancelExecutionProcessRequest = { reason: string | null, };
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -110,6 +140,8 @@ export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue,
 
 export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
 
+export type PendingApproval = { id: string, execution_process_id: string, session_id: string, workspace_id: string, tool_name: string, requested_at: string, timeout_at: string, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
@@ -122,7 +154,13 @@ additions: number | null, deletions: number | null, repoId: string | null, };
 
 export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
 
-export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
+export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, code: ErrorCode | null, };
+
+export type ErrorCode = "BAD_REQUEST" | "UNAUTHORIZED" | "FORBIDDEN" | "NOT_FOUND" | "PROJECT_NOT_FOUND" | "WORKSPACE_NOT_FOUND" | "SESSION_NOT_FOUND" | "CONFLICT" | "MERGE_CONFLICTS" | "REBASE_IN_PROGRESS" | "SESSION_CLOSED" | "PAYLOAD_TOO_LARGE" | "UNPROCESSABLE_ENTITY" | "INVALID_IMAGE_FORMAT" | "QUOTA_EXCEEDED" | "REMOTE_UNAVAILABLE" | "REMOTE_TIMEOUT" | "INTERNAL_ERROR";
+
+export type PaginatedResponse<T> = { items: Array<T>, next_cursor: string | null, has_more: boolean, };
+
+export type PaginationQuery = { after: string | null, limit: number | null, };
 
 export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
 
@@ -184,10 +222,54 @@ export type CreateRemoteProjectRequest = { organization_id: string, name: string
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type ProjectStatsResponse = { 
+/**
+ * Non-deleted tasks in the project
+ */
+total: bigint, 
+/**
+ * Task counts per status; statuses without tasks are omitted
+ */
+by_status: { [key in TaskStatus]?: bigint }, workspaces: bigint, sessions: bigint, };
+
+export type ProjectSyncStatus = { 
+/**
+ * When the project last pulled metadata from its remote project
+ */
+last_synced_at: string | null, is_linked: boolean, 
+/**
+ * Remote project name seen at the last sync
+ */
+remote_project_name: string | null, };
+
+export type DuplicateProjectRequest = { name: string, 
+/**
+ * Also copy each task's workspaces
+ */
+deep: boolean, };
+
+export type ImportTasksResponse = { imported: number, 
+/**
+ * Rows that were skipped, numbered from 1 excluding the CSV header
+ */
+errors: Array<TaskImportRowError>, };
+
+export type TaskImportRowError = { row: number, message: string, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
+export type CloneRepoRequest = { git_url: string, 
+/**
+ * Folder to clone into, created inside the user's workspace (K8s) or home directory
+ */
+destination_name: string, 
+/**
+ * Only fetch the latest commit
+ */
+shallow: boolean, };
+
 export type TagSearchParams = { search: string | null, };
 
 export type TokenResponse = { access_token: string, expires_at: string | null, };
@@ -214,6 +296,37 @@ export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
 
 export type CurrentUserResponse = { user_id: string, };
 
+export type TokenExchangeRequest = { 
+/**
+ * A token issued by the identity provider, verified against `JWKS_URI`
+ * or `JWT_SECRET`.
+ */
+id_token: string, };
+
+export type RefreshTokenRequest = { 
+/**
+ * The access token being replaced. It may already be expired.
+ */
+access_token: string, 
+/**
+ * The opaque refresh token issued alongside the access token.
+ */
+refresh_token: string, };
+
+export type RefreshTokenResponse = { access_token: string, access_token_expires_at: string, refresh_token: string, refresh_token_expires_at: string, };
+
+export type HealthResponse = { db_backend: string, db_healthy: boolean, db_active_connections: number, db_idle_connections: number, db_max_connections: number, 
+/**
+ * Event stream subscribers (WebSocket clients) currently connected
+ */
+event_subscribers: number, deployment_mode: DeploymentMode, 
+/**
+ * Whether API requests need a bearer token, so clients know to show a login screen
+ */
+auth_required: boolean, };
+
+export type DeploymentMode = "desktop" | "kubernetes";
+
 export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
 
 export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
@@ -228,7 +341,14 @@ export type RenameBranchRequest = { new_branch_name: string, };
 
 export type RenameBranchResponse = { branch: string, };
 
-export type StartReviewRequest = { executor_profile_id: ExecutorProfileId, additional_prompt: string | null, use_all_workspace_commits: boolean, };
+export type ReviewType = "code_quality" | "security_audit" | "full_review";
+
+export type StartReviewRequest = { 
+/**
+ * Executor to review with, e.g. "CLAUDE_CODE". Defaults to the executor
+ * of the session's latest coding agent turn, else the session's executor.
+ */
+executor: string | null, review_type: ReviewType, additional_prompt: string | null, use_all_workspace_commits: boolean, };
 
 export type ReviewError = { "type": "process_already_running" };
 
@@ -238,6 +358,16 @@ export type OpenEditorResponse = { url: string | null, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
+export type MoveTaskRequest = { project_id: string, };
+
+export type BulkUpdateTaskStatusRequest = { ids: Array<string>, status: TaskStatus, };
+
+export type BulkUpdateTaskStatusResponse = { updated: bigint, 
+/**
+ * Requested tasks that were missing, deleted, or not owned by the caller
+ */
+skipped: bigint, };
+
 export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
@@ -292,7 +422,7 @@ export type GetPrCommentsQuery = { repo_id: string, };
 
 export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, };
 
-export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
+export type ProviderKind = "git_hub" | "azure_dev_ops" | "git_lab" | "unknown";
 
 export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
@@ -358,14 +488,87 @@ export type WorkspaceSummaryResponse = { summaries: Array<WorkspaceSummary>, };
 
 export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
 
+export type WorktreeStatus = { repo_id: string, path: string, exists: boolean, branch: string | null, 
+/**
+ * Branch last recorded for the worktree in the database. Left empty by
+ * [`WorkspaceManager::list_worktrees`], which does not read the database.
+ */
+active_branch: string, is_dirty: boolean, };
+
+export type WorkspaceQuota = { used_bytes: bigint, limit_bytes: bigint | null, remaining_bytes: bigint | null, };
+
+export type ChangedFile = { path: string, additions: number | null, deletions: number | null, };
+
+export type RepoDiff = { repo_id: string, repo_name: string, 
+/**
+ * `git diff --stat` summary
+ */
+diff_stat: string, files_changed: Array<ChangedFile>, 
+/**
+ * Full diff text, only included when requested
+ */
+diff: string | null, 
+/**
+ * True when `diff` was cut short by `DIFF_MAX_BYTES`
+ */
+truncated: boolean, };
+
+export type CommitResult = { repo_name: string, sha: string, files_committed: number, };
+
+export type CommitWorkspaceRequest = { message: string, };
+
+export type PushResult = { repo_name: string, remote_url: string | null, branch: string | null, sha: string | null, error: string | null, };
+
+export type PushWorkspaceRequest = { 
+/**
+ * Remote to push to; defaults to the repo's push remote
+ */
+remote: string | null, force: boolean, };
+
+export type ResetWorkspaceRequest = { 
+/**
+ * Discard uncommitted changes instead of refusing when there are any
+ */
+force: boolean, };
+
+export type CreateWorkspacePullRequest = { 
+/**
+ * Repo to open the pull request for; may be omitted when the workspace
+ * has a single repo
+ */
+repo_id: string | null, 
+/**
+ * Branch to merge into; defaults to the repo's target branch
+ */
+base_branch: string | null, title: string, body: string | null, };
+
+export type UpdateAgentWorkingDirRequest = { 
+/**
+ * Worktree directory of one of the workspace's repos, relative to the
+ * workspace root or absolute; empty or null runs agents in the root
+ */
+agent_working_dir: string | null, };
+
+export type PtySessionInfo = { id: string, user_id: string, workspace_id: string | null, created_at: string, last_activity_at: string, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
+export type FileMetadata = { size_bytes: bigint, modified_at: string, created_at: string | null, mime_type: string, is_readable: boolean, is_writable: boolean, };
+
+export type ReadFileRequest = { path: string, };
+
+export type ReadFileResponse = { path: string, content: string, };
+
+export type WriteFileRequest = { path: string, content: string, };
+
 export type SearchMode = "taskform" | "settings";
 
 export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, };
 
+export type UpdateConfig = { theme?: ThemeMode, executor_profile?: ExecutorProfileId, disclaimer_acknowledged?: boolean, onboarding_acknowledged?: boolean, notifications?: NotificationConfig, editor?: EditorConfig, github?: GitHubConfig, analytics_enabled?: boolean, workspace_dir?: string | null, last_app_version?: string | null, show_release_notes?: boolean, language?: UiLanguage, git_branch_prefix?: string, showcases?: ShowcaseState, pr_auto_description_enabled?: boolean, pr_auto_description_prompt?: string | null, beta_workspaces?: boolean, beta_workspaces_invitation_sent?: boolean, commit_reminder?: boolean, };
+
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
 export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
@@ -386,6 +589,12 @@ export type ShowcaseState = { seen_features: Array<string>, };
 
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
+export type BranchInfo = { name: string, is_current: boolean, is_remote: boolean, last_commit_sha: string, 
+/**
+ * First line of the tip commit's message
+ */
+last_commit_message: string | null, last_commit_date: Date, };
+
 export type QueuedMessage = { 
 /**
  * The session this message is queued for
@@ -402,6 +611,14 @@ queued_at: string, };
 
 export type QueueStatus = { "status": "empty" } | { "status": "queued", message: QueuedMessage, };
 
+export type AgentTokenRole = "assistant" | "thinking";
+
+export type AgentToken = { text: string, role: AgentTokenRole, 
+/**
+ * The model configured for the agent, if known
+ */
+model: string | null, };
+
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
@@ -447,6 +664,16 @@ executor: BaseCodingAgent,
  */
 variant: string | null, };
 
+export type ProfileValidation = { available: boolean, 
+/**
+ * Why the executor cannot be started, when it is unavailable
+ */
+reason: string | null, 
+/**
+ * First line of `<program> --version`, when the program reports one
+ */
+version: string | null, };
+
 export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } };
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };